@@ -0,0 +1,234 @@
+//! Append-only, hash-chained audit log stored as JSON lines.
+//!
+//! Every entry stores the hash of its predecessor, so editing, reordering, or
+//! deleting any line breaks the chain from that point on.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::utils::time::unix_now_seconds;
+
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: i64,
+    pub actor: String,
+    pub operation: String,
+    pub parameters: Value,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(
+            format!(
+                "{}\n{}\n{}\n{}\n{}\n{}",
+                self.prev_hash,
+                self.seq,
+                self.timestamp,
+                self.actor,
+                self.operation,
+                self.parameters
+            )
+            .as_bytes(),
+        );
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditVerification {
+    pub valid: bool,
+    pub entries: usize,
+    pub first_invalid_seq: Option<u64>,
+    pub reason: Option<String>,
+}
+
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn open(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    pub fn open_default() -> Self {
+        Self::open(&default_audit_log_path())
+    }
+
+    pub fn append(&self, actor: &str, operation: &str, parameters: Value) -> Result<AuditEntry> {
+        let entries = self.read_entries()?;
+        let (seq, prev_hash) = entries
+            .last()
+            .map(|last| (last.seq + 1, last.hash.clone()))
+            .unwrap_or((1, GENESIS_HASH.to_string()));
+        let mut entry = AuditEntry {
+            seq,
+            timestamp: unix_now_seconds(),
+            actor: actor.to_string(),
+            operation: operation.to_string(),
+            parameters,
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create audit directory '{}'.", parent.display())
+            })?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open audit log '{}'.", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)
+            .with_context(|| format!("Failed to append to audit log '{}'.", self.path.display()))?;
+        Ok(entry)
+    }
+
+    pub fn read_entries(&self) -> Result<Vec<AuditEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read audit log '{}'.", self.path.display()))?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(index, line)| {
+                serde_json::from_str::<AuditEntry>(line).with_context(|| {
+                    format!(
+                        "Audit log '{}' line {} is not a valid entry.",
+                        self.path.display(),
+                        index + 1
+                    )
+                })
+            })
+            .collect()
+    }
+
+    pub fn verify(&self) -> Result<AuditVerification> {
+        let entries = match self.read_entries() {
+            Ok(entries) => entries,
+            Err(err) => {
+                return Ok(AuditVerification {
+                    valid: false,
+                    entries: 0,
+                    first_invalid_seq: None,
+                    reason: Some(format!("{:#}", err)),
+                })
+            }
+        };
+        Ok(verify_chain(&entries))
+    }
+}
+
+fn verify_chain(entries: &[AuditEntry]) -> AuditVerification {
+    let mut expected_prev = GENESIS_HASH.to_string();
+    for (index, entry) in entries.iter().enumerate() {
+        let expected_seq = index as u64 + 1;
+        let reason = if entry.seq != expected_seq {
+            Some(format!(
+                "expected seq {} but found {}",
+                expected_seq, entry.seq
+            ))
+        } else if entry.prev_hash != expected_prev {
+            Some("prev_hash does not match the preceding entry".to_string())
+        } else if entry.hash != entry.compute_hash() {
+            Some("entry hash does not match its contents".to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return AuditVerification {
+                valid: false,
+                entries: entries.len(),
+                first_invalid_seq: Some(expected_seq),
+                reason: Some(reason),
+            };
+        }
+        expected_prev = entry.hash.clone();
+    }
+    AuditVerification {
+        valid: true,
+        entries: entries.len(),
+        first_invalid_seq: None,
+        reason: None,
+    }
+}
+
+pub fn default_audit_log_path() -> PathBuf {
+    PathBuf::from(".nexus/audit/audit.log")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn appended_entries_form_a_verifiable_chain() {
+        let dir = tempdir().expect("tempdir");
+        let log = AuditLog::open(&dir.path().join("audit/audit.log"));
+        let first = log
+            .append("alice", "setup", serde_json::json!({"harness": "opencode"}))
+            .expect("append first");
+        let second = log
+            .append(
+                "bob",
+                "marketplace.install",
+                serde_json::json!({"target": "x"}),
+            )
+            .expect("append second");
+
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.seq, 2);
+        assert_eq!(second.prev_hash, first.hash);
+        let verification = log.verify().expect("verify");
+        assert!(verification.valid);
+        assert_eq!(verification.entries, 2);
+    }
+
+    #[test]
+    fn verify_detects_edited_and_deleted_entries() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("audit.log");
+        let log = AuditLog::open(&path);
+        for operation in ["setup", "update", "uninstall"] {
+            log.append("alice", operation, serde_json::json!({}))
+                .expect("append");
+        }
+        let original = fs::read_to_string(&path).expect("read log");
+
+        fs::write(&path, original.replace("\"update\"", "\"upgrade\"")).expect("tamper");
+        let edited = log.verify().expect("verify edited");
+        assert!(!edited.valid);
+        assert_eq!(edited.first_invalid_seq, Some(2));
+
+        let without_second = original
+            .lines()
+            .enumerate()
+            .filter(|(index, _)| *index != 1)
+            .map(|(_, line)| format!("{}\n", line))
+            .collect::<String>();
+        fs::write(&path, without_second).expect("delete entry");
+        let deleted = log.verify().expect("verify deleted");
+        assert!(!deleted.valid);
+        assert_eq!(deleted.first_invalid_seq, Some(2));
+    }
+}
@@ -4,14 +4,31 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::config::stateless::is_stateless;
 use crate::core::context::model::{BackfillContextResult, ContextRunMode};
 
 pub struct ContextObservabilityStore {
     connection: Connection,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillRunSummary {
+    pub outcome: String,
+    pub implemented_count: i64,
+    pub failed_count: i64,
+    pub missing_count: i64,
+    pub finished_at: i64,
+}
+
 impl ContextObservabilityStore {
     pub fn open_default() -> Result<Self> {
+        if is_stateless() {
+            let connection = Connection::open_in_memory()
+                .context("Failed to open in-memory observability SQLite.")?;
+            let store = Self { connection };
+            store.initialize_schema()?;
+            return Ok(store);
+        }
         let path = default_database_path();
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).with_context(|| {
@@ -108,6 +125,38 @@ impl ContextObservabilityStore {
         Ok(())
     }
 
+    pub fn latest_backfill_for_context(
+        &self,
+        context_id: &str,
+    ) -> Result<Option<BackfillRunSummary>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT outcome, implemented_count, failed_count, missing_count, finished_at
+                 FROM cdd_runs WHERE context_id=?1 AND run_mode=?2
+                 ORDER BY id DESC LIMIT 1",
+            )
+            .context("Failed to prepare latest backfill query.")?;
+        let mut rows = statement
+            .query(params![context_id, ContextRunMode::Backfill.as_str()])
+            .with_context(|| {
+                format!(
+                    "Failed to query latest backfill run for context '{}'.",
+                    context_id
+                )
+            })?;
+        if let Some(row) = rows.next()? {
+            return Ok(Some(BackfillRunSummary {
+                outcome: row.get(0)?,
+                implemented_count: row.get(1)?,
+                failed_count: row.get(2)?,
+                missing_count: row.get(3)?,
+                finished_at: row.get(4)?,
+            }));
+        }
+        Ok(None)
+    }
+
     fn initialize_schema(&self) -> Result<()> {
         self.connection
             .execute_batch(
@@ -192,5 +241,16 @@ mod tests {
             )
             .expect("run should exist");
         assert_eq!(run_mode, "backfill");
+
+        let latest = store
+            .latest_backfill_for_context("CDD_017")
+            .expect("query should pass")
+            .expect("backfill run should exist");
+        assert_eq!(latest.outcome, "complete");
+        assert_eq!(latest.implemented_count, 1);
+        assert!(store
+            .latest_backfill_for_context("CDD_999")
+            .expect("query should pass")
+            .is_none());
     }
 }
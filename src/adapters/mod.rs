@@ -1,3 +1,4 @@
 pub mod agents;
+pub mod audit_log;
 pub mod context_observability;
 pub mod orchestration_store;
@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use rusqlite::params;
+
+use super::connection::now_epoch;
+use super::{ActionTestCacheEntry, OrchestrationStore};
+
+impl OrchestrationStore {
+    pub fn cached_action_test(
+        &self,
+        context_id: &str,
+        test_id: &str,
+    ) -> Result<Option<ActionTestCacheEntry>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT action_hash, test_file, test_hash
+                 FROM orchestration_action_test_cache
+                 WHERE context_id=?1 AND test_id=?2",
+            )
+            .context("Failed preparing action test cache query.")?;
+        let mut rows = stmt.query(params![context_id, test_id]).with_context(|| {
+            format!(
+                "Failed querying action test cache for context_id='{}' test_id='{}'.",
+                context_id, test_id
+            )
+        })?;
+        if let Some(row) = rows.next()? {
+            return Ok(Some(ActionTestCacheEntry {
+                action_hash: row.get(0)?,
+                test_file: row.get(1)?,
+                test_hash: row.get(2)?,
+            }));
+        }
+        Ok(None)
+    }
+
+    pub fn record_action_test(
+        &self,
+        context_id: &str,
+        test_id: &str,
+        entry: &ActionTestCacheEntry,
+    ) -> Result<()> {
+        self.connection
+            .execute(
+                "INSERT INTO orchestration_action_test_cache
+                    (context_id, test_id, action_hash, test_file, test_hash, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(context_id, test_id) DO UPDATE SET
+                    action_hash=excluded.action_hash,
+                    test_file=excluded.test_file,
+                    test_hash=excluded.test_hash,
+                    updated_at=excluded.updated_at",
+                params![
+                    context_id,
+                    test_id,
+                    entry.action_hash,
+                    entry.test_file,
+                    entry.test_hash,
+                    now_epoch()
+                ],
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to record action test cache for context_id='{}' test_id='{}'.",
+                    context_id, test_id
+                )
+            })?;
+        Ok(())
+    }
+
+    pub fn forget_action_test(&self, context_id: &str, test_id: &str) -> Result<()> {
+        self.connection
+            .execute(
+                "DELETE FROM orchestration_action_test_cache WHERE context_id=?1 AND test_id=?2",
+                params![context_id, test_id],
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to clear action test cache for context_id='{}' test_id='{}'.",
+                    context_id, test_id
+                )
+            })?;
+        Ok(())
+    }
+}
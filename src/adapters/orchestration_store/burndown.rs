@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+
+use super::dependencies::infer_project_from_context_path;
+use super::{ActionHistoryRow, OrchestrationStore};
+
+impl OrchestrationStore {
+    /// Every next action ever snapshotted, optionally limited to one project,
+    /// ordered by when it was first seen.
+    pub fn list_action_history(&self, project: Option<&str>) -> Result<Vec<ActionHistoryRow>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT s.context_id, s.action_key, MIN(s.created_at),
+                    MIN(CASE WHEN r.status='success' THEN r.finished_at END),
+                    (SELECT MIN(l.created_at) FROM orchestration_next_action_lifecycle l
+                     WHERE l.context_id=s.context_id AND l.action_key=s.action_key
+                       AND l.lifecycle_state='cancelled'),
+                    MAX(r.context_file)
+                 FROM orchestration_next_action_snapshots s
+                 JOIN orchestration_runs r ON r.id=s.run_id
+                 GROUP BY s.context_id, s.action_key
+                 ORDER BY MIN(s.created_at) ASC, s.context_id ASC, s.action_key ASC",
+            )
+            .context("Failed preparing action history query.")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    ActionHistoryRow {
+                        context_id: row.get(0)?,
+                        action_key: row.get(1)?,
+                        first_seen_at: row.get(2)?,
+                        completed_at: row.get(3)?,
+                        removed_at: row.get(4)?,
+                    },
+                    row.get::<_, String>(5)?,
+                ))
+            })
+            .context("Failed querying action history.")?;
+        let wanted = project.map(|name| name.trim().to_ascii_lowercase());
+        Ok(rows
+            .filter_map(Result::ok)
+            .filter(|(_, context_file)| {
+                wanted.as_ref().is_none_or(|wanted| {
+                    infer_project_from_context_path(context_file).as_ref() == Some(wanted)
+                })
+            })
+            .map(|(row, _)| row)
+            .collect())
+    }
+}
@@ -5,9 +5,16 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::OrchestrationStore;
+use crate::config::stateless::is_stateless;
 
 impl OrchestrationStore {
+    /// Open the store at `path`, or an in-memory store in stateless mode.
     pub fn open(path: &Path) -> Result<Self> {
+        if is_stateless() {
+            let connection = Connection::open_in_memory()
+                .context("Failed to open in-memory orchestration SQLite.")?;
+            return Self::from_connection(connection, path);
+        }
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).with_context(|| {
                 format!(
@@ -19,6 +26,10 @@ impl OrchestrationStore {
         let connection = Connection::open(path).with_context(|| {
             format!("Failed to open orchestration SQLite '{}'.", path.display())
         })?;
+        Self::from_connection(connection, path)
+    }
+
+    fn from_connection(connection: Connection, path: &Path) -> Result<Self> {
         connection
             .execute_batch("PRAGMA foreign_keys=ON;")
             .with_context(|| {
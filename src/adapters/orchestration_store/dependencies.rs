@@ -1,8 +1,10 @@
 use anyhow::{bail, Context, Result};
 use rusqlite::params;
 use std::collections::BTreeSet;
+use std::path::Path;
 
 use super::OrchestrationStore;
+use crate::utils::paths::to_slash;
 
 impl OrchestrationStore {
     pub fn latest_success_for_context_id(&self, context_id: &str) -> Result<Option<i64>> {
@@ -121,8 +123,8 @@ impl OrchestrationStore {
     }
 }
 
-fn infer_project_from_context_path(context_file: &str) -> Option<String> {
-    let normalized = context_file.replace('\\', "/");
+pub(super) fn infer_project_from_context_path(context_file: &str) -> Option<String> {
+    let normalized = to_slash(Path::new(context_file));
     let marker = "/context/";
     let marker_index = normalized.find(marker)?;
     let after = &normalized[(marker_index + marker.len())..];
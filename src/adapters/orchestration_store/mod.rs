@@ -1,11 +1,14 @@
 use rusqlite::Connection;
 use serde::Serialize;
 
+mod action_test_cache;
 mod artifacts;
+mod burndown;
 mod connection;
 mod dependencies;
 mod next_actions;
 mod red_test_classifications;
+mod retention;
 mod runs;
 mod schema;
 mod snapshots;
@@ -20,6 +23,57 @@ pub use connection::default_orchestration_database_path;
 
 use crate::core::context::model::ContextNextAction;
 
+/// `(run_id, pipeline, status, terminal_reason, finished_at, started_at, stop_reason)`.
+pub type LatestRunRow = (
+    i64,
+    String,
+    String,
+    Option<String>,
+    Option<i64>,
+    i64,
+    Option<String>,
+);
+
+/// `(run_id, pipeline, status, terminal_reason, started_at, finished_at, stop_reason)`.
+pub type RunListingRow = (
+    i64,
+    String,
+    String,
+    Option<String>,
+    i64,
+    Option<i64>,
+    Option<String>,
+);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunableRun {
+    pub run_id: i64,
+    pub started_at: i64,
+    pub payload_bytes: i64,
+}
+
+/// A run still marked `running`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningRun {
+    pub run_id: i64,
+    pub context_file: String,
+    pub pipeline_name: String,
+    pub runner_pid: Option<i64>,
+    pub started_at: i64,
+}
+
+/// One next action's history across all runs of its context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionHistoryRow {
+    pub context_id: String,
+    pub action_key: String,
+    pub first_seen_at: i64,
+    /// When the first successful run that included the action finished.
+    pub completed_at: Option<i64>,
+    /// When the action was first dropped from its context.
+    pub removed_at: Option<i64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TraceRecordInput {
     pub step_id: String,
@@ -106,10 +160,31 @@ pub struct RunRecord {
     pub context_file: String,
     pub status: String,
     pub terminal_reason: Option<String>,
+    pub stop_reason: Option<String>,
     pub started_at: i64,
     pub finished_at: Option<i64>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct StepAttemptRecord {
+    pub step_attempt_id: i64,
+    pub step_id: String,
+    pub attempt_index: i64,
+    pub status: String,
+    pub details: Option<String>,
+    pub terminal_reason: Option<String>,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub latency_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunParameters {
+    pub run_fingerprint: String,
+    pub context_id: String,
+    pub context_snapshot_hash: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct NextActionReconciliationSummary {
     pub added_pending: usize,
@@ -117,6 +192,14 @@ pub struct NextActionReconciliationSummary {
     pub retained_active: usize,
 }
 
+/// Hashes recorded when red tests were last authored for a next action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionTestCacheEntry {
+    pub action_hash: String,
+    pub test_file: String,
+    pub test_hash: String,
+}
+
 pub struct OrchestrationStore {
     connection: Connection,
 }
@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use rusqlite::params;
+
+use super::{OrchestrationStore, PrunableRun};
+
+impl OrchestrationStore {
+    /// Pin or unpin a run; pinned runs are never pruned. Returns `false` when
+    /// the run does not exist.
+    pub fn set_run_pinned(&self, run_id: i64, pinned: bool) -> Result<bool> {
+        let updated = self
+            .connection
+            .execute(
+                "UPDATE orchestration_runs SET pinned=?1 WHERE id=?2",
+                params![pinned as i64, run_id],
+            )
+            .with_context(|| format!("Failed updating pin state for run_id={}.", run_id))?;
+        Ok(updated > 0)
+    }
+
+    pub fn count_pinned_runs(&self) -> Result<i64> {
+        self.connection
+            .query_row(
+                "SELECT COUNT(*) FROM orchestration_runs WHERE pinned=1",
+                [],
+                |row| row.get(0),
+            )
+            .context("Failed counting pinned runs.")
+    }
+
+    /// Finished, unpinned runs oldest first, with the bytes their transcripts,
+    /// step payloads, and snapshots occupy.
+    pub fn list_prunable_runs(&self) -> Result<Vec<PrunableRun>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT r.id, r.started_at,
+                    COALESCE((SELECT SUM(LENGTH(prompt_payload) + LENGTH(response_payload))
+                              FROM orchestration_traces WHERE run_id=r.id), 0)
+                  + COALESCE((SELECT SUM(LENGTH(COALESCE(step_input_json, '')) + LENGTH(COALESCE(step_output_json, '')))
+                              FROM orchestration_step_attempts WHERE run_id=r.id), 0)
+                  + COALESCE((SELECT SUM(LENGTH(snapshot_json))
+                              FROM orchestration_context_snapshots WHERE run_id=r.id), 0)
+                 FROM orchestration_runs r
+                 WHERE r.status <> 'running' AND r.pinned = 0
+                 ORDER BY r.started_at ASC, r.id ASC",
+            )
+            .context("Failed preparing prunable run query.")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(PrunableRun {
+                    run_id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    payload_bytes: row.get(2)?,
+                })
+            })
+            .context("Failed querying prunable runs.")?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    /// Delete runs (and their cascaded rows), then compact the database file.
+    pub fn delete_runs(&self, run_ids: &[i64]) -> Result<usize> {
+        if run_ids.is_empty() {
+            return Ok(0);
+        }
+        let deleted = self.in_transaction("run pruning", |connection| {
+            let mut deleted = 0usize;
+            for run_id in run_ids {
+                deleted += connection
+                    .execute(
+                        "DELETE FROM orchestration_runs WHERE id=?1 AND pinned=0",
+                        params![run_id],
+                    )
+                    .with_context(|| format!("Failed deleting run_id={}.", run_id))?;
+            }
+            Ok(deleted)
+        })?;
+        self.connection
+            .execute_batch("VACUUM;")
+            .context("Failed compacting orchestration SQLite after pruning.")?;
+        Ok(deleted)
+    }
+}
@@ -1,9 +1,12 @@
 use anyhow::{Context, Result};
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 
 use super::connection::now_epoch;
 use super::next_actions::reconcile_next_actions_tx;
-use super::{ContextNextAction, NextActionReconciliationSummary, OrchestrationStore, RunRecord};
+use super::{
+    ContextNextAction, LatestRunRow, NextActionReconciliationSummary, OrchestrationStore,
+    RunListingRow, RunParameters, RunRecord, RunningRun,
+};
 
 impl OrchestrationStore {
     pub fn create_run(
@@ -141,14 +144,11 @@ impl OrchestrationStore {
         Ok(None)
     }
 
-    pub fn latest_run_for_context(
-        &self,
-        context_file: &str,
-    ) -> Result<Option<(i64, String, String, Option<String>, Option<i64>, i64)>> {
+    pub fn latest_run_for_context(&self, context_file: &str) -> Result<Option<LatestRunRow>> {
         let mut stmt = self
             .connection
             .prepare(
-                "SELECT id, pipeline_name, status, terminal_reason, finished_at, started_at
+                "SELECT id, pipeline_name, status, terminal_reason, finished_at, started_at, stop_reason
                  FROM orchestration_runs WHERE context_file=?1 ORDER BY id DESC LIMIT 1",
             )
             .context("Failed preparing latest run query.")?;
@@ -163,6 +163,7 @@ impl OrchestrationStore {
                 row.get(3)?,
                 row.get(4)?,
                 row.get(5)?,
+                row.get(6)?,
             )));
         }
         Ok(None)
@@ -204,6 +205,7 @@ impl OrchestrationStore {
         context_file: &str,
         pipeline_filter: Option<&str>,
         terminal_reason: &str,
+        stop_reason: Option<&str>,
     ) -> Result<Option<(i64, String, Option<i64>)>> {
         self.in_transaction("stop active run", |conn| {
             let (sql, params_vec): (&str, Vec<rusqlite::types::Value>) =
@@ -236,8 +238,8 @@ impl OrchestrationStore {
             let runner_pid: Option<i64> = row.get(2)?;
 
             conn.execute(
-                "UPDATE orchestration_runs SET status='stopped', terminal_reason=?1, finished_at=?2 WHERE id=?3 AND status='running'",
-                params![terminal_reason, now_epoch(), run_id],
+                "UPDATE orchestration_runs SET status='stopped', terminal_reason=?1, stop_reason=?2, finished_at=?3 WHERE id=?4 AND status='running'",
+                params![terminal_reason, stop_reason, now_epoch(), run_id],
             )
             .with_context(|| format!("Failed to mark run {} as stopped.", run_id))?;
 
@@ -245,14 +247,31 @@ impl OrchestrationStore {
         })
     }
 
-    pub fn list_runs_for_context(
-        &self,
-        context_file: &str,
-    ) -> Result<Vec<(i64, String, String, Option<String>, i64, Option<i64>)>> {
+    pub fn list_running_runs(&self) -> Result<Vec<RunningRun>> {
         let mut stmt = self
             .connection
             .prepare(
-                "SELECT id, pipeline_name, status, terminal_reason, started_at, finished_at
+                "SELECT id, context_file, pipeline_name, runner_pid, started_at
+                 FROM orchestration_runs WHERE status='running' ORDER BY id",
+            )
+            .context("Failed preparing running run query.")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(RunningRun {
+                run_id: row.get(0)?,
+                context_file: row.get(1)?,
+                pipeline_name: row.get(2)?,
+                runner_pid: row.get(3)?,
+                started_at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    pub fn list_runs_for_context(&self, context_file: &str) -> Result<Vec<RunListingRow>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT id, pipeline_name, status, terminal_reason, started_at, finished_at, stop_reason
                  FROM orchestration_runs WHERE context_file=?1 ORDER BY id DESC",
             )
             .context("Failed preparing run listing query.")?;
@@ -264,6 +283,7 @@ impl OrchestrationStore {
                 row.get(3)?,
                 row.get(4)?,
                 row.get(5)?,
+                row.get(6)?,
             ))
         })?;
         Ok(rows.filter_map(Result::ok).collect())
@@ -284,7 +304,7 @@ impl OrchestrationStore {
         let mut stmt = self
             .connection
             .prepare(
-                "SELECT id, pipeline_name, context_file, status, terminal_reason, started_at, finished_at FROM orchestration_runs WHERE id=?1 LIMIT 1",
+                "SELECT id, pipeline_name, context_file, status, terminal_reason, stop_reason, started_at, finished_at FROM orchestration_runs WHERE id=?1 LIMIT 1",
             )
             .context("Failed preparing run detail query.")?;
         let mut rows = stmt
@@ -297,8 +317,49 @@ impl OrchestrationStore {
                 context_file: row.get(2)?,
                 status: row.get(3)?,
                 terminal_reason: row.get(4)?,
-                started_at: row.get(5)?,
-                finished_at: row.get(6)?,
+                stop_reason: row.get(5)?,
+                started_at: row.get(6)?,
+                finished_at: row.get(7)?,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Most recent run that ended as `failed`, across all contexts.
+    pub fn latest_failed_run(&self) -> Result<Option<RunRecord>> {
+        let run_id: Option<i64> = self
+            .connection
+            .query_row(
+                "SELECT id FROM orchestration_runs WHERE status='failed' ORDER BY id DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed querying latest failed run.")?;
+        match run_id {
+            Some(run_id) => self.get_run_by_id(run_id),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_run_parameters(&self, run_id: i64) -> Result<Option<RunParameters>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT r.run_fingerprint, r.context_id,
+                        (SELECT s.snapshot_hash FROM orchestration_context_snapshots s
+                         WHERE s.run_id = r.id ORDER BY s.id DESC LIMIT 1)
+                 FROM orchestration_runs r WHERE r.id=?1 LIMIT 1",
+            )
+            .context("Failed preparing run parameter query.")?;
+        let mut rows = stmt
+            .query(params![run_id])
+            .with_context(|| format!("Failed querying run parameters for id {}.", run_id))?;
+        if let Some(row) = rows.next()? {
+            return Ok(Some(RunParameters {
+                run_fingerprint: row.get(0)?,
+                context_id: row.get(1)?,
+                context_snapshot_hash: row.get(2)?,
             }));
         }
         Ok(None)
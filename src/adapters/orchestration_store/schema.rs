@@ -25,6 +25,8 @@ impl OrchestrationStore {
                 overwrite_requested INTEGER NOT NULL,
                 supersedes_run_id INTEGER,
                 runner_pid INTEGER,
+                stop_reason TEXT,
+                pinned INTEGER NOT NULL DEFAULT 0,
                 started_at INTEGER NOT NULL,
                 finished_at INTEGER
             );
@@ -106,6 +108,16 @@ impl OrchestrationStore {
                 FOREIGN KEY(run_id) REFERENCES orchestration_runs(id) ON DELETE CASCADE
             );
 
+            CREATE TABLE IF NOT EXISTS orchestration_action_test_cache (
+                context_id TEXT NOT NULL,
+                test_id TEXT NOT NULL,
+                action_hash TEXT NOT NULL,
+                test_file TEXT NOT NULL,
+                test_hash TEXT NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (context_id, test_id)
+            );
+
             CREATE TABLE IF NOT EXISTS orchestration_next_action_lifecycle (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 run_id INTEGER NOT NULL,
@@ -178,6 +190,18 @@ impl OrchestrationStore {
             "runner_pid",
             "INTEGER",
         )?;
+        ensure_column(
+            &self.connection,
+            "orchestration_runs",
+            "stop_reason",
+            "TEXT",
+        )?;
+        ensure_column(
+            &self.connection,
+            "orchestration_runs",
+            "pinned",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
 
         self.connection.execute(
             "INSERT OR REPLACE INTO orchestration_schema_meta (key, value) VALUES ('schema_version', '2')",
@@ -49,6 +49,24 @@ impl OrchestrationStore {
         Ok(None)
     }
 
+    /// `(run_id, context_file, context_snapshot)` for every running run, newest first.
+    pub fn list_active_run_snapshots(&self) -> Result<Vec<(i64, String, String)>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT r.id, r.context_file, s.snapshot_json
+                 FROM orchestration_runs r
+                 JOIN orchestration_context_snapshots s ON s.run_id = r.id
+                 WHERE r.status='running'
+                 ORDER BY r.id DESC",
+            )
+            .context("Failed preparing active run snapshot query.")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .context("Failed querying active run snapshots.")?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
     pub fn mark_context_runs_stale(&self, context_id: &str, newest_hash: &str) -> Result<()> {
         self.connection
             .execute(
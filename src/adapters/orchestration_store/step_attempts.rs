@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use rusqlite::params;
 
 use super::connection::now_epoch;
-use super::{OrchestrationStore, StepAttemptPersistence};
+use super::{OrchestrationStore, StepAttemptPersistence, StepAttemptRecord};
 
 impl OrchestrationStore {
     pub fn persist_step_attempt_with_traces(&self, record: &StepAttemptPersistence) -> Result<i64> {
@@ -78,4 +78,34 @@ impl OrchestrationStore {
         let rows = stmt.query_map(params![step_attempt_id], |row| row.get(0))?;
         Ok(rows.filter_map(Result::ok).collect())
     }
+
+    pub fn list_step_attempts_for_run(&self, run_id: i64) -> Result<Vec<StepAttemptRecord>> {
+        let mut stmt = self
+            .connection
+            .prepare(
+                "SELECT a.id, a.step_id, a.attempt_index, a.status, a.details, a.terminal_reason,
+                        a.started_at, a.finished_at,
+                        COALESCE((SELECT SUM(t.latency_ms) FROM orchestration_traces t
+                                  WHERE t.step_attempt_id = a.id), 0)
+                 FROM orchestration_step_attempts a
+                 WHERE a.run_id=?1 ORDER BY a.id ASC",
+            )
+            .context("Failed preparing step attempt listing query.")?;
+        let rows = stmt
+            .query_map(params![run_id], |row| {
+                Ok(StepAttemptRecord {
+                    step_attempt_id: row.get(0)?,
+                    step_id: row.get(1)?,
+                    attempt_index: row.get(2)?,
+                    status: row.get(3)?,
+                    details: row.get(4)?,
+                    terminal_reason: row.get(5)?,
+                    started_at: row.get(6)?,
+                    finished_at: row.get(7)?,
+                    latency_ms: row.get(8)?,
+                })
+            })
+            .with_context(|| format!("Failed listing step attempts for run {}.", run_id))?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
 }
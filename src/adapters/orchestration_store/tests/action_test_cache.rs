@@ -0,0 +1,41 @@
+use super::temp_store;
+use crate::adapters::orchestration_store::ActionTestCacheEntry;
+
+#[test]
+fn records_replaces_and_forgets_action_tests() {
+    let store = temp_store();
+    assert!(store
+        .cached_action_test("CDD_001", "login_test")
+        .expect("query empty cache")
+        .is_none());
+
+    let first = ActionTestCacheEntry {
+        action_hash: "a1".to_string(),
+        test_file: "tests/login_test.rs".to_string(),
+        test_hash: "t1".to_string(),
+    };
+    store
+        .record_action_test("CDD_001", "login_test", &first)
+        .expect("record first");
+    let second = ActionTestCacheEntry {
+        test_hash: "t2".to_string(),
+        ..first
+    };
+    store
+        .record_action_test("CDD_001", "login_test", &second)
+        .expect("record second");
+    assert_eq!(
+        store
+            .cached_action_test("CDD_001", "login_test")
+            .expect("query cache"),
+        Some(second)
+    );
+
+    store
+        .forget_action_test("CDD_001", "login_test")
+        .expect("forget");
+    assert!(store
+        .cached_action_test("CDD_001", "login_test")
+        .expect("query forgotten")
+        .is_none());
+}
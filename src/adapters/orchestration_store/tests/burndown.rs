@@ -0,0 +1,68 @@
+use crate::core::context::model::ContextNextAction;
+
+use super::temp_store;
+
+fn action(test_id: &str) -> ContextNextAction {
+    ContextNextAction {
+        description: format!("implement {}", test_id),
+        test_id: test_id.to_string(),
+    }
+}
+
+#[test]
+fn action_history_tracks_completion_removal_and_project() {
+    let store = temp_store();
+    let (first, _) = store
+        .create_run_with_snapshot_and_actions(
+            "default",
+            "BRN_001",
+            ".nexus/context/app/core/BRN_001.md",
+            "fp1",
+            false,
+            None,
+            None,
+            "h1",
+            "{}",
+            &[action("one"), action("two")],
+        )
+        .expect("first run");
+    store.finish_run(first, "failed", None).expect("fail first");
+    let (second, _) = store
+        .create_run_with_snapshot_and_actions(
+            "default",
+            "BRN_001",
+            ".nexus/context/app/core/BRN_001.md",
+            "fp2",
+            false,
+            None,
+            None,
+            "h2",
+            "{}",
+            &[action("one")],
+        )
+        .expect("second run");
+    store.finish_run(second, "success", None).expect("finish");
+    store
+        .create_run_with_snapshot_and_actions(
+            "default",
+            "OTH_001",
+            ".nexus/context/other/misc/OTH_001.md",
+            "fp3",
+            false,
+            None,
+            None,
+            "h3",
+            "{}",
+            &[action("three")],
+        )
+        .expect("other project run");
+
+    let history = store
+        .list_action_history(Some("app"))
+        .expect("app action history");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].action_key, "one::implement one");
+    assert!(history[0].completed_at.is_some());
+    assert!(history[1].completed_at.is_none());
+    assert_eq!(store.list_action_history(None).expect("all").len(), 3);
+}
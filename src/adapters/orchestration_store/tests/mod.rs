@@ -1,7 +1,10 @@
 use super::OrchestrationStore;
 
+mod action_test_cache;
+mod burndown;
 mod dependencies;
 mod next_actions;
+mod retention;
 mod runs;
 mod step_attempts;
 mod timeline;
@@ -0,0 +1,41 @@
+use super::temp_store;
+
+#[test]
+fn pinned_and_running_runs_are_not_prunable() {
+    let store = temp_store();
+    let old = store
+        .create_run("default", "RET_001", "ctx.md", "fp1", false, None, None)
+        .expect("create old run");
+    store.finish_run(old, "failed", None).expect("finish old");
+    let pinned = store
+        .create_run("default", "RET_001", "ctx.md", "fp2", false, None, None)
+        .expect("create pinned run");
+    store
+        .finish_run(pinned, "success", None)
+        .expect("finish pinned");
+    let running = store
+        .create_run("default", "RET_002", "other.md", "fp3", false, None, None)
+        .expect("create running run");
+
+    assert!(store.set_run_pinned(pinned, true).expect("pin"));
+    assert!(!store.set_run_pinned(9_999, true).expect("pin missing"));
+    assert_eq!(store.count_pinned_runs().expect("count pinned"), 1);
+
+    let prunable = store
+        .list_prunable_runs()
+        .expect("list prunable")
+        .into_iter()
+        .map(|run| run.run_id)
+        .collect::<Vec<_>>();
+    assert_eq!(prunable, vec![old]);
+
+    assert_eq!(
+        store
+            .delete_runs(&[old, pinned])
+            .expect("delete unpinned only"),
+        1
+    );
+    assert!(store.get_run_by_id(old).expect("get old").is_none());
+    assert!(store.get_run_by_id(pinned).expect("get pinned").is_some());
+    assert!(store.get_run_by_id(running).expect("get running").is_some());
+}
@@ -16,7 +16,7 @@ fn stop_active_run_marks_terminal_stopped_state() {
         .expect("create run");
 
     let stopped = store
-        .stop_active_run_for_context("ctx.md", None, "stopped_by_operator")
+        .stop_active_run_for_context("ctx.md", None, "stopped_by_operator", None)
         .expect("stop active run")
         .expect("run should be found");
     assert_eq!(stopped.0, run_id);
@@ -27,13 +27,44 @@ fn stop_active_run_marks_terminal_stopped_state() {
         .expect("run exists");
     assert_eq!(run.status, "stopped");
     assert_eq!(run.terminal_reason.as_deref(), Some("stopped_by_operator"));
+    assert!(run.stop_reason.is_none());
+}
+
+#[test]
+fn stop_reason_is_persisted_and_listed() {
+    let store = temp_store();
+    let run_id = store
+        .create_run("default", "ORC_REASON", "ctx.md", "fp", false, None, None)
+        .expect("create run");
+    store
+        .stop_active_run_for_context(
+            "ctx.md",
+            None,
+            "stopped_by_operator",
+            Some("flaky model endpoint"),
+        )
+        .expect("stop active run")
+        .expect("run should be found");
+
+    let run = store
+        .get_run_by_id(run_id)
+        .expect("get run")
+        .expect("run exists");
+    assert_eq!(run.stop_reason.as_deref(), Some("flaky model endpoint"));
+    let latest = store
+        .latest_run_for_context("ctx.md")
+        .expect("latest run")
+        .expect("run exists");
+    assert_eq!(latest.6.as_deref(), Some("flaky model endpoint"));
+    let listed = store.list_runs_for_context("ctx.md").expect("list runs");
+    assert_eq!(listed[0].6.as_deref(), Some("flaky model endpoint"));
 }
 
 #[test]
 fn stop_active_run_returns_none_when_no_active_run() {
     let store = temp_store();
     let stopped = store
-        .stop_active_run_for_context("ctx.md", None, "stopped_by_operator")
+        .stop_active_run_for_context("ctx.md", None, "stopped_by_operator", None)
         .expect("query should succeed");
     assert!(stopped.is_none());
 }
@@ -53,7 +84,7 @@ fn hard_restart_lineage_sets_supersedes_run_id() {
         )
         .expect("first run");
     let _ = store
-        .stop_active_run_for_context("ctx.md", None, "stopped_by_operator")
+        .stop_active_run_for_context("ctx.md", None, "stopped_by_operator", None)
         .expect("stop first");
 
     let second = store
@@ -78,3 +109,45 @@ fn hard_restart_lineage_sets_supersedes_run_id() {
         .expect("query supersedes");
     assert_eq!(supersedes, Some(first));
 }
+
+#[test]
+fn active_run_snapshots_exclude_finished_runs() {
+    let store = temp_store();
+    let (active, _) = store
+        .create_run_with_snapshot_and_actions(
+            "gen-code",
+            "ORC_ACTIVE",
+            "a.md",
+            "fp-a",
+            false,
+            None,
+            None,
+            "hash-a",
+            "Edit `src/a.rs`.",
+            &[],
+        )
+        .expect("active run");
+    let (finished, _) = store
+        .create_run_with_snapshot_and_actions(
+            "gen-code",
+            "ORC_DONE",
+            "b.md",
+            "fp-b",
+            false,
+            None,
+            None,
+            "hash-b",
+            "Edit `src/b.rs`.",
+            &[],
+        )
+        .expect("finished run");
+    store
+        .finish_run(finished, "success", None)
+        .expect("finish run");
+
+    let snapshots = store.list_active_run_snapshots().expect("active snapshots");
+    assert_eq!(
+        snapshots,
+        vec![(active, "a.md".to_string(), "Edit `src/a.rs`.".to_string())]
+    );
+}
@@ -41,3 +41,50 @@ fn persists_step_attempt_with_io_and_trace_transactionally() {
     assert_eq!(traces[0].4, 5);
     assert_eq!(traces[0].5, 42);
 }
+
+#[test]
+fn lists_step_attempts_with_details_and_trace_latency() {
+    let store = temp_store();
+    let run_id = store
+        .create_run("default", "ORC_004", "ctx.md", "fp", false, None, None)
+        .expect("create run");
+    store
+        .persist_step_attempt_with_traces(&StepAttemptPersistence {
+            run_id,
+            step_id: "verify_red_tests".to_string(),
+            attempt_index: 1,
+            status: "failed".to_string(),
+            details: "compilation failed".to_string(),
+            terminal_reason: Some("red_gate_failed".to_string()),
+            step_input_json: "{}".to_string(),
+            step_output_json: "{}".to_string(),
+            traces: vec![TraceRecordInput {
+                step_id: "verify_red_tests".to_string(),
+                attempt_index: 1,
+                model: "opencode/default".to_string(),
+                prompt: "p".to_string(),
+                response: "r".to_string(),
+                status: "failed".to_string(),
+                latency_ms: 30,
+                token_usage: 1,
+                terminal_status: "failed".to_string(),
+                artifact_refs_json: "[]".to_string(),
+            }],
+        })
+        .expect("persist step attempt");
+
+    let attempts = store
+        .list_step_attempts_for_run(run_id)
+        .expect("list attempts");
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].step_id, "verify_red_tests");
+    assert_eq!(attempts[0].details.as_deref(), Some("compilation failed"));
+    assert_eq!(attempts[0].latency_ms, 30);
+
+    let parameters = store
+        .get_run_parameters(run_id)
+        .expect("query parameters")
+        .expect("run exists");
+    assert_eq!(parameters.run_fingerprint, "fp");
+    assert!(parameters.context_snapshot_hash.is_none());
+}
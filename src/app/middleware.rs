@@ -0,0 +1,566 @@
+//! Middleware layers wrapped around command dispatch.
+//!
+//! Cross-cutting concerns (role policy, audit recording, timing, output flushing) live here
+//! as `Middleware` layers instead of inside individual `run_*` functions.
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::{
+    AssetsCommands, AuditCommands, Commands, ConfigCommands, ContextCommands, GraphCommands,
+    MarketplaceCommands, PluginCommands, PresetCommands, RefsCommands, Role, SetupCommands,
+};
+use crate::commands::audit::READ_ONLY_ORCHESTRATION_MODES;
+use crate::commands::{audit_operation, prune_summary_message, record_audit_event};
+use crate::config::nexus::{load_nexus_config, NEXUS_CONFIG_PATH};
+use crate::config::stateless::is_stateless;
+use crate::core::error::{CliError, ErrorCode};
+use crate::features::retention::{lazy_prune_due, prune_history, record_lazy_prune};
+use crate::output::{print_error, print_info, print_warning};
+use crate::services::progress::{emit_progress, ProgressEvent};
+use crate::utils::time::unix_now_seconds;
+
+/// Per-invocation facts shared with every middleware layer.
+#[derive(Debug, Clone)]
+pub struct CommandContext {
+    pub name: String,
+    /// Whether the command writes files or changes repository or machine state.
+    pub mutates: bool,
+    pub audit: Option<(String, Value)>,
+}
+
+impl CommandContext {
+    pub fn new(command: Option<&Commands>) -> Self {
+        Self {
+            name: command_name(command),
+            mutates: command.is_some_and(command_mutates),
+            audit: command.and_then(audit_operation),
+        }
+    }
+}
+
+pub trait Middleware {
+    /// Runs before the command; an error aborts dispatch.
+    fn before(&mut self, _context: &CommandContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs after the command, innermost layer first.
+    fn after(&mut self, _context: &CommandContext, _outcome: &Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct MiddlewarePipeline {
+    layers: Vec<Box<dyn Middleware>>,
+}
+
+impl MiddlewarePipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pipeline used by the CLI binary.
+    pub fn standard(role: Role) -> Self {
+        Self::new()
+            .with(RolePolicyMiddleware { role })
+            .with(FlushOutputMiddleware)
+            .with(TimingMiddleware::default())
+            .with(ProgressMiddleware)
+            .with(AuditMiddleware)
+            .with(RetentionMiddleware)
+    }
+
+    pub fn with(mut self, layer: impl Middleware + 'static) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// Run `command` inside every layer.
+    ///
+    /// A failing `after` hook surfaces as the result only when the command
+    /// itself succeeded; otherwise it is reported and the command error wins.
+    pub fn run(
+        &mut self,
+        context: &CommandContext,
+        command: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        for layer in &mut self.layers {
+            layer.before(context)?;
+        }
+        let outcome = command();
+        let mut after_error = None;
+        for layer in self.layers.iter_mut().rev() {
+            if let Err(err) = layer.after(context, &outcome) {
+                if outcome.is_err() || after_error.is_some() {
+                    print_error(&format!("{:#}", err));
+                } else {
+                    after_error = Some(err);
+                }
+            }
+        }
+        match after_error {
+            Some(err) => Err(err),
+            None => outcome,
+        }
+    }
+}
+
+/// Refuses mutating commands for the read-only `viewer` role.
+///
+/// The check reads [`CommandContext::mutates`], not the audit operation: some
+/// commands write files without being audited.
+pub struct RolePolicyMiddleware {
+    pub role: Role,
+}
+
+impl Middleware for RolePolicyMiddleware {
+    fn before(&mut self, context: &CommandContext) -> Result<()> {
+        if self.role == Role::Viewer && context.mutates {
+            bail!(CliError::new(
+                ErrorCode::Forbidden,
+                format!(
+                    "`{}` is disabled for the viewer role because it modifies repository or machine state. Remediation: ask a maintainer to run it; listing, status, and report commands remain available.",
+                    context.name.replace('.', " ")
+                )
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Records successful mutating commands in the audit log.
+pub struct AuditMiddleware;
+
+impl Middleware for AuditMiddleware {
+    fn after(&mut self, context: &CommandContext, outcome: &Result<()>) -> Result<()> {
+        match (&context.audit, outcome) {
+            (Some((operation, parameters)), Ok(())) => {
+                record_audit_event(operation, parameters.clone())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Lazily prunes run history under the configured retention policy, at most
+/// once a day. Failures are reported as warnings and never block the command.
+pub struct RetentionMiddleware;
+
+impl Middleware for RetentionMiddleware {
+    fn before(&mut self, context: &CommandContext) -> Result<()> {
+        if is_stateless() || matches!(context.name.as_str(), "clean" | "complete") {
+            return Ok(());
+        }
+        if let Err(err) = lazy_prune_history() {
+            print_warning(&format!("Skipped history pruning: {:#}", err));
+        }
+        Ok(())
+    }
+}
+
+fn lazy_prune_history() -> Result<()> {
+    let now = unix_now_seconds();
+    if !lazy_prune_due(now) {
+        return Ok(());
+    }
+    let policy = load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?.retention;
+    let database_path = default_orchestration_database_path();
+    if policy.is_empty() || !database_path.exists() {
+        return Ok(());
+    }
+    let summary = prune_history(
+        &OrchestrationStore::open(&database_path)?,
+        &policy,
+        now,
+        false,
+    )?;
+    record_lazy_prune(now)?;
+    if summary.runs_pruned > 0 {
+        print_info(&prune_summary_message(&summary));
+    }
+    Ok(())
+}
+
+/// Reports command start and finish on `--progress-fd`.
+pub struct ProgressMiddleware;
+
+impl Middleware for ProgressMiddleware {
+    fn before(&mut self, context: &CommandContext) -> Result<()> {
+        emit_progress(ProgressEvent::CommandStarted {
+            command: context.name.clone(),
+        });
+        Ok(())
+    }
+
+    fn after(&mut self, context: &CommandContext, outcome: &Result<()>) -> Result<()> {
+        emit_progress(ProgressEvent::CommandFinished {
+            command: context.name.clone(),
+            status: if outcome.is_ok() { "success" } else { "failed" },
+            error: outcome.as_ref().err().map(|err| format!("{:#}", err)),
+        });
+        Ok(())
+    }
+}
+
+/// Prints command wall time to stderr when `NEXUS_TIMING` is set.
+#[derive(Default)]
+pub struct TimingMiddleware {
+    started_at: Option<Instant>,
+}
+
+impl Middleware for TimingMiddleware {
+    fn before(&mut self, context: &CommandContext) -> Result<()> {
+        tracing::debug!(command = %context.name, "command started");
+        self.started_at = Some(Instant::now());
+        Ok(())
+    }
+
+    fn after(&mut self, context: &CommandContext, outcome: &Result<()>) -> Result<()> {
+        if let Some(started_at) = self.started_at {
+            tracing::debug!(
+                command = %context.name,
+                elapsed_ms = started_at.elapsed().as_millis() as u64,
+                success = outcome.is_ok(),
+                "command finished"
+            );
+        }
+        if std::env::var_os("NEXUS_TIMING").is_none() {
+            return Ok(());
+        }
+        if let Some(started_at) = self.started_at {
+            print_info(&format!(
+                "{} finished in {}ms",
+                context.name,
+                started_at.elapsed().as_millis()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Flushes stdout and stderr once the command has finished.
+pub struct FlushOutputMiddleware;
+
+impl Middleware for FlushOutputMiddleware {
+    fn after(&mut self, _context: &CommandContext, _outcome: &Result<()>) -> Result<()> {
+        std::io::stdout().flush()?;
+        std::io::stderr().flush()?;
+        Ok(())
+    }
+}
+
+fn command_name(command: Option<&Commands>) -> String {
+    let name = match command {
+        None => "help",
+        Some(Commands::Setup {
+            command: Some(SetupCommands::Rollback),
+            ..
+        }) => "setup.rollback",
+        Some(Commands::Setup {
+            command: Some(SetupCommands::Verify),
+            ..
+        }) => "setup.verify",
+        Some(Commands::Setup {
+            command: Some(SetupCommands::Repair),
+            ..
+        }) => "setup.repair",
+        Some(Commands::Setup { .. }) => "setup",
+        Some(Commands::Update { check: true, .. }) => "update.check",
+        Some(Commands::Update { .. }) => "update",
+        Some(Commands::Uninstall { .. }) => "uninstall",
+        Some(Commands::Marketplace { command }) => match command {
+            MarketplaceCommands::List => "marketplace.list",
+            MarketplaceCommands::Search { .. } => "marketplace.search",
+            MarketplaceCommands::Install { .. } => "marketplace.install",
+        },
+        Some(Commands::Ralph(_)) => "ralph",
+        Some(Commands::Orchestration(command)) => {
+            return format!("orchestration.{}", command.pipeline_name)
+        }
+        Some(Commands::Analyze { .. }) => "analyze",
+        Some(Commands::Apply { .. }) => "apply",
+        Some(Commands::Learn { .. }) => "learn",
+        Some(Commands::Badge { .. }) => "badge",
+        Some(Commands::Status) => "status",
+        Some(Commands::Doctor) => "doctor",
+        Some(Commands::BugReport { .. }) => "bug-report",
+        Some(Commands::Scaffold { .. }) => "scaffold",
+        Some(Commands::Burndown { .. }) => "burndown",
+        Some(Commands::Clean { .. }) => "clean",
+        Some(Commands::Context { command }) => match command {
+            ContextCommands::Index { .. } => "context.index",
+            ContextCommands::Implement(_) => "context.implement",
+            ContextCommands::TestStatus { .. } => "context.test-status",
+            ContextCommands::Backfill { .. } => "context.backfill",
+        },
+        Some(Commands::Refs { command }) => match command {
+            RefsCommands::Remap { .. } => "refs.remap",
+        },
+        Some(Commands::Audit { command }) => match command {
+            AuditCommands::Verify => "audit.verify",
+            AuditCommands::Export { .. } => "audit.export",
+        },
+        Some(Commands::Config { command }) => match command {
+            ConfigCommands::Doctor { .. } => "config.doctor",
+            ConfigCommands::Get { .. } => "config.get",
+            ConfigCommands::Set { .. } => "config.set",
+            ConfigCommands::Unset { .. } => "config.unset",
+            ConfigCommands::List { .. } => "config.list",
+        },
+        Some(Commands::Preset { command }) => match command {
+            PresetCommands::Save { .. } => "preset.save",
+            PresetCommands::List => "preset.list",
+            PresetCommands::Delete { .. } => "preset.delete",
+        },
+        Some(Commands::Graph { command }) => match command {
+            GraphCommands::Serve { .. } => "graph.serve",
+        },
+        Some(Commands::Assets { command }) => match command {
+            AssetsCommands::Lint { .. } => "assets.lint",
+        },
+        Some(Commands::Shell) => "shell",
+        Some(Commands::Plugin { command }) => match command {
+            PluginCommands::List => "plugin.list",
+        },
+        Some(Commands::External(args)) => {
+            return format!("plugin.{}", args.first().map_or("", String::as_str))
+        }
+        Some(Commands::Complete { .. }) => "complete",
+    };
+    name.to_string()
+}
+
+/// Whether `command` writes files or changes repository or machine state.
+///
+/// Every command is classified here on its own rather than through
+/// [`audit_operation`], so a new command cannot reach viewers unclassified.
+fn command_mutates(command: &Commands) -> bool {
+    match command {
+        Commands::Setup {
+            command: Some(SetupCommands::Verify),
+            ..
+        } => false,
+        Commands::Setup {
+            command: Some(SetupCommands::Rollback | SetupCommands::Repair),
+            ..
+        } => true,
+        Commands::Setup {
+            command: None,
+            dry_run,
+            ..
+        } => !dry_run,
+        Commands::Update { check, .. } => !check,
+        Commands::Uninstall { dry_run, .. } => !dry_run,
+        Commands::Marketplace { command } => match command {
+            MarketplaceCommands::Install { .. } => true,
+            MarketplaceCommands::List | MarketplaceCommands::Search { .. } => false,
+        },
+        Commands::Ralph(_) => true,
+        Commands::Orchestration(command) => {
+            !READ_ONLY_ORCHESTRATION_MODES.contains(&command.pipeline_name.as_str())
+        }
+        Commands::Analyze { apply, .. } => *apply,
+        Commands::Apply { dry_run, .. } => !dry_run,
+        // Writes the tutorial sandbox and its checkpoints.
+        Commands::Learn { .. } => true,
+        Commands::Badge { .. } => true,
+        // Writes the diagnostic bundle, to a default path without --out.
+        Commands::BugReport { .. } => true,
+        Commands::Scaffold { .. } => true,
+        Commands::Clean { dry_run, .. } => !dry_run,
+        Commands::Context { command } => match command {
+            ContextCommands::Index { .. }
+            | ContextCommands::Implement(_)
+            | ContextCommands::Backfill { .. } => true,
+            ContextCommands::TestStatus { .. } => false,
+        },
+        Commands::Refs {
+            command: RefsCommands::Remap { dry_run, .. },
+        } => !dry_run,
+        Commands::Audit { command } => match command {
+            AuditCommands::Verify => false,
+            AuditCommands::Export { out } => out.is_some(),
+        },
+        Commands::Config { command } => match command {
+            ConfigCommands::Doctor { dry_run } => !dry_run,
+            ConfigCommands::Set { .. } | ConfigCommands::Unset { .. } => true,
+            ConfigCommands::Get { .. } | ConfigCommands::List { .. } => false,
+        },
+        Commands::Preset { command } => match command {
+            PresetCommands::Save { .. } | PresetCommands::Delete { .. } => true,
+            PresetCommands::List => false,
+        },
+        // A plugin can do anything.
+        Commands::External(_) => true,
+        // Each line is dispatched, and checked, on its own.
+        Commands::Shell => false,
+        Commands::Status
+        | Commands::Doctor
+        | Commands::Burndown { .. }
+        | Commands::Graph { .. }
+        | Commands::Assets { .. }
+        | Commands::Plugin { .. }
+        | Commands::Complete { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Recorder {
+        label: &'static str,
+        events: Rc<RefCell<Vec<String>>>,
+        fail_after: bool,
+    }
+
+    impl Middleware for Recorder {
+        fn before(&mut self, _context: &CommandContext) -> Result<()> {
+            self.events
+                .borrow_mut()
+                .push(format!("before:{}", self.label));
+            Ok(())
+        }
+
+        fn after(&mut self, _context: &CommandContext, outcome: &Result<()>) -> Result<()> {
+            self.events
+                .borrow_mut()
+                .push(format!("after:{}:{}", self.label, outcome.is_ok()));
+            if self.fail_after {
+                return Err(anyhow!("{} after failed", self.label));
+            }
+            Ok(())
+        }
+    }
+
+    fn context() -> CommandContext {
+        CommandContext::new(None)
+    }
+
+    #[test]
+    fn layers_wrap_command_in_onion_order() {
+        let events = Rc::new(RefCell::new(Vec::<String>::new()));
+        let mut pipeline = MiddlewarePipeline::new()
+            .with(Recorder {
+                label: "outer",
+                events: events.clone(),
+                fail_after: false,
+            })
+            .with(Recorder {
+                label: "inner",
+                events: events.clone(),
+                fail_after: false,
+            });
+
+        let command_events = events.clone();
+        pipeline
+            .run(&context(), || {
+                command_events.borrow_mut().push("command".to_string());
+                Ok(())
+            })
+            .expect("pipeline should succeed");
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "before:outer",
+                "before:inner",
+                "command",
+                "after:inner:true",
+                "after:outer:true",
+            ]
+        );
+    }
+
+    #[test]
+    fn command_error_takes_precedence_over_after_errors() {
+        let events = Rc::new(RefCell::new(Vec::<String>::new()));
+        let mut pipeline = MiddlewarePipeline::new().with(Recorder {
+            label: "only",
+            events: events.clone(),
+            fail_after: true,
+        });
+
+        let err = pipeline
+            .run(&context(), || Err(anyhow!("command failed")))
+            .expect_err("command error should surface");
+        assert_eq!(err.to_string(), "command failed");
+
+        let err = pipeline
+            .run(&context(), || Ok(()))
+            .expect_err("after error should surface");
+        assert_eq!(err.to_string(), "only after failed");
+    }
+
+    #[test]
+    fn command_context_names_nested_subcommands() {
+        use crate::cli::Cli;
+        use clap::Parser;
+
+        let cli = Cli::parse_from(["opennexus", "marketplace", "install", "fumadocs"]);
+        let context = CommandContext::new(cli.command.as_ref());
+        assert_eq!(context.name, "marketplace.install");
+        assert_eq!(
+            context.audit.map(|(operation, _)| operation),
+            Some("marketplace.install".to_string())
+        );
+    }
+
+    #[test]
+    fn viewer_role_blocks_only_mutating_commands() {
+        use crate::cli::Cli;
+        use clap::Parser;
+
+        let mut viewer =
+            MiddlewarePipeline::new().with(RolePolicyMiddleware { role: Role::Viewer });
+        let install = Cli::parse_from(["opennexus", "marketplace", "install", "fumadocs"]);
+        let err = viewer
+            .run(&CommandContext::new(install.command.as_ref()), || Ok(()))
+            .expect_err("viewer should not install");
+        assert!(err
+            .to_string()
+            .starts_with("`marketplace install` is disabled"));
+
+        // Written files count even when the command is not audited.
+        for args in [
+            &["opennexus", "bug-report", "--out", "report.json"][..],
+            &["opennexus", "audit", "export", "--out", "audit.json"],
+            &["opennexus", "learn"],
+            &["opennexus", "badge", "--out", "badges"],
+        ] {
+            let cli = Cli::parse_from(args);
+            viewer
+                .run(&CommandContext::new(cli.command.as_ref()), || Ok(()))
+                .expect_err("viewer should not write files");
+        }
+
+        for args in [
+            &["opennexus", "orchestration", "runs"][..],
+            &["opennexus", "audit", "export"],
+            &["opennexus", "setup", "--dry-run"],
+        ] {
+            let cli = Cli::parse_from(args);
+            viewer
+                .run(&CommandContext::new(cli.command.as_ref()), || Ok(()))
+                .expect("viewer may read");
+        }
+
+        let mut maintainer = MiddlewarePipeline::new().with(RolePolicyMiddleware {
+            role: Role::Maintainer,
+        });
+        maintainer
+            .run(&CommandContext::new(install.command.as_ref()), || Ok(()))
+            .expect("maintainer may install");
+    }
+}
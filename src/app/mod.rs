@@ -1,3 +1,4 @@
+pub mod middleware;
 pub mod orchestration;
 pub mod ralph;
 
@@ -1,20 +1,27 @@
 use anyhow::Result;
 
 use crate::core::context::model::{
-    ContextBackfillOptions, ContextImplementOptions, ContextTestStatusOptions,
+    ContextBackfillOptions, ContextBackfillReport, ContextImplementOptions,
+    ContextImplementSummary, ContextTestStatusOptions, ContextTestStatusReport,
 };
 use crate::features::context::{
     run_context_backfill, run_context_implement, run_context_test_status,
 };
 
-pub fn run_orchestration_implement_app(options: &ContextImplementOptions) -> Result<()> {
+pub fn run_orchestration_implement_app(
+    options: &ContextImplementOptions,
+) -> Result<ContextImplementSummary> {
     run_context_implement(options)
 }
 
-pub fn run_orchestration_test_status_app(options: &ContextTestStatusOptions) -> Result<()> {
+pub fn run_orchestration_test_status_app(
+    options: &ContextTestStatusOptions,
+) -> Result<ContextTestStatusReport> {
     run_context_test_status(options)
 }
 
-pub fn run_orchestration_backfill_app(options: &ContextBackfillOptions) -> Result<()> {
+pub fn run_orchestration_backfill_app(
+    options: &ContextBackfillOptions,
+) -> Result<ContextBackfillReport> {
     run_context_backfill(options)
 }
@@ -3,7 +3,7 @@ use std::env;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, ExitCode};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -66,10 +66,13 @@ struct NexusConfig {
     extra: BTreeMap<String, Value>,
 }
 
-fn main() {
-    if let Err(err) = run() {
-        eprintln!("docs-sync error: {err}");
-        std::process::exit(1);
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("docs-sync error: {err}");
+            ExitCode::FAILURE
+        }
     }
 }
 
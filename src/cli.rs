@@ -1,6 +1,10 @@
 //! CLI argument parsing for the OpenNexus binary.
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+
+use crate::features::setup_template::parse_template_var;
 
 #[derive(Debug, Parser)]
 #[command(name = "opennexus")]
@@ -11,40 +15,322 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
-    /// Output format.
-    #[arg(long, global = true, default_value = "text")]
-    pub format: OutputFormat,
+    /// Output format (default: text on a terminal, tsv when stdout is piped).
+    #[arg(long, global = true)]
+    pub format: Option<OutputFormat>,
+
+    /// When to color status messages (default: auto, or the user config preference).
+    #[arg(long, global = true, value_name = "WHEN")]
+    pub color: Option<ColorChoice>,
+
+    /// JMESPath expression applied to JSON output (requires --format json).
+    #[arg(long, global = true, value_name = "EXPR")]
+    pub query: Option<String>,
+
+    /// Skip local state writes (run history, caches, audit log); also NEXUS_STATELESS=1.
+    #[arg(long, global = true)]
+    pub stateless: bool,
+
+    /// Show absolute UTC RFC 3339 timestamps instead of relative times in text output.
+    #[arg(long, global = true, visible_alias = "utc")]
+    pub iso: bool,
+
+    /// Write JSON-lines progress events to this already-open file descriptor.
+    #[arg(long, global = true, value_name = "FD")]
+    pub progress_fd: Option<u32>,
+
+    /// Permission role; `viewer` disables every command that mutates state.
+    #[arg(long, global = true)]
+    pub role: Option<Role>,
+
+    /// Mirror log events to stderr: -v for info, -vv for debug.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only print warnings and errors on stderr.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Append debug logs here instead of ~/.local/share/opennexus/logs/cli.log.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<String>,
+
+    /// Attempts per network fetch before giving up (default: 4).
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub retry_attempts: Option<u32>,
+
+    /// Fail on the first network error instead of retrying with backoff.
+    #[arg(long, global = true, conflicts_with = "retry_attempts")]
+    pub no_retry: bool,
 }
 
 impl Cli {
     pub fn parse_args() -> Self {
         Self::parse()
     }
+
+    /// The explicit `--format`, the user's preferred format, or one picked
+    /// from whether stdout is a terminal.
+    pub fn output_format(&self, preferred: Option<OutputFormat>) -> OutputFormat {
+        resolve_output_format(self.format.or(preferred), std::io::stdout().is_terminal())
+    }
+
+    /// Whether any global flag was passed. `shell` fixes them for the session,
+    /// so lines typed inside it must not repeat them.
+    pub fn has_global_flags(&self) -> bool {
+        self.format.is_some()
+            || self.color.is_some()
+            || self.query.is_some()
+            || self.stateless
+            || self.iso
+            || self.progress_fd.is_some()
+            || self.role.is_some()
+            || self.verbose > 0
+            || self.quiet
+            || self.log_file.is_some()
+            || self.retry_attempts.is_some()
+            || self.no_retry
+    }
+
+    /// The more restrictive of `--role` and the role pinned in the user
+    /// config, so a flag can drop privileges but never regain them.
+    pub fn role(&self, configured: Option<Role>) -> Role {
+        self.role
+            .unwrap_or_default()
+            .max(configured.unwrap_or_default())
+    }
+}
+
+fn resolve_output_format(explicit: Option<OutputFormat>, stdout_is_tty: bool) -> OutputFormat {
+    match explicit {
+        Some(format) => format,
+        None if stdout_is_tty => OutputFormat::Text,
+        None => OutputFormat::Tsv,
+    }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub const DEFAULT_MAX_ITERATIONS: usize = 3;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 600;
+pub const DEFAULT_GRAPH_HOST: &str = "127.0.0.1";
+pub const DEFAULT_GRAPH_PORT: u16 = 7878;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     #[default]
     Text,
     Json,
+    /// Tab-separated rows for list commands; other commands print text.
+    Tsv,
+    /// The JSON documents rendered as YAML.
+    Yaml,
+    /// Newline-delimited JSON: one compact document per line, arrays split per element.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Whether commands should emit documents (`json`, `yaml`, `ndjson`)
+    /// rather than human-readable text or TSV rows.
+    pub fn is_structured(self) -> bool {
+        matches!(self, Self::Json | Self::Yaml | Self::Ndjson)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BurndownChart {
+    /// Remaining actions per day.
+    #[default]
+    Burndown,
+    /// Completed actions stacked under remaining scope.
+    Flow,
+}
+
+/// How setup points harness directories at `.nexus` assets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    /// Relative symlinks; on Windows they need Developer Mode or admin rights.
+    Symlink,
+    /// Hard links, file by file; both paths must be on one volume.
+    Hardlink,
+    /// Directory junctions (Windows only), with files hard linked.
+    Junction,
+    /// Independent copies, re-synced by `setup repair`.
+    Copy,
+}
+
+impl LinkMode {
+    /// Symlinks on Unix, copies elsewhere.
+    pub fn platform_default() -> Self {
+        if cfg!(unix) {
+            Self::Symlink
+        } else {
+            Self::Copy
+        }
+    }
+}
+
+/// How `update` replaces the running binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateMethod {
+    /// Rebuild from crates.io with `cargo install`.
+    Cargo,
+    /// Download the prebuilt release binary for this platform.
+    Binary,
+}
+
+/// Release stream `update` follows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// The latest full release.
+    #[default]
+    Stable,
+    /// The newest release, prereleases included.
+    Beta,
+    /// The rolling build of the default branch.
+    Nightly,
+}
+
+/// Ordered from most to least privileged.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    Maintainer,
+    /// Read-only: listing, status, and report commands only.
+    Viewer,
 }
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum Commands {
     /// Set up Nexus in the current project (extracts .nexus directory).
+    #[command(args_conflicts_with_subcommands = true)]
     Setup {
+        #[command(subcommand)]
+        command: Option<SetupCommands>,
+
         /// Harness to configure in .nexus/config.json.
         ///
-        /// If omitted, setup opens an interactive fuzzy picker.
+        /// Without any flags in a terminal, setup runs an interactive wizard
+        /// for harness integrations and context templates; with other flags
+        /// but no harness, it opens a fuzzy picker.
         #[arg(long)]
         harness: Option<String>,
+
+        /// Asset profile to install: `full`, `minimal`, or one defined under
+        /// `setup_profiles` in .nexus/config.json.
+        ///
+        /// If omitted, the profile recorded by the last setup is reused.
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Set a template variable substituted into `{{name}}` placeholders in
+        /// the bundled assets, e.g. `--var prefix=APP`. Repeatable; values
+        /// are remembered for later runs.
+        #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_template_var)]
+        vars: Vec<(String, String)>,
+
+        /// How to link harness directories to .nexus assets. Defaults to the
+        /// mode of the last setup, else symlinks on Unix and copies elsewhere.
+        #[arg(long, value_enum)]
+        link_mode: Option<LinkMode>,
+
+        /// Install assets from this bundle instead of the one built into the
+        /// CLI: a git URL (`url#ref` for a branch or tag), a tarball URL, or
+        /// a local directory or tarball. Remembered for later runs; pass
+        /// `builtin` to switch back.
+        #[arg(long, value_name = "SOURCE")]
+        from: Option<String>,
+
+        /// SHA-256 the `--from` bundle's digest must match. Setup prints the
+        /// digest of the bundle it installs.
+        #[arg(long, requires = "from")]
+        sha256: Option<String>,
+
+        /// Do not add setup's generated paths to a managed block in
+        /// .gitignore. Remembered as `"gitignore": false` in
+        /// .nexus/config.json.
+        #[arg(long)]
+        no_gitignore: bool,
+
+        /// Skip the executables in .nexus/hooks/post-setup.d/ that otherwise
+        /// run, in name order, after setup completes.
+        #[arg(long)]
+        no_hooks: bool,
+
+        /// List the files and links setup would create, replace, or prune
+        /// without writing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Update Nexus to the latest published version.
+    #[command(disable_version_flag = true)]
+    Update {
+        /// How to install it; defaults to cargo when this binary lives in
+        /// cargo's bin directory and to the prebuilt release binary otherwise.
+        #[arg(long, value_enum)]
+        method: Option<UpdateMethod>,
+
+        /// Install this exact version instead of the channel's latest.
+        #[arg(long, conflicts_with = "channel")]
+        version: Option<String>,
+
+        /// Release channel to follow; remembered in the user config for
+        /// later updates (default: the remembered one, else stable).
+        #[arg(long, value_enum)]
+        channel: Option<UpdateChannel>,
+
+        /// Rerun this project's setup with the new binary when its .nexus
+        /// assets are older, without asking.
+        #[arg(long)]
+        sync_assets: bool,
+
+        /// Only report whether a newer release is available.
+        #[arg(long, conflicts_with_all = ["method", "version", "sync_assets"])]
+        check: bool,
     },
 
-    /// Update Nexus to the latest published version via cargo.
-    Update,
+    /// Uninstall Nexus the way it was installed, optionally purging its files.
+    ///
+    /// Runs `cargo uninstall` for cargo installs and deletes the release binary
+    /// otherwise. --remove-assets also removes this project's setup files, and
+    /// --purge deletes the global data, cache, and config directories.
+    Uninstall {
+        /// Also remove the files and links setup recorded in
+        /// .nexus/.manifest.json, keeping locally modified files.
+        #[arg(long)]
+        remove_assets: bool,
+
+        /// Also delete the global data, cache, and config directories
+        /// (logs, bundle cache, update check, shell history, preferences).
+        #[arg(long)]
+        purge: bool,
 
-    /// Uninstall Nexus via cargo.
-    Uninstall,
+        /// With --purge, also delete this project's .nexus directory and the
+        /// harness links setup recorded, whether or not they were edited.
+        #[arg(long, requires = "purge", conflicts_with = "remove_assets")]
+        project: bool,
+
+        /// List what --purge would remove without removing anything or
+        /// uninstalling the binary.
+        #[arg(long, requires = "purge")]
+        dry_run: bool,
+    },
 
     /// Search and install Nexus marketplace assets.
     Marketplace {
@@ -57,6 +343,187 @@ pub enum Commands {
 
     /// Run an orchestration pipeline by name.
     Orchestration(OrchestrationCommand),
+
+    /// Analyze a repository and propose an initial context structure.
+    Analyze {
+        /// Repository root to analyze.
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// Project name to use instead of the repository directory name.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Write the proposed index files that do not exist yet.
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Converge projects and contexts to a declarative YAML manifest.
+    Apply {
+        /// Manifest listing projects and their contexts.
+        manifest: String,
+
+        /// Delete contexts in manifest projects that the manifest does not list.
+        #[arg(long)]
+        prune: bool,
+
+        /// Apply without the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Show the plan without applying it.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Walk through a resumable Nexus tutorial in a sandbox directory.
+    Learn {
+        /// Sandbox directory for tutorial files and checkpoints.
+        #[arg(long, default_value = "nexus-tutorial")]
+        dir: String,
+
+        /// Discard stored checkpoints and start over.
+        #[arg(long)]
+        reset: bool,
+    },
+
+    /// Generate SVG status badges for README embedding.
+    Badge {
+        /// Only include contexts under .nexus/context/<project>/.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Directory the badge SVG files are written to.
+        #[arg(long, default_value = "badges")]
+        out: String,
+    },
+
+    /// Chart next actions completed over time from orchestration history.
+    Burndown {
+        /// Only include contexts under .nexus/context/<project>/.
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Limit the chart to the last N days.
+        #[arg(long)]
+        days: Option<u32>,
+
+        /// Chart style.
+        #[arg(long, value_enum, default_value_t = BurndownChart::Burndown)]
+        chart: BurndownChart,
+    },
+
+    /// Summarize setup, context counts per project, and in-progress runs.
+    Status,
+
+    /// Check binaries, config, assets, harness links, contexts, and stale runs.
+    Doctor,
+
+    /// Write a redacted diagnostic bundle and print a pre-filled GitHub issue URL.
+    BugReport {
+        /// Bundle path [default: nexus-bug-report-<unix-time>.json].
+        #[arg(long)]
+        out: Option<String>,
+    },
+
+    /// Create stub tests and module skeletons for a context's pending actions.
+    Scaffold {
+        /// Context id from the context frontmatter, e.g. CDD_001.
+        context_id: String,
+
+        /// Write the files without creating a scaffold/<context-id> branch and commit.
+        #[arg(long)]
+        no_commit: bool,
+    },
+
+    /// Prune local run history under the retention policy.
+    Clean {
+        /// Prune orchestration runs, traces, and snapshots (pinned runs are kept).
+        #[arg(long)]
+        history: bool,
+
+        /// Prune runs started more than this many days ago [default: retention.max_age_days].
+        #[arg(long)]
+        max_age_days: Option<u64>,
+
+        /// Prune oldest runs until history payloads fit in this many MB [default: retention.max_size_mb].
+        #[arg(long)]
+        max_size_mb: Option<u64>,
+
+        /// Report what would be pruned without deleting anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Maintain context documents.
+    Context {
+        #[command(subcommand)]
+        command: ContextCommands,
+    },
+
+    /// Update file references in context documents after files move.
+    Refs {
+        #[command(subcommand)]
+        command: RefsCommands,
+    },
+
+    /// Verify or export the hash-chained audit log.
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+
+    /// Validate and migrate .nexus/config.json.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Save, list, and delete named orchestration flag presets.
+    Preset {
+        #[command(subcommand)]
+        command: PresetCommands,
+    },
+
+    /// Visualize projects, contexts, dependencies, and file references.
+    Graph {
+        #[command(subcommand)]
+        command: GraphCommands,
+    },
+
+    /// Check bundled or extracted harness assets.
+    Assets {
+        #[command(subcommand)]
+        command: AssetsCommands,
+    },
+
+    /// Run commands interactively with history and tab completion.
+    Shell,
+
+    /// Inspect external `opennexus-<name>` plugins.
+    Plugin {
+        #[command(subcommand)]
+        command: PluginCommands,
+    },
+
+    /// Print completion candidates for a partial command line (used by shell completion scripts).
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Words typed after the binary name; the last one is being completed.
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+
+    /// Any other name runs the `opennexus-<name>` or `nexus-<name>` plugin on PATH.
+    #[command(external_subcommand)]
+    External(Vec<String>),
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum PluginCommands {
+    /// List plugins found on PATH.
+    List,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -85,6 +552,275 @@ pub enum MarketplaceCommands {
 
         /// Optional marketplace package name under .nexus/marketplace/<package> when target is a GitHub repo.
         package: Option<String>,
+
+        /// Rename an imported context id prefix (FROM=TO, repeatable).
+        #[arg(long = "map-prefix", value_name = "FROM=TO")]
+        map_prefix: Vec<String>,
+
+        /// Merge an imported context project into a local one (FROM=TO, repeatable).
+        #[arg(long = "map-project", value_name = "FROM=TO")]
+        map_project: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ContextCommands {
+    /// Regenerate the Context Files table (IDs, titles, statuses, action counts) in project index.md files.
+    Index {
+        /// Only regenerate .nexus/context/<project>/index.md.
+        #[arg(long)]
+        project: Option<String>,
+    },
+
+    /// Generate tests for a context and run the coder/validator loop, without run history.
+    Implement(ContextImplementCommand),
+
+    /// Report which of a context's Next Actions tests exist and pass.
+    TestStatus {
+        /// Path to a specification file with frontmatter and Next Actions table.
+        #[arg(long)]
+        context_file: String,
+    },
+
+    /// Record implemented/failed/missing action states from the existing test suite.
+    Backfill {
+        /// Path to a specification file with frontmatter and Next Actions table.
+        #[arg(long, required_unless_present = "all")]
+        context_file: Option<String>,
+
+        /// Backfill every valid context under .nexus/context/.
+        #[arg(long, conflicts_with = "context_file")]
+        all: bool,
+    },
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ContextImplementCommand {
+    /// Path to a specification file with frontmatter and Next Actions table.
+    #[arg(long)]
+    pub context_file: String,
+
+    /// Maximum coder/validator iterations before stopping; 0 only generates test scaffolds [default: 3].
+    #[arg(long)]
+    pub max_iterations: Option<usize>,
+
+    /// Timeout bound in seconds for the full loop [default: 600].
+    #[arg(long)]
+    pub timeout_seconds: Option<u64>,
+
+    /// Optional rule file under .nexus/ai_harness/rules/ to resolve ambiguity.
+    #[arg(long)]
+    pub rule_file: Option<String>,
+
+    /// Optional explicit test command template. Use {test_id} placeholder.
+    #[arg(long)]
+    pub test_command: Option<String>,
+
+    /// Optional explicit test discovery command used before coding starts.
+    #[arg(long)]
+    pub test_discovery_command: Option<String>,
+
+    /// Optional OpenCode model id for LLM stages.
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Optional checkpoint file path to persist step state after each step.
+    #[arg(long)]
+    pub checkpoint_file: Option<String>,
+
+    /// Resume from a saved checkpoint file.
+    #[arg(long)]
+    pub resume_checkpoint: Option<String>,
+
+    /// Bypass context dependency blocking gates.
+    #[arg(long, default_value_t = false)]
+    pub allow_dependency_bypass: bool,
+
+    /// Allow implementing a context marked `frozen: true`.
+    #[arg(long, default_value_t = false)]
+    pub unfreeze: bool,
+
+    /// Re-author red tests for every action, ignoring the per-action cache.
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum RefsCommands {
+    /// Rewrite backtick file references matching --from to --to in every context.
+    Remap {
+        /// Old path; `*` matches within a path segment and `**` across segments.
+        #[arg(long)]
+        from: String,
+
+        /// New path, using the same wildcards as --from in the same order.
+        #[arg(long)]
+        to: String,
+
+        /// Report the contexts that would change without writing them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AuditCommands {
+    /// Check the audit log hash chain for tampering.
+    Verify,
+
+    /// Export audit entries and verification status as JSON.
+    Export {
+        /// Write the export to this file instead of stdout.
+        #[arg(long)]
+        out: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SetupCommands {
+    /// Restore the files the most recent setup overwrote from .nexus/.backup.
+    Rollback,
+    /// Compare .nexus with the bundled assets and the setup manifest; exits
+    /// non-zero on drift, missing files, broken links, or a version mismatch.
+    Verify,
+    /// Re-sync copied or hard-linked harness entries whose .nexus source
+    /// changed, and recreate missing ones.
+    Repair,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCommands {
+    /// Report schema errors and deprecated keys, migrating old formats in place.
+    Doctor {
+        /// Report migrations without writing them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print the value of a dotted key such as `defaults.format`.
+    Get {
+        key: String,
+
+        #[command(flatten)]
+        scope: ConfigScope,
+    },
+
+    /// Set a dotted key; values are parsed as JSON, falling back to a string.
+    Set {
+        key: String,
+
+        value: String,
+
+        #[command(flatten)]
+        scope: ConfigScope,
+    },
+
+    /// Remove a dotted key.
+    Unset {
+        key: String,
+
+        #[command(flatten)]
+        scope: ConfigScope,
+    },
+
+    /// List every key that is set.
+    List {
+        #[command(flatten)]
+        scope: ConfigScope,
+    },
+}
+
+/// Which config file `config get/set/unset/list` reads and writes.
+#[derive(Debug, Clone, Copy, Args)]
+pub struct ConfigScope {
+    /// Use the user config in ~/.config/opennexus/config.json.
+    #[arg(long, conflicts_with = "project")]
+    pub global: bool,
+
+    /// Use the project config in .nexus/config.json (the default).
+    #[arg(long)]
+    pub project: bool,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum PresetCommands {
+    /// Save a preset into .nexus/config.json.
+    Save {
+        /// Preset name used with `orchestration <pipeline> --preset <name>`.
+        name: String,
+
+        /// Start from the flags of the most recent orchestration run.
+        #[arg(long)]
+        from_last: bool,
+
+        /// Pipeline definition file.
+        #[arg(long)]
+        pipeline_file: Option<String>,
+
+        /// Maximum coder/validator iterations.
+        #[arg(long)]
+        max_iterations: Option<usize>,
+
+        /// Timeout bound in seconds for the full loop.
+        #[arg(long)]
+        timeout_seconds: Option<u64>,
+
+        /// Rule file under .nexus/ai_harness/rules/.
+        #[arg(long)]
+        rule_file: Option<String>,
+
+        /// Test command template with {test_id} placeholder.
+        #[arg(long)]
+        test_command: Option<String>,
+
+        /// Test discovery command.
+        #[arg(long)]
+        test_discovery_command: Option<String>,
+
+        /// OpenCode model id.
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Bypass context dependency blocking gates.
+        #[arg(long)]
+        allow_dependency_bypass: bool,
+
+        /// Force new runs even when an equivalent run succeeded.
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// List saved presets.
+    List,
+
+    /// Delete a saved preset.
+    Delete {
+        /// Preset name.
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum GraphCommands {
+    /// Serve an interactive force-directed graph that live-reloads on context changes.
+    Serve {
+        /// Address to bind the local web server to [default: 127.0.0.1].
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Port to listen on; 0 picks a free port [default: 7878].
+        #[arg(long)]
+        port: Option<u16>,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AssetsCommands {
+    /// Validate command/skill/rule frontmatter, placeholders, and relative links.
+    Lint {
+        /// Lint the assets bundled into this binary instead of `.nexus/` on disk.
+        #[arg(long, default_value_t = false)]
+        embedded: bool,
     },
 }
 
@@ -104,13 +840,13 @@ pub struct OrchestrationCommand {
     #[arg(long)]
     pub pipeline_file: Option<String>,
 
-    /// Maximum coder/validator iterations before stopping.
-    #[arg(long, default_value_t = 3)]
-    pub max_iterations: usize,
+    /// Maximum coder/validator iterations before stopping [default: 3].
+    #[arg(long)]
+    pub max_iterations: Option<usize>,
 
-    /// Timeout bound in seconds for the full loop.
-    #[arg(long, default_value_t = 600)]
-    pub timeout_seconds: u64,
+    /// Timeout bound in seconds for the full loop [default: 600].
+    #[arg(long)]
+    pub timeout_seconds: Option<u64>,
 
     /// Optional rule file under .nexus/ai_harness/rules/ to resolve ambiguity.
     #[arg(long)]
@@ -144,10 +880,30 @@ pub struct OrchestrationCommand {
     #[arg(long, default_value_t = false)]
     pub overwrite: bool,
 
+    /// Allow pipelines to run against a context marked `frozen: true`.
+    #[arg(long, default_value_t = false)]
+    pub unfreeze: bool,
+
+    /// Write each action's test result into the context's Next Actions Outcome column without prompting.
+    #[arg(long, default_value_t = false)]
+    pub auto_outcome: bool,
+
+    /// Refuse to start when files referenced by the context overlap an active run on another context.
+    #[arg(long, default_value_t = false)]
+    pub exclusive_files: bool,
+
+    /// Re-author red tests for every action, ignoring the per-action cache of unchanged rows and test files.
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+
     /// Run id for `orchestration traces` query mode.
     #[arg(long)]
     pub run_id: Option<i64>,
 
+    /// Second run id for `orchestration compare` (diffed against --run-id).
+    #[arg(long)]
+    pub compare_run_id: Option<i64>,
+
     /// Filter timelines/runs by context id.
     #[arg(long)]
     pub context_id: Option<String>,
@@ -155,6 +911,14 @@ pub struct OrchestrationCommand {
     /// Filter timelines by pipeline name.
     #[arg(long)]
     pub pipeline_filter: Option<String>,
+
+    /// Why the run is being stopped (`orchestration stop`/`restart`); shown in status and runs.
+    #[arg(long)]
+    pub reason: Option<String>,
+
+    /// Fill unset flags from a preset saved with `opennexus preset save`.
+    #[arg(long)]
+    pub preset: Option<String>,
 }
 
 #[cfg(test)]
@@ -195,7 +959,9 @@ mod tests {
         ]);
         match cli.command {
             Some(Commands::Marketplace { command }) => match command {
-                MarketplaceCommands::Install { target, package } => {
+                MarketplaceCommands::Install {
+                    target, package, ..
+                } => {
                     assert_eq!(target, "github.com/owner/repo");
                     assert!(package.is_none());
                 }
@@ -213,12 +979,23 @@ mod tests {
             "install",
             "Alpha-Innovation-Labs/opennexus",
             "fumadocs",
+            "--map-prefix",
+            "DOC=WEB",
+            "--map-project",
+            "fumadocs=site",
         ]);
         match cli.command {
             Some(Commands::Marketplace { command }) => match command {
-                MarketplaceCommands::Install { target, package } => {
+                MarketplaceCommands::Install {
+                    target,
+                    package,
+                    map_prefix,
+                    map_project,
+                } => {
                     assert_eq!(target, "Alpha-Innovation-Labs/opennexus");
                     assert_eq!(package.as_deref(), Some("fumadocs"));
+                    assert_eq!(map_prefix, vec!["DOC=WEB"]);
+                    assert_eq!(map_project, vec!["fumadocs=site"]);
                 }
                 _ => panic!("expected marketplace install command"),
             },
@@ -230,20 +1007,59 @@ mod tests {
     fn parses_setup_with_harness() {
         let cli = Cli::parse_from(["opennexus", "setup", "--harness", "opencode"]);
         match cli.command {
-            Some(Commands::Setup { harness }) => assert_eq!(harness.as_deref(), Some("opencode")),
+            Some(Commands::Setup {
+                harness,
+                profile,
+                dry_run,
+                ..
+            }) => {
+                assert_eq!(harness.as_deref(), Some("opencode"));
+                assert_eq!(profile, None);
+                assert!(!dry_run);
+            }
             _ => panic!("expected setup command"),
         }
     }
 
     #[test]
     fn parses_setup_without_harness() {
-        let cli = Cli::parse_from(["opennexus", "setup"]);
+        let cli = Cli::parse_from(["opennexus", "setup", "--dry-run", "--link-mode", "hardlink"]);
         match cli.command {
-            Some(Commands::Setup { harness }) => assert!(harness.is_none()),
+            Some(Commands::Setup {
+                harness,
+                link_mode,
+                dry_run,
+                ..
+            }) => {
+                assert!(harness.is_none());
+                assert_eq!(link_mode, Some(LinkMode::Hardlink));
+                assert!(dry_run);
+            }
             _ => panic!("expected setup command"),
         }
     }
 
+    #[test]
+    fn parses_setup_rollback() {
+        let cli = Cli::parse_from(["opennexus", "setup", "rollback"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Setup {
+                command: Some(SetupCommands::Rollback),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["opennexus", "setup", "--dry-run", "rollback"]).is_err());
+        let cli = Cli::parse_from(["opennexus", "setup", "verify"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Setup {
+                command: Some(SetupCommands::Verify),
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn parses_ralph_passthrough_args() {
         let cli = Cli::parse_from([
@@ -299,8 +1115,8 @@ mod tests {
                     .as_deref()
                     .unwrap_or_default()
                     .contains("CDD_001"));
-                assert_eq!(command.max_iterations, 5);
-                assert_eq!(command.timeout_seconds, 120);
+                assert_eq!(command.max_iterations, Some(5));
+                assert_eq!(command.timeout_seconds, Some(120));
                 assert_eq!(command.rule_file.as_deref(), Some("rust/SKILL.md"));
                 assert_eq!(command.model.as_deref(), Some("openai/gpt-5.3-codex"));
                 assert!(command.test_command.is_none());
@@ -310,6 +1126,7 @@ mod tests {
                 assert!(!command.allow_dependency_bypass);
                 assert!(!command.overwrite);
                 assert!(command.run_id.is_none());
+                assert!(command.compare_run_id.is_none());
                 assert!(command.context_id.is_none());
                 assert!(command.pipeline_filter.is_none());
             }
@@ -366,4 +1183,306 @@ mod tests {
             _ => panic!("expected orchestration restart command"),
         }
     }
+
+    #[test]
+    fn parses_orchestration_compare_command() {
+        let cli = Cli::parse_from([
+            "opennexus",
+            "orchestration",
+            "compare",
+            "--run-id",
+            "3",
+            "--compare-run-id",
+            "7",
+        ]);
+        match cli.command {
+            Some(Commands::Orchestration(command)) => {
+                assert_eq!(command.pipeline_name, "compare");
+                assert_eq!(command.run_id, Some(3));
+                assert_eq!(command.compare_run_id, Some(7));
+                assert!(command.context_file.is_none());
+            }
+            _ => panic!("expected orchestration compare command"),
+        }
+    }
+
+    #[test]
+    fn parses_badge_command() {
+        let cli = Cli::parse_from([
+            "opennexus",
+            "badge",
+            "--project",
+            "nexus-cli",
+            "--out",
+            "docs/badges",
+        ]);
+        match cli.command {
+            Some(Commands::Badge { project, out }) => {
+                assert_eq!(project.as_deref(), Some("nexus-cli"));
+                assert_eq!(out, "docs/badges");
+            }
+            _ => panic!("expected badge command"),
+        }
+    }
+
+    #[test]
+    fn parses_context_subcommands() {
+        let cli = Cli::parse_from([
+            "opennexus",
+            "context",
+            "implement",
+            "--context-file",
+            "ctx.md",
+            "--max-iterations",
+            "0",
+        ]);
+        match cli.command {
+            Some(Commands::Context {
+                command: ContextCommands::Implement(command),
+            }) => {
+                assert_eq!(command.context_file, "ctx.md");
+                assert_eq!(command.max_iterations, Some(0));
+                assert_eq!(command.timeout_seconds, None);
+            }
+            _ => panic!("expected context implement command"),
+        }
+
+        let cli = Cli::parse_from(["opennexus", "context", "backfill", "--all"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Context {
+                command: ContextCommands::Backfill {
+                    context_file: None,
+                    all: true
+                }
+            })
+        ));
+        assert!(Cli::try_parse_from(["opennexus", "context", "backfill"]).is_err());
+    }
+
+    #[test]
+    fn parses_analyze_command() {
+        let cli = Cli::parse_from(["opennexus", "analyze", "../legacy", "--apply"]);
+        match cli.command {
+            Some(Commands::Analyze {
+                path,
+                project,
+                apply,
+            }) => {
+                assert_eq!(path, "../legacy");
+                assert!(project.is_none());
+                assert!(apply);
+            }
+            _ => panic!("expected analyze command"),
+        }
+    }
+
+    #[test]
+    fn parses_learn_command() {
+        let cli = Cli::parse_from(["opennexus", "learn", "--reset"]);
+        match cli.command {
+            Some(Commands::Learn { dir, reset }) => {
+                assert_eq!(dir, "nexus-tutorial");
+                assert!(reset);
+            }
+            _ => panic!("expected learn command"),
+        }
+    }
+
+    #[test]
+    fn parses_audit_export_command() {
+        let cli = Cli::parse_from(["opennexus", "audit", "export", "--out", "audit.json"]);
+        match cli.command {
+            Some(Commands::Audit {
+                command: AuditCommands::Export { out },
+            }) => assert_eq!(out.as_deref(), Some("audit.json")),
+            _ => panic!("expected audit export command"),
+        }
+    }
+
+    #[test]
+    fn parses_verbosity_flags() {
+        let cli = Cli::parse_from(["opennexus", "-vv", "preset", "list"]);
+        assert_eq!(cli.verbose, 2);
+        assert!(!cli.quiet);
+        let cli = Cli::parse_from(["opennexus", "preset", "list", "--quiet"]);
+        assert!(cli.quiet);
+        assert!(Cli::try_parse_from(["opennexus", "-v", "-q", "preset", "list"]).is_err());
+    }
+
+    #[test]
+    fn parses_unknown_commands_as_plugins() {
+        let cli = Cli::parse_from(["opennexus", "--format", "json", "lint", "--fix", "src"]);
+        assert_eq!(cli.format, Some(OutputFormat::Json));
+        match cli.command {
+            Some(Commands::External(args)) => assert_eq!(args, vec!["lint", "--fix", "src"]),
+            _ => panic!("expected external plugin command"),
+        }
+        assert!(matches!(
+            Cli::parse_from(["opennexus", "plugin", "list"]).command,
+            Some(Commands::Plugin {
+                command: PluginCommands::List
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_retry_flags() {
+        let cli = Cli::parse_from(["opennexus", "marketplace", "list", "--retry-attempts", "6"]);
+        assert_eq!(cli.retry_attempts, Some(6));
+        assert!(!cli.no_retry);
+        assert!(Cli::parse_from(["opennexus", "--no-retry", "marketplace", "list"]).no_retry);
+        assert!(
+            Cli::try_parse_from(["opennexus", "--retry-attempts", "0", "preset", "list"]).is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "opennexus",
+            "--no-retry",
+            "--retry-attempts",
+            "2",
+            "preset",
+            "list"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parses_config_key_commands() {
+        let cli = Cli::parse_from([
+            "opennexus",
+            "config",
+            "set",
+            "defaults.format",
+            "json",
+            "--global",
+        ]);
+        match cli.command {
+            Some(Commands::Config {
+                command: ConfigCommands::Set { key, value, scope },
+            }) => {
+                assert_eq!(key, "defaults.format");
+                assert_eq!(value, "json");
+                assert!(scope.global);
+            }
+            _ => panic!("expected config set command"),
+        }
+        assert!(
+            Cli::try_parse_from(["opennexus", "config", "list", "--global", "--project"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_config_doctor_command() {
+        let cli = Cli::parse_from(["opennexus", "config", "doctor", "--dry-run"]);
+        match cli.command {
+            Some(Commands::Config {
+                command: ConfigCommands::Doctor { dry_run },
+            }) => assert!(dry_run),
+            _ => panic!("expected config doctor command"),
+        }
+    }
+
+    #[test]
+    fn parses_graph_serve_command() {
+        let cli = Cli::parse_from(["opennexus", "graph", "serve", "--port", "9000"]);
+        match cli.command {
+            Some(Commands::Graph {
+                command: GraphCommands::Serve { host, port },
+            }) => {
+                assert_eq!(host, None);
+                assert_eq!(port, Some(9000));
+            }
+            _ => panic!("expected graph serve command"),
+        }
+    }
+
+    #[test]
+    fn output_format_defaults_to_tsv_when_piped() {
+        assert_eq!(resolve_output_format(None, true), OutputFormat::Text);
+        assert_eq!(resolve_output_format(None, false), OutputFormat::Tsv);
+        assert_eq!(
+            resolve_output_format(Some(OutputFormat::Text), false),
+            OutputFormat::Text
+        );
+
+        let cli = Cli::parse_from(["opennexus", "preset", "list", "--color", "never"]);
+        assert_eq!(cli.color, Some(ColorChoice::Never));
+        assert_eq!(cli.format, None);
+    }
+
+    #[test]
+    fn configured_viewer_role_cannot_be_overridden() {
+        let cli = Cli::parse_from(["opennexus", "preset", "list", "--role", "maintainer"]);
+        assert_eq!(cli.role(Some(Role::Viewer)), Role::Viewer);
+        assert_eq!(cli.role(None), Role::Maintainer);
+        let cli = Cli::parse_from(["opennexus", "preset", "list", "--role", "viewer"]);
+        assert_eq!(cli.role(Some(Role::Maintainer)), Role::Viewer);
+    }
+
+    #[test]
+    fn parses_global_query_flag() {
+        let cli = Cli::parse_from([
+            "opennexus",
+            "orchestration",
+            "runs",
+            "--format",
+            "json",
+            "--query",
+            "runs[0].run_id",
+        ]);
+        assert_eq!(cli.format, Some(OutputFormat::Json));
+        assert_eq!(cli.query.as_deref(), Some("runs[0].run_id"));
+        assert!(!cli.stateless);
+        assert!(cli.has_global_flags());
+
+        let cli = Cli::parse_from(["opennexus", "preset", "list", "--stateless"]);
+        assert!(cli.stateless);
+        assert!(!Cli::parse_from(["opennexus", "preset", "list"]).has_global_flags());
+    }
+
+    #[test]
+    fn parses_preset_save_and_orchestration_preset() {
+        let cli = Cli::parse_from([
+            "opennexus",
+            "preset",
+            "save",
+            "gen-code-fast",
+            "--from-last",
+            "--max-iterations",
+            "1",
+        ]);
+        match cli.command {
+            Some(Commands::Preset {
+                command:
+                    PresetCommands::Save {
+                        name,
+                        from_last,
+                        max_iterations,
+                        ..
+                    },
+            }) => {
+                assert_eq!(name, "gen-code-fast");
+                assert!(from_last);
+                assert_eq!(max_iterations, Some(1));
+            }
+            _ => panic!("expected preset save command"),
+        }
+
+        let cli = Cli::parse_from([
+            "opennexus",
+            "orchestration",
+            "gen-code",
+            "--context-file",
+            "ctx.md",
+            "--preset",
+            "gen-code-fast",
+        ]);
+        match cli.command {
+            Some(Commands::Orchestration(command)) => {
+                assert_eq!(command.preset.as_deref(), Some("gen-code-fast"));
+                assert!(command.max_iterations.is_none());
+            }
+            _ => panic!("expected orchestration command"),
+        }
+    }
 }
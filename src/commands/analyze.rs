@@ -0,0 +1,87 @@
+//! Analyze command for onboarding existing repositories.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::features::analyze::{analyze_repository, apply_onboarding_plan, build_onboarding_plan};
+use crate::output::{print_info, print_json, print_success};
+
+/// Run the analyze command.
+///
+/// Prints the detected repository shape and the proposed context structure;
+/// with `apply`, writes the proposed index files that do not exist yet.
+pub fn run_analyze(
+    path: &str,
+    project: Option<&str>,
+    apply: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let repo_root = Path::new(path);
+    let analysis = analyze_repository(repo_root, project)?;
+    let plan = build_onboarding_plan(repo_root, &analysis);
+    let written = if apply {
+        apply_onboarding_plan(repo_root, &plan)?
+    } else {
+        Vec::new()
+    };
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if apply { "applied" } else { "planned" },
+            "analysis": analysis,
+            "plan": plan,
+            "written": written,
+        }))?;
+        return Ok(());
+    }
+
+    print_success(&format!(
+        "Analyzed '{}' as project '{}'",
+        path, analysis.project
+    ));
+    let languages = analysis
+        .languages
+        .iter()
+        .map(|entry| format!("{} ({})", entry.language, entry.files))
+        .collect::<Vec<_>>();
+    eprintln!("  toolchain: {}", analysis.toolchain);
+    eprintln!("  languages: {}", display_list(&languages));
+    eprintln!(
+        "  test frameworks: {}",
+        display_list(&analysis.test_frameworks)
+    );
+    eprintln!("  docs: {}", display_list(&analysis.docs));
+    eprintln!("  modules:");
+    for module in &analysis.modules {
+        eprintln!(
+            "  - {} ({}, {} source files)",
+            module.name, module.path, module.source_files
+        );
+    }
+
+    eprintln!("Proposed context structure:");
+    for file in &plan {
+        let marker = if file.exists { "exists" } else { "new" };
+        eprintln!("  - {} [{}]", file.path, marker);
+    }
+
+    if apply {
+        print_success(&format!("Wrote {} index file(s).", written.len()));
+    } else {
+        print_info("Run `opennexus analyze --apply` to write the proposed index files.");
+    }
+    if !analysis.nexus_present {
+        print_info("Run `opennexus setup` to install Nexus assets for this repository.");
+    }
+
+    Ok(())
+}
+
+fn display_list(values: &[String]) -> String {
+    if values.is_empty() {
+        "none detected".to_string()
+    } else {
+        values.join(", ")
+    }
+}
@@ -0,0 +1,115 @@
+//! Apply command for GitOps-style management of projects and contexts.
+
+use anyhow::{bail, Context, Result};
+use dialoguer::Confirm;
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::commands::context::refresh_context_indexes;
+use crate::config::nexus::{load_nexus_config, NEXUS_CONFIG_PATH};
+use crate::features::apply::{
+    apply_plan, load_manifest, plan_manifest, ChangeAction, PlannedChange,
+};
+use crate::output::{print_info, print_json, print_success};
+
+#[derive(Debug, Serialize)]
+struct ApplyPayload {
+    manifest: String,
+    plan: Vec<PlannedChange>,
+    applied: bool,
+}
+
+/// Run `apply <manifest>`: print the plan, then apply it once confirmed.
+pub fn run_apply(
+    manifest_path: &str,
+    prune: bool,
+    yes: bool,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let manifest = load_manifest(Path::new(manifest_path))?;
+    let plan = plan_manifest(Path::new(".nexus/context"), &manifest, prune)?;
+
+    if !format.is_structured() {
+        for change in &plan {
+            println!("{}", describe_change(change));
+        }
+    }
+    if plan.is_empty() || dry_run {
+        return finish(manifest_path, plan, false, format);
+    }
+
+    let confirmed = yes
+        || (format == OutputFormat::Text
+            && std::io::stdin().is_terminal()
+            && Confirm::new()
+                .with_prompt(format!("Apply {} change(s)?", plan.len()))
+                .default(false)
+                .interact()
+                .context("Failed to read apply confirmation")?);
+    if !confirmed {
+        if !std::io::stdin().is_terminal() || format != OutputFormat::Text {
+            bail!(
+                "Refusing to apply {} change(s) without confirmation. Remediation: re-run with --yes, or --dry-run to only show the plan.",
+                plan.len()
+            );
+        }
+        print_info("Apply cancelled; nothing was changed.");
+        return Ok(());
+    }
+
+    apply_plan(&manifest, &plan)?;
+    if load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?
+        .context_index
+        .auto
+    {
+        refresh_context_indexes(None)?;
+    }
+    finish(manifest_path, plan, true, format)
+}
+
+fn finish(
+    manifest_path: &str,
+    plan: Vec<PlannedChange>,
+    applied: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    if format.is_structured() {
+        return print_json(&ApplyPayload {
+            manifest: manifest_path.to_string(),
+            plan,
+            applied,
+        });
+    }
+    if plan.is_empty() {
+        print_info("No changes; local state already matches the manifest.");
+    } else if applied {
+        print_success(&format!(
+            "Applied {} change(s) from {}",
+            plan.len(),
+            manifest_path
+        ));
+    } else {
+        print_info(&format!("Plan: {} change(s); nothing applied.", plan.len()));
+    }
+    Ok(())
+}
+
+fn describe_change(change: &PlannedChange) -> String {
+    let (symbol, verb) = match change.action {
+        ChangeAction::Create => ('+', "create"),
+        ChangeAction::Update => ('~', "update"),
+        ChangeAction::Delete => ('-', "delete"),
+    };
+    let fields = if change.fields.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", change.fields.join(", "))
+    };
+    format!(
+        "  {} {} {} {} ({}){}",
+        symbol, verb, change.kind, change.id, change.path, fields
+    )
+}
@@ -0,0 +1,109 @@
+//! Assets command for linting harness commands, skills, and rules.
+
+use anyhow::{bail, Result};
+use include_dir::Dir;
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::commands::setup::embedded_nexus_assets;
+use crate::features::assets_lint::{
+    collect_assets_from_dir, lint_assets, AssetFile, AssetLintIssue, HARNESS_ASSET_ROOT,
+};
+use crate::output::{print_error, print_json, print_success};
+
+/// Run `assets lint` against `.nexus/` on disk, or the bundled copy with `embedded`.
+pub fn run_assets_lint(embedded: bool, format: OutputFormat) -> Result<()> {
+    let (source, files, issues) = if embedded {
+        let files = collect_embedded_assets(embedded_nexus_assets());
+        let issues = lint_embedded_assets(&files);
+        ("embedded", files, issues)
+    } else {
+        let root = Path::new(".nexus");
+        if !root.join(HARNESS_ASSET_ROOT).is_dir() {
+            bail!(
+                "No .nexus/{}/ directory found. Remediation: run `opennexus setup` first, or pass --embedded.",
+                HARNESS_ASSET_ROOT
+            );
+        }
+        let files = collect_assets_from_dir(root)?;
+        let issues = lint_assets(&files, |path| root.join(path).exists());
+        (".nexus", files, issues)
+    };
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if issues.is_empty() { "ok" } else { "invalid" },
+            "source": source,
+            "files": files.len(),
+            "issues": issues,
+        }))?;
+    } else if issues.is_empty() {
+        print_success(&format!(
+            "Linted {} asset file(s) from {}: no issues.",
+            files.len(),
+            source
+        ));
+    } else {
+        for issue in &issues {
+            print_error(&format!(
+                "{}:{} [{}] {}",
+                issue.path, issue.line, issue.rule, issue.message
+            ));
+        }
+    }
+
+    if !issues.is_empty() {
+        bail!(
+            "Asset lint found {} issue(s). Remediation: fix the reported files and rerun `opennexus assets lint`.",
+            issues.len()
+        );
+    }
+    Ok(())
+}
+
+fn lint_embedded_assets(files: &[AssetFile]) -> Vec<AssetLintIssue> {
+    let assets = embedded_nexus_assets();
+    lint_assets(files, |path| assets.get_entry(path).is_some())
+}
+
+fn collect_embedded_assets(assets: &Dir<'_>) -> Vec<AssetFile> {
+    let mut files = Vec::<AssetFile>::new();
+    let mut stack = assets
+        .get_dir(HARNESS_ASSET_ROOT)
+        .into_iter()
+        .collect::<Vec<_>>();
+    while let Some(dir) = stack.pop() {
+        stack.extend(dir.dirs());
+        for file in dir.files() {
+            let path = file.path().to_string_lossy().replace('\\', "/");
+            if !path.ends_with(".md") {
+                continue;
+            }
+            if let Some(content) = file.contents_utf8() {
+                files.push(AssetFile {
+                    path,
+                    content: content.to_string(),
+                });
+            }
+        }
+    }
+    files.sort_by(|left, right| left.path.cmp(&right.path));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_assets_pass_lint() {
+        let files = collect_embedded_assets(embedded_nexus_assets());
+        assert!(!files.is_empty());
+        let issues = lint_embedded_assets(&files);
+        assert!(
+            issues.is_empty(),
+            "embedded asset lint issues: {:#?}",
+            issues
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Audit command and recording of mutating operations.
+//!
+//! Recording is opt-in per repository via `"audit": { "enabled": true }` in
+//! `.nexus/config.json`; `audit verify` and `audit export` always read the log.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::adapters::audit_log::{default_audit_log_path, AuditLog};
+use crate::cli::{
+    Commands, ConfigCommands, ContextCommands, MarketplaceCommands, OutputFormat, PresetCommands,
+    RefsCommands, SetupCommands,
+};
+use crate::config::nexus::{load_nexus_config, NEXUS_CONFIG_PATH};
+use crate::config::stateless::is_stateless;
+use crate::output::{print_info, print_json, print_json_pretty, print_success};
+
+/// `orchestration` modes that only read run history.
+pub(crate) const READ_ONLY_ORCHESTRATION_MODES: &[&str] = &[
+    "status",
+    "active",
+    "runs",
+    "traces",
+    "artifacts",
+    "timeline",
+    "compare",
+];
+
+/// Describe a command as an audit operation, or `None` when it does not mutate state.
+pub fn audit_operation(command: &Commands) -> Option<(String, Value)> {
+    match command {
+        Commands::Setup {
+            command: Some(SetupCommands::Rollback),
+            ..
+        } => Some(("setup.rollback".to_string(), serde_json::json!({}))),
+        Commands::Setup {
+            command: Some(SetupCommands::Repair),
+            ..
+        } => Some(("setup.repair".to_string(), serde_json::json!({}))),
+        Commands::Setup {
+            command: Some(SetupCommands::Verify),
+            ..
+        } => None,
+        Commands::Setup {
+            command: None,
+            harness,
+            profile,
+            vars,
+            link_mode,
+            from,
+            sha256,
+            no_gitignore,
+            no_hooks,
+            dry_run,
+        } => (!dry_run).then(|| {
+            (
+                "setup".to_string(),
+                serde_json::json!({ "harness": harness, "profile": profile, "vars": vars.iter().cloned().collect::<BTreeMap<_, _>>(), "link_mode": link_mode, "from": from, "sha256": sha256, "no_gitignore": no_gitignore, "no_hooks": no_hooks }),
+            )
+        }),
+        Commands::Update { check: true, .. } => None,
+        Commands::Update {
+            method,
+            version,
+            channel,
+            sync_assets,
+            ..
+        } => Some((
+            "update".to_string(),
+            serde_json::json!({ "method": method, "version": version, "channel": channel, "sync_assets": sync_assets }),
+        )),
+        Commands::Uninstall { dry_run: true, .. } => None,
+        Commands::Uninstall {
+            remove_assets,
+            purge,
+            project,
+            ..
+        } => Some((
+            "uninstall".to_string(),
+            serde_json::json!({ "remove_assets": remove_assets, "purge": purge, "project": project }),
+        )),
+        Commands::Marketplace {
+            command:
+                MarketplaceCommands::Install {
+                    target,
+                    package,
+                    map_prefix,
+                    map_project,
+                },
+        } => Some((
+            "marketplace.install".to_string(),
+            serde_json::json!({
+                "target": target,
+                "package": package,
+                "map_prefix": map_prefix,
+                "map_project": map_project,
+            }),
+        )),
+        Commands::Marketplace { .. } => None,
+        Commands::Ralph(command) => Some((
+            "ralph".to_string(),
+            serde_json::json!({ "args": command.args }),
+        )),
+        Commands::Orchestration(command) => {
+            if READ_ONLY_ORCHESTRATION_MODES.contains(&command.pipeline_name.as_str()) {
+                return None;
+            }
+            let operation = match command.pipeline_name.as_str() {
+                "stop" | "restart" | "pin" | "unpin" => {
+                    format!("orchestration.{}", command.pipeline_name)
+                }
+                _ => "orchestration.run".to_string(),
+            };
+            Some((
+                operation,
+                serde_json::json!({
+                    "pipeline_name": command.pipeline_name,
+                    "target_pipeline_name": command.target_pipeline_name,
+                    "context_file": command.context_file,
+                    "run_id": command.run_id,
+                    "overwrite": command.overwrite,
+                    "allow_dependency_bypass": command.allow_dependency_bypass,
+                    "preset": command.preset,
+                    "reason": command.reason,
+                }),
+            ))
+        }
+        Commands::Analyze {
+            path,
+            project,
+            apply,
+        } => apply.then(|| {
+            (
+                "analyze.apply".to_string(),
+                serde_json::json!({ "path": path, "project": project }),
+            )
+        }),
+        Commands::Apply {
+            manifest,
+            prune,
+            dry_run,
+            ..
+        } => (!dry_run).then(|| {
+            (
+                "apply".to_string(),
+                serde_json::json!({ "manifest": manifest, "prune": prune }),
+            )
+        }),
+        Commands::Badge { project, out } => Some((
+            "badge".to_string(),
+            serde_json::json!({ "project": project, "out": out }),
+        )),
+        // A plugin can do anything, so it counts as mutating.
+        Commands::External(args) => Some((
+            "plugin.run".to_string(),
+            serde_json::json!({ "plugin": args.first(), "args": args.get(1..) }),
+        )),
+        Commands::Scaffold {
+            context_id,
+            no_commit,
+        } => Some((
+            "scaffold".to_string(),
+            serde_json::json!({ "context_id": context_id, "no_commit": no_commit }),
+        )),
+        Commands::Clean {
+            history,
+            max_age_days,
+            max_size_mb,
+            dry_run,
+        } => (!dry_run).then(|| {
+            (
+                "clean".to_string(),
+                serde_json::json!({
+                    "history": history,
+                    "max_age_days": max_age_days,
+                    "max_size_mb": max_size_mb,
+                }),
+            )
+        }),
+        Commands::Context { command } => match command {
+            ContextCommands::Index { project } => Some((
+                "context.index".to_string(),
+                serde_json::json!({ "project": project }),
+            )),
+            ContextCommands::Implement(command) => Some((
+                "context.implement".to_string(),
+                serde_json::json!({
+                    "context_file": command.context_file,
+                    "max_iterations": command.max_iterations,
+                    "unfreeze": command.unfreeze,
+                }),
+            )),
+            ContextCommands::Backfill { context_file, all } => Some((
+                "context.backfill".to_string(),
+                serde_json::json!({ "context_file": context_file, "all": all }),
+            )),
+            ContextCommands::TestStatus { .. } => None,
+        },
+        Commands::Refs {
+            command: RefsCommands::Remap { from, to, dry_run },
+        } => (!dry_run).then(|| {
+            (
+                "refs.remap".to_string(),
+                serde_json::json!({ "from": from, "to": to }),
+            )
+        }),
+        Commands::Config { command } => match command {
+            ConfigCommands::Doctor { dry_run } => {
+                (!dry_run).then(|| ("config.doctor".to_string(), serde_json::json!({})))
+            }
+            ConfigCommands::Set { key, value, scope } => Some((
+                "config.set".to_string(),
+                serde_json::json!({ "key": key, "value": value, "global": scope.global }),
+            )),
+            ConfigCommands::Unset { key, scope } => Some((
+                "config.unset".to_string(),
+                serde_json::json!({ "key": key, "global": scope.global }),
+            )),
+            ConfigCommands::Get { .. } | ConfigCommands::List { .. } => None,
+        },
+        Commands::Preset { command } => match command {
+            PresetCommands::Save { name, .. } => Some((
+                "preset.save".to_string(),
+                serde_json::json!({ "name": name }),
+            )),
+            PresetCommands::Delete { name } => Some((
+                "preset.delete".to_string(),
+                serde_json::json!({ "name": name }),
+            )),
+            PresetCommands::List => None,
+        },
+        Commands::Learn { .. }
+        | Commands::Audit { .. }
+        | Commands::Graph { .. }
+        | Commands::Assets { .. }
+        | Commands::Status
+        | Commands::Doctor
+        | Commands::BugReport { .. }
+        | Commands::Burndown { .. }
+        | Commands::Shell
+        | Commands::Plugin { .. }
+        | Commands::Complete { .. } => None,
+    }
+}
+
+/// Append an audit entry when audit logging is enabled for this repository.
+pub fn record_audit_event(operation: &str, parameters: Value) -> Result<()> {
+    if is_stateless() {
+        return Ok(());
+    }
+    if !audit_enabled(Path::new(NEXUS_CONFIG_PATH))? {
+        return Ok(());
+    }
+    AuditLog::open_default()
+        .append(&resolve_actor(), operation, parameters)
+        .context("Failed to record audit entry. Remediation: ensure .nexus/audit/ is writable.")?;
+    Ok(())
+}
+
+pub fn run_audit_verify(format: OutputFormat) -> Result<()> {
+    let path = default_audit_log_path();
+    let verification = AuditLog::open(&path).verify()?;
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if verification.valid { "valid" } else { "tampered" },
+            "log": path.display().to_string(),
+            "verification": verification,
+        }))?;
+    } else if verification.valid {
+        if verification.entries == 0 {
+            print_info(&format!("Audit log '{}' has no entries.", path.display()));
+        } else {
+            print_success(&format!(
+                "Audit log '{}' verified ({} entries).",
+                path.display(),
+                verification.entries
+            ));
+        }
+    }
+
+    if !verification.valid {
+        bail!(
+            "Audit log '{}' failed verification at entry {}: {}. Remediation: restore the log from a trusted copy and investigate changes after that entry.",
+            path.display(),
+            verification
+                .first_invalid_seq
+                .map(|seq| seq.to_string())
+                .unwrap_or_else(|| "?".to_string()),
+            verification.reason.unwrap_or_default()
+        );
+    }
+    if matches!(audit_enabled(Path::new(NEXUS_CONFIG_PATH)), Ok(false)) && !format.is_structured() {
+        print_info(
+            "Audit recording is disabled. Enable it with \"audit\": { \"enabled\": true } in .nexus/config.json.",
+        );
+    }
+    Ok(())
+}
+
+pub fn run_audit_export(out: Option<&str>, format: OutputFormat) -> Result<()> {
+    let path = default_audit_log_path();
+    let log = AuditLog::open(&path);
+    let entries = log.read_entries()?;
+    let verification = log.verify()?;
+    let document = serde_json::json!({
+        "log": path.display().to_string(),
+        "verification": verification,
+        "entries": entries,
+    });
+    let serialized = serde_json::to_string_pretty(&document)?;
+
+    match out {
+        Some(target) => {
+            fs::write(target, format!("{}\n", serialized))
+                .with_context(|| format!("Failed to write audit export '{}'.", target))?;
+            if format.is_structured() {
+                print_json(&serde_json::json!({
+                    "status": "completed",
+                    "out": target,
+                    "entries": entries.len(),
+                }))?;
+            } else {
+                print_success(&format!(
+                    "Exported {} audit entries to '{}'.",
+                    entries.len(),
+                    target
+                ));
+            }
+        }
+        None => print_json_pretty(&document)?,
+    }
+    Ok(())
+}
+
+fn audit_enabled(config_path: &Path) -> Result<bool> {
+    Ok(load_nexus_config(config_path)?.audit.enabled)
+}
+
+fn resolve_actor() -> String {
+    let git_email = Command::new("git")
+        .args(["config", "user.email"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|email| !email.is_empty());
+    git_email
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+    use tempfile::tempdir;
+
+    #[test]
+    fn audit_operation_skips_read_only_commands() {
+        let status = Cli::parse_from([
+            "opennexus",
+            "orchestration",
+            "status",
+            "--context-file",
+            "ctx.md",
+        ]);
+        assert!(audit_operation(&status.command.expect("command")).is_none());
+
+        let stop = Cli::parse_from(["opennexus", "orchestration", "stop", "--run-id", "4"]);
+        let (operation, parameters) =
+            audit_operation(&stop.command.expect("command")).expect("stop is audited");
+        assert_eq!(operation, "orchestration.stop");
+        assert_eq!(parameters["run_id"], 4);
+
+        let search = Cli::parse_from(["opennexus", "marketplace", "search", "docs"]);
+        assert!(audit_operation(&search.command.expect("command")).is_none());
+    }
+
+    #[test]
+    fn audit_enabled_reads_policy_from_config() {
+        let dir = tempdir().expect("tempdir");
+        let config = dir.path().join("config.json");
+        assert!(!audit_enabled(&config).expect("missing config"));
+
+        fs::write(&config, "{\"audit\":{\"enabled\":true}}").expect("write config");
+        assert!(audit_enabled(&config).expect("enabled config"));
+
+        fs::write(&config, "{\"audit\":{\"enabled\":\"yes\"}}").expect("write config");
+        assert!(audit_enabled(&config).is_err());
+    }
+}
@@ -0,0 +1,321 @@
+//! Badge command for generating README status badges.
+//!
+//! Badges are derived from local context specs plus the orchestration and
+//! backfill stores, so a CI job can refresh them without running agents.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::adapters::context_observability::{
+    default_database_path, BackfillRunSummary, ContextObservabilityStore,
+};
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::OutputFormat;
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::scan::{context_project, scan_valid_context_specs};
+use crate::output::{print_json, print_success};
+
+const COLOR_GREEN: &str = "#4c1";
+const COLOR_YELLOW: &str = "#dfb317";
+const COLOR_RED: &str = "#e05d44";
+const COLOR_GREY: &str = "#9f9f9f";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct BadgeStats {
+    contexts_total: usize,
+    contexts_done: usize,
+    backfilled_contexts: usize,
+    tests_passing: i64,
+    tests_failing: i64,
+    actions_total: usize,
+    actions_implemented: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+struct Badge {
+    file_name: String,
+    label: String,
+    message: String,
+    color: String,
+}
+
+/// Run the badge command.
+///
+/// Writes `contexts.svg`, `tests.svg`, and `coverage.svg` into `out_dir`.
+pub fn run_badge(project: Option<&str>, out_dir: &str, format: OutputFormat) -> Result<()> {
+    let context_root = Path::new(".nexus/context");
+    if !context_root.exists() {
+        bail!(
+            "Badge generation requires '.nexus/context/' to exist. Remediation: run `opennexus setup` or create context specs first."
+        );
+    }
+
+    let scan = scan_valid_context_specs(context_root)?;
+    let context_files = scan
+        .valid_context_files
+        .into_iter()
+        .filter(|path| {
+            project
+                .is_none_or(|wanted| context_project(context_root, path).as_deref() == Some(wanted))
+        })
+        .collect::<Vec<_>>();
+    if context_files.is_empty() {
+        match project {
+            Some(name) => bail!(
+                "No valid context specs found for project '{}' under .nexus/context/{}/.",
+                name,
+                name
+            ),
+            None => bail!("No valid context specs found under .nexus/context/."),
+        }
+    }
+
+    let orchestration_path = default_orchestration_database_path();
+    let orchestration = if orchestration_path.exists() {
+        Some(OrchestrationStore::open(&orchestration_path)?)
+    } else {
+        None
+    };
+    let observability_path = default_database_path();
+    let observability = if observability_path.exists() {
+        Some(ContextObservabilityStore::open(&observability_path)?)
+    } else {
+        None
+    };
+
+    let mut stats = BadgeStats::default();
+    for context_file in &context_files {
+        let parsed = parse_context_file(context_file)?;
+        stats.contexts_total += 1;
+        stats.actions_total += parsed.next_actions.len();
+
+        if let Some(store) = &orchestration {
+            if store
+                .latest_success_for_context_id(&parsed.context_id)?
+                .is_some()
+            {
+                stats.contexts_done += 1;
+            }
+        }
+        if let Some(store) = &observability {
+            if let Some(summary) = store.latest_backfill_for_context(&parsed.context_id)? {
+                apply_backfill_summary(&mut stats, &summary);
+            }
+        }
+    }
+
+    let badges = build_badges(&stats);
+    let out_path = Path::new(out_dir);
+    fs::create_dir_all(out_path)
+        .with_context(|| format!("Failed to create badge directory '{}'.", out_dir))?;
+    for badge in &badges {
+        let target = out_path.join(&badge.file_name);
+        fs::write(
+            &target,
+            render_badge_svg(&badge.label, &badge.message, &badge.color),
+        )
+        .with_context(|| format!("Failed to write badge '{}'.", target.display()))?;
+    }
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "completed",
+            "project": project,
+            "out_dir": out_dir,
+            "badges": badges,
+        }))?;
+    } else {
+        for badge in &badges {
+            print_success(&format!(
+                "Wrote {}/{} ({}: {})",
+                out_dir.trim_end_matches('/'),
+                badge.file_name,
+                badge.label,
+                badge.message
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_backfill_summary(stats: &mut BadgeStats, summary: &BackfillRunSummary) {
+    stats.backfilled_contexts += 1;
+    stats.tests_passing += summary.implemented_count;
+    stats.tests_failing += summary.failed_count + summary.missing_count;
+    stats.actions_implemented += summary.implemented_count;
+}
+
+fn build_badges(stats: &BadgeStats) -> Vec<Badge> {
+    let contexts_color = if stats.contexts_total > 0 && stats.contexts_done == stats.contexts_total
+    {
+        COLOR_GREEN
+    } else if stats.contexts_done > 0 {
+        COLOR_YELLOW
+    } else {
+        COLOR_GREY
+    };
+
+    let (tests_message, tests_color) = if stats.backfilled_contexts == 0 {
+        ("unknown".to_string(), COLOR_GREY)
+    } else if stats.tests_failing == 0 {
+        (format!("{} passing", stats.tests_passing), COLOR_GREEN)
+    } else {
+        (
+            format!(
+                "{} passing, {} failing",
+                stats.tests_passing, stats.tests_failing
+            ),
+            COLOR_RED,
+        )
+    };
+
+    let implemented_percent =
+        (stats.actions_implemented.max(0) as usize * 100).checked_div(stats.actions_total);
+    let (coverage_message, coverage_color) = match implemented_percent {
+        None => ("n/a".to_string(), COLOR_GREY),
+        Some(percent) => {
+            let color = match percent {
+                80.. => COLOR_GREEN,
+                40..=79 => COLOR_YELLOW,
+                _ => COLOR_RED,
+            };
+            (format!("{}%", percent), color)
+        }
+    };
+
+    vec![
+        Badge {
+            file_name: "contexts.svg".to_string(),
+            label: "contexts".to_string(),
+            message: format!("{}/{} done", stats.contexts_done, stats.contexts_total),
+            color: contexts_color.to_string(),
+        },
+        Badge {
+            file_name: "tests.svg".to_string(),
+            label: "tests".to_string(),
+            message: tests_message,
+            color: tests_color.to_string(),
+        },
+        Badge {
+            file_name: "coverage.svg".to_string(),
+            label: "spec coverage".to_string(),
+            message: coverage_message,
+            color: coverage_color.to_string(),
+        },
+    ]
+}
+
+fn render_badge_svg(label: &str, message: &str, color: &str) -> String {
+    let label_width = badge_text_width(label);
+    let message_width = badge_text_width(message);
+    let total_width = label_width + message_width;
+    let label = escape_xml(label);
+    let message = escape_xml(message);
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{total}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{total}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        total = total_width,
+        label = label,
+        message = message,
+        label_width = label_width,
+        message_width = message_width,
+        color = color,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+fn badge_text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_badge_svg_embeds_label_message_and_color() {
+        let svg = render_badge_svg("tests", "3 passing", COLOR_GREEN);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">tests</text>"));
+        assert!(svg.contains(">3 passing</text>"));
+        assert!(svg.contains(&format!("fill=\"{}\"", COLOR_GREEN)));
+    }
+
+    #[test]
+    fn render_badge_svg_escapes_markup() {
+        let svg = render_badge_svg("a<b", "x & y", COLOR_GREY);
+        assert!(svg.contains("a&lt;b"));
+        assert!(svg.contains("x &amp; y"));
+    }
+
+    #[test]
+    fn build_badges_reports_unknown_without_backfill_data() {
+        let badges = build_badges(&BadgeStats {
+            contexts_total: 4,
+            contexts_done: 1,
+            actions_total: 10,
+            ..BadgeStats::default()
+        });
+        assert_eq!(badges[0].message, "1/4 done");
+        assert_eq!(badges[0].color, COLOR_YELLOW);
+        assert_eq!(badges[1].message, "unknown");
+        assert_eq!(badges[2].message, "0%");
+        assert_eq!(badges[2].color, COLOR_RED);
+    }
+
+    #[test]
+    fn build_badges_uses_latest_backfill_counts() {
+        let mut stats = BadgeStats {
+            contexts_total: 2,
+            contexts_done: 2,
+            actions_total: 5,
+            ..BadgeStats::default()
+        };
+        apply_backfill_summary(
+            &mut stats,
+            &BackfillRunSummary {
+                outcome: "incomplete".to_string(),
+                implemented_count: 4,
+                failed_count: 1,
+                missing_count: 0,
+                finished_at: 0,
+            },
+        );
+        let badges = build_badges(&stats);
+        assert_eq!(badges[0].color, COLOR_GREEN);
+        assert_eq!(badges[1].message, "4 passing, 1 failing");
+        assert_eq!(badges[1].color, COLOR_RED);
+        assert_eq!(badges[2].message, "80%");
+        assert_eq!(badges[2].color, COLOR_GREEN);
+    }
+}
@@ -0,0 +1,260 @@
+//! Bug-report command for collecting a redacted diagnostic bundle.
+//!
+//! The bundle is a single JSON file holding version and environment details,
+//! the sanitized `.nexus/config.json`, the audit log tail, and the transcript
+//! of the most recent failed orchestration run. A pre-filled GitHub issue URL
+//! is printed alongside so the reporter only has to attach the file.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::adapters::audit_log::default_audit_log_path;
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::OutputFormat;
+use crate::config::nexus::{read_config_value, NEXUS_CONFIG_PATH};
+use crate::output::{print_info, print_json, print_success};
+use crate::utils::paths::home_dir;
+use crate::utils::time::unix_now_seconds;
+
+const ISSUE_URL: &str = "https://github.com/Alpha-Innovation-Labs/nexus/issues/new";
+const AUDIT_TAIL_LINES: usize = 50;
+/// Prompt/response payloads are cut to this many characters per trace.
+const TRACE_PAYLOAD_CHARS: usize = 4_000;
+const REDACTED: &str = "[redacted]";
+
+#[derive(Debug, Serialize)]
+struct BugReportPayload {
+    bundle: String,
+    issue_url: String,
+    failed_run_id: Option<i64>,
+}
+
+/// Run `bug-report`, writing the bundle to `out` or a timestamped file.
+pub fn run_bug_report(out: Option<&str>, format: OutputFormat) -> Result<()> {
+    let bundle_path = out
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("nexus-bug-report-{}.json", unix_now_seconds())));
+    let home = home_dir().map(|home| home.display().to_string());
+
+    let failed_run = failed_run_transcript()?;
+    let failed_run_id = failed_run
+        .as_ref()
+        .and_then(|run| run["run"]["run_id"].as_i64());
+    let mut bundle = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "generated_at": unix_now_seconds(),
+        "environment": environment_details(),
+        "config": sanitized_config()?,
+        "audit_log_tail": audit_log_tail(),
+        "last_failed_run": failed_run,
+    });
+    redact_value(&mut bundle, home.as_deref());
+
+    let serialized = serde_json::to_string_pretty(&bundle)?;
+    fs::write(&bundle_path, format!("{}\n", serialized))
+        .with_context(|| format!("Failed to write '{}'.", bundle_path.display()))?;
+    let issue_url = issue_url(&bundle, &bundle_path);
+
+    if format.is_structured() {
+        return print_json(&BugReportPayload {
+            bundle: bundle_path.display().to_string(),
+            issue_url,
+            failed_run_id,
+        });
+    }
+    print_success(&format!(
+        "Wrote redacted diagnostic bundle to {}",
+        bundle_path.display()
+    ));
+    print_info("Review the bundle, then open the issue and attach it:");
+    println!("{}", issue_url);
+    Ok(())
+}
+
+fn environment_details() -> Value {
+    json!({
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "shell": std::env::var("SHELL").ok(),
+        "term": std::env::var("TERM").ok(),
+        "git": tool_version("git"),
+        "opencode": tool_version("opencode"),
+    })
+}
+
+fn tool_version(tool: &str) -> Option<String> {
+    let output = Command::new(tool).arg("--version").output().ok()?;
+    output.status.success().then(|| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    })
+}
+
+fn sanitized_config() -> Result<Value> {
+    let path = Path::new(NEXUS_CONFIG_PATH);
+    if !path.exists() {
+        return Ok(Value::Null);
+    }
+    Ok(read_config_value(path).unwrap_or_else(|err| json!({ "error": format!("{:#}", err) })))
+}
+
+/// Last audit entries, parsed so their fields go through key redaction.
+fn audit_log_tail() -> Vec<Value> {
+    let Ok(content) = fs::read_to_string(default_audit_log_path()) else {
+        return Vec::new();
+    };
+    let lines = content.lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(AUDIT_TAIL_LINES)..]
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|_| Value::String(line.to_string())))
+        .collect()
+}
+
+fn failed_run_transcript() -> Result<Option<Value>> {
+    let path = default_orchestration_database_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let store = OrchestrationStore::open(&path)?;
+    let Some(run) = store.latest_failed_run()? else {
+        return Ok(None);
+    };
+    let steps = store.list_step_attempts_for_run(run.run_id)?;
+    let traces = store
+        .query_traces_for_run(run.run_id)?
+        .into_iter()
+        .map(|mut trace| {
+            trace.prompt_payload = truncate_chars(&trace.prompt_payload, TRACE_PAYLOAD_CHARS);
+            trace.response_payload = truncate_chars(&trace.response_payload, TRACE_PAYLOAD_CHARS);
+            trace
+        })
+        .collect::<Vec<_>>();
+    Ok(Some(json!({
+        "run": run,
+        "step_attempts": steps,
+        "traces": traces,
+    })))
+}
+
+fn truncate_chars(value: &str, limit: usize) -> String {
+    match value.char_indices().nth(limit) {
+        Some((index, _)) => format!("{}… [truncated]", &value[..index]),
+        None => value.to_string(),
+    }
+}
+
+/// Blank out secret-looking keys and replace the home directory with `~`.
+fn redact_value(value: &mut Value, home: Option<&str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if is_secret_key(key) && !child.is_null() {
+                    *child = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(child, home);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact_value(item, home)),
+        Value::String(text) => {
+            if let Some(home) = home {
+                *text = text.replace(home, "~");
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let lowered = key.to_ascii_lowercase();
+    [
+        "token",
+        "secret",
+        "password",
+        "api_key",
+        "apikey",
+        "credential",
+        "authorization",
+    ]
+    .iter()
+    .any(|marker| lowered.contains(marker))
+}
+
+fn issue_url(bundle: &Value, bundle_path: &Path) -> String {
+    let failed = &bundle["last_failed_run"]["run"];
+    let title = match failed["pipeline_name"].as_str() {
+        Some(pipeline) => format!("Orchestration `{}` run failed", pipeline),
+        None => "Bug report".to_string(),
+    };
+    let body = format!(
+        "**Version:** {}\n**OS:** {} ({})\n**Last failed run:** {}\n\n### What happened\n\n<!-- Describe what you ran and what you expected. -->\n\n### Diagnostics\n\nAttach `{}` (generated by `opennexus bug-report`).\n",
+        bundle["version"].as_str().unwrap_or_default(),
+        bundle["environment"]["os"].as_str().unwrap_or_default(),
+        bundle["environment"]["arch"].as_str().unwrap_or_default(),
+        match (failed["run_id"].as_i64(), failed["terminal_reason"].as_str()) {
+            (Some(run_id), Some(reason)) => format!("{} ({})", run_id, reason),
+            (Some(run_id), None) => run_id.to_string(),
+            (None, _) => "none".to_string(),
+        },
+        bundle_path.display()
+    );
+    format!(
+        "{}?title={}&body={}",
+        ISSUE_URL,
+        percent_encode(&title),
+        percent_encode(&body)
+    )
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::paths::home_dir_from;
+    use std::ffi::OsString;
+
+    #[test]
+    fn redacts_secrets_and_home_paths() {
+        let mut value = json!({
+            "marketplace": { "registry_token": "abc123", "url": "https://example.com" },
+            "paths": ["/home/dev/project/.nexus/config.json"],
+            "api_key": null,
+        });
+        redact_value(&mut value, Some("/home/dev"));
+        assert_eq!(value["marketplace"]["registry_token"], REDACTED);
+        assert_eq!(value["marketplace"]["url"], "https://example.com");
+        assert_eq!(value["paths"][0], "~/project/.nexus/config.json");
+        assert!(value["api_key"].is_null());
+        assert_eq!(percent_encode("a b&c/é"), "a%20b%26c%2F%C3%A9");
+        assert_eq!(truncate_chars("héllo", 2), "hé… [truncated]");
+
+        // Windows usually has no HOME; the profile directory is redacted.
+        let home =
+            home_dir_from(|name| (name == "USERPROFILE").then(|| OsString::from(r"C:\Users\dev")))
+                .map(|home| home.display().to_string());
+        let mut value = json!({ "paths": [r"C:\Users\dev\project\.nexus\config.json"] });
+        redact_value(&mut value, home.as_deref());
+        assert_eq!(value["paths"][0], r"~\project\.nexus\config.json");
+    }
+}
@@ -0,0 +1,55 @@
+//! Burndown command for charting next-action progress from run history.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::{BurndownChart, OutputFormat};
+use crate::features::burndown::{burndown_series, render_chart, BurndownPoint};
+use crate::output::{print_info, print_json};
+use crate::utils::time::unix_now_seconds;
+
+#[derive(Debug, Serialize)]
+struct BurndownPayload<'a> {
+    project: Option<&'a str>,
+    chart: BurndownChart,
+    series: Vec<BurndownPoint>,
+}
+
+/// Run `burndown`, printing a chart or (with `--format json`) the daily series.
+pub fn run_burndown(
+    project: Option<&str>,
+    days: Option<u32>,
+    chart: BurndownChart,
+    format: OutputFormat,
+) -> Result<()> {
+    let database_path = default_orchestration_database_path();
+    let rows = if database_path.exists() {
+        OrchestrationStore::open(&database_path)?.list_action_history(project)?
+    } else {
+        Vec::new()
+    };
+    let series = burndown_series(&rows, unix_now_seconds(), days);
+
+    if format.is_structured() {
+        return print_json(&BurndownPayload {
+            project,
+            chart,
+            series,
+        });
+    }
+    let Some(latest) = series.last() else {
+        print_info(
+            "No action history yet. Run an orchestration pipeline to start recording progress.",
+        );
+        return Ok(());
+    };
+    println!("{}", render_chart(&series, chart));
+    println!(
+        "{} of {} action(s) completed, {} remaining",
+        latest.completed, latest.scope, latest.remaining
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Clean command for pruning local history under the retention policy.
+
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::OutputFormat;
+use crate::config::nexus::{load_nexus_config, RetentionConfig, NEXUS_CONFIG_PATH};
+use crate::features::retention::{prune_history, PruneSummary};
+use crate::output::{print_info, print_json, print_success};
+use crate::utils::time::unix_now_seconds;
+
+/// Run `clean --history`; flag limits override the configured `retention` policy.
+pub fn run_clean(
+    history: bool,
+    max_age_days: Option<u64>,
+    max_size_mb: Option<u64>,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    if !history {
+        bail!("Nothing selected to clean. Remediation: pass --history to prune run history.");
+    }
+    let configured = load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?.retention;
+    let policy = RetentionConfig {
+        max_age_days: max_age_days.or(configured.max_age_days),
+        max_size_mb: max_size_mb.or(configured.max_size_mb),
+    };
+    if policy.is_empty() {
+        bail!(
+            "No retention limits set. Remediation: pass --max-age-days/--max-size-mb, or set \"retention\": {{ \"max_age_days\": 90 }} in {}.",
+            NEXUS_CONFIG_PATH
+        );
+    }
+
+    let database_path = default_orchestration_database_path();
+    let summary = if database_path.exists() {
+        let store = OrchestrationStore::open(&database_path)?;
+        prune_history(&store, &policy, unix_now_seconds(), dry_run)?
+    } else {
+        PruneSummary {
+            dry_run,
+            ..PruneSummary::default()
+        }
+    };
+
+    if format.is_structured() {
+        return print_json(&summary);
+    }
+    if summary.runs_pruned == 0 {
+        print_info("No runs exceed the retention policy.");
+    } else {
+        print_success(&prune_summary_message(&summary));
+    }
+    if summary.pinned_kept > 0 {
+        print_info(&format!(
+            "Kept {} pinned run(s); unpin with `opennexus orchestration unpin --context-file <path> --run-id <id>`.",
+            summary.pinned_kept
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn prune_summary_message(summary: &PruneSummary) -> String {
+    format!(
+        "{} {} run(s) ({:.1} MB of transcripts and payloads)",
+        if summary.dry_run {
+            "Would prune"
+        } else {
+            "Pruned"
+        },
+        summary.runs_pruned,
+        summary.bytes_pruned as f64 / (1024.0 * 1024.0)
+    )
+}
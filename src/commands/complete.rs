@@ -0,0 +1,378 @@
+//! Hidden `__complete` command backing dynamic shell completion.
+//!
+//! Shells call `opennexus __complete <words…>` with the words typed after the
+//! binary name (the last one possibly empty) and read one
+//! `value<TAB>description` candidate per line. Subcommands, flags, and enum
+//! values come from the clap tree; context files and ids, project names,
+//! pipeline names, and run ids come from the current repository.
+
+use anyhow::Result;
+use clap::{Arg, Command, CommandFactory};
+
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::Cli;
+use crate::commands::orchestration::{default_pipeline_summaries, ORCHESTRATION_ACTIONS};
+use crate::features::context::translate::context_markdown_files;
+use crate::utils::time::format_unix_rfc3339;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONTEXT_ROOT: &str = ".nexus/context";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionCandidate {
+    pub value: String,
+    pub description: String,
+}
+
+/// Repository data offered as completion values.
+pub trait CompletionSources {
+    fn context_files(&self) -> Vec<CompletionCandidate>;
+    fn context_ids(&self) -> Vec<CompletionCandidate>;
+    fn projects(&self) -> Vec<CompletionCandidate>;
+    fn pipelines(&self) -> Vec<CompletionCandidate>;
+    fn runs(&self, context_file: &str) -> Vec<CompletionCandidate>;
+}
+
+/// Run `__complete` and print candidates for the last word in `words`.
+pub fn run_complete(words: &[String]) -> Result<()> {
+    let mut command = Cli::command();
+    command.build();
+    for candidate in complete_words(&command, words, &RepoCompletionSources) {
+        if candidate.description.is_empty() {
+            println!("{}", candidate.value);
+        } else {
+            println!("{}\t{}", candidate.value, candidate.description);
+        }
+    }
+    Ok(())
+}
+
+/// Candidates for the last word of `words`, given the words before it.
+pub fn complete_words(
+    root: &Command,
+    words: &[String],
+    sources: &dyn CompletionSources,
+) -> Vec<CompletionCandidate> {
+    let (current, previous) = match words.split_last() {
+        Some((current, previous)) => (current.as_str(), previous),
+        None => ("", words),
+    };
+
+    let mut command = root;
+    let mut positionals = Vec::<&str>::new();
+    let mut pending_value: Option<&Arg> = None;
+    let mut context_file: Option<&str> = None;
+    for word in previous {
+        if let Some(arg) = pending_value.take() {
+            if arg.get_long() == Some("context-file") {
+                context_file = Some(word);
+            }
+            continue;
+        }
+        if let Some(long) = word.strip_prefix("--") {
+            let (name, inline_value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (long, None),
+            };
+            let arg = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(name));
+            match (arg, inline_value) {
+                (Some(_), Some(value)) if name == "context-file" => context_file = Some(value),
+                (Some(arg), None) if arg.get_action().takes_values() => pending_value = Some(arg),
+                _ => {}
+            }
+            continue;
+        }
+        if word.starts_with('-') {
+            continue;
+        }
+        match command.find_subcommand(word) {
+            Some(subcommand) if positionals.is_empty() => command = subcommand,
+            _ => positionals.push(word),
+        }
+    }
+
+    let candidates = if let Some(arg) = pending_value {
+        value_candidates(arg, context_file, sources)
+    } else if current.starts_with('-') {
+        command
+            .get_arguments()
+            .filter(|arg| !arg.is_hide_set())
+            .filter_map(|arg| {
+                arg.get_long().map(|long| CompletionCandidate {
+                    value: format!("--{}", long),
+                    description: help_text(arg),
+                })
+            })
+            .collect()
+    } else if command.has_subcommands() {
+        command
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .map(|subcommand| CompletionCandidate {
+                value: subcommand.get_name().to_string(),
+                description: subcommand
+                    .get_about()
+                    .map(|about| about.to_string())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    } else if command.get_name() == "orchestration" {
+        match positionals.as_slice() {
+            [] => ORCHESTRATION_ACTIONS
+                .iter()
+                .map(|action| CompletionCandidate {
+                    value: action.to_string(),
+                    description: "built-in action".to_string(),
+                })
+                .chain(sources.pipelines())
+                .collect(),
+            ["restart"] => sources.pipelines(),
+            _ => Vec::new(),
+        }
+    } else if command.get_name() == "scaffold" && positionals.is_empty() {
+        sources.context_ids()
+    } else {
+        Vec::new()
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.value.starts_with(current))
+        .collect()
+}
+
+fn value_candidates(
+    arg: &Arg,
+    context_file: Option<&str>,
+    sources: &dyn CompletionSources,
+) -> Vec<CompletionCandidate> {
+    match arg.get_long() {
+        Some("context-file") => sources.context_files(),
+        Some("context-id") => sources.context_ids(),
+        Some("project") => sources.projects(),
+        Some("run-id" | "compare-run-id") => context_file
+            .map(|context_file| sources.runs(context_file))
+            .unwrap_or_default(),
+        _ => arg
+            .get_possible_values()
+            .into_iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| CompletionCandidate {
+                value: value.get_name().to_string(),
+                description: value
+                    .get_help()
+                    .map(|help| help.to_string())
+                    .unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+fn help_text(arg: &Arg) -> String {
+    arg.get_help()
+        .map(|help| help.to_string())
+        .unwrap_or_default()
+}
+
+pub(crate) struct RepoCompletionSources;
+
+/// `(path, context_id, heading)` for every context under `.nexus/context/`.
+fn repo_contexts() -> Vec<(PathBuf, String, String)> {
+    context_markdown_files(Path::new(CONTEXT_ROOT))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            let context_id = content
+                .lines()
+                .find_map(|line| line.strip_prefix("context_id:"))?
+                .trim()
+                .trim_matches('"')
+                .to_string();
+            let title = content
+                .lines()
+                .find_map(|line| line.strip_prefix("# "))
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            Some((path, context_id, title))
+        })
+        .collect()
+}
+
+impl CompletionSources for RepoCompletionSources {
+    fn context_files(&self) -> Vec<CompletionCandidate> {
+        repo_contexts()
+            .into_iter()
+            .map(|(path, context_id, title)| CompletionCandidate {
+                value: path.display().to_string(),
+                description: if title.starts_with(&context_id) {
+                    title
+                } else {
+                    format!("{} {}", context_id, title).trim().to_string()
+                },
+            })
+            .collect()
+    }
+
+    fn context_ids(&self) -> Vec<CompletionCandidate> {
+        repo_contexts()
+            .into_iter()
+            .map(|(_, context_id, title)| CompletionCandidate {
+                description: title
+                    .strip_prefix(&context_id)
+                    .map(|rest| rest.trim_start_matches([':', ' ']).to_string())
+                    .unwrap_or(title),
+                value: context_id,
+            })
+            .collect()
+    }
+
+    fn projects(&self) -> Vec<CompletionCandidate> {
+        let Ok(entries) = fs::read_dir(CONTEXT_ROOT) else {
+            return Vec::new();
+        };
+        let mut projects = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| !name.starts_with(['.', '_']))
+            .map(|name| CompletionCandidate {
+                value: name,
+                description: "project".to_string(),
+            })
+            .collect::<Vec<_>>();
+        projects.sort_by(|left, right| left.value.cmp(&right.value));
+        projects
+    }
+
+    fn pipelines(&self) -> Vec<CompletionCandidate> {
+        default_pipeline_summaries()
+            .into_iter()
+            .map(|(name, steps)| CompletionCandidate {
+                value: name,
+                description: format!("pipeline, {} steps", steps),
+            })
+            .collect()
+    }
+
+    fn runs(&self, context_file: &str) -> Vec<CompletionCandidate> {
+        let path = default_orchestration_database_path();
+        if !path.exists() {
+            return Vec::new();
+        }
+        OrchestrationStore::open(&path)
+            .and_then(|store| store.list_runs_for_context(context_file))
+            .unwrap_or_default()
+            .into_iter()
+            .map(
+                |(run_id, pipeline, status, _, started_at, _, _)| CompletionCandidate {
+                    value: run_id.to_string(),
+                    description: format!(
+                        "{} {} {}",
+                        pipeline,
+                        status,
+                        format_unix_rfc3339(started_at)
+                    ),
+                },
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixtureSources;
+
+    impl CompletionSources for FixtureSources {
+        fn context_files(&self) -> Vec<CompletionCandidate> {
+            vec![CompletionCandidate {
+                value: ".nexus/context/app/CDD_001-login.md".to_string(),
+                description: "CDD_001 Login".to_string(),
+            }]
+        }
+
+        fn context_ids(&self) -> Vec<CompletionCandidate> {
+            vec![CompletionCandidate {
+                value: "CDD_001".to_string(),
+                description: "Login".to_string(),
+            }]
+        }
+
+        fn projects(&self) -> Vec<CompletionCandidate> {
+            ["app", "docs"]
+                .iter()
+                .map(|name| CompletionCandidate {
+                    value: name.to_string(),
+                    description: "project".to_string(),
+                })
+                .collect()
+        }
+
+        fn pipelines(&self) -> Vec<CompletionCandidate> {
+            vec![CompletionCandidate {
+                value: "gen-code".to_string(),
+                description: "pipeline, 4 steps".to_string(),
+            }]
+        }
+
+        fn runs(&self, context_file: &str) -> Vec<CompletionCandidate> {
+            vec![CompletionCandidate {
+                value: "7".to_string(),
+                description: context_file.to_string(),
+            }]
+        }
+    }
+
+    fn values(words: &[&str]) -> Vec<String> {
+        let mut command = Cli::command();
+        command.build();
+        let words = words
+            .iter()
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>();
+        complete_words(&command, &words, &FixtureSources)
+            .into_iter()
+            .map(|candidate| candidate.value)
+            .collect()
+    }
+
+    #[test]
+    fn completes_subcommands_flags_and_repo_values() {
+        assert_eq!(values(&["mark"]), vec!["marketplace"]);
+        assert!(!values(&[""]).contains(&"__complete".to_string()));
+        assert_eq!(values(&["orchestration", "re"]), vec!["restart"]);
+        assert_eq!(values(&["orchestration", "restart", "g"]), vec!["gen-code"]);
+        assert_eq!(
+            values(&["orchestration", "runs", "--context-file", ""]),
+            vec![".nexus/context/app/CDD_001-login.md"]
+        );
+        assert_eq!(
+            values(&[
+                "orchestration",
+                "traces",
+                "--context-file",
+                "ctx.md",
+                "--run-id",
+                ""
+            ]),
+            vec!["7"]
+        );
+        assert!(values(&["orchestration", "runs", "--ru"]).contains(&"--run-id".to_string()));
+        assert_eq!(values(&["preset", "list", "--format", "j"]), vec!["json"]);
+        assert_eq!(values(&["badge", "--project", "d"]), vec!["docs"]);
+        assert_eq!(
+            values(&["context", "index", "--project", ""]),
+            vec!["app", "docs"]
+        );
+        assert_eq!(values(&["scaffold", "CDD"]), vec!["CDD_001"]);
+        assert!(values(&["scaffold", "CDD_001", ""]).is_empty());
+    }
+}
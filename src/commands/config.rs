@@ -0,0 +1,300 @@
+//! Config commands for validating, migrating, and editing `.nexus/config.json`
+//! and the user config.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{ConfigScope, OutputFormat};
+use crate::config::keys::{display_value, flatten_keys, get_key, parse_value, set_key, unset_key};
+use crate::config::nexus::{
+    migrate_deprecated_config, read_config_value, validate_config_value, write_config_value,
+    ConfigIssueSeverity, LEGACY_DOCS_SYNC_STATE_PATH, NEXUS_CONFIG_PATH,
+};
+use crate::config::user::{user_config_path, UserConfig, USER_CONFIG_KEYS};
+use crate::core::error::CliError;
+use crate::output::{print_error, print_info, print_json, print_success, print_tsv_row};
+use crate::utils::text::closest_match;
+
+/// Run `config doctor`.
+///
+/// Reports schema errors and deprecated keys, then migrates deprecated
+/// formats in place unless `dry_run` is set.
+pub fn run_config_doctor(dry_run: bool, format: OutputFormat) -> Result<()> {
+    let config_path = Path::new(NEXUS_CONFIG_PATH);
+    if !config_path.exists() {
+        bail!(
+            "No {} found. Remediation: run `opennexus setup` first.",
+            NEXUS_CONFIG_PATH
+        );
+    }
+
+    let original = read_config_value(config_path)?;
+    let issues = validate_config_value(&original);
+
+    let legacy_path = Path::new(LEGACY_DOCS_SYNC_STATE_PATH);
+    let legacy_state = if legacy_path.exists() {
+        Some(read_config_value(legacy_path)?)
+    } else {
+        None
+    };
+
+    let mut migrated = original.clone();
+    let migrations = migrate_deprecated_config(&mut migrated, legacy_state);
+    if !dry_run && !migrations.is_empty() {
+        write_config_value(config_path, &migrated)?;
+        if legacy_path.exists() {
+            fs::remove_file(legacy_path)
+                .with_context(|| format!("Failed to remove '{}'.", legacy_path.display()))?;
+        }
+    }
+
+    let error_count = issues
+        .iter()
+        .filter(|issue| issue.severity == ConfigIssueSeverity::Error)
+        .count();
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if error_count == 0 { "ok" } else { "invalid" },
+            "config": NEXUS_CONFIG_PATH,
+            "issues": issues,
+            "migrations": migrations,
+            "applied": !dry_run && !migrations.is_empty(),
+        }))?;
+    } else {
+        for issue in &issues {
+            match issue.severity {
+                ConfigIssueSeverity::Error => {
+                    print_error(&format!("{}: {}", issue.path, issue.message))
+                }
+                ConfigIssueSeverity::Deprecated => {
+                    print_info(&format!("{}: {}", issue.path, issue.message))
+                }
+            }
+        }
+        for migration in &migrations {
+            if dry_run {
+                print_info(&format!("Would migrate: {}", migration));
+            } else {
+                print_success(&format!("Migrated: {}", migration));
+            }
+        }
+        if issues.is_empty() && migrations.is_empty() {
+            print_success(&format!("{} is valid.", NEXUS_CONFIG_PATH));
+        }
+    }
+
+    if error_count > 0 {
+        bail!(
+            "{} has {} schema error(s). Remediation: fix the reported keys and rerun `opennexus config doctor`.",
+            NEXUS_CONFIG_PATH,
+            error_count
+        );
+    }
+    Ok(())
+}
+
+pub fn run_config_get(key: &str, scope: ConfigScope, format: OutputFormat) -> Result<()> {
+    let (path, scope_name) = scope_target(scope)?;
+    let document = read_scope_document(&path)?;
+    let Some(value) = get_key(&document, key) else {
+        bail!(CliError::not_found(format!(
+            "'{}' is not set in '{}'. Remediation: run `opennexus config list{}` to see the keys that are set.",
+            key,
+            path.display(),
+            scope_flag(scope_name)
+        )));
+    };
+    match format {
+        OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Ndjson => {
+            print_json(&serde_json::json!({
+                "scope": scope_name,
+                "key": key,
+                "value": value,
+            }))
+        }
+        OutputFormat::Tsv => {
+            print_tsv_row(&[key, &display_value(value)]);
+            Ok(())
+        }
+        OutputFormat::Text => {
+            println!("{}", display_value(value));
+            Ok(())
+        }
+    }
+}
+
+pub fn run_config_set(
+    key: &str,
+    raw_value: &str,
+    scope: ConfigScope,
+    format: OutputFormat,
+) -> Result<()> {
+    let (path, scope_name) = scope_target(scope)?;
+    if scope_name == "project" && !path.exists() {
+        bail!(
+            "No {} found. Remediation: run `opennexus setup` first, or pass --global.",
+            NEXUS_CONFIG_PATH
+        );
+    }
+    let mut document = read_scope_document(&path)?;
+    let value = parse_value(raw_value);
+    set_key(&mut document, key, value.clone())?;
+    validate_scope_document(&document, key, scope_name)?;
+    write_scope_document(&path, &document)?;
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "set",
+            "scope": scope_name,
+            "key": key,
+            "value": value,
+        }))
+    } else {
+        print_success(&format!(
+            "Set {} = {} in '{}'.",
+            key,
+            display_value(&value),
+            path.display()
+        ));
+        Ok(())
+    }
+}
+
+pub fn run_config_unset(key: &str, scope: ConfigScope, format: OutputFormat) -> Result<()> {
+    let (path, scope_name) = scope_target(scope)?;
+    let mut document = read_scope_document(&path)?;
+    let removed = unset_key(&mut document, key);
+    if removed.is_some() {
+        write_scope_document(&path, &document)?;
+    }
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if removed.is_some() { "unset" } else { "not_set" },
+            "scope": scope_name,
+            "key": key,
+        }))
+    } else {
+        if removed.is_some() {
+            print_success(&format!("Unset {} in '{}'.", key, path.display()));
+        } else {
+            print_info(&format!("{} was not set in '{}'.", key, path.display()));
+        }
+        Ok(())
+    }
+}
+
+pub fn run_config_list(scope: ConfigScope, format: OutputFormat) -> Result<()> {
+    let (path, scope_name) = scope_target(scope)?;
+    let document = read_scope_document(&path)?;
+    if format.is_structured() {
+        return print_json(&serde_json::json!({
+            "scope": scope_name,
+            "path": path.display().to_string(),
+            "config": document,
+        }));
+    }
+    let entries = flatten_keys(&document);
+    if format == OutputFormat::Tsv {
+        for (key, value) in &entries {
+            print_tsv_row(&[key, &display_value(value)]);
+        }
+        return Ok(());
+    }
+    if entries.is_empty() {
+        print_info(&format!("No keys are set in '{}'.", path.display()));
+        return Ok(());
+    }
+    for (key, value) in &entries {
+        println!("{} = {}", key, display_value(value));
+    }
+    Ok(())
+}
+
+/// The config file for `scope` and its name; the project config is the default.
+fn scope_target(scope: ConfigScope) -> Result<(PathBuf, &'static str)> {
+    if !scope.global {
+        return Ok((PathBuf::from(NEXUS_CONFIG_PATH), "project"));
+    }
+    let Some(path) = user_config_path() else {
+        bail!("Cannot locate the user config directory. Remediation: set HOME or XDG_CONFIG_HOME.");
+    };
+    Ok((path, "global"))
+}
+
+fn scope_flag(scope_name: &str) -> &'static str {
+    if scope_name == "global" {
+        " --global"
+    } else {
+        ""
+    }
+}
+
+/// Read a config document; a missing file reads as an empty object.
+fn read_scope_document(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Object(Map::new()));
+    }
+    read_config_value(path)
+}
+
+fn write_scope_document(path: &Path, document: &Value) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+    }
+    write_config_value(path, document)
+}
+
+/// Reject a `config set` whose key or value breaks the schema of its scope.
+/// Only issues at or below `key` count, so unrelated problems elsewhere in
+/// the file do not block the edit.
+fn validate_scope_document(document: &Value, key: &str, scope_name: &str) -> Result<()> {
+    if scope_name == "global" {
+        let top = key.split('.').next().unwrap_or(key);
+        if !USER_CONFIG_KEYS.contains(&top) {
+            let hint = closest_match(top, &USER_CONFIG_KEYS)
+                .map(|candidate| format!(" Did you mean `{}`?", candidate))
+                .unwrap_or_default();
+            bail!(
+                "Unknown user config key '{}'.{} Remediation: the user config supports {}.",
+                key,
+                hint,
+                USER_CONFIG_KEYS.join(", ")
+            );
+        }
+        serde_json::from_value::<UserConfig>(document.clone()).with_context(|| {
+            format!(
+                "Invalid value for '{}'. Remediation: color is auto/always/never, format is text/json/tsv/yaml/ndjson, role is maintainer/viewer, update_channel is stable/beta/nightly, and update_check is true/false.",
+                key
+            )
+        })?;
+        return Ok(());
+    }
+
+    let errors = validate_config_value(document)
+        .into_iter()
+        .filter(|issue| issue.severity == ConfigIssueSeverity::Error)
+        .filter(|issue| {
+            issue.path == key
+                || issue.path.starts_with(&format!("{}.", key))
+                || issue.path.starts_with(&format!("{}[", key))
+                || key.starts_with(&format!("{}.", issue.path))
+        })
+        .map(|issue| format!("{}: {}", issue.path, issue.message))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        bail!(
+            "Refusing to set '{}':\n  - {}\nRemediation: fix the key or value and retry.",
+            key,
+            errors.join("\n  - ")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,285 @@
+//! Context command group for maintaining context documents.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::app::{
+    run_orchestration_backfill_app, run_orchestration_implement_app,
+    run_orchestration_test_status_app,
+};
+use crate::cli::{
+    ContextImplementCommand, OutputFormat, DEFAULT_MAX_ITERATIONS, DEFAULT_TIMEOUT_SECONDS,
+};
+use crate::commands::orchestration::ensure_context_unfrozen;
+use crate::core::context::model::{
+    ContextBackfillOptions, ContextImplementOptions, ContextTestStatusOptions,
+};
+use crate::features::context::index::{regenerate_context_indexes, ProjectIndexUpdate};
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::{
+    ensure_backfill_complete, print_backfill_report, print_test_status_report,
+    scaffold_only_step_ids,
+};
+use crate::output::{print_info, print_json, print_success, print_warning};
+
+#[derive(Debug, Serialize)]
+struct ImplementPayload {
+    context_file: String,
+    outcome: Option<&'static str>,
+    tests_passed: bool,
+    generated_tests: Vec<GeneratedTest>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeneratedTest {
+    test_id: String,
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TestStatusPayload {
+    context_file: String,
+    context_id: String,
+    discovered: usize,
+    total: usize,
+    tests: Vec<TestStatusEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct TestStatusEntry {
+    test_id: String,
+    discovered: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct BackfillPayload {
+    contexts_audited: usize,
+    contexts: Vec<BackfillContextPayload>,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BackfillContextPayload {
+    context_id: String,
+    context_file: String,
+    implemented: usize,
+    failed: usize,
+    missing: usize,
+    tasks: Vec<BackfillTaskPayload>,
+}
+
+#[derive(Debug, Serialize)]
+struct BackfillTaskPayload {
+    test_id: String,
+    status: &'static str,
+    details: Option<String>,
+}
+
+/// Run `context implement`: the default pipeline steps against one context,
+/// without the run history, dedupe, and hooks of `orchestration`.
+pub fn run_context_implement(
+    command: &ContextImplementCommand,
+    format: OutputFormat,
+) -> Result<()> {
+    let parsed = parse_context_file(Path::new(&command.context_file))?;
+    ensure_context_unfrozen(
+        &parsed,
+        &command.context_file,
+        "context implement",
+        command.unfreeze,
+    )?;
+    let max_iterations = command.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+    let options = ContextImplementOptions {
+        pipeline_name: "default".to_string(),
+        context_file: PathBuf::from(&command.context_file),
+        max_iterations,
+        timeout_seconds: command.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS),
+        rule_file: command.rule_file.clone(),
+        test_command: command.test_command.clone(),
+        test_discovery_command: command.test_discovery_command.clone(),
+        agent_model: command.model.clone(),
+        pipeline_steps: (max_iterations == 0).then(scaffold_only_step_ids),
+        red_failure_patterns: Vec::new(),
+        checkpoint_file: command.checkpoint_file.as_ref().map(PathBuf::from),
+        resume_checkpoint: command.resume_checkpoint.as_ref().map(PathBuf::from),
+        allow_dependency_bypass: command.allow_dependency_bypass,
+        overwrite: false,
+        force_regenerate_tests: command.force,
+        run_id: None,
+    };
+    let summary = run_orchestration_implement_app(&options)?;
+    let outcome = summary.terminal_outcome.map(|outcome| outcome.as_str());
+
+    if format.is_structured() {
+        return print_json(&ImplementPayload {
+            context_file: command.context_file.clone(),
+            outcome,
+            tests_passed: summary.tests_passed,
+            generated_tests: summary
+                .generated_tests
+                .iter()
+                .map(|(test_id, path)| GeneratedTest {
+                    test_id: test_id.clone(),
+                    path: path.display().to_string(),
+                })
+                .collect(),
+        });
+    }
+    for (test_id, path) in &summary.generated_tests {
+        println!("  {} -> {}", test_id, path.display());
+    }
+    match outcome {
+        Some(outcome) if summary.tests_passed => {
+            print_success(&format!("Implemented {} ({})", parsed.context_id, outcome))
+        }
+        Some(outcome) => print_warning(&format!(
+            "Stopped implementing {}: {}",
+            parsed.context_id, outcome
+        )),
+        None => print_info(&format!(
+            "Generated {} test file(s) for {}",
+            summary.generated_tests.len(),
+            parsed.context_id
+        )),
+    }
+    Ok(())
+}
+
+/// Run `context test-status` for one context.
+pub fn run_context_test_status(context_file: &str, format: OutputFormat) -> Result<()> {
+    let report = run_orchestration_test_status_app(&ContextTestStatusOptions {
+        context_file: PathBuf::from(context_file),
+        command_name: "test-status".to_string(),
+    })?;
+    if !format.is_structured() {
+        print_test_status_report(&report);
+        return Ok(());
+    }
+    print_json(&TestStatusPayload {
+        context_file: report.context_file.display().to_string(),
+        context_id: report.context_id.clone(),
+        discovered: report.discovered_count(),
+        total: report.tests.len(),
+        tests: report
+            .tests
+            .iter()
+            .map(|(test_id, discovered)| TestStatusEntry {
+                test_id: test_id.clone(),
+                discovered: *discovered,
+            })
+            .collect(),
+    })
+}
+
+/// Run `context backfill` for one context or, with `all`, every context.
+///
+/// The summary is printed before incomplete task states fail the command.
+pub fn run_context_backfill(
+    context_file: Option<&str>,
+    all: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let report = run_orchestration_backfill_app(&ContextBackfillOptions {
+        context_file: context_file.map(PathBuf::from),
+        all,
+    })?;
+    if format.is_structured() {
+        print_json(&BackfillPayload {
+            contexts_audited: report.contexts_audited,
+            contexts: report
+                .results
+                .iter()
+                .map(|result| BackfillContextPayload {
+                    context_id: result.context_id.clone(),
+                    context_file: result.context_file.display().to_string(),
+                    implemented: result.implemented_count(),
+                    failed: result.failed_count(),
+                    missing: result.missing_count(),
+                    tasks: result
+                        .tasks
+                        .iter()
+                        .map(|task| BackfillTaskPayload {
+                            test_id: task.test_id.clone(),
+                            status: task.status.as_str(),
+                            details: task.details.clone(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            errors: report.partial_errors.clone(),
+        })?;
+    } else {
+        print_backfill_report(&report);
+    }
+    ensure_backfill_complete(&report)
+}
+
+/// Run `context index`, regenerating project `index.md` context tables.
+pub fn run_context_index(project: Option<&str>, format: OutputFormat) -> Result<()> {
+    let updates = refresh_context_indexes(project)?;
+    if format.is_structured() {
+        return print_json(&updates);
+    }
+    for update in &updates {
+        let state = if update.changed {
+            "updated"
+        } else {
+            "unchanged"
+        };
+        println!(
+            "  {} ({} context(s), {})",
+            update.path, update.contexts, state
+        );
+    }
+    let changed = updates.iter().filter(|update| update.changed).count();
+    if changed == 0 {
+        print_info("Every project index is already current.");
+    } else {
+        print_success(&format!("Regenerated {} project index file(s)", changed));
+    }
+    Ok(())
+}
+
+/// Regenerate indexes with statuses from the orchestration store.
+pub(crate) fn refresh_context_indexes(project: Option<&str>) -> Result<Vec<ProjectIndexUpdate>> {
+    let store = open_existing_store()?;
+    regenerate_context_indexes(
+        Path::new(".nexus/context"),
+        project,
+        |context_id, frozen| context_status(store.as_ref(), context_id, frozen).to_string(),
+    )
+}
+
+/// The orchestration store, or `None` before the first run created it.
+pub(crate) fn open_existing_store() -> Result<Option<OrchestrationStore>> {
+    let store_path = default_orchestration_database_path();
+    if store_path.exists() {
+        Ok(Some(OrchestrationStore::open(&store_path)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// `done` once a pipeline succeeded for the context, else `frozen` or `pending`.
+pub(crate) fn context_status(
+    store: Option<&OrchestrationStore>,
+    context_id: &str,
+    frozen: bool,
+) -> &'static str {
+    let done = store.is_some_and(|store| {
+        store
+            .latest_success_for_context_id(context_id)
+            .ok()
+            .flatten()
+            .is_some()
+    });
+    match (done, frozen) {
+        (true, _) => "done",
+        (false, true) => "frozen",
+        (false, false) => "pending",
+    }
+}
@@ -0,0 +1,385 @@
+//! Doctor command for diagnosing a broken installation or repository.
+//!
+//! Each check reports `ok`, `warn`, or `fail` with a suggested fix: required
+//! binaries on `PATH`, the project config schema, the asset version recorded
+//! by `setup` against this CLI, dangling links under `.opencode/`, unparseable
+//! context files, and runs still marked running whose runner process is gone.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, OrchestrationStore,
+};
+use crate::cli::OutputFormat;
+use crate::config::nexus::{
+    read_config_value, validate_config_value, ConfigIssueSeverity, NEXUS_CONFIG_PATH,
+};
+use crate::features::context::scan::scan_valid_context_specs;
+use crate::output::{print_error, print_info, print_json, print_success, print_warning};
+
+const CONTEXT_ROOT: &str = ".nexus/context";
+const MAX_LISTED_FILES: usize = 3;
+const HARNESS_LINK_DIRS: [&str; 3] = [".opencode/command", ".opencode/skills", ".opencode/rules"];
+
+/// Ordered by severity, so the overall status is the maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: CheckStatus,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorReport {
+    status: CheckStatus,
+    checks: Vec<DoctorCheck>,
+}
+
+/// Run `doctor`, failing when any check fails; warnings alone succeed.
+pub fn run_doctor(format: OutputFormat) -> Result<()> {
+    let mut checks = ["git", "opencode"]
+        .into_iter()
+        .map(check_binary)
+        .collect::<Vec<_>>();
+    checks.push(check_project_config(Path::new(NEXUS_CONFIG_PATH)));
+    checks.push(check_asset_version(Path::new(NEXUS_CONFIG_PATH)));
+    checks.push(check_harness_links(Path::new(".")));
+    checks.push(check_context_tree(Path::new(CONTEXT_ROOT)));
+    checks.push(check_stale_runs());
+
+    let status = checks
+        .iter()
+        .map(|check| check.status)
+        .max()
+        .unwrap_or(CheckStatus::Ok);
+    let failures = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+
+    if format.is_structured() {
+        print_json(&DoctorReport { status, checks })?;
+    } else {
+        for check in &checks {
+            let line = format!("{}: {}", check.name, check.detail);
+            match check.status {
+                CheckStatus::Ok => print_success(&line),
+                CheckStatus::Warn => print_warning(&line),
+                CheckStatus::Fail => print_error(&line),
+            }
+            if let Some(fix) = &check.fix {
+                print_info(&format!("  Fix: {}", fix));
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!(
+            "{} doctor check(s) failed. Remediation: apply the suggested fixes and rerun `opennexus doctor`.",
+            failures
+        );
+    }
+    Ok(())
+}
+
+fn check_binary(tool: &'static str) -> DoctorCheck {
+    let version = Command::new(tool)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        });
+    match version {
+        Some(version) => DoctorCheck::ok(tool, format!("found ({})", version)),
+        None => DoctorCheck::problem(
+            tool,
+            CheckStatus::Fail,
+            "not found on PATH",
+            format!("install `{}` and make sure it is on PATH.", tool),
+        ),
+    }
+}
+
+fn check_project_config(path: &Path) -> DoctorCheck {
+    const NAME: &str = "config";
+    if !path.exists() {
+        return DoctorCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            format!("{} not found", path.display()),
+            "run `opennexus setup` in the repository root.",
+        );
+    }
+    let value = match read_config_value(path) {
+        Ok(value) => value,
+        Err(err) => {
+            return DoctorCheck::problem(
+                NAME,
+                CheckStatus::Fail,
+                err.to_string(),
+                format!("fix the JSON syntax in {}.", path.display()),
+            )
+        }
+    };
+    let errors = validate_config_value(&value)
+        .into_iter()
+        .filter(|issue| issue.severity == ConfigIssueSeverity::Error)
+        .count();
+    if errors == 0 {
+        DoctorCheck::ok(NAME, format!("{} is valid", path.display()))
+    } else {
+        DoctorCheck::problem(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} has {} schema error(s)", path.display(), errors),
+            "run `opennexus config doctor` for details.",
+        )
+    }
+}
+
+fn check_asset_version(path: &Path) -> DoctorCheck {
+    const NAME: &str = "assets";
+    let cli_version = env!("CARGO_PKG_VERSION");
+    let recorded = read_config_value(path)
+        .ok()
+        .and_then(|value| value.get("version")?.as_str().map(str::to_string));
+    match recorded {
+        Some(version) if version == cli_version => {
+            DoctorCheck::ok(NAME, format!("set up with this CLI ({})", cli_version))
+        }
+        Some(version) => DoctorCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "assets were set up by {}, this CLI is {}",
+                version, cli_version
+            ),
+            "run `opennexus setup` to refresh the .nexus assets.",
+        ),
+        None => DoctorCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            "no setup version recorded",
+            "run `opennexus setup` to install the .nexus assets.",
+        ),
+    }
+}
+
+fn check_harness_links(root: &Path) -> DoctorCheck {
+    const NAME: &str = "links";
+    let mut dangling = Vec::<String>::new();
+    for dir in HARNESS_LINK_DIRS {
+        let Ok(entries) = fs::read_dir(root.join(dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_symlink())
+                && !path.exists()
+            {
+                dangling.push(format!("{}/{}", dir, entry.file_name().to_string_lossy()));
+            }
+        }
+    }
+    dangling.sort();
+    if dangling.is_empty() {
+        DoctorCheck::ok(NAME, "no dangling harness links")
+    } else {
+        DoctorCheck::problem(
+            NAME,
+            CheckStatus::Fail,
+            format!("dangling: {}", dangling.join(", ")),
+            "run `opennexus setup` to relink harness assets.",
+        )
+    }
+}
+
+fn check_context_tree(root: &Path) -> DoctorCheck {
+    const NAME: &str = "contexts";
+    if !root.is_dir() {
+        return DoctorCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            format!("{} not found", root.display()),
+            "run `opennexus setup` or create context specs.",
+        );
+    }
+    match scan_valid_context_specs(root) {
+        Ok(scan) if scan.parse_errors.is_empty() => DoctorCheck::ok(
+            NAME,
+            format!("{} valid context file(s)", scan.valid_context_files.len()),
+        ),
+        Ok(scan) => {
+            let mut files = scan
+                .parse_errors
+                .iter()
+                .map(|error| {
+                    error
+                        .split_once(": ")
+                        .map_or(error.as_str(), |(path, _)| path)
+                })
+                .take(MAX_LISTED_FILES)
+                .collect::<Vec<_>>()
+                .join(", ");
+            if scan.parse_errors.len() > MAX_LISTED_FILES {
+                files.push_str(&format!(
+                    " (+{} more)",
+                    scan.parse_errors.len() - MAX_LISTED_FILES
+                ));
+            }
+            DoctorCheck::problem(
+                NAME,
+                CheckStatus::Warn,
+                format!(
+                    "{} file(s) skipped as unparseable: {}",
+                    scan.parse_errors.len(),
+                    files
+                ),
+                "fix their frontmatter and Next Actions table, or move non-context notes out of .nexus/context.",
+            )
+        }
+        Err(err) => DoctorCheck::problem(
+            NAME,
+            CheckStatus::Fail,
+            err.to_string(),
+            format!("check the permissions on {}.", root.display()),
+        ),
+    }
+}
+
+fn check_stale_runs() -> DoctorCheck {
+    const NAME: &str = "runs";
+    let path = default_orchestration_database_path();
+    if !path.exists() {
+        return DoctorCheck::ok(NAME, "no orchestration history");
+    }
+    let running = match OrchestrationStore::open(&path).and_then(|store| store.list_running_runs())
+    {
+        Ok(running) => running,
+        Err(err) => {
+            return DoctorCheck::problem(
+                NAME,
+                CheckStatus::Fail,
+                err.to_string(),
+                format!("check that {} is readable.", path.display()),
+            )
+        }
+    };
+    let stale = running
+        .iter()
+        .filter(|run| run.runner_pid.is_none_or(|pid| !process_alive(pid)))
+        .collect::<Vec<_>>();
+    match stale.first() {
+        None => DoctorCheck::ok(NAME, format!("{} run(s) in progress", running.len())),
+        Some(first) => DoctorCheck::problem(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "run(s) {} are marked running but their runner exited",
+                stale
+                    .iter()
+                    .map(|run| run.run_id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!(
+                "run `opennexus orchestration stop --context-file {}` for each affected context.",
+                first.context_file
+            ),
+        ),
+    }
+}
+
+/// Whether `pid` names a live process; assumed alive where that cannot be
+/// checked, so doctor never reports a live run as stale.
+fn process_alive(pid: i64) -> bool {
+    if cfg!(unix) {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map_or(true, |status| status.success())
+    } else {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reports_config_asset_and_link_problems() {
+        let dir = tempdir().expect("tempdir");
+        let config = dir.path().join("config.json");
+        assert_eq!(check_project_config(&config).status, CheckStatus::Warn);
+
+        fs::write(&config, r#"{ "harness": "opencode", "version": "0.0.1" }"#).expect("config");
+        assert_eq!(check_project_config(&config).status, CheckStatus::Ok);
+        let assets = check_asset_version(&config);
+        assert_eq!(assets.status, CheckStatus::Warn);
+        assert!(assets.detail.contains("0.0.1"));
+
+        fs::write(&config, r#"{ "harness": "opencod" }"#).expect("config");
+        assert_eq!(check_project_config(&config).status, CheckStatus::Fail);
+
+        let commands = dir.path().join(".opencode").join("command");
+        fs::create_dir_all(&commands).expect("command dir");
+        assert_eq!(check_harness_links(dir.path()).status, CheckStatus::Ok);
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("../../missing.md", commands.join("gone.md"))
+                .expect("symlink");
+            let links = check_harness_links(dir.path());
+            assert_eq!(links.status, CheckStatus::Fail);
+            assert_eq!(links.detail, "dangling: .opencode/command/gone.md");
+        }
+    }
+}
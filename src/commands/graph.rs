@@ -0,0 +1,161 @@
+//! Graph command serving an interactive context graph over local HTTP.
+//!
+//! The server is a single-threaded `std::net` loop: `/` returns the D3 page,
+//! `/graph.json` the current graph, and `/version` a fingerprint the page
+//! polls to re-render when context files change.
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::features::graph::{build_context_graph, context_tree_fingerprint};
+use crate::output::{print_error, print_info, print_json, print_success};
+
+const CONTEXT_ROOT: &str = ".nexus/context";
+const GRAPH_PAGE: &str = include_str!("graph_page.html");
+
+struct HttpResponse {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+/// Run `graph serve` until interrupted.
+pub fn run_graph_serve(host: &str, port: u16, format: OutputFormat) -> Result<()> {
+    let context_root = Path::new(CONTEXT_ROOT);
+    if !context_root.exists() {
+        bail!(
+            "Graph serve requires '{}/' to exist. Remediation: run `opennexus setup` or create context specs first.",
+            CONTEXT_ROOT
+        );
+    }
+    // Fail fast on an unreadable tree instead of serving a broken page.
+    build_context_graph(context_root)?;
+
+    let listener = TcpListener::bind((host, port)).with_context(|| {
+        format!(
+            "Failed to bind {}:{}. Remediation: pass a free port with --port.",
+            host, port
+        )
+    })?;
+    let address = listener.local_addr()?;
+    let url = format!("http://{}", address);
+    if format.is_structured() {
+        print_json(
+            &serde_json::json!({ "status": "serving", "url": url, "context_root": CONTEXT_ROOT }),
+        )?;
+    } else {
+        print_success(&format!("Serving context graph at {}", url));
+        print_info("Press Ctrl+C to stop.");
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                print_error(&format!("Failed to accept connection: {}", err));
+                continue;
+            }
+        };
+        if let Err(err) = serve_connection(stream, context_root) {
+            print_error(&format!("{:#}", err));
+        }
+    }
+    Ok(())
+}
+
+fn serve_connection(stream: TcpStream, context_root: &Path) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or("/");
+
+    let response = if method == "GET" {
+        handle_request(target, context_root)
+    } else {
+        HttpResponse {
+            status: "405 Method Not Allowed",
+            content_type: "text/plain; charset=utf-8",
+            body: "method not allowed\n".to_string(),
+        }
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn handle_request(target: &str, context_root: &Path) -> HttpResponse {
+    let path = target.split('?').next().unwrap_or(target);
+    match path {
+        "/" | "/index.html" => HttpResponse {
+            status: "200 OK",
+            content_type: "text/html; charset=utf-8",
+            body: GRAPH_PAGE.to_string(),
+        },
+        "/graph.json" => match build_context_graph(context_root) {
+            Ok(graph) => HttpResponse {
+                status: "200 OK",
+                content_type: "application/json",
+                body: serde_json::to_string(&graph).unwrap_or_else(|_| "{}".to_string()),
+            },
+            Err(err) => HttpResponse {
+                status: "500 Internal Server Error",
+                content_type: "application/json",
+                body: serde_json::json!({ "error": format!("{:#}", err) }).to_string(),
+            },
+        },
+        "/version" => HttpResponse {
+            status: "200 OK",
+            content_type: "text/plain; charset=utf-8",
+            body: context_tree_fingerprint(context_root),
+        },
+        _ => HttpResponse {
+            status: "404 Not Found",
+            content_type: "text/plain; charset=utf-8",
+            body: "not found\n".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn handle_request_routes_page_graph_and_version() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path().join("context");
+        fs::create_dir_all(root.join("cli")).expect("create dirs");
+        fs::write(
+            root.join("cli/CDD_001-graph.md"),
+            "---\ncontext_id: CDD_001\n---\n\n## Next Actions\n\n| Description | Test |\n|-------------|------|\n| Draw | `draws_graph` |\n",
+        )
+        .expect("write context");
+
+        let page = handle_request("/", &root);
+        assert!(page.body.contains("graph.json"));
+
+        let graph = handle_request("/graph.json?t=1", &root);
+        assert_eq!(graph.status, "200 OK");
+        let parsed: serde_json::Value = serde_json::from_str(&graph.body).expect("json body");
+        assert_eq!(parsed["nodes"][0]["id"], "context:CDD_001");
+
+        assert_eq!(handle_request("/version", &root).status, "200 OK");
+        assert_eq!(handle_request("/missing", &root).status, "404 Not Found");
+    }
+}
@@ -0,0 +1,222 @@
+//! Learn command: a resumable, checkpointed tutorial run in a sandbox directory.
+//!
+//! Each invocation verifies the current lesson against the sandbox contents,
+//! records completed lessons in `.nexus-learn.json`, and prints the next lesson.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::config::stateless::is_stateless;
+use crate::features::context::scan::scan_valid_context_specs;
+use crate::output::{print_info, print_json, print_success};
+
+const PROGRESS_FILE: &str = ".nexus-learn.json";
+const TUTORIAL_PROJECT: &str = "tutorial";
+
+struct Lesson {
+    id: &'static str,
+    title: &'static str,
+    instructions: &'static str,
+    is_complete: fn(&Path) -> bool,
+}
+
+const LESSONS: &[Lesson] = &[
+    Lesson {
+        id: "setup",
+        title: "Set up Nexus in the sandbox",
+        instructions: "Run `opennexus setup --harness opencode` inside the sandbox directory.",
+        is_complete: setup_complete,
+    },
+    Lesson {
+        id: "project",
+        title: "Create a context project",
+        instructions: "Run `opennexus analyze --project tutorial --apply` inside the sandbox to write .nexus/context/tutorial/index.md.",
+        is_complete: project_complete,
+    },
+    Lesson {
+        id: "context",
+        title: "Write your first context file",
+        instructions: "Create .nexus/context/tutorial/basics/TUT_001-first-context.md with frontmatter (context_id: TUT_001, title, project: tutorial, feature: basics) and a `## Next Actions` table with `| Description | Test |` rows.",
+        is_complete: context_complete,
+    },
+    Lesson {
+        id: "review",
+        title: "Review results",
+        instructions: "Run `opennexus badge --project tutorial` inside the sandbox and open badges/contexts.svg.",
+        is_complete: review_complete,
+    },
+];
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LearnProgress {
+    completed: Vec<String>,
+}
+
+/// Run the learn command.
+///
+/// Creates `sandbox` when missing. With `reset`, clears stored checkpoints
+/// before verifying lessons again from the start.
+pub fn run_learn(sandbox: &str, reset: bool, format: OutputFormat) -> Result<()> {
+    let sandbox_path = Path::new(sandbox);
+    fs::create_dir_all(sandbox_path)
+        .with_context(|| format!("Failed to create tutorial sandbox '{}'.", sandbox))?;
+
+    let progress_path = sandbox_path.join(PROGRESS_FILE);
+    let mut progress = if reset {
+        LearnProgress::default()
+    } else {
+        load_progress(&progress_path)?
+    };
+    let newly_completed = advance_progress(sandbox_path, &mut progress);
+    if !is_stateless() {
+        save_progress(&progress_path, &progress)?;
+    }
+
+    let next_lesson = LESSONS
+        .iter()
+        .find(|lesson| !progress.completed.iter().any(|id| id == lesson.id));
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if next_lesson.is_some() { "in_progress" } else { "completed" },
+            "sandbox": sandbox,
+            "completed": progress.completed,
+            "newly_completed": newly_completed,
+            "next_lesson": next_lesson.map(|lesson| serde_json::json!({
+                "id": lesson.id,
+                "title": lesson.title,
+                "instructions": lesson.instructions,
+            })),
+        }))?;
+        return Ok(());
+    }
+
+    for lesson_id in &newly_completed {
+        print_success(&format!("Checkpoint reached: {}", lesson_id));
+    }
+    match next_lesson {
+        Some(lesson) => {
+            let position = LESSONS
+                .iter()
+                .position(|candidate| candidate.id == lesson.id)
+                .unwrap_or(0);
+            print_info(&format!(
+                "Lesson {}/{}: {}",
+                position + 1,
+                LESSONS.len(),
+                lesson.title
+            ));
+            eprintln!("  sandbox: {}", sandbox);
+            eprintln!("  {}", lesson.instructions);
+            eprintln!("  Re-run `opennexus learn` when done to record the checkpoint.");
+        }
+        None => print_success("Tutorial complete. Remove the sandbox directory when finished."),
+    }
+
+    Ok(())
+}
+
+/// Mark lessons complete in order until one is not yet satisfied.
+fn advance_progress(sandbox: &Path, progress: &mut LearnProgress) -> Vec<String> {
+    let mut newly_completed = Vec::<String>::new();
+    for lesson in LESSONS {
+        if progress.completed.iter().any(|id| id == lesson.id) {
+            continue;
+        }
+        if !(lesson.is_complete)(sandbox) {
+            break;
+        }
+        progress.completed.push(lesson.id.to_string());
+        newly_completed.push(lesson.id.to_string());
+    }
+    newly_completed
+}
+
+fn load_progress(path: &Path) -> Result<LearnProgress> {
+    if !path.exists() {
+        return Ok(LearnProgress::default());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tutorial progress '{}'.", path.display()))?;
+    serde_json::from_str(&content).with_context(|| {
+        format!(
+            "Tutorial progress '{}' is invalid. Re-run with --reset to start over.",
+            path.display()
+        )
+    })
+}
+
+fn save_progress(path: &Path, progress: &LearnProgress) -> Result<()> {
+    let content = serde_json::to_string_pretty(progress)?;
+    fs::write(path, format!("{}\n", content))
+        .with_context(|| format!("Failed to write tutorial progress '{}'.", path.display()))
+}
+
+fn setup_complete(sandbox: &Path) -> bool {
+    sandbox.join(".nexus/config.json").is_file()
+}
+
+fn project_complete(sandbox: &Path) -> bool {
+    sandbox
+        .join(".nexus/context")
+        .join(TUTORIAL_PROJECT)
+        .join("index.md")
+        .is_file()
+}
+
+fn context_complete(sandbox: &Path) -> bool {
+    let project_dir = sandbox.join(".nexus/context").join(TUTORIAL_PROJECT);
+    project_dir.is_dir()
+        && scan_valid_context_specs(&project_dir)
+            .map(|scan| !scan.valid_context_files.is_empty())
+            .unwrap_or(false)
+}
+
+fn review_complete(sandbox: &Path) -> bool {
+    sandbox.join("badges/contexts.svg").is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn advance_progress_stops_at_first_unsatisfied_lesson() {
+        let dir = tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join(".nexus/context/tutorial")).expect("create dirs");
+        fs::write(dir.path().join(".nexus/config.json"), "{}").expect("write config");
+
+        let mut progress = LearnProgress::default();
+        let newly_completed = advance_progress(dir.path(), &mut progress);
+        assert_eq!(newly_completed, vec!["setup".to_string()]);
+
+        fs::write(
+            dir.path().join(".nexus/context/tutorial/index.md"),
+            "# tutorial\n",
+        )
+        .expect("write index");
+        let newly_completed = advance_progress(dir.path(), &mut progress);
+        assert_eq!(newly_completed, vec!["project".to_string()]);
+        assert_eq!(progress.completed, vec!["setup", "project"]);
+    }
+
+    #[test]
+    fn progress_round_trips_through_checkpoint_file() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(PROGRESS_FILE);
+        assert_eq!(
+            load_progress(&path).expect("load missing"),
+            LearnProgress::default()
+        );
+
+        let progress = LearnProgress {
+            completed: vec!["setup".to_string()],
+        };
+        save_progress(&path, &progress).expect("save");
+        assert_eq!(load_progress(&path).expect("load"), progress);
+    }
+}
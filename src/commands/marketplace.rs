@@ -1,19 +1,31 @@
 //! Marketplace commands for searching and installing Nexus assets.
 
 use anyhow::{bail, Context, Result};
+use dialoguer::{theme::ColorfulTheme, Input};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use std::fs;
-#[cfg(unix)]
-use std::os::unix::fs::symlink;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
 use tempfile::TempDir;
 
 use crate::cli::OutputFormat;
-use crate::output::{print_info, print_success};
+use crate::commands::context::refresh_context_indexes;
+use crate::config::nexus::{load_nexus_config, NEXUS_CONFIG_PATH};
+use crate::core::error::is_retryable;
+use crate::features::context::translate::{
+    context_markdown_files, declared_prefixes, parse_mapping, translate_installed_contexts,
+    ContextTranslation, TranslationReport,
+};
+use crate::output::{print_info, print_json, print_success, print_tsv_row};
+use crate::services::hooks::{fire_hook, HookEvent};
+use crate::services::retry::{retry_with_backoff, retry_with_backoff_if, RetryBudget};
+use crate::utils::paths::{
+    copy_dir_recursive, expand_home, is_explicit_path, link_or_copy, path_exists_or_symlink,
+    remove_path,
+};
 
 const DEFAULT_REGISTRY_URL: &str =
     "https://raw.githubusercontent.com/Alpha-Innovation-Labs/nexus/main/.nexus/marketplace/registry.json";
@@ -53,21 +65,25 @@ struct InstallReport {
     installed_commands: usize,
     installed_skills: usize,
     installed_rules: usize,
+    /// Copied (not symlinked) context project folders, eligible for translation.
+    #[serde(skip)]
+    context_dirs: Vec<PathBuf>,
 }
 
 pub fn run_marketplace_search(query: &str, format: OutputFormat) -> Result<()> {
     let registry = fetch_registry_entries()?;
     let matches = search_entries(query, &registry);
 
-    if format == OutputFormat::Json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "status": "ok",
-                "query": query,
-                "results": matches,
-            })
-        );
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "ok",
+            "query": query,
+            "results": matches,
+        }))?;
+        return Ok(());
+    }
+    if format == OutputFormat::Tsv {
+        print_registry_rows(&matches);
         return Ok(());
     }
 
@@ -93,14 +109,16 @@ pub fn run_marketplace_search(query: &str, format: OutputFormat) -> Result<()> {
 pub fn run_marketplace_list(format: OutputFormat) -> Result<()> {
     let registry = fetch_registry_entries()?;
 
-    if format == OutputFormat::Json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "status": "ok",
-                "results": registry,
-            })
-        );
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "ok",
+            "results": registry,
+        }))?;
+        return Ok(());
+    }
+    if format == OutputFormat::Tsv {
+        let entries = registry.iter().collect::<Vec<_>>();
+        print_registry_rows(&entries);
         return Ok(());
     }
 
@@ -126,16 +144,25 @@ pub fn run_marketplace_list(format: OutputFormat) -> Result<()> {
 pub fn run_marketplace_install(
     target: &str,
     package: Option<&str>,
+    map_prefix: &[String],
+    map_project: &[String],
     format: OutputFormat,
 ) -> Result<()> {
-    if format == OutputFormat::Json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "status": "starting",
-                "target": target,
-            })
-        );
+    let mut translation = ContextTranslation::default();
+    for raw in map_prefix {
+        let (from, to) = parse_mapping(raw, "--map-prefix")?;
+        translation.prefixes.insert(from, to);
+    }
+    for raw in map_project {
+        let (from, to) = parse_mapping(raw, "--map-project")?;
+        translation.projects.insert(from, to);
+    }
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "starting",
+            "target": target,
+        }))?;
     } else {
         print_info(&format!("Installing marketplace target '{}'...", target));
     }
@@ -165,19 +192,61 @@ pub fn run_marketplace_install(
             .with_context(|| format!("Failed to install from '{}'.", target))?
     };
 
-    if format == OutputFormat::Json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "status": "completed",
-                "target": target,
-                "installed_contexts": report.installed_contexts,
-                "installed_commands": report.installed_commands,
-                "installed_skills": report.installed_skills,
-                "installed_rules": report.installed_rules,
-            })
-        );
+    let translation_report = if report.context_dirs.is_empty() {
+        TranslationReport::default()
+    } else {
+        if translation.is_empty() && interactive(format) {
+            translation = prompt_context_translation(&report.context_dirs)?;
+        }
+        translate_installed_contexts(
+            Path::new(".nexus/context"),
+            &report.context_dirs,
+            &translation.without_identity(),
+        )
+        .context("Failed to translate imported contexts.")?
+    };
+    let refreshed_indexes = if report.installed_contexts > 0
+        && load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?
+            .context_index
+            .auto
+    {
+        refresh_context_indexes(None)?
+            .into_iter()
+            .filter(|update| update.changed)
+            .map(|update| update.path)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "completed",
+            "target": target,
+            "installed_contexts": report.installed_contexts,
+            "installed_commands": report.installed_commands,
+            "installed_skills": report.installed_skills,
+            "installed_rules": report.installed_rules,
+            "translated_files": translation_report.translated_files,
+            "unresolved_links": translation_report.unresolved_links,
+            "refreshed_indexes": refreshed_indexes,
+        }))?;
     } else {
+        if translation_report.translated_files > 0 {
+            print_info(&format!(
+                "Translated {} imported context file(s).",
+                translation_report.translated_files
+            ));
+        }
+        for path in &refreshed_indexes {
+            print_info(&format!("Regenerated context index {}.", path));
+        }
+        for link in &translation_report.unresolved_links {
+            print_info(&format!(
+                "Unresolved link: {} references {}, which is not defined locally.",
+                link.file, link.context_id
+            ));
+        }
         print_success(&format!(
             "Installed target '{}' (contexts: {}, commands: {}, skills: {}, rules: {})",
             target,
@@ -191,6 +260,40 @@ pub fn run_marketplace_install(
     Ok(())
 }
 
+fn interactive(format: OutputFormat) -> bool {
+    format == OutputFormat::Text
+        && std::io::stdin().is_terminal()
+        && std::io::stdout().is_terminal()
+}
+
+/// Ask for a local name for each imported prefix and project; Enter keeps it.
+fn prompt_context_translation(context_dirs: &[PathBuf]) -> Result<ContextTranslation> {
+    let theme = ColorfulTheme::default();
+    let mut translation = ContextTranslation::default();
+    let mut files = Vec::<PathBuf>::new();
+    for dir in context_dirs {
+        files.extend(context_markdown_files(dir)?);
+        let Some(project) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let local: String = Input::with_theme(&theme)
+            .with_prompt(format!("Local project for imported '{}'", project))
+            .default(project.to_string())
+            .interact_text()
+            .context("Failed to read project mapping")?;
+        translation.projects.insert(project.to_string(), local);
+    }
+    for prefix in declared_prefixes(&files) {
+        let local: String = Input::with_theme(&theme)
+            .with_prompt(format!("Local prefix for imported '{}' contexts", prefix))
+            .default(prefix.clone())
+            .interact_text()
+            .context("Failed to read prefix mapping")?;
+        translation.prefixes.insert(prefix, local);
+    }
+    Ok(translation)
+}
+
 fn fetch_registry_entries() -> Result<Vec<RegistryEntry>> {
     let registry_url = std::env::var("NEXUS_MARKETPLACE_REGISTRY_URL")
         .ok()
@@ -206,16 +309,23 @@ fn fetch_registry_entries() -> Result<Vec<RegistryEntry>> {
             .build()
             .context("Failed to create HTTP client for marketplace registry fetch.")?;
 
-        let response = client
-            .get(&registry_url)
-            .send()
-            .context("Failed to fetch marketplace registry.")?;
-        let response = response
-            .error_for_status()
-            .context("Failed to fetch marketplace registry.")?;
-        response
-            .text()
-            .context("Failed to decode marketplace registry response body.")?
+        retry_with_backoff_if(
+            "Marketplace registry fetch",
+            RetryBudget::NETWORK,
+            is_retryable,
+            || {
+                let response = client
+                    .get(&registry_url)
+                    .send()
+                    .context("Failed to fetch marketplace registry.")?;
+                let response = response
+                    .error_for_status()
+                    .context("Failed to fetch marketplace registry.")?;
+                response
+                    .text()
+                    .context("Failed to decode marketplace registry response body.")
+            },
+        )?
     };
 
     serde_json::from_str::<Vec<RegistryEntry>>(&body)
@@ -230,6 +340,12 @@ fn default_registry_source() -> String {
     DEFAULT_REGISTRY_URL.to_string()
 }
 
+fn print_registry_rows(entries: &[&RegistryEntry]) {
+    for entry in entries {
+        print_tsv_row(&[&entry.id, &entry.name, &entry.description]);
+    }
+}
+
 fn search_entries<'a>(query: &str, entries: &'a [RegistryEntry]) -> Vec<&'a RegistryEntry> {
     let lowered = query.trim().to_ascii_lowercase();
     if lowered.is_empty() {
@@ -308,10 +424,8 @@ fn parse_filesystem_source(target: &str) -> Option<PathBuf> {
         return None;
     }
 
-    if let Some(rest) = trimmed.strip_prefix("~/") {
-        if let Ok(home) = std::env::var("HOME") {
-            return Some(PathBuf::from(home).join(rest));
-        }
+    if let Some(expanded) = expand_home(trimmed) {
+        return Some(expanded);
     }
 
     let candidate = PathBuf::from(trimmed);
@@ -319,7 +433,7 @@ fn parse_filesystem_source(target: &str) -> Option<PathBuf> {
         return Some(candidate);
     }
 
-    if trimmed.starts_with('/') || trimmed.starts_with("./") || trimmed.starts_with("../") {
+    if is_explicit_path(trimmed) {
         return Some(candidate);
     }
 
@@ -418,6 +532,7 @@ fn link_marketplace_assets(package_name: &str) -> Result<InstallReport> {
         installed_commands: 0,
         installed_skills: 0,
         installed_rules: 0,
+        context_dirs: Vec::new(),
     };
 
     let context_dir = package_root.join("context");
@@ -430,18 +545,18 @@ fn link_marketplace_assets(package_name: &str) -> Result<InstallReport> {
             remove_path(&target)?;
         }
 
-        #[cfg(unix)]
-        {
-            let link_target = format!("../marketplace/{}/context", package_name);
-            symlink(&link_target, &target)?;
-        }
-
-        #[cfg(not(unix))]
-        {
-            copy_dir_recursive(&context_dir, &target)?;
-        }
+        let link_target = format!("../marketplace/{}/context", package_name);
+        link_or_copy(&link_target, &context_dir, &target)?;
 
         report.installed_contexts = 1;
+        fire_hook(
+            HookEvent::ContextCreated,
+            serde_json::json!({
+                "source": "marketplace",
+                "package": package_name,
+                "context_dir": target.to_string_lossy(),
+            }),
+        );
     }
 
     let commands_dir = package_root.join("commands");
@@ -473,17 +588,8 @@ fn link_marketplace_assets(package_name: &str) -> Result<InstallReport> {
                 remove_path(&target)?;
             }
 
-            #[cfg(unix)]
-            {
-                let link_target =
-                    format!("../../marketplace/{}/commands/{}", package_name, file_name);
-                symlink(&link_target, &target)?;
-            }
-
-            #[cfg(not(unix))]
-            {
-                fs::copy(&source_file, &target)?;
-            }
+            let link_target = format!("../../marketplace/{}/commands/{}", package_name, file_name);
+            link_or_copy(&link_target, &source_file, &target)?;
 
             report.installed_commands += 1;
         }
@@ -517,17 +623,8 @@ fn link_marketplace_assets(package_name: &str) -> Result<InstallReport> {
                 remove_path(&target)?;
             }
 
-            #[cfg(unix)]
-            {
-                let link_target =
-                    format!("../../marketplace/{}/skills/{}", package_name, skill_name);
-                symlink(&link_target, &target)?;
-            }
-
-            #[cfg(not(unix))]
-            {
-                copy_dir_recursive(&source_dir, &target)?;
-            }
+            let link_target = format!("../../marketplace/{}/skills/{}", package_name, skill_name);
+            link_or_copy(&link_target, &source_dir, &target)?;
 
             report.installed_skills += 1;
         }
@@ -553,20 +650,8 @@ fn link_marketplace_assets(package_name: &str) -> Result<InstallReport> {
                 remove_path(&target)?;
             }
 
-            #[cfg(unix)]
-            {
-                let link_target = format!("../../marketplace/{}/rules/{}", package_name, name);
-                symlink(&link_target, &target)?;
-            }
-
-            #[cfg(not(unix))]
-            {
-                if source_path.is_dir() {
-                    copy_dir_recursive(&source_path, &target)?;
-                } else if source_path.is_file() {
-                    fs::copy(&source_path, &target)?;
-                }
-            }
+            let link_target = format!("../../marketplace/{}/rules/{}", package_name, name);
+            link_or_copy(&link_target, &source_path, &target)?;
 
             report.installed_rules += 1;
         }
@@ -576,7 +661,7 @@ fn link_marketplace_assets(package_name: &str) -> Result<InstallReport> {
 }
 
 fn link_harness_assets_from_config() -> Result<()> {
-    let harness = configured_harness().unwrap_or_else(|| "opencode".to_string());
+    let harness = configured_harness()?.unwrap_or_else(|| "opencode".to_string());
 
     if harness.eq_ignore_ascii_case("opencode") {
         link_commands_to_opencode()?;
@@ -589,14 +674,8 @@ fn link_harness_assets_from_config() -> Result<()> {
     Ok(())
 }
 
-fn configured_harness() -> Option<String> {
-    let config_path = Path::new(".nexus/config.json");
-    let content = fs::read_to_string(config_path).ok()?;
-    let value = serde_json::from_str::<Value>(&content).ok()?;
-    value
-        .get("harness")
-        .and_then(Value::as_str)
-        .map(ToOwned::to_owned)
+fn configured_harness() -> Result<Option<String>> {
+    Ok(load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?.harness)
 }
 
 fn link_commands_to_opencode() -> Result<()> {
@@ -640,16 +719,8 @@ fn link_command_files(source_dir: &Path, target_dir: &Path, relative_prefix: &st
             remove_path(&target)?;
         }
 
-        #[cfg(unix)]
-        {
-            let link_target = format!("{}/{}", relative_prefix, file_name);
-            symlink(&link_target, &target)?;
-        }
-
-        #[cfg(not(unix))]
-        {
-            fs::copy(&source_path, &target)?;
-        }
+        let link_target = format!("{}/{}", relative_prefix, file_name);
+        link_or_copy(&link_target, &source_path, &target)?;
     }
 
     Ok(())
@@ -682,16 +753,8 @@ fn link_skills_to_opencode() -> Result<()> {
             remove_path(&target)?;
         }
 
-        #[cfg(unix)]
-        {
-            let link_target = format!("../../.nexus/ai_harness/skills/{}", name);
-            symlink(&link_target, &target)?;
-        }
-
-        #[cfg(not(unix))]
-        {
-            copy_dir_recursive(&source_path, &target)?;
-        }
+        let link_target = format!("../../.nexus/ai_harness/skills/{}", name);
+        link_or_copy(&link_target, &source_path, &target)?;
     }
 
     Ok(())
@@ -720,59 +783,48 @@ fn link_rules_to_opencode() -> Result<()> {
             remove_path(&target)?;
         }
 
-        #[cfg(unix)]
-        {
-            let link_target = format!("../../.nexus/ai_harness/rules/{}", name);
-            symlink(&link_target, &target)?;
-        }
-
-        #[cfg(not(unix))]
-        {
-            if source_path.is_dir() {
-                copy_dir_recursive(&source_path, &target)?;
-            } else if source_path.is_file() {
-                fs::copy(&source_path, &target)?;
-            }
-        }
+        let link_target = format!("../../.nexus/ai_harness/rules/{}", name);
+        link_or_copy(&link_target, &source_path, &target)?;
     }
 
     Ok(())
 }
 
-fn path_exists_or_symlink(path: &Path) -> bool {
-    path.exists() || fs::symlink_metadata(path).is_ok()
-}
-
-fn remove_path(path: &Path) -> Result<()> {
-    let metadata = fs::symlink_metadata(path)?;
-    if metadata.file_type().is_dir() {
-        fs::remove_dir_all(path)?;
-    } else {
-        fs::remove_file(path)?;
-    }
-    Ok(())
-}
-
 fn clone_github_repo(source: &GitHubRepoRef) -> Result<TempDir> {
     let tmp =
         TempDir::new().context("Failed to create temporary directory for repository clone.")?;
     let checkout_dir = tmp.path().join("repo");
     let repo_url = format!("https://github.com/{}/{}.git", source.owner, source.repo);
 
-    let output = Command::new("git")
-        .args(["clone", "--depth", "1", &repo_url])
-        .arg(&checkout_dir)
-        .output()
-        .context("Failed to invoke git while installing marketplace package.")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!(
-            "Unable to clone repository '{}': {}",
-            repo_url,
-            stderr.trim()
-        );
-    }
+    retry_with_backoff(
+        &format!("Clone of '{}'", repo_url),
+        RetryBudget::NETWORK,
+        || {
+            if checkout_dir.exists() {
+                fs::remove_dir_all(&checkout_dir).with_context(|| {
+                    format!(
+                        "Failed to clear partial clone '{}'.",
+                        checkout_dir.display()
+                    )
+                })?;
+            }
+            let output = Command::new("git")
+                .args(["clone", "--depth", "1", &repo_url])
+                .arg(&checkout_dir)
+                .output()
+                .context("Failed to invoke git while installing marketplace package.")?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!(
+                    "Unable to clone repository '{}': {}",
+                    repo_url,
+                    stderr.trim()
+                );
+            }
+            Ok(())
+        },
+    )?;
 
     Ok(tmp)
 }
@@ -835,6 +887,7 @@ fn install_bundle(source_nexus_dir: PathBuf) -> Result<InstallReport> {
         installed_commands: 0,
         installed_skills: 0,
         installed_rules: 0,
+        context_dirs: Vec::new(),
     };
 
     let contexts = source_nexus_dir.join("context");
@@ -853,6 +906,7 @@ fn install_bundle(source_nexus_dir: PathBuf) -> Result<InstallReport> {
             let target = target_root.join(name);
             copy_dir_recursive(&path, &target)?;
             report.installed_contexts += 1;
+            report.context_dirs.push(target);
         }
     }
 
@@ -955,6 +1009,7 @@ fn install_context(source: PathBuf, install_name: Option<&str>) -> Result<Instal
         installed_commands: 0,
         installed_skills: 0,
         installed_rules: 0,
+        context_dirs: vec![target],
     })
 }
 
@@ -970,6 +1025,7 @@ fn install_skill(source: PathBuf, install_name: Option<&str>) -> Result<InstallR
         installed_commands: 0,
         installed_skills: 1,
         installed_rules: 0,
+        context_dirs: Vec::new(),
     })
 }
 
@@ -1026,6 +1082,7 @@ fn install_rule(source: PathBuf, install_name: Option<&str>) -> Result<InstallRe
         installed_commands: 0,
         installed_skills: 0,
         installed_rules: installed,
+        context_dirs: Vec::new(),
     })
 }
 
@@ -1068,30 +1125,6 @@ fn ensure_dir(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
-    ensure_dir(target)?;
-    for entry in fs::read_dir(source)? {
-        let entry = entry?;
-        let source_path = entry.path();
-        let target_path = target.join(entry.file_name());
-        if source_path.is_dir() {
-            copy_dir_recursive(&source_path, &target_path)?;
-        } else {
-            if let Some(parent) = target_path.parent() {
-                ensure_dir(parent)?;
-            }
-            fs::copy(&source_path, &target_path).with_context(|| {
-                format!(
-                    "Failed to copy '{}' to '{}'.",
-                    source_path.display(),
-                    target_path.display()
-                )
-            })?;
-        }
-    }
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
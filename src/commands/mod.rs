@@ -1,15 +1,57 @@
 //! Command implementations for the Nexus CLI.
 
+pub mod analyze;
+pub mod apply;
+pub mod assets;
+pub mod audit;
+pub mod badge;
+pub mod bug_report;
+pub mod burndown;
+pub mod clean;
+pub mod complete;
+pub mod config;
+pub mod context;
+pub mod doctor;
+pub mod graph;
+pub mod learn;
 pub mod marketplace;
+pub mod onboarding;
 pub mod orchestration;
+pub mod plugin;
+pub mod preset;
 pub mod ralph;
+pub mod refs;
+pub mod scaffold;
 pub mod setup;
+pub mod shell;
+pub mod status;
 pub mod uninstall;
 pub mod update;
 
+pub use analyze::*;
+pub use apply::*;
+pub use assets::*;
+pub use audit::*;
+pub use badge::*;
+pub use bug_report::*;
+pub use burndown::*;
+pub use clean::*;
+pub use complete::*;
+pub use config::*;
+pub use context::*;
+pub use doctor::*;
+pub use graph::*;
+pub use learn::*;
 pub use marketplace::*;
+pub use onboarding::*;
 pub use orchestration::*;
+pub use plugin::*;
+pub use preset::*;
 pub use ralph::*;
+pub use refs::*;
+pub use scaffold::*;
 pub use setup::*;
+pub use shell::*;
+pub use status::*;
 pub use uninstall::*;
 pub use update::*;
@@ -0,0 +1,60 @@
+//! First-run onboarding for a machine without a user config.
+
+use anyhow::{Context, Result};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::Select;
+use std::io::IsTerminal;
+
+use crate::cli::{ColorChoice, OutputFormat};
+use crate::config::stateless::is_stateless;
+use crate::config::user::{load_user_config, save_user_config, user_config_path, UserConfig};
+use crate::output::print_info;
+
+/// Load the user config, asking for preferences on the first interactive run.
+///
+/// Non-interactive and stateless invocations never prompt or write; they use
+/// built-in defaults until a config exists.
+pub fn load_or_onboard_user_config(explicit_json: bool) -> Result<UserConfig> {
+    let Some(path) = user_config_path() else {
+        return Ok(UserConfig::default());
+    };
+    if let Some(config) = load_user_config(&path)? {
+        return Ok(config);
+    }
+    if explicit_json
+        || is_stateless()
+        || !std::io::stdin().is_terminal()
+        || !std::io::stdout().is_terminal()
+    {
+        return Ok(UserConfig::default());
+    }
+
+    print_info("First run on this machine; a few preferences (saved for later runs).");
+    let theme = ColorfulTheme::default();
+    let color = Select::with_theme(&theme)
+        .with_prompt("Color status messages")
+        .items(&["auto (when stderr is a terminal)", "always", "never"])
+        .default(0)
+        .interact()
+        .context("Failed to read color preference")?;
+    let format = Select::with_theme(&theme)
+        .with_prompt("Default output format")
+        .items(&["auto (text on a terminal, tsv when piped)", "text", "json"])
+        .default(0)
+        .interact()
+        .context("Failed to read output format preference")?;
+
+    let config = UserConfig {
+        color: [None, Some(ColorChoice::Always), Some(ColorChoice::Never)][color],
+        format: [None, Some(OutputFormat::Text), Some(OutputFormat::Json)][format],
+        role: None,
+        update_check: None,
+        update_channel: None,
+    };
+    save_user_config(&path, &config)?;
+    print_info(&format!(
+        "Saved preferences to {}. Delete it to run onboarding again.",
+        path.display()
+    ));
+    Ok(config)
+}
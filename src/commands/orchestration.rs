@@ -1,19 +1,52 @@
 use anyhow::{bail, Context, Result};
+use dialoguer::Confirm;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::adapters::orchestration_store::{
-    default_orchestration_database_path, OrchestrationStore, TimelineFilter, TimelineRow,
-    TraceQueryRow,
+    default_orchestration_database_path, OrchestrationStore, RunParameters, RunRecord,
+    StepAttemptRecord, TimelineFilter, TimelineRow, TraceQueryRow,
 };
 use crate::app::run_orchestration_implement_app;
-use crate::cli::OutputFormat;
-use crate::core::context::model::ContextImplementOptions;
+use crate::cli::{
+    OrchestrationCommand, OutputFormat, DEFAULT_MAX_ITERATIONS, DEFAULT_TIMEOUT_SECONDS,
+};
+use crate::commands::preset::remember_orchestration_parameters;
+use crate::config::nexus::{load_nexus_config, OrchestrationPreset, NEXUS_CONFIG_PATH};
+use crate::core::context::model::{
+    ContextImplementOptions, ContextImplementSummary, ContextParseResult,
+};
+use crate::core::error::CliError;
+use crate::features::context::outcome::{action_outcomes, apply_action_outcomes};
 use crate::features::context::parser::parse_context_file;
+use crate::features::context::reservation::{
+    referenced_files, reservation_conflicts, ReservationConflict,
+};
+use crate::output::{
+    format_timestamp, print_info, print_json_pretty, print_tsv_row, print_warning,
+};
+use crate::services::hooks::{fire_hook, HookEvent};
+use crate::services::post_processors::run_post_processors;
+
+/// Built-in `orchestration <action>` names dispatched before pipeline lookup.
+pub(crate) const ORCHESTRATION_ACTIONS: [&str; 11] = [
+    "status",
+    "active",
+    "runs",
+    "stop",
+    "restart",
+    "pin",
+    "unpin",
+    "traces",
+    "artifacts",
+    "timeline",
+    "compare",
+];
 
 #[derive(Debug, Deserialize)]
 struct PipelineCatalog {
@@ -26,130 +59,176 @@ struct PipelineDefinition {
     red_failure_patterns: Vec<String>,
 }
 
+/// `orchestration` flags with presets and config defaults applied.
+#[derive(Debug, Clone)]
+pub struct OrchestrationRunOptions {
+    pub pipeline_name: String,
+    pub target_pipeline_name: Option<String>,
+    pub pipeline_file: Option<String>,
+    pub context_file: Option<String>,
+    pub max_iterations: usize,
+    pub timeout_seconds: u64,
+    pub rule_file: Option<String>,
+    pub test_command: Option<String>,
+    pub test_discovery_command: Option<String>,
+    pub model: Option<String>,
+    pub checkpoint_file: Option<String>,
+    pub resume_checkpoint: Option<String>,
+    pub allow_dependency_bypass: bool,
+    pub overwrite: bool,
+    /// Run against a context marked `frozen: true`.
+    pub unfreeze: bool,
+    /// Record action outcomes in the context without prompting.
+    pub auto_outcome: bool,
+    /// Refuse to start when referenced files overlap another active run.
+    pub exclusive_files: bool,
+    /// Re-author red tests for every action, ignoring the per-action cache.
+    pub force: bool,
+    pub run_id: Option<i64>,
+    pub compare_run_id: Option<i64>,
+    pub context_id: Option<String>,
+    pub pipeline_filter: Option<String>,
+    /// Why runs are stopped, for `stop` and `restart`.
+    pub reason: Option<String>,
+}
+
+impl From<&OrchestrationCommand> for OrchestrationRunOptions {
+    fn from(command: &OrchestrationCommand) -> Self {
+        Self {
+            pipeline_name: command.pipeline_name.clone(),
+            target_pipeline_name: command.target_pipeline_name.clone(),
+            pipeline_file: command.pipeline_file.clone(),
+            context_file: command.context_file.clone(),
+            max_iterations: command.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS),
+            timeout_seconds: command.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS),
+            rule_file: command.rule_file.clone(),
+            test_command: command.test_command.clone(),
+            test_discovery_command: command.test_discovery_command.clone(),
+            model: command.model.clone(),
+            checkpoint_file: command.checkpoint_file.clone(),
+            resume_checkpoint: command.resume_checkpoint.clone(),
+            allow_dependency_bypass: command.allow_dependency_bypass,
+            overwrite: command.overwrite,
+            unfreeze: command.unfreeze,
+            auto_outcome: command.auto_outcome,
+            exclusive_files: command.exclusive_files,
+            force: command.force,
+            run_id: command.run_id,
+            compare_run_id: command.compare_run_id,
+            context_id: command.context_id.clone(),
+            pipeline_filter: command.pipeline_filter.clone(),
+            reason: command.reason.clone(),
+        }
+    }
+}
+
 pub fn run_orchestration_pipeline(
+    options: &OrchestrationRunOptions,
     format: OutputFormat,
-    pipeline_name: &str,
-    target_pipeline_name: Option<&str>,
-    pipeline_file: Option<&str>,
-    context_file: Option<&str>,
-    max_iterations: usize,
-    timeout_seconds: u64,
-    rule_file: Option<&str>,
-    test_command: Option<&str>,
-    test_discovery_command: Option<&str>,
-    model: Option<&str>,
-    checkpoint_file: Option<&str>,
-    resume_checkpoint: Option<&str>,
-    allow_dependency_bypass: bool,
-    overwrite: bool,
-    run_id: Option<i64>,
-    context_id_filter: Option<&str>,
-    pipeline_filter: Option<&str>,
 ) -> Result<()> {
-    if !matches!(pipeline_name, "timeline") {
-        context_file.context("`orchestration <pipeline>` requires --context-file <path>")?;
+    if options.pipeline_name == "compare" {
+        let base_run_id = options
+            .run_id
+            .context("`orchestration compare` requires --run-id <id>")?;
+        let candidate_run_id = options
+            .compare_run_id
+            .context("`orchestration compare` requires --compare-run-id <id>")?;
+        return orchestration_compare(base_run_id, candidate_run_id, format);
     }
-    if pipeline_name == "status" {
-        return orchestration_status(context_file.expect("context file checked above"), format);
-    }
-    if pipeline_name == "active" {
-        return orchestration_active(
-            context_file.expect("context file checked above"),
-            pipeline_filter,
+    if options.pipeline_name == "timeline" {
+        return orchestration_timeline(
+            TimelineFilter {
+                run_id: options.run_id,
+                context_id: options.context_id.clone(),
+                context_file: options.context_file.clone(),
+                pipeline_name: options.pipeline_filter.clone(),
+            },
             format,
         );
     }
-    if pipeline_name == "runs" {
-        return orchestration_runs(context_file.expect("context file checked above"), format);
+    let context_file = options
+        .context_file
+        .as_deref()
+        .context("`orchestration <pipeline>` requires --context-file <path>")?;
+    if options.pipeline_name == "status" {
+        return orchestration_status(context_file, format);
+    }
+    if options.pipeline_name == "active" {
+        return orchestration_active(context_file, options.pipeline_filter.as_deref(), format);
     }
-    if pipeline_name == "stop" {
+    if options.pipeline_name == "runs" {
+        return orchestration_runs(context_file, format);
+    }
+    if options.pipeline_name == "stop" {
         return orchestration_stop(
-            context_file.expect("context file checked above"),
-            pipeline_filter,
+            context_file,
+            options.pipeline_filter.as_deref(),
+            options.reason.as_deref(),
             format,
         );
     }
-    if pipeline_name == "restart" {
-        let next_pipeline = target_pipeline_name
+    if matches!(options.pipeline_name.as_str(), "pin" | "unpin") {
+        let run_id = options.run_id.with_context(|| {
+            format!(
+                "`orchestration {}` requires --run-id <id>",
+                options.pipeline_name
+            )
+        })?;
+        return orchestration_pin(context_file, run_id, options.pipeline_name == "pin", format);
+    }
+    if options.pipeline_name == "restart" {
+        let next_pipeline = options
+            .target_pipeline_name
+            .as_deref()
             .context("`orchestration restart <pipeline_name>` requires a target pipeline name.")?;
-        return orchestration_restart(
-            format,
-            next_pipeline,
-            pipeline_file,
-            context_file.expect("context file checked above"),
-            max_iterations,
-            timeout_seconds,
-            rule_file,
-            test_command,
-            test_discovery_command,
-            model,
-            checkpoint_file,
-            allow_dependency_bypass,
-            run_id,
-            context_id_filter,
-            pipeline_filter,
-        );
+        return orchestration_restart(options, format, next_pipeline, context_file);
     }
-    if pipeline_name == "traces" {
-        let run_id = run_id.context("`orchestration traces` requires --run-id <id>")?;
-        return orchestration_traces(
-            context_file.expect("context file checked above"),
-            run_id,
-            format,
-        );
+    if options.pipeline_name == "traces" {
+        let run_id = options
+            .run_id
+            .context("`orchestration traces` requires --run-id <id>")?;
+        return orchestration_traces(context_file, run_id, format);
     }
-    if pipeline_name == "artifacts" {
-        let run_id = run_id.context("`orchestration artifacts` requires --run-id <id>")?;
-        return orchestration_artifacts(
-            context_file.expect("context file checked above"),
-            run_id,
-            format,
-        );
-    }
-    if pipeline_name == "timeline" {
-        return orchestration_timeline(
-            TimelineFilter {
-                run_id,
-                context_id: context_id_filter.map(str::to_string),
-                context_file: context_file.map(str::to_string),
-                pipeline_name: pipeline_filter.map(str::to_string),
-            },
-            format,
-        );
+    if options.pipeline_name == "artifacts" {
+        let run_id = options
+            .run_id
+            .context("`orchestration artifacts` requires --run-id <id>")?;
+        return orchestration_artifacts(context_file, run_id, format);
     }
 
-    let context_file = context_file.expect("context file checked above");
-
-    if target_pipeline_name.is_some() {
+    if options.target_pipeline_name.is_some() {
         bail!(
             "Unexpected extra positional argument '{}'. Only `orchestration restart <pipeline_name>` accepts a second positional pipeline name.",
-            target_pipeline_name.unwrap_or_default()
+            options.target_pipeline_name.as_deref().unwrap_or_default()
         );
     }
 
-    let (pipeline_path, catalog) = resolve_pipeline_catalog(pipeline_file)?;
-    let pipeline = catalog.pipelines.get(pipeline_name).ok_or_else(|| {
-        anyhow::anyhow!(
-            "Pipeline '{}' not found in '{}'. Available: {}",
-            pipeline_name,
-            pipeline_path.display(),
-            catalog
-                .pipelines
-                .keys()
-                .cloned()
-                .collect::<Vec<String>>()
-                .join(", ")
-        )
-    })?;
+    let (pipeline_path, catalog) = resolve_pipeline_catalog(options.pipeline_file.as_deref())?;
+    let pipeline = catalog
+        .pipelines
+        .get(&options.pipeline_name)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Pipeline '{}' not found in '{}'. Available: {}",
+                options.pipeline_name,
+                pipeline_path.display(),
+                catalog
+                    .pipelines
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })?;
 
-    let mut options = ContextImplementOptions {
-        pipeline_name: pipeline_name.to_string(),
+    let mut implement_options = ContextImplementOptions {
+        pipeline_name: options.pipeline_name.clone(),
         context_file: PathBuf::from(context_file),
-        max_iterations,
-        timeout_seconds,
-        rule_file: rule_file.map(str::to_string),
-        test_command: test_command.map(str::to_string),
-        test_discovery_command: test_discovery_command.map(str::to_string),
+        max_iterations: options.max_iterations,
+        timeout_seconds: options.timeout_seconds,
+        rule_file: options.rule_file.clone(),
+        test_command: options.test_command.clone(),
+        test_discovery_command: options.test_discovery_command.clone(),
         agent_model: None,
         pipeline_steps: Some(pipeline.steps.clone()),
         red_failure_patterns: pipeline
@@ -157,36 +236,54 @@ pub fn run_orchestration_pipeline(
             .iter()
             .map(|s| s.to_ascii_lowercase())
             .collect(),
-        checkpoint_file: checkpoint_file.map(PathBuf::from),
-        resume_checkpoint: resume_checkpoint.map(PathBuf::from),
-        allow_dependency_bypass,
-        overwrite,
+        checkpoint_file: options.checkpoint_file.as_ref().map(PathBuf::from),
+        resume_checkpoint: options.resume_checkpoint.as_ref().map(PathBuf::from),
+        allow_dependency_bypass: options.allow_dependency_bypass,
+        overwrite: options.overwrite,
+        force_regenerate_tests: options.force,
         run_id: None,
     };
 
-    if options.red_failure_patterns.is_empty() {
+    if implement_options.red_failure_patterns.is_empty() {
         bail!(
             "Pipeline '{}' must define at least one red_failure_patterns entry in '{}'.",
-            pipeline_name,
+            options.pipeline_name,
             pipeline_path.display()
         );
     }
 
-    let selected_model = resolve_opencode_model(model)?;
+    let selected_model = resolve_opencode_model(options.model.as_deref())?;
     if let Some(model_id) = &selected_model {
         println!("Orchestration model: {}", model_id);
     } else {
         println!("Orchestration model: opencode/default");
     }
-    options.agent_model = selected_model;
+    implement_options.agent_model = selected_model;
 
     let parsed = parse_context_file(&PathBuf::from(context_file))?;
+    ensure_context_unfrozen(
+        &parsed,
+        context_file,
+        &options.pipeline_name,
+        options.unfreeze,
+    )?;
     println!(
         "Orchestration startup: context_id={}, pipeline={}, steps={}",
         parsed.context_id,
-        pipeline_name,
+        options.pipeline_name,
         pipeline.steps.len()
     );
+    remember_orchestration_parameters(&OrchestrationPreset {
+        pipeline_file: options.pipeline_file.clone(),
+        max_iterations: Some(options.max_iterations),
+        timeout_seconds: Some(options.timeout_seconds),
+        rule_file: options.rule_file.clone(),
+        test_command: options.test_command.clone(),
+        test_discovery_command: options.test_discovery_command.clone(),
+        model: options.model.clone(),
+        allow_dependency_bypass: options.allow_dependency_bypass,
+        overwrite: options.overwrite,
+    })?;
 
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
     let context_snapshot = fs::read_to_string(context_file)
@@ -201,24 +298,32 @@ pub fn run_orchestration_pipeline(
             store.mark_context_runs_stale(&parsed.context_id, &context_snapshot_hash)?;
         }
     }
+    ensure_file_reservations(
+        &reservation_conflicts(
+            &referenced_files(&context_snapshot),
+            context_file,
+            &store.list_active_run_snapshots()?,
+        ),
+        options.exclusive_files,
+    )?;
     let fingerprint = compute_run_fingerprint(
-        pipeline_name,
+        &options.pipeline_name,
         &pipeline.steps,
         &context_snapshot,
-        rule_file,
-        test_command,
-        test_discovery_command,
-        options.agent_model.as_deref(),
+        options.rule_file.as_deref(),
+        options.test_command.as_deref(),
+        options.test_discovery_command.as_deref(),
+        implement_options.agent_model.as_deref(),
     );
     let prior_equivalent = store.find_successful_run_by_fingerprint(&fingerprint)?;
-    if !overwrite {
+    if !options.overwrite {
         if let Some(existing) = prior_equivalent {
             emit_json_or_text(
                 format,
                 &ActionResultPayload {
                     action: "start".to_string(),
                     context_file: context_file.to_string(),
-                    pipeline_name: Some(pipeline_name.to_string()),
+                    pipeline_name: Some(options.pipeline_name.clone()),
                     run_id: Some(existing.0),
                     status: "success".to_string(),
                     terminal_reason: Some("dedup_skipped".to_string()),
@@ -235,12 +340,12 @@ pub fn run_orchestration_pipeline(
     }
 
     let (run_id, action_summary) = store.create_run_with_snapshot_and_actions(
-        pipeline_name,
+        &options.pipeline_name,
         &parsed.context_id,
         context_file,
         &fingerprint,
-        overwrite,
-        if overwrite {
+        options.overwrite,
+        if options.overwrite {
             prior_equivalent.map(|entry| entry.0)
         } else {
             None
@@ -250,7 +355,7 @@ pub fn run_orchestration_pipeline(
         &context_snapshot,
         &parsed.next_actions,
     )?;
-    options.run_id = Some(run_id);
+    implement_options.run_id = Some(run_id);
     println!(
         "Next-action reconciliation: added_pending={} removed_cancelled={} retained_active={}",
         action_summary.added_pending,
@@ -258,11 +363,37 @@ pub fn run_orchestration_pipeline(
         action_summary.retained_active
     );
 
-    run_orchestration_implement_app(&options).or_else(|err| {
+    let workflow_payload = |status: &str, terminal_reason: Option<String>| {
+        serde_json::json!({
+            "run_id": run_id,
+            "pipeline_name": options.pipeline_name,
+            "context_id": parsed.context_id,
+            "context_file": context_file,
+            "status": status,
+            "terminal_reason": terminal_reason,
+        })
+    };
+    let summary = run_orchestration_implement_app(&implement_options).or_else(|err| {
         let _ = store.finish_run(run_id, "failed", Some(&err.to_string()));
+        fire_hook(
+            HookEvent::WorkflowFinished,
+            workflow_payload("failed", Some(err.to_string())),
+        );
         Err(err)
     })?;
+    let post_processor_failures = run_pipeline_post_processors(
+        &store,
+        &options.pipeline_name,
+        context_file,
+        run_id,
+        &summary,
+    )?;
     store.finish_run(run_id, "success", None)?;
+    fire_hook(
+        HookEvent::WorkflowFinished,
+        workflow_payload("success", None),
+    );
+    record_action_outcomes(context_file, run_id, &summary, options.auto_outcome, format)?;
 
     let latest = store.latest_run_for_context(context_file)?;
     let (status, terminal_reason, ended_at) = if let Some(run) = latest {
@@ -275,14 +406,24 @@ pub fn run_orchestration_pipeline(
         &ActionResultPayload {
             action: "start".to_string(),
             context_file: context_file.to_string(),
-            pipeline_name: Some(pipeline_name.to_string()),
+            pipeline_name: Some(options.pipeline_name.clone()),
             run_id: Some(run_id),
             status,
             terminal_reason,
             started_at: None,
             ended_at,
-            message: "Pipeline execution completed.".to_string(),
-            remediation: None,
+            message: if post_processor_failures == 0 {
+                "Pipeline execution completed.".to_string()
+            } else {
+                format!(
+                    "Pipeline execution completed; {} post-processor(s) failed.",
+                    post_processor_failures
+                )
+            },
+            remediation: (post_processor_failures > 0).then(|| {
+                "Inspect post-processor output with `opennexus orchestration artifacts --run-id <id>`."
+                    .to_string()
+            }),
             active_run_ids: vec![],
         },
     )?;
@@ -303,10 +444,7 @@ fn orchestration_timeline(filter: TimelineFilter, format: OutputFormat) -> Resul
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
     if let Some(run_id) = filter.run_id {
         if !store.run_exists(run_id)? {
-            bail!(
-                "Unknown run id '{}'. Use `opennexus orchestration runs --context-file <path>` to list valid run ids.",
-                run_id
-            );
+            bail!(unknown_run(run_id));
         }
     }
 
@@ -316,12 +454,12 @@ fn orchestration_timeline(filter: TimelineFilter, format: OutputFormat) -> Resul
         return Ok(());
     }
 
-    if format == OutputFormat::Json {
+    if format.is_structured() {
         let payload = rows
             .iter()
             .map(TimelineJsonRow::from)
             .collect::<Vec<TimelineJsonRow>>();
-        println!("{}", serde_json::to_string_pretty(&payload)?);
+        print_json_pretty(&payload)?;
         return Ok(());
     }
 
@@ -337,8 +475,8 @@ fn orchestration_timeline(filter: TimelineFilter, format: OutputFormat) -> Resul
             row.attempt_index,
             row.step_status,
             row.terminal_reason.unwrap_or_else(|| "n/a".to_string()),
-            row.started_at,
-            row.finished_at,
+            format_timestamp(row.started_at),
+            format_timestamp(row.finished_at),
             row.trace_ids,
             row.artifact_refs
         );
@@ -370,11 +508,10 @@ fn resolve_opencode_model(requested: Option<&str>) -> Result<Option<String>> {
         .cloned()
         .collect::<Vec<String>>()
         .join(", ");
-    bail!(
+    bail!(CliError::not_found(format!(
         "Requested model '{}' was not found in `opencode models`. Sample available models: {}",
-        requested_model,
-        preview
-    )
+        requested_model, preview
+    )))
 }
 
 fn list_opencode_models() -> Result<BTreeSet<String>> {
@@ -403,10 +540,10 @@ fn list_opencode_models() -> Result<BTreeSet<String>> {
 
 fn load_pipeline_catalog(path: &PathBuf) -> Result<PipelineCatalog> {
     if !path.exists() {
-        bail!(
+        bail!(CliError::not_found(format!(
             "Pipeline definition file '{}' does not exist.",
             path.display()
-        );
+        )));
     }
     let content = fs::read_to_string(path).with_context(|| {
         format!(
@@ -462,6 +599,19 @@ fn resolve_pipeline_catalog(explicit: Option<&str>) -> Result<(PathBuf, Pipeline
     )
 }
 
+/// `(pipeline_name, step_count)` from the default pipeline file; empty when none loads.
+pub(crate) fn default_pipeline_summaries() -> Vec<(String, usize)> {
+    resolve_pipeline_catalog(None)
+        .map(|(_, catalog)| {
+            catalog
+                .pipelines
+                .into_iter()
+                .map(|(name, pipeline)| (name, pipeline.steps.len()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn compute_run_fingerprint(
     pipeline_name: &str,
     steps: &[String],
@@ -498,6 +648,7 @@ struct StatusPayload {
     run_id: Option<i64>,
     status: String,
     terminal_reason: Option<String>,
+    stop_reason: Option<String>,
     started_at: Option<i64>,
     ended_at: Option<i64>,
     message: String,
@@ -532,6 +683,7 @@ struct RunListRow {
     pipeline_name: String,
     status: String,
     terminal_reason: Option<String>,
+    stop_reason: Option<String>,
     started_at: i64,
     ended_at: Option<i64>,
 }
@@ -566,13 +718,20 @@ struct ArtifactRow {
     created_at: i64,
 }
 
+fn unknown_run(run_id: i64) -> CliError {
+    CliError::not_found(format!(
+        "Unknown run id '{}'. Use `opennexus orchestration runs --context-file <path>` to list valid run ids.",
+        run_id
+    ))
+}
+
 fn emit_json_or_text<T: Serialize>(format: OutputFormat, payload: &T) -> Result<()> {
     match format {
-        OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(payload)?);
+        OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Ndjson => {
+            print_json_pretty(payload)?;
         }
-        OutputFormat::Text => {
-            println!("{}", serde_json::to_string_pretty(payload)?);
+        OutputFormat::Text | OutputFormat::Tsv => {
+            print_json_pretty(payload)?;
         }
     }
     Ok(())
@@ -606,16 +765,21 @@ fn orchestration_active(
 fn orchestration_stop(
     context_file: &str,
     pipeline_filter: Option<&str>,
+    stop_reason: Option<&str>,
     format: OutputFormat,
 ) -> Result<()> {
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
-    let Some((run_id, pipeline_name, runner_pid)) =
-        store.stop_active_run_for_context(context_file, pipeline_filter, "stopped_by_operator")?
+    let Some((run_id, pipeline_name, runner_pid)) = store.stop_active_run_for_context(
+        context_file,
+        pipeline_filter,
+        "stopped_by_operator",
+        stop_reason,
+    )?
     else {
-        bail!(
+        bail!(CliError::not_found(format!(
             "No active orchestration run found for context '{}'. Start one with `opennexus orchestration <pipeline> --context-file <path>`.",
             context_file
-        );
+        )));
     };
 
     let mut signal_result = "not_sent".to_string();
@@ -640,7 +804,53 @@ fn orchestration_stop(
         terminal_reason: Some("stopped_by_operator".to_string()),
         started_at: None,
         ended_at: None,
-        message: format!("Stopped active run. signal={}", signal_result),
+        message: match stop_reason {
+            Some(reason) => format!(
+                "Stopped active run. reason={:?} signal={}",
+                reason, signal_result
+            ),
+            None => format!("Stopped active run. signal={}", signal_result),
+        },
+        remediation: None,
+        active_run_ids: vec![],
+    };
+    emit_json_or_text(format, &payload)
+}
+
+/// Pin or unpin a run so retention pruning keeps it.
+fn orchestration_pin(
+    context_file: &str,
+    run_id: i64,
+    pinned: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let store = OrchestrationStore::open(&default_orchestration_database_path())?;
+    let run = store
+        .get_run_by_id(run_id)?
+        .ok_or_else(|| unknown_run(run_id))?;
+    if run.context_file != context_file {
+        bail!(
+            "Run {} does not belong to context file '{}'. Use matching --context-file value.",
+            run_id,
+            context_file
+        );
+    }
+    store.set_run_pinned(run_id, pinned)?;
+
+    let payload = ActionResultPayload {
+        action: if pinned { "pin" } else { "unpin" }.to_string(),
+        context_file: context_file.to_string(),
+        pipeline_name: Some(run.pipeline_name),
+        run_id: Some(run_id),
+        status: run.status,
+        terminal_reason: run.terminal_reason,
+        started_at: None,
+        ended_at: None,
+        message: if pinned {
+            "Pinned run; history pruning will keep it.".to_string()
+        } else {
+            "Unpinned run; history pruning may remove it.".to_string()
+        },
         remediation: None,
         active_run_ids: vec![],
     };
@@ -648,50 +858,174 @@ fn orchestration_stop(
 }
 
 #[allow(clippy::too_many_arguments)]
+/// Stop the context's active runs and start `next_pipeline` with the same
+/// flags, always as a new run.
 fn orchestration_restart(
+    options: &OrchestrationRunOptions,
     format: OutputFormat,
     next_pipeline: &str,
-    pipeline_file: Option<&str>,
     context_file: &str,
-    max_iterations: usize,
-    timeout_seconds: u64,
-    rule_file: Option<&str>,
-    test_command: Option<&str>,
-    test_discovery_command: Option<&str>,
-    model: Option<&str>,
-    checkpoint_file: Option<&str>,
-    allow_dependency_bypass: bool,
-    run_id: Option<i64>,
-    context_id_filter: Option<&str>,
-    pipeline_filter: Option<&str>,
 ) -> Result<()> {
-    let _ = orchestration_stop(context_file, pipeline_filter, OutputFormat::Json);
+    let parsed = parse_context_file(&PathBuf::from(context_file))?;
+    ensure_context_unfrozen(&parsed, context_file, next_pipeline, options.unfreeze)?;
+    let _ = orchestration_stop(
+        context_file,
+        options.pipeline_filter.as_deref(),
+        Some(options.reason.as_deref().unwrap_or("restarted")),
+        OutputFormat::Json,
+    );
     run_orchestration_pipeline(
+        &OrchestrationRunOptions {
+            pipeline_name: next_pipeline.to_string(),
+            target_pipeline_name: None,
+            context_file: Some(context_file.to_string()),
+            resume_checkpoint: None,
+            overwrite: true,
+            compare_run_id: None,
+            reason: None,
+            ..options.clone()
+        },
         format,
-        next_pipeline,
-        None,
-        pipeline_file,
-        Some(context_file),
-        max_iterations,
-        timeout_seconds,
-        rule_file,
-        test_command,
-        test_discovery_command,
-        model,
-        checkpoint_file,
-        None,
-        allow_dependency_bypass,
-        true,
-        run_id,
-        context_id_filter,
-        pipeline_filter,
     )
 }
 
+/// Run the pipeline's configured post-processors and record each as a run
+/// artifact; returns how many failed.
+fn run_pipeline_post_processors(
+    store: &OrchestrationStore,
+    pipeline_name: &str,
+    context_file: &str,
+    run_id: i64,
+    summary: &ContextImplementSummary,
+) -> Result<usize> {
+    let config = load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?;
+    let Some(commands) = config.post_processors.get(pipeline_name) else {
+        return Ok(0);
+    };
+    let generated_files = summary
+        .generated_tests
+        .iter()
+        .map(|(_, path)| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let results = run_post_processors(
+        commands,
+        &[
+            ("NEXUS_RUN_ID", run_id.to_string()),
+            ("NEXUS_PIPELINE", pipeline_name.to_string()),
+            ("NEXUS_CONTEXT_FILE", context_file.to_string()),
+            ("NEXUS_GENERATED_FILES", generated_files),
+        ],
+    );
+
+    let mut artifacts = Vec::<(&str, String)>::new();
+    for result in &results {
+        let status = match result.exit_code {
+            Some(code) => format!("exit={}", code),
+            None => "exit=signal".to_string(),
+        };
+        let line = format!("post-processor `{}` {}", result.command, status);
+        if result.success {
+            print_info(&line);
+        } else {
+            print_warning(&format!("{}\n{}", line, result.output));
+        }
+        artifacts.push((
+            "post_processor",
+            format!("{} {}\n{}", result.command, status, result.output),
+        ));
+    }
+    store.persist_artifacts_batch(run_id, "post_processors", &artifacts)?;
+    Ok(results.iter().filter(|result| !result.success).count())
+}
+
+/// Write per-action results into the context's Next Actions Outcome column.
+///
+/// Runs without prompting under `--auto-outcome`; otherwise asks on an
+/// interactive terminal and does nothing when piped.
+fn record_action_outcomes(
+    context_file: &str,
+    run_id: i64,
+    summary: &ContextImplementSummary,
+    auto_outcome: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    if summary.tests.is_empty() {
+        return Ok(());
+    }
+    let confirmed = auto_outcome
+        || (format == OutputFormat::Text
+            && std::io::stdin().is_terminal()
+            && std::io::stdout().is_terminal()
+            && Confirm::new()
+                .with_prompt(format!("Record action outcomes in {}?", context_file))
+                .default(true)
+                .interact()
+                .context("Failed to read outcome confirmation")?);
+    if !confirmed {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(context_file)
+        .with_context(|| format!("Unable to read context file '{}'.", context_file))?;
+    let updated = apply_action_outcomes(&content, &action_outcomes(summary, run_id))
+        .with_context(|| format!("Unable to record outcomes in '{}'.", context_file))?;
+    if updated != content {
+        fs::write(context_file, updated)
+            .with_context(|| format!("Unable to write context file '{}'.", context_file))?;
+        print_info(&format!(
+            "Recorded outcomes for {} action(s) in {}.",
+            summary.tests.len(),
+            context_file
+        ));
+    }
+    Ok(())
+}
+
+/// Refuse to run pipelines against finalized specs unless `--unfreeze` is passed.
+pub(crate) fn ensure_context_unfrozen(
+    parsed: &ContextParseResult,
+    context_file: &str,
+    pipeline_name: &str,
+    unfreeze: bool,
+) -> Result<()> {
+    if parsed.frozen && !unfreeze {
+        bail!(
+            "Context '{}' ({}) is frozen. Remediation: pass --unfreeze to run '{}' against it, or remove `frozen: true` from its frontmatter.",
+            parsed.context_id,
+            context_file,
+            pipeline_name
+        );
+    }
+    Ok(())
+}
+
+/// Warn about files reserved by other active runs; block under `--exclusive-files`.
+fn ensure_file_reservations(
+    conflicts: &[ReservationConflict],
+    exclusive_files: bool,
+) -> Result<()> {
+    for conflict in conflicts {
+        print_warning(&format!(
+            "Active run {} ({}) also references: {}",
+            conflict.run_id,
+            conflict.context_file,
+            conflict.files.join(", ")
+        ));
+    }
+    if exclusive_files && !conflicts.is_empty() {
+        bail!(
+            "Context files overlap with {} active run(s). Remediation: wait for them to finish, stop them with `opennexus orchestration stop --context-file <path>`, or drop --exclusive-files.",
+            conflicts.len()
+        );
+    }
+    Ok(())
+}
+
 fn orchestration_status(context_file: &str, format: OutputFormat) -> Result<()> {
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
     let active_runs = store.list_active_runs_for_context(context_file, None)?;
-    if let Some((run_id, pipeline, status, terminal_reason, finished_at, started_at)) =
+    if let Some((run_id, pipeline, status, terminal_reason, finished_at, started_at, stop_reason)) =
         store.latest_run_for_context(context_file)?
     {
         let payload = StatusPayload {
@@ -700,6 +1034,7 @@ fn orchestration_status(context_file: &str, format: OutputFormat) -> Result<()>
             run_id: Some(run_id),
             status,
             terminal_reason,
+            stop_reason,
             started_at: Some(started_at),
             ended_at: finished_at,
             message: if active_runs.is_empty() {
@@ -718,6 +1053,7 @@ fn orchestration_status(context_file: &str, format: OutputFormat) -> Result<()>
             run_id: None,
             status: "unknown".to_string(),
             terminal_reason: Some("no_runs".to_string()),
+            stop_reason: None,
             started_at: None,
             ended_at: None,
             message: format!(
@@ -738,7 +1074,7 @@ fn orchestration_status(context_file: &str, format: OutputFormat) -> Result<()>
 fn orchestration_runs(context_file: &str, format: OutputFormat) -> Result<()> {
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
     let runs = store.list_runs_for_context(context_file)?;
-    if format == OutputFormat::Json {
+    if format.is_structured() {
         let payload = RunsPayload {
             context_file: context_file.to_string(),
             runs: runs
@@ -748,12 +1084,29 @@ fn orchestration_runs(context_file: &str, format: OutputFormat) -> Result<()> {
                     pipeline_name: run.1,
                     status: run.2,
                     terminal_reason: run.3,
+                    stop_reason: run.6,
                     started_at: run.4,
                     ended_at: run.5,
                 })
                 .collect(),
         };
-        println!("{}", serde_json::to_string_pretty(&payload)?);
+        print_json_pretty(&payload)?;
+        return Ok(());
+    }
+    if format == OutputFormat::Tsv {
+        for run in runs {
+            print_tsv_row(&[
+                &run.0.to_string(),
+                &run.1,
+                &run.2,
+                run.3.as_deref().unwrap_or_default(),
+                &run.4.to_string(),
+                &run.5
+                    .map(|finished| finished.to_string())
+                    .unwrap_or_default(),
+                run.6.as_deref().unwrap_or_default(),
+            ]);
+        }
         return Ok(());
     }
     if runs.is_empty() {
@@ -766,13 +1119,16 @@ fn orchestration_runs(context_file: &str, format: OutputFormat) -> Result<()> {
 
     for run in runs {
         println!(
-            "run_id={} pipeline={} status={} terminal={} started={} finished={}",
+            "run_id={} pipeline={} status={} terminal={} started={} finished={}{}",
             run.0,
             run.1,
             run.2,
             run.3.unwrap_or_else(|| "n/a".to_string()),
-            run.4,
-            run.5.unwrap_or_default()
+            format_timestamp(run.4),
+            run.5.map(format_timestamp).unwrap_or_default(),
+            run.6
+                .map(|reason| format!(" reason={:?}", reason))
+                .unwrap_or_default()
         );
     }
     Ok(())
@@ -781,10 +1137,7 @@ fn orchestration_runs(context_file: &str, format: OutputFormat) -> Result<()> {
 fn orchestration_traces(context_file: &str, run_id: i64, format: OutputFormat) -> Result<()> {
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
     if !store.run_exists(run_id)? {
-        bail!(
-            "Unknown run id '{}'. Use `opennexus orchestration runs --context-file <path>` to list valid run ids.",
-            run_id
-        );
+        bail!(unknown_run(run_id));
     }
     let run = store
         .get_run_by_id(run_id)?
@@ -797,7 +1150,7 @@ fn orchestration_traces(context_file: &str, run_id: i64, format: OutputFormat) -
         );
     }
 
-    if format == OutputFormat::Json {
+    if format.is_structured() {
         let traces = store.query_traces_for_run(run_id)?;
         let payload = TracePayload {
             context_file: run.context_file,
@@ -809,7 +1162,7 @@ fn orchestration_traces(context_file: &str, run_id: i64, format: OutputFormat) -
             ended_at: run.finished_at,
             traces: traces.iter().map(TraceJsonRow::from).collect(),
         };
-        println!("{}", serde_json::to_string_pretty(&payload)?);
+        print_json_pretty(&payload)?;
         return Ok(());
     }
 
@@ -946,10 +1299,7 @@ impl From<&TraceQueryRow> for TraceJsonRow {
 fn orchestration_artifacts(context_file: &str, run_id: i64, format: OutputFormat) -> Result<()> {
     let store = OrchestrationStore::open(&default_orchestration_database_path())?;
     if !store.run_exists(run_id)? {
-        bail!(
-            "Unknown run id '{}'. Use `opennexus orchestration runs --context-file <path>` to list valid run ids.",
-            run_id
-        );
+        bail!(unknown_run(run_id));
     }
     let artifacts = store.list_artifacts_for_run(run_id)?;
     let run = store
@@ -962,7 +1312,7 @@ fn orchestration_artifacts(context_file: &str, run_id: i64, format: OutputFormat
             context_file
         );
     }
-    if format == OutputFormat::Json {
+    if format.is_structured() {
         let payload = ArtifactPayload {
             context_file: run.context_file,
             pipeline_name: run.pipeline_name,
@@ -982,7 +1332,7 @@ fn orchestration_artifacts(context_file: &str, run_id: i64, format: OutputFormat
                 })
                 .collect(),
         };
-        println!("{}", serde_json::to_string_pretty(&payload)?);
+        print_json_pretty(&payload)?;
         return Ok(());
     }
     if artifacts.is_empty() {
@@ -992,12 +1342,287 @@ fn orchestration_artifacts(context_file: &str, run_id: i64, format: OutputFormat
     for artifact in artifacts {
         println!(
             "artifact_id={} step={} kind={} ref={} created_at={}",
-            artifact.0, artifact.1, artifact.2, artifact.3, artifact.4
+            artifact.0,
+            artifact.1,
+            artifact.2,
+            artifact.3,
+            format_timestamp(artifact.4)
         );
     }
     Ok(())
 }
 
+#[derive(Debug, Serialize)]
+struct RunComparisonPayload {
+    pipeline_name: String,
+    base: RunComparisonSide,
+    candidate: RunComparisonSide,
+    parameters: ParameterComparison,
+    steps: Vec<StepComparisonRow>,
+    regressions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RunComparisonSide {
+    run_id: i64,
+    context_file: String,
+    status: String,
+    terminal_reason: Option<String>,
+    started_at: i64,
+    ended_at: Option<i64>,
+    duration_s: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+struct ParameterComparison {
+    fingerprint_changed: bool,
+    context_id_changed: bool,
+    context_snapshot_changed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct StepComparisonRow {
+    step_id: String,
+    base: Option<StepComparisonSide>,
+    candidate: Option<StepComparisonSide>,
+    regression: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StepComparisonSide {
+    status: String,
+    attempts: usize,
+    duration_s: i64,
+    latency_ms: i64,
+    error: Option<String>,
+}
+
+impl From<&RunRecord> for RunComparisonSide {
+    fn from(value: &RunRecord) -> Self {
+        Self {
+            run_id: value.run_id,
+            context_file: value.context_file.clone(),
+            status: value.status.clone(),
+            terminal_reason: value.terminal_reason.clone(),
+            started_at: value.started_at,
+            ended_at: value.finished_at,
+            duration_s: value.finished_at.map(|end| end - value.started_at),
+        }
+    }
+}
+
+fn orchestration_compare(
+    base_run_id: i64,
+    candidate_run_id: i64,
+    format: OutputFormat,
+) -> Result<()> {
+    let store = OrchestrationStore::open(&default_orchestration_database_path())?;
+    let load_run = |run_id: i64| -> Result<(RunRecord, RunParameters, Vec<StepAttemptRecord>)> {
+        let run = store
+            .get_run_by_id(run_id)?
+            .ok_or_else(|| unknown_run(run_id))?;
+        let parameters = store
+            .get_run_parameters(run_id)?
+            .context("Run parameters missing for requested run id.")?;
+        let attempts = store.list_step_attempts_for_run(run_id)?;
+        Ok((run, parameters, attempts))
+    };
+    let base = load_run(base_run_id)?;
+    let candidate = load_run(candidate_run_id)?;
+    if base.0.pipeline_name != candidate.0.pipeline_name {
+        bail!(
+            "Runs {} and {} belong to different pipelines ('{}' vs '{}'). Compare two executions of the same pipeline.",
+            base_run_id,
+            candidate_run_id,
+            base.0.pipeline_name,
+            candidate.0.pipeline_name
+        );
+    }
+
+    let payload = build_run_comparison(
+        (&base.0, &base.1, &base.2),
+        (&candidate.0, &candidate.1, &candidate.2),
+    );
+
+    if format.is_structured() {
+        print_json_pretty(&payload)?;
+        return Ok(());
+    }
+
+    println!(
+        "pipeline={} base_run={} base_status={} candidate_run={} candidate_status={}",
+        payload.pipeline_name,
+        payload.base.run_id,
+        payload.base.status,
+        payload.candidate.run_id,
+        payload.candidate.status
+    );
+    println!(
+        "parameters fingerprint_changed={} context_id_changed={} context_snapshot_changed={}",
+        payload.parameters.fingerprint_changed,
+        payload.parameters.context_id_changed,
+        payload.parameters.context_snapshot_changed
+    );
+    for row in &payload.steps {
+        println!(
+            "{}step={} base={} candidate={}",
+            if row.regression { "REGRESSION " } else { "" },
+            row.step_id,
+            describe_step_side(row.base.as_ref()),
+            describe_step_side(row.candidate.as_ref())
+        );
+    }
+    if payload.regressions.is_empty() {
+        println!("No regressions detected.");
+    } else {
+        println!("Regressions: {}", payload.regressions.len());
+        for regression in &payload.regressions {
+            println!("- {}", regression);
+        }
+    }
+    Ok(())
+}
+
+fn describe_step_side(side: Option<&StepComparisonSide>) -> String {
+    let Some(side) = side else {
+        return "not_run".to_string();
+    };
+    let mut rendered = format!(
+        "{}(attempts={} duration_s={} latency_ms={})",
+        side.status, side.attempts, side.duration_s, side.latency_ms
+    );
+    if let Some(error) = &side.error {
+        rendered.push_str(&format!(" error={:?}", error));
+    }
+    rendered
+}
+
+fn build_run_comparison(
+    base: (&RunRecord, &RunParameters, &[StepAttemptRecord]),
+    candidate: (&RunRecord, &RunParameters, &[StepAttemptRecord]),
+) -> RunComparisonPayload {
+    let base_steps = summarize_step_attempts(base.2);
+    let candidate_steps = summarize_step_attempts(candidate.2);
+
+    let mut step_ids = base_steps
+        .iter()
+        .map(|(step_id, _)| step_id.clone())
+        .collect::<Vec<String>>();
+    for (step_id, _) in &candidate_steps {
+        if !step_ids.contains(step_id) {
+            step_ids.push(step_id.clone());
+        }
+    }
+
+    let mut regressions = Vec::new();
+    if base.0.status == "success" && candidate.0.status != "success" {
+        regressions.push(format!(
+            "run status: success -> {}{}",
+            candidate.0.status,
+            candidate
+                .0
+                .terminal_reason
+                .as_deref()
+                .map(|reason| format!(" ({})", reason))
+                .unwrap_or_default()
+        ));
+    }
+
+    let mut steps = Vec::new();
+    for step_id in step_ids {
+        let base_side = take_step_summary(&base_steps, &step_id);
+        let candidate_side = take_step_summary(&candidate_steps, &step_id);
+        let regression = match (&base_side, &candidate_side) {
+            (Some(before), Some(after)) => before.status == "success" && after.status != "success",
+            (Some(before), None) => before.status == "success",
+            _ => false,
+        };
+        if regression {
+            regressions.push(format!(
+                "step {}: success -> {}{}",
+                step_id,
+                candidate_side
+                    .as_ref()
+                    .map(|side| side.status.as_str())
+                    .unwrap_or("not_run"),
+                candidate_side
+                    .as_ref()
+                    .and_then(|side| side.error.as_deref())
+                    .map(|error| format!(" ({})", error))
+                    .unwrap_or_default()
+            ));
+        }
+        steps.push(StepComparisonRow {
+            step_id,
+            base: base_side,
+            candidate: candidate_side,
+            regression,
+        });
+    }
+
+    RunComparisonPayload {
+        pipeline_name: base.0.pipeline_name.clone(),
+        base: RunComparisonSide::from(base.0),
+        candidate: RunComparisonSide::from(candidate.0),
+        parameters: ParameterComparison {
+            fingerprint_changed: base.1.run_fingerprint != candidate.1.run_fingerprint,
+            context_id_changed: base.1.context_id != candidate.1.context_id,
+            context_snapshot_changed: base.1.context_snapshot_hash
+                != candidate.1.context_snapshot_hash,
+        },
+        steps,
+        regressions,
+    }
+}
+
+fn summarize_step_attempts(attempts: &[StepAttemptRecord]) -> Vec<(String, StepComparisonSide)> {
+    let mut summaries = Vec::<(String, StepComparisonSide)>::new();
+    for attempt in attempts {
+        let error = if attempt.status == "success" {
+            None
+        } else {
+            attempt
+                .details
+                .clone()
+                .filter(|details| !details.trim().is_empty())
+                .or_else(|| attempt.terminal_reason.clone())
+        };
+        let duration_s = attempt.finished_at - attempt.started_at;
+        if let Some((_, summary)) = summaries
+            .iter_mut()
+            .find(|(step_id, _)| *step_id == attempt.step_id)
+        {
+            summary.status = attempt.status.clone();
+            summary.attempts += 1;
+            summary.duration_s += duration_s;
+            summary.latency_ms += attempt.latency_ms;
+            summary.error = error;
+            continue;
+        }
+        summaries.push((
+            attempt.step_id.clone(),
+            StepComparisonSide {
+                status: attempt.status.clone(),
+                attempts: 1,
+                duration_s,
+                latency_ms: attempt.latency_ms,
+                error,
+            },
+        ));
+    }
+    summaries
+}
+
+fn take_step_summary(
+    summaries: &[(String, StepComparisonSide)],
+    step_id: &str,
+) -> Option<StepComparisonSide> {
+    summaries
+        .iter()
+        .find(|(id, _)| id == step_id)
+        .map(|(_, summary)| summary.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1066,6 +1691,7 @@ mod tests {
             run_id: Some(44),
             status: "running".to_string(),
             terminal_reason: None,
+            stop_reason: None,
             started_at: Some(100),
             ended_at: None,
             message: "active".to_string(),
@@ -1102,4 +1728,107 @@ mod tests {
         assert_eq!(value["status"], "stopped");
         assert_eq!(value["terminal_reason"], "stopped_by_operator");
     }
+
+    fn comparison_fixture(
+        run_id: i64,
+        status: &str,
+        fingerprint: &str,
+        attempts: Vec<(&str, &str, Option<&str>)>,
+    ) -> (RunRecord, RunParameters, Vec<StepAttemptRecord>) {
+        (
+            RunRecord {
+                run_id,
+                pipeline_name: "default".to_string(),
+                context_file: ".nexus/context/demo/CTX_001.md".to_string(),
+                status: status.to_string(),
+                terminal_reason: None,
+                stop_reason: None,
+                started_at: 100,
+                finished_at: Some(160),
+            },
+            RunParameters {
+                run_fingerprint: fingerprint.to_string(),
+                context_id: "CTX_001".to_string(),
+                context_snapshot_hash: Some(fingerprint.to_string()),
+            },
+            attempts
+                .into_iter()
+                .enumerate()
+                .map(|(index, (step_id, status, details))| StepAttemptRecord {
+                    step_attempt_id: index as i64 + 1,
+                    step_id: step_id.to_string(),
+                    attempt_index: 1,
+                    status: status.to_string(),
+                    details: details.map(str::to_string),
+                    terminal_reason: None,
+                    started_at: 100,
+                    finished_at: 110,
+                    latency_ms: 25,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn run_comparison_flags_step_and_run_regressions() {
+        let base = comparison_fixture(
+            1,
+            "success",
+            "fp-a",
+            vec![
+                ("parse_context", "success", None),
+                ("verify_red_tests", "success", None),
+            ],
+        );
+        let candidate = comparison_fixture(
+            2,
+            "failed",
+            "fp-b",
+            vec![
+                ("parse_context", "success", None),
+                ("verify_red_tests", "failed", Some("compilation failed")),
+            ],
+        );
+
+        let payload = build_run_comparison(
+            (&base.0, &base.1, &base.2),
+            (&candidate.0, &candidate.1, &candidate.2),
+        );
+        assert!(payload.parameters.fingerprint_changed);
+        assert!(payload.parameters.context_snapshot_changed);
+        assert!(!payload.parameters.context_id_changed);
+        assert_eq!(payload.steps.len(), 2);
+        assert!(!payload.steps[0].regression);
+        assert!(payload.steps[1].regression);
+        assert_eq!(payload.regressions.len(), 2);
+        assert!(payload.regressions[1].contains("compilation failed"));
+    }
+
+    #[test]
+    fn run_comparison_aggregates_retried_step_attempts() {
+        let base = comparison_fixture(
+            1,
+            "success",
+            "fp",
+            vec![
+                ("coder_iteration", "failed", Some("tests red")),
+                ("coder_iteration", "success", None),
+            ],
+        );
+        let candidate = comparison_fixture(2, "success", "fp", vec![]);
+
+        let payload = build_run_comparison(
+            (&base.0, &base.1, &base.2),
+            (&candidate.0, &candidate.1, &candidate.2),
+        );
+        let row = &payload.steps[0];
+        let summary = row.base.as_ref().expect("base summary");
+        assert_eq!(summary.attempts, 2);
+        assert_eq!(summary.status, "success");
+        assert_eq!(summary.duration_s, 20);
+        assert_eq!(summary.latency_ms, 50);
+        assert!(summary.error.is_none());
+        assert!(row.candidate.is_none());
+        assert!(row.regression);
+    }
 }
@@ -0,0 +1,209 @@
+//! External subcommands, git/cargo style.
+//!
+//! `opennexus foo ...` runs the first `opennexus-foo` (or `nexus-foo`) found
+//! on `PATH` with the remaining arguments. The resolved global settings are
+//! passed down through the environment: `NEXUS_FORMAT`, `NEXUS_STATELESS`,
+//! `NO_COLOR`, plus the config paths and this binary for plugins that call
+//! back into the CLI. The plugin's exit status becomes ours.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::OutputFormat;
+use crate::config::layered::FORMAT_ENV;
+use crate::config::nexus::NEXUS_CONFIG_PATH;
+use crate::config::stateless::{is_stateless, STATELESS_ENV};
+use crate::config::user::user_config_path;
+use crate::core::error::{CliError, ErrorCode};
+use crate::output::{print_info, print_json, print_tsv_row, propagate_color_choice};
+use crate::utils::paths::is_executable;
+
+/// Executable prefixes, in lookup order.
+pub const PLUGIN_PREFIXES: [&str; 2] = ["opennexus-", "nexus-"];
+
+pub const PROJECT_CONFIG_ENV: &str = "NEXUS_PROJECT_CONFIG";
+pub const USER_CONFIG_ENV: &str = "NEXUS_USER_CONFIG";
+pub const CLI_BIN_ENV: &str = "NEXUS_CLI";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Run `plugin list`.
+pub fn run_plugin_list(format: OutputFormat) -> Result<()> {
+    let plugins = discover_plugins(std::env::var_os("PATH").as_deref());
+    if format.is_structured() {
+        return print_json(&plugins);
+    }
+    if format == OutputFormat::Tsv {
+        for plugin in &plugins {
+            print_tsv_row(&[&plugin.name, &plugin.path.display().to_string()]);
+        }
+        return Ok(());
+    }
+    if plugins.is_empty() {
+        print_info("No plugins found. Put an `opennexus-<name>` executable on PATH to add one.");
+        return Ok(());
+    }
+    let width = plugins
+        .iter()
+        .map(|plugin| plugin.name.len())
+        .max()
+        .unwrap_or_default();
+    for plugin in &plugins {
+        println!("{:<width$}  {}", plugin.name, plugin.path.display());
+    }
+    Ok(())
+}
+
+/// Run the plugin named by `args[0]` with the rest of `args`.
+pub fn run_external_plugin(args: &[String], format: OutputFormat) -> Result<()> {
+    let Some((name, plugin_args)) = args.split_first() else {
+        bail!("No plugin name given.");
+    };
+    let plugins = discover_plugins(std::env::var_os("PATH").as_deref());
+    // Exit 2 like any other unrecognized subcommand.
+    let Some(plugin) = plugins.iter().find(|plugin| &plugin.name == name) else {
+        bail!(CliError::new(
+            ErrorCode::Failed,
+            format!(
+                "Unknown command '{}'. Remediation: run `opennexus --help` for built-in commands, or install an `opennexus-{}` plugin on PATH.",
+                name, name
+            )
+        )
+        .with_exit_code(2));
+    };
+
+    let mut command = Command::new(&plugin.path);
+    command.args(plugin_args);
+    if let Some(format) = format.to_possible_value() {
+        command.env(FORMAT_ENV, format.get_name());
+    }
+    if is_stateless() {
+        command.env(STATELESS_ENV, "1");
+    }
+    if let Ok(project_config) = fs::canonicalize(NEXUS_CONFIG_PATH) {
+        command.env(PROJECT_CONFIG_ENV, project_config);
+    }
+    if let Some(user_config) = user_config_path() {
+        command.env(USER_CONFIG_ENV, user_config);
+    }
+    if let Ok(current_exe) = std::env::current_exe() {
+        command.env(CLI_BIN_ENV, current_exe);
+    }
+    propagate_color_choice(&mut command);
+    tracing::debug!(plugin = %plugin.path.display(), "running plugin");
+
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run plugin '{}'.", plugin.path.display()))?;
+    if status.success() {
+        return Ok(());
+    }
+    let exit_code = status
+        .code()
+        .and_then(|code| u8::try_from(code).ok())
+        .unwrap_or(1);
+    bail!(
+        CliError::new(ErrorCode::Failed, format!("Plugin '{}' {}.", name, status))
+            .with_exit_code(exit_code)
+    )
+}
+
+/// Plugins on `path_var`, sorted by name; the first directory wins when a
+/// name appears more than once, as it would for the shell.
+pub fn discover_plugins(path_var: Option<&OsStr>) -> Vec<Plugin> {
+    let mut plugins = BTreeMap::<String, PathBuf>::new();
+    let Some(path_var) = path_var else {
+        return Vec::new();
+    };
+    for dir in std::env::split_paths(path_var) {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let (name, rank) = plugin_name(&path)?;
+                is_executable(&path).then_some((name, rank, path))
+            })
+            .collect::<Vec<_>>();
+        found.sort();
+        for (name, _, path) in found {
+            plugins.entry(name).or_insert(path);
+        }
+    }
+    plugins
+        .into_iter()
+        .map(|(name, path)| Plugin { name, path })
+        .collect()
+}
+
+/// The plugin name and the rank of its prefix in `PLUGIN_PREFIXES`.
+fn plugin_name(path: &Path) -> Option<(String, usize)> {
+    let stem = if cfg!(windows) {
+        path.file_stem()?
+    } else {
+        path.file_name()?
+    }
+    .to_str()?;
+    PLUGIN_PREFIXES
+        .iter()
+        .enumerate()
+        .find_map(|(rank, prefix)| Some((stem.strip_prefix(prefix)?, rank)))
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, rank)| (name.to_string(), rank))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn discovers_executables_with_plugin_prefixes_first_path_wins() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let first = tempdir().expect("tempdir");
+        let second = tempdir().expect("tempdir");
+        let write = |dir: &Path, name: &str, mode: u32| {
+            let path = dir.join(name);
+            fs::write(&path, "#!/bin/sh\n").expect("plugin");
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).expect("chmod");
+        };
+        write(first.path(), "opennexus-lint", 0o755);
+        write(first.path(), "nexus-lint", 0o755);
+        write(first.path(), "nexus-notes", 0o644);
+        write(second.path(), "nexus-lint", 0o755);
+        write(second.path(), "nexus-deploy", 0o755);
+        write(second.path(), "nexus-", 0o755);
+        write(second.path(), "other-tool", 0o755);
+
+        let path_var = std::env::join_paths([first.path(), second.path()]).expect("PATH");
+        let plugins = discover_plugins(Some(&path_var));
+        assert_eq!(
+            plugins,
+            vec![
+                Plugin {
+                    name: "deploy".to_string(),
+                    path: second.path().join("nexus-deploy"),
+                },
+                Plugin {
+                    name: "lint".to_string(),
+                    path: first.path().join("opennexus-lint"),
+                },
+            ]
+        );
+        assert!(discover_plugins(None).is_empty());
+    }
+}
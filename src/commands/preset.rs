@@ -0,0 +1,277 @@
+//! Preset commands for reusable orchestration flag combinations.
+//!
+//! Presets live under `presets.<name>` in `.nexus/config.json`. Every
+//! orchestration run also records its effective flags so `preset save
+//! --from-last` can capture a combination that just worked.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::{OrchestrationCommand, OutputFormat};
+use crate::config::nexus::{
+    load_nexus_config, read_config_value, write_config_value, OrchestrationPreset,
+    NEXUS_CONFIG_PATH,
+};
+use crate::config::stateless::is_stateless;
+use crate::core::error::CliError;
+use crate::output::{print_info, print_json, print_success, print_tsv_row};
+use crate::utils::text::closest_match;
+
+const LAST_PARAMETERS_PATH: &str = ".nexus/orchestration/last-parameters.json";
+
+/// Run `preset save`; explicit flags override values taken from the last run.
+pub fn run_preset_save(
+    name: &str,
+    from_last: bool,
+    overrides: OrchestrationPreset,
+    format: OutputFormat,
+) -> Result<()> {
+    validate_preset_name(name)?;
+    let base = if from_last {
+        read_last_parameters(Path::new(LAST_PARAMETERS_PATH))?
+    } else {
+        OrchestrationPreset::default()
+    };
+    let preset = merge_preset(base, overrides);
+    if preset == OrchestrationPreset::default() {
+        bail!(
+            "Preset '{}' would be empty. Remediation: pass --from-last or at least one flag such as --max-iterations.",
+            name
+        );
+    }
+
+    let config_path = Path::new(NEXUS_CONFIG_PATH);
+    let mut config = read_existing_config(config_path)?;
+    let root = config
+        .as_object_mut()
+        .context("'.nexus/config.json' must contain a JSON object.")?;
+    let presets = root
+        .entry("presets")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let presets = presets
+        .as_object_mut()
+        .context("`presets` in .nexus/config.json must be an object.")?;
+    let replaced = presets
+        .insert(name.to_string(), serde_json::to_value(&preset)?)
+        .is_some();
+    write_config_value(config_path, &config)?;
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "saved",
+            "name": name,
+            "replaced": replaced,
+            "preset": preset,
+        }))?;
+    } else {
+        print_success(&format!(
+            "{} preset '{}' ({}).",
+            if replaced { "Updated" } else { "Saved" },
+            name,
+            describe_preset(&preset)
+        ));
+    }
+    Ok(())
+}
+
+pub fn run_preset_list(format: OutputFormat) -> Result<()> {
+    let config = load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?;
+    if format.is_structured() {
+        return print_json(&serde_json::json!({ "presets": config.presets }));
+    }
+    if format == OutputFormat::Tsv {
+        for (name, preset) in &config.presets {
+            print_tsv_row(&[name, &describe_preset(preset)]);
+        }
+        return Ok(());
+    }
+    if config.presets.is_empty() {
+        print_info("No presets saved. Create one with `opennexus preset save <name> --from-last`.");
+        return Ok(());
+    }
+    for (name, preset) in &config.presets {
+        println!("- {}: {}", name, describe_preset(preset));
+    }
+    Ok(())
+}
+
+pub fn run_preset_delete(name: &str, format: OutputFormat) -> Result<()> {
+    let config_path = Path::new(NEXUS_CONFIG_PATH);
+    let mut config = read_existing_config(config_path)?;
+    let Some(presets) = config.get_mut("presets").and_then(Value::as_object_mut) else {
+        return Err(unknown_preset(name, &[]));
+    };
+    if presets.remove(name).is_none() {
+        let names = presets.keys().map(String::as_str).collect::<Vec<_>>();
+        return Err(unknown_preset(name, &names));
+    }
+    write_config_value(config_path, &config)?;
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({ "status": "deleted", "name": name }))?;
+    } else {
+        print_success(&format!("Deleted preset '{}'.", name));
+    }
+    Ok(())
+}
+
+/// Fill flags the user left unset from `--preset`; explicit flags always win.
+pub fn apply_orchestration_preset(
+    mut command: OrchestrationCommand,
+) -> Result<OrchestrationCommand> {
+    let Some(name) = command.preset.as_deref() else {
+        return Ok(command);
+    };
+    let config = load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?;
+    let Some(preset) = config.presets.get(name).cloned() else {
+        let names = config
+            .presets
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        return Err(unknown_preset(name, &names));
+    };
+
+    command.pipeline_file = command.pipeline_file.or(preset.pipeline_file);
+    command.max_iterations = command.max_iterations.or(preset.max_iterations);
+    command.timeout_seconds = command.timeout_seconds.or(preset.timeout_seconds);
+    command.rule_file = command.rule_file.or(preset.rule_file);
+    command.test_command = command.test_command.or(preset.test_command);
+    command.test_discovery_command = command
+        .test_discovery_command
+        .or(preset.test_discovery_command);
+    command.model = command.model.or(preset.model);
+    command.allow_dependency_bypass |= preset.allow_dependency_bypass;
+    command.overwrite |= preset.overwrite;
+    Ok(command)
+}
+
+/// Record the effective flags of an orchestration run for `preset save --from-last`.
+pub(crate) fn remember_orchestration_parameters(parameters: &OrchestrationPreset) -> Result<()> {
+    if is_stateless() {
+        return Ok(());
+    }
+    let path = Path::new(LAST_PARAMETERS_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+    }
+    fs::write(
+        path,
+        format!("{}\n", serde_json::to_string_pretty(parameters)?),
+    )
+    .with_context(|| format!("Failed to write '{}'.", path.display()))
+}
+
+fn read_last_parameters(path: &Path) -> Result<OrchestrationPreset> {
+    if !path.exists() {
+        bail!(
+            "No orchestration run has been recorded yet. Remediation: run `opennexus orchestration <pipeline> --context-file <path>` first, or pass flags explicitly."
+        );
+    }
+    serde_json::from_value(read_config_value(path)?)
+        .with_context(|| format!("Failed to parse '{}'.", path.display()))
+}
+
+fn read_existing_config(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        bail!(
+            "No {} found. Remediation: run `opennexus setup` first.",
+            path.display()
+        );
+    }
+    read_config_value(path)
+}
+
+fn merge_preset(base: OrchestrationPreset, overrides: OrchestrationPreset) -> OrchestrationPreset {
+    OrchestrationPreset {
+        pipeline_file: overrides.pipeline_file.or(base.pipeline_file),
+        max_iterations: overrides.max_iterations.or(base.max_iterations),
+        timeout_seconds: overrides.timeout_seconds.or(base.timeout_seconds),
+        rule_file: overrides.rule_file.or(base.rule_file),
+        test_command: overrides.test_command.or(base.test_command),
+        test_discovery_command: overrides
+            .test_discovery_command
+            .or(base.test_discovery_command),
+        model: overrides.model.or(base.model),
+        allow_dependency_bypass: overrides.allow_dependency_bypass || base.allow_dependency_bypass,
+        overwrite: overrides.overwrite || base.overwrite,
+    }
+}
+
+fn describe_preset(preset: &OrchestrationPreset) -> String {
+    match serde_json::to_value(preset) {
+        Ok(Value::Object(fields)) if !fields.is_empty() => fields
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(text) => format!("{}={}", key, text),
+                other => format!("{}={}", key, other),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => "no flags".to_string(),
+    }
+}
+
+fn validate_preset_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if !valid {
+        bail!(
+            "Invalid preset name '{}'. Remediation: use letters, digits, '-' or '_'.",
+            name
+        );
+    }
+    Ok(())
+}
+
+fn unknown_preset(name: &str, known: &[&str]) -> anyhow::Error {
+    let suggestion = closest_match(name, known)
+        .map(|candidate| format!(" Did you mean '{}'?", candidate))
+        .unwrap_or_default();
+    CliError::not_found(format!(
+        "Unknown preset '{}'.{} Remediation: run `opennexus preset list` to see saved presets.",
+        name, suggestion
+    ))
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_prefers_explicit_flags_over_last_run() {
+        let last = OrchestrationPreset {
+            max_iterations: Some(3),
+            model: Some("openai/gpt-5".to_string()),
+            overwrite: true,
+            ..OrchestrationPreset::default()
+        };
+        let overrides = OrchestrationPreset {
+            max_iterations: Some(1),
+            ..OrchestrationPreset::default()
+        };
+        let merged = merge_preset(last, overrides);
+        assert_eq!(merged.max_iterations, Some(1));
+        assert_eq!(merged.model.as_deref(), Some("openai/gpt-5"));
+        assert!(merged.overwrite);
+        assert_eq!(
+            describe_preset(&merged),
+            "max_iterations=1, model=openai/gpt-5, overwrite=true"
+        );
+    }
+
+    #[test]
+    fn preset_names_are_restricted_to_slug_characters() {
+        assert!(validate_preset_name("gen-code_fast2").is_ok());
+        assert!(validate_preset_name("").is_err());
+        assert!(validate_preset_name("fast mode").is_err());
+        let err = unknown_preset("gen-code-fats", &["gen-code-fast"]);
+        assert!(err.to_string().contains("Did you mean 'gen-code-fast'?"));
+    }
+}
@@ -0,0 +1,47 @@
+//! Refs command for keeping context file references in step with repo moves.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::OutputFormat;
+use crate::features::context::refs::{remap_context_references, ReferenceRemap};
+use crate::output::{print_info, print_json, print_success};
+
+/// Run `refs remap`, rewriting references that match `from` to `to`.
+pub fn run_refs_remap(from: &str, to: &str, dry_run: bool, format: OutputFormat) -> Result<()> {
+    let remap = ReferenceRemap::new(from, to)?;
+    let report = remap_context_references(Path::new(".nexus/context"), &remap, dry_run)?;
+
+    if format.is_structured() {
+        return print_json(&serde_json::json!({
+            "from": from,
+            "to": to,
+            "dry_run": dry_run,
+            "scanned": report.scanned,
+            "contexts": report.contexts,
+        }));
+    }
+    if report.contexts.is_empty() {
+        print_info(&format!(
+            "No references to '{}' in {} context file(s).",
+            from, report.scanned
+        ));
+        return Ok(());
+    }
+    for context in &report.contexts {
+        println!("  {} ({} reference(s))", context.file, context.replacements);
+    }
+    let replacements = report
+        .contexts
+        .iter()
+        .map(|context| context.replacements)
+        .sum::<usize>();
+    let verb = if dry_run { "Would remap" } else { "Remapped" };
+    print_success(&format!(
+        "{} {} reference(s) across {} context(s)",
+        verb,
+        replacements,
+        report.contexts.len()
+    ));
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Scaffold command for giving gen-code concrete targets before it runs.
+//!
+//! Stub tests and module skeletons are committed on a `scaffold/<context-id>`
+//! branch so the generated files can be reviewed, rebased, or discarded as a
+//! unit.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::OutputFormat;
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::scaffold::scaffold_context;
+use crate::features::context::scan::scan_valid_context_specs;
+use crate::output::{print_info, print_json, print_success};
+use crate::services::vcs::open_vcs;
+
+#[derive(Debug, Serialize)]
+struct ScaffoldPayload {
+    context_id: String,
+    context_file: String,
+    tests_created: Vec<String>,
+    modules_created: Vec<String>,
+    branch: Option<String>,
+}
+
+/// Run `scaffold <context-id>`, committing created files unless `no_commit`.
+pub fn run_scaffold(context_id: &str, no_commit: bool, format: OutputFormat) -> Result<()> {
+    let context_file = find_context_file(context_id)?;
+    let report = scaffold_context(&context_file)?;
+    let created = report
+        .created_files()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+
+    let branch = if no_commit || created.is_empty() {
+        None
+    } else {
+        let branch = format!("scaffold/{}", report.context_id.to_lowercase());
+        open_vcs()?.commit_on_new_branch(
+            &branch,
+            &created,
+            &format!("Scaffold pending actions for {}", report.context_id),
+        )?;
+        Some(branch)
+    };
+
+    if format.is_structured() {
+        return print_json(&ScaffoldPayload {
+            context_id: report.context_id.clone(),
+            context_file: context_file.display().to_string(),
+            tests_created: display_paths(&report.tests_created),
+            modules_created: display_paths(&report.modules_created),
+            branch,
+        });
+    }
+    if created.is_empty() {
+        print_info(&format!(
+            "Nothing to scaffold for {}; every test stub and referenced module already exists.",
+            report.context_id
+        ));
+        return Ok(());
+    }
+    for path in &created {
+        println!("  created {}", path);
+    }
+    match branch {
+        Some(branch) => print_success(&format!(
+            "Scaffolded {} file(s) for {} on branch {}",
+            created.len(),
+            report.context_id,
+            branch
+        )),
+        None => print_success(&format!(
+            "Scaffolded {} file(s) for {} (not committed)",
+            created.len(),
+            report.context_id
+        )),
+    }
+    Ok(())
+}
+
+fn find_context_file(context_id: &str) -> Result<PathBuf> {
+    let scan = scan_valid_context_specs(Path::new(".nexus/context"))?;
+    for path in scan.valid_context_files {
+        if parse_context_file(&path)?
+            .context_id
+            .eq_ignore_ascii_case(context_id)
+        {
+            return Ok(path);
+        }
+    }
+    bail!(
+        "No context with id '{}' under .nexus/context/. Remediation: check the id in the context frontmatter.",
+        context_id
+    );
+}
+
+fn display_paths(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect()
+}
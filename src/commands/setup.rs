@@ -1,96 +1,1534 @@
 //! Setup command for initializing OpenNexus in a project.
 //!
 //! This is a local operation that extracts the bundled .nexus directory
-//! (containing harness assets) to the current working directory.
-
-use anyhow::{Context, Result};
-use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+//! (containing harness assets) to the current working directory and links
+//! its commands into each selected [`IntegrationTarget`]. Every step goes
+//! through [`SetupFs`], which only records the changes under `--dry-run`.
+//! The manifest, migrations, templates, bundles, and `.gitignore` block are
+//! described in the `crate::features::setup_*` modules.
+
+use anyhow::{bail, Context, Result};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, MultiSelect};
 use include_dir::{include_dir, Dir};
+use serde::Serialize;
 use serde_json::{Map, Value};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fs;
-use std::io::IsTerminal;
-#[cfg(unix)]
-use std::os::unix::fs::symlink;
-use std::path::Path;
-
-use crate::cli::OutputFormat;
-use crate::output::{print_info, print_success};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{LinkMode, OutputFormat};
+use crate::config::nexus::read_config_value;
+use crate::config::nexus::NEXUS_CONFIG_PATH;
+use crate::config::nexus::SUPPORTED_HARNESSES;
+use crate::core::error::CliError;
+use crate::features::assets_lint::{collect_assets_from_dir, lint_assets, AssetLintIssue};
+use crate::features::setup_bundle::{resolve_bundle, AssetBundle, BundleRecord, BUILTIN_BUNDLE};
+use crate::features::setup_gitignore::{render_gitignore, GITIGNORE_PATH};
+use crate::features::setup_integrations::{
+    resolve_integrations, IntegrationTarget, INTEGRATION_TARGETS, NEXUS_COMMANDS_DIR,
+};
+use crate::features::setup_manifest::{
+    file_drift, sha256_hex, DriftIssue, DriftKind, SetupManifest, SETUP_MANIFEST_PATH,
+};
+use crate::features::setup_migrations::{pending_migrations, MigrationAction, MIGRATIONS};
+use crate::features::setup_profile::SetupProfile;
+use crate::features::setup_template::{default_template_vars, render, TemplateVars};
+use crate::output::{print_error, print_info, print_json, print_success, print_warning};
+use crate::services::hooks::{run_hook_dir, HookRun, POST_SETUP_HOOKS_DIR};
+use crate::utils::paths::{link_with_mode, path_exists_or_symlink, remove_path, same_contents};
+use crate::utils::time::{format_unix_rfc3339, unix_now_seconds};
+
+const NEXUS_ROOT: &str = ".nexus";
+
+/// Bundled top-level directories that setup does not extract.
+const UNEXTRACTED_ROOT_DIRS: [&str; 2] = ["marketplace", "context"];
+
+/// The bundled config is this repository's own; `write_nexus_config` creates
+/// or updates the project's instead.
+const BUNDLED_CONFIG_FILE: &str = "config.json";
+
+/// Previous versions of overwritten `.nexus` files, one directory per run.
+const SETUP_BACKUP_ROOT: &str = ".nexus/.backup";
+
+/// Journal of files extracted by an in-progress setup; removed on completion.
+const SETUP_JOURNAL_PATH: &str = ".nexus/.setup-journal";
+
+/// Files extracted between journal flushes.
+const SETUP_JOURNAL_CHUNK: usize = 64;
+
+/// Bundled context projects setup may copy into `.nexus/context/`.
+pub const CONTEXT_TEMPLATES: [&str; 1] = ["_reference"];
 
 /// Embedded .nexus directory with setup-managed assets.
 static NEXUS_ASSETS: Dir = include_dir!("$CARGO_MANIFEST_DIR/.nexus");
 
-const SUPPORTED_HARNESSES: [&str; 1] = ["opencode"];
+/// Bundled `.nexus/` tree, shared with `assets lint --embedded`.
+pub(crate) fn embedded_nexus_assets() -> &'static Dir<'static> {
+    &NEXUS_ASSETS
+}
+
+/// What one setup run installs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetupSelection {
+    /// Harness recorded in `.nexus/config.json`.
+    pub harness: String,
+    /// Integration targets to link commands into, remembered in the config;
+    /// empty to reuse the config's.
+    pub integrations: Vec<String>,
+    pub profile: Option<String>,
+    /// `None` reuses the mode of the last setup.
+    pub link_mode: Option<LinkMode>,
+    /// Template variables overriding the last setup's values and the defaults.
+    pub template_vars: TemplateVars,
+    /// Entries of [`CONTEXT_TEMPLATES`] to copy into `.nexus/context/`.
+    pub templates: Vec<String>,
+    /// Asset bundle source; `None` reuses the last setup's, else the
+    /// compiled-in bundle.
+    pub from: Option<String>,
+    /// SHA-256 the bundle's digest must match.
+    pub sha256: Option<String>,
+    /// Leave `.gitignore` alone, now and on later runs.
+    pub no_gitignore: bool,
+    /// Skip the post-setup hooks for this run.
+    pub no_hooks: bool,
+}
+
+impl SetupSelection {
+    pub fn new(harness: impl Into<String>, profile: Option<String>) -> Self {
+        Self {
+            harness: harness.into(),
+            integrations: Vec::new(),
+            profile,
+            link_mode: None,
+            template_vars: TemplateVars::new(),
+            templates: Vec::new(),
+            from: None,
+            sha256: None,
+            no_gitignore: false,
+            no_hooks: false,
+        }
+    }
+}
+
+/// Run the setup command.
+///
+/// This extracts the bundled .nexus directory to the current working directory.
+/// Existing files are overwritten to keep assets up to date. The selection's
+/// profile picks which assets are extracted and is remembered in the config
+/// for later runs. With `dry_run` nothing is written and the planned changes
+/// are reported instead.
+pub fn run_setup(format: OutputFormat, selection: &SetupSelection, dry_run: bool) -> Result<()> {
+    let (profile, targets, bundle) = resolve_setup_selection(selection)?;
+    let mut setup_fs = SetupFs::new(dry_run);
+    setup_fs.events = format.is_structured() && !dry_run;
+    if !dry_run {
+        if format.is_structured() {
+            print_json(
+                &serde_json::json!({ "status": "starting", "bundle": bundle.as_ref().map(|(_, record)| record) }),
+            )?;
+        } else {
+            print_info(&format!(
+                "Setting up OpenNexus (harness: {}, profile: {})...",
+                selection.harness, profile.name
+            ));
+            if let Some((_, record)) = &bundle {
+                print_info(&format!(
+                    "Using asset bundle {} (sha256 {})",
+                    record.source, record.sha256
+                ));
+            }
+        }
+    }
+    setup_fs.use_bundle(bundle);
+
+    install(format, selection, &profile, &targets, &mut setup_fs)?;
+
+    if dry_run {
+        return print_setup_plan(
+            format,
+            &setup_fs.into_plan(&selection.harness, &profile.name),
+        );
+    }
+
+    setup_fs.discard_unchanged_backups()?;
+    check_installed_assets(format, &setup_fs.manifest)?;
+    let backup = (!setup_fs.backups.is_empty()).then(|| setup_fs.backup_dir.display().to_string());
+    let hooks = if selection.no_hooks {
+        Vec::new()
+    } else {
+        run_post_setup_hooks(format, selection, &profile, &targets)
+    };
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "completed",
+            "summary": setup_fs.counts,
+            "backup": backup,
+            "hooks": hooks,
+        }))?;
+    } else {
+        if let Some(backup) = &backup {
+            print_info(&format!(
+                "Backed up {} locally modified file(s) to {}. Run `opennexus setup rollback` to restore them.",
+                setup_fs.backups.len(),
+                backup
+            ));
+        }
+        println!();
+        print_success("OpenNexus setup complete!");
+    }
+
+    Ok(())
+}
+
+/// Lint the harness assets this run installed and make sure every harness
+/// link it recorded resolves, failing setup with a report instead of leaving
+/// a broken install behind. Runs before the post-setup hooks.
+fn check_installed_assets(format: OutputFormat, manifest: &SetupManifest) -> Result<()> {
+    let root = Path::new(NEXUS_ROOT);
+    let files = collect_assets_from_dir(root)?
+        .into_iter()
+        .filter(|file| {
+            manifest
+                .files
+                .contains_key(&format!("{}/{}", NEXUS_ROOT, file.path))
+        })
+        .collect::<Vec<_>>();
+    let mut issues = lint_assets(&files, |path| root.join(path).exists())
+        .into_iter()
+        .map(|issue| AssetLintIssue {
+            path: format!("{}/{}", NEXUS_ROOT, issue.path),
+            ..issue
+        })
+        .collect::<Vec<_>>();
+    issues.extend(
+        manifest
+            .links
+            .iter()
+            .filter(|(path, _)| !Path::new(path).exists())
+            .map(|(path, target)| AssetLintIssue {
+                path: path.clone(),
+                line: 0,
+                rule: "harness-link",
+                message: format!("harness link to '{}' does not resolve", target),
+            }),
+    );
+    if issues.is_empty() {
+        return Ok(());
+    }
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({ "status": "invalid", "issues": issues }))?;
+    } else {
+        for issue in &issues {
+            print_error(&format!(
+                "{}:{} [{}] {}",
+                issue.path, issue.line, issue.rule, issue.message
+            ));
+        }
+    }
+    bail!(
+        "Setup installed {} asset issue(s); post-setup hooks were skipped. Remediation: fix the reported files in the asset bundle, or run `opennexus setup repair` for broken links, then rerun `opennexus setup`.",
+        issues.len()
+    );
+}
+
+/// A `--from` bundle loaded for a run, with its manifest record.
+type SelectedBundle = Option<(AssetBundle, BundleRecord)>;
+
+/// Run the executables in `.nexus/hooks/post-setup.d/` in name order, with
+/// the run's selection as payload. Only real runs without `--no-hooks` get
+/// here; a failing hook is reported but does not fail setup.
+fn run_post_setup_hooks(
+    format: OutputFormat,
+    selection: &SetupSelection,
+    profile: &SetupProfile,
+    targets: &[&IntegrationTarget],
+) -> Vec<HookRun> {
+    let payload = serde_json::json!({
+        "harness": selection.harness,
+        "profile": profile.name,
+        "integrations": targets.iter().map(|target| target.name).collect::<Vec<_>>(),
+        "version": env!("CARGO_PKG_VERSION"),
+    });
+    let runs = run_hook_dir(Path::new(POST_SETUP_HOOKS_DIR), "post-setup", payload);
+    if !format.is_structured() {
+        for run in &runs {
+            match (&run.error, run.exit_code) {
+                _ if run.success => print_success(&format!("Ran post-setup hook {}", run.hook)),
+                (Some(error), _) => {
+                    print_warning(&format!("Post-setup hook {} failed: {}", run.hook, error))
+                }
+                (None, Some(code)) => print_warning(&format!(
+                    "Post-setup hook {} exited with status {}",
+                    run.hook, code
+                )),
+                (None, None) => print_warning(&format!(
+                    "Post-setup hook {} was terminated by a signal",
+                    run.hook
+                )),
+            }
+        }
+    }
+    runs
+}
+
+/// Resolve the selection's profile, integration targets, and asset bundle
+/// against the project config and the last setup.
+fn resolve_setup_selection(
+    selection: &SetupSelection,
+) -> Result<(
+    SetupProfile,
+    Vec<&'static IntegrationTarget>,
+    SelectedBundle,
+)> {
+    if selection.link_mode == Some(LinkMode::Junction) && !cfg!(windows) {
+        bail!("--link-mode junction is only available on Windows. Remediation: use symlink, hardlink, or copy.");
+    }
+    let config = read_config_value(Path::new(NEXUS_CONFIG_PATH)).ok();
+    let profile = SetupProfile::resolve(selection.profile.as_deref(), config.as_ref())?;
+    let targets =
+        resolve_integrations(&selection.integrations, config.as_ref(), &selection.harness)?;
+    let previous = SetupManifest::read(Path::new(SETUP_MANIFEST_PATH))?;
+    let bundle = resolve_setup_bundle(selection, previous.as_ref())?;
+    Ok((profile, targets, bundle))
+}
+
+/// Load the selection's bundle, else the last setup's; `None` for the
+/// compiled-in one. A pinned digest is kept while the source is unchanged.
+fn resolve_setup_bundle(
+    selection: &SetupSelection,
+    previous: Option<&SetupManifest>,
+) -> Result<SelectedBundle> {
+    let recorded = previous.and_then(|manifest| manifest.bundle.as_ref());
+    let Some(source) = selection
+        .from
+        .clone()
+        .or_else(|| recorded.map(|record| record.source.clone()))
+        .filter(|source| source != BUILTIN_BUNDLE)
+    else {
+        return Ok(None);
+    };
+    let pin = selection.sha256.clone().or_else(|| {
+        recorded
+            .filter(|record| record.pinned && record.source == source)
+            .map(|record| record.sha256.clone())
+    });
+    let bundle = resolve_bundle(&source, pin.as_deref())?;
+    let record = BundleRecord {
+        source,
+        sha256: bundle.digest.to_string(),
+        pinned: pin.is_some(),
+    };
+    Ok(Some((bundle, record)))
+}
+
+/// Run every setup step against `setup_fs`.
+fn install(
+    format: OutputFormat,
+    selection: &SetupSelection,
+    profile: &SetupProfile,
+    targets: &[&IntegrationTarget],
+    setup_fs: &mut SetupFs,
+) -> Result<()> {
+    let manifest_path = Path::new(SETUP_MANIFEST_PATH);
+    let previous_manifest = SetupManifest::read(manifest_path)?;
+    // Without a manifest to diff against, sweep the harness directories for
+    // entries the bundle does not have.
+    let sweep_stale_entries = previous_manifest.is_none();
+    let manage_gitignore = !selection.no_gitignore
+        && read_config_value(Path::new(NEXUS_CONFIG_PATH))
+            .ok()
+            .and_then(|config| config.get("gitignore")?.as_bool())
+            != Some(false);
+    setup_fs.link_mode = selection.link_mode.unwrap_or_else(|| {
+        previous_manifest
+            .as_ref()
+            .map_or_else(LinkMode::platform_default, SetupManifest::link_mode)
+    });
+    setup_fs.manifest.link_mode = Some(setup_fs.link_mode);
+    setup_fs.manifest.template_vars =
+        resolve_template_vars(previous_manifest.as_ref(), &selection.template_vars);
+
+    // Bring layouts from older versions up to date before extracting
+    run_migrations(format, setup_fs, previous_manifest.as_ref())?;
+
+    // Extract bundled .nexus directory
+    extract_nexus_directory(format, setup_fs, profile)?;
+
+    // Remove context seed files older versions extracted, then copy the
+    // requested templates, which are the project's own from then on.
+    if sweep_stale_entries {
+        let (_seeded_context_files_removed, _seeded_context_dirs_removed) =
+            prune_embedded_context_seed(format, setup_fs)?;
+    }
+    copy_context_templates(format, setup_fs, &selection.templates)?;
+
+    // Remove files earlier runs extracted that the bundle no longer has
+    if let Some(previous) = &previous_manifest {
+        prune_dropped_files(format, setup_fs, previous)?;
+    }
+
+    // Persist selected harness configuration
+    write_nexus_config(format, setup_fs, selection, &profile.name)?;
+
+    link_harness(format, setup_fs, &selection.harness, sweep_stale_entries)?;
+
+    // Link commands into each editor integration
+    for &target in targets {
+        if sweep_stale_entries {
+            prune_stale_target_links(format, setup_fs, target)?;
+        }
+        let (_symlinks_created, _symlinks_replaced) =
+            create_command_symlinks(format, setup_fs, target)?;
+    }
+
+    // Remove links earlier runs made that this run did not recreate
+    if let Some(previous) = &previous_manifest {
+        prune_dropped_links(format, setup_fs, previous)?;
+    }
+
+    // Remove legacy .nexus/rules directory if present
+    remove_legacy_rules_directory(format, setup_fs)?;
+
+    if manage_gitignore {
+        update_gitignore(format, setup_fs)?;
+    }
+
+    // Record what this run installed for the next setup and uninstall
+    let manifest = setup_fs.manifest.to_json()?;
+    setup_fs
+        .write_file(manifest_path, manifest.as_bytes())
+        .map(|_| ())
+}
+
+/// Apply the layout migrations the project still needs, in order, and record
+/// every migration as done.
+fn run_migrations(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    previous: Option<&SetupManifest>,
+) -> Result<()> {
+    let applied = previous
+        .map(|manifest| manifest.migrations.clone())
+        .unwrap_or_default();
+    let layout_version = previous
+        .map(|manifest| manifest.version.clone())
+        .or_else(|| {
+            read_config_value(Path::new(NEXUS_CONFIG_PATH))
+                .ok()?
+                .get("version")?
+                .as_str()
+                .map(str::to_string)
+        });
+
+    for migration in pending_migrations(&applied, layout_version.as_deref()) {
+        let actions = (migration.plan)()
+            .with_context(|| format!("Failed to plan migration '{}'.", migration.id))?;
+        let mut changed = false;
+        for action in actions {
+            changed |= apply_migration_action(format, setup_fs, action)?;
+        }
+        if !changed {
+            continue;
+        }
+        setup_fs.plan.migrations.push(migration.id.to_string());
+        if setup_fs.reports(format) {
+            print_success(&format!(
+                "Applied migration {}: {}",
+                migration.id, migration.description
+            ));
+        }
+    }
+    setup_fs.manifest.migrations = MIGRATIONS
+        .iter()
+        .map(|migration| migration.id.to_string())
+        .collect();
+    Ok(())
+}
+
+/// Apply one migration action, returning whether it changed anything.
+fn apply_migration_action(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    action: MigrationAction,
+) -> Result<bool> {
+    match action {
+        MigrationAction::Move { from, to } => {
+            if setup_fs.exists(&to) {
+                if setup_fs.reports(format) {
+                    print_warning(&format!(
+                        "Kept {}: {} already exists. Merge them by hand.",
+                        from.display(),
+                        to.display()
+                    ));
+                }
+                return Ok(false);
+            }
+            if let Some(parent) = to.parent() {
+                if !setup_fs.exists(parent) {
+                    setup_fs.create_dir_all(parent)?;
+                }
+            }
+            setup_fs.rename(&from, &to)?;
+        }
+        MigrationAction::RemoveEmptyDir(dir) => {
+            if !setup_fs.exists(&dir) || !setup_fs.entries(&dir)?.is_empty() {
+                return Ok(false);
+            }
+            setup_fs.remove(&dir)?;
+        }
+        MigrationAction::Write { path, contents } => {
+            setup_fs.write_untracked(&path, &contents)?;
+        }
+        MigrationAction::Relink { path, target } => {
+            setup_fs.relink(&target, &path)?;
+        }
+    }
+    Ok(true)
+}
+
+/// Keep the setup backups, journal, and harness link directories in the
+/// managed block of the repository's `.gitignore`. Outside a git repository
+/// without a `.gitignore`, nothing is written.
+fn update_gitignore(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<()> {
+    let path = Path::new(GITIGNORE_PATH);
+    if !path.exists() && !Path::new(".git").exists() {
+        return Ok(());
+    }
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read '{}'.", path.display()))
+        }
+    };
+    let link_dirs = setup_fs
+        .manifest
+        .links
+        .keys()
+        .filter_map(|link| Some(format!("{}/", Path::new(link).parent()?.to_str()?)))
+        .collect::<BTreeSet<_>>();
+    let entries = [
+        format!("{SETUP_BACKUP_ROOT}/"),
+        SETUP_JOURNAL_PATH.to_string(),
+    ]
+    .into_iter()
+    .chain(link_dirs)
+    .collect::<Vec<_>>();
+    let updated = render_gitignore(&existing, &entries);
+    if updated == existing {
+        return Ok(());
+    }
+    setup_fs.write_untracked(path, updated.as_bytes())?;
+    if setup_fs.reports(format) {
+        print_success(&format!(
+            "Updated the managed block in {} ({} entries)",
+            GITIGNORE_PATH,
+            entries.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Template variables for a run: `overrides`, else the values the last setup
+/// recorded, else the defaults.
+fn resolve_template_vars(
+    previous: Option<&SetupManifest>,
+    overrides: &TemplateVars,
+) -> TemplateVars {
+    let project_dir = std::env::current_dir().unwrap_or_default();
+    let mut vars = default_template_vars(&project_dir, unix_now_seconds());
+    if let Some(previous) = previous {
+        vars.extend(previous.template_vars.clone());
+    }
+    vars.extend(overrides.clone());
+    vars
+}
+
+/// Link the extracted tools, skills, and rules into `harness`'s directory.
+fn link_harness(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    harness: &str,
+    sweep_stale_entries: bool,
+) -> Result<()> {
+    let is_opencode_harness = harness.eq_ignore_ascii_case("opencode");
+
+    if is_opencode_harness {
+        // Remove stale command files that no longer exist in embedded assets
+        if sweep_stale_entries {
+            let (_nexus_removed, _opencode_removed) = prune_stale_command_files(format, setup_fs)?;
+        }
+
+        // Remove stale tool files that no longer exist in embedded assets
+        if sweep_stale_entries {
+            let (_nexus_tools_removed, _opencode_tools_removed) =
+                prune_stale_tool_files(format, setup_fs)?;
+        }
+
+        // Create symlinks in .opencode/tools/ for all nexus tools
+        let (_tool_symlinks_created, _tool_symlinks_replaced) =
+            create_tool_symlinks(format, setup_fs)?;
+
+        // Remove stale skill entries in .opencode/skills
+        if sweep_stale_entries {
+            let (_stale_skill_entries_removed, _missing_embedded_skills) =
+                prune_stale_skill_entries(format, setup_fs)?;
+        }
+
+        // Create symlinks in .opencode/skills/ for all nexus skills
+        let (_skill_symlinks_created, _skill_symlinks_replaced) =
+            create_skill_symlinks(format, setup_fs)?;
+
+        // Remove stale rule entries in .opencode/rules
+        if sweep_stale_entries {
+            let (_stale_rule_entries_removed, _missing_embedded_rules) =
+                prune_stale_rule_entries(format, setup_fs)?;
+        }
+
+        // Create symlinks in .opencode/rules/ for all nexus rules
+        let (_rule_symlinks_created, _rule_symlinks_replaced) =
+            create_rule_symlinks(format, setup_fs)?;
+    } else if setup_fs.reports(format) {
+        print_info(&format!(
+            "Harness '{}' selected: skipped .opencode tool/skill/rule linkage",
+            harness
+        ));
+    }
+
+    Ok(())
+}
+
+/// Changes `setup --dry-run` found, grouped by kind.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SetupPlan {
+    pub harness: String,
+    pub profile: String,
+    pub create: Vec<String>,
+    pub replace: Vec<String>,
+    pub prune: Vec<String>,
+    pub link: Vec<SetupLink>,
+    /// Layout migrations that would run.
+    pub migrations: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SetupLink {
+    pub path: String,
+    pub target: String,
+}
+
+impl SetupPlan {
+    fn is_empty(&self) -> bool {
+        self.create.is_empty()
+            && self.replace.is_empty()
+            && self.prune.is_empty()
+            && self.link.is_empty()
+            && self.migrations.is_empty()
+    }
+}
+
+fn print_setup_plan(format: OutputFormat, plan: &SetupPlan) -> Result<()> {
+    if format.is_structured() {
+        return print_json(&serde_json::json!({ "status": "dry_run", "plan": plan }));
+    }
+    if plan.is_empty() {
+        print_info(&format!(
+            "Dry run (harness: {}, profile: {}): setup would change nothing.",
+            plan.harness, plan.profile
+        ));
+        return Ok(());
+    }
+    print_info(&format!(
+        "Dry run (harness: {}, profile: {}): setup would make these changes. Nothing was written.",
+        plan.harness, plan.profile
+    ));
+    for (heading, paths) in [
+        ("Migrate", &plan.migrations),
+        ("Create", &plan.create),
+        ("Replace", &plan.replace),
+        ("Prune", &plan.prune),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+        println!("{} ({}):", heading, paths.len());
+        for path in paths {
+            println!("  {}", path);
+        }
+    }
+    if !plan.link.is_empty() {
+        println!("Link ({}):", plan.link.len());
+        for link in &plan.link {
+            println!("  {} -> {}", link.path, link.target);
+        }
+    }
+    Ok(())
+}
+
+/// Whether setup backs up `path` before overwriting it: `.nexus` files other
+/// than the manifest.
+fn is_backed_up(path: &Path) -> bool {
+    path.starts_with(NEXUS_ROOT) && path != Path::new(SETUP_MANIFEST_PATH)
+}
+
+/// Filesystem access for the setup steps.
+///
+/// In dry-run mode mutations are only recorded, and the read helpers overlay
+/// the recorded writes and removals on the disk so each step sees the tree
+/// the previous steps would have left.
+struct SetupFs {
+    dry_run: bool,
+    written: BTreeSet<PathBuf>,
+    removed: BTreeSet<PathBuf>,
+    plan: SetupPlan,
+    backup_dir: PathBuf,
+    /// Files copied into `backup_dir`, as (original, backup) paths.
+    backups: Vec<(PathBuf, PathBuf)>,
+    /// Paths a real run has written, so only pre-run content is backed up.
+    touched: HashSet<PathBuf>,
+    /// Existing `.nexus` assets a dry run would change, for the wizard.
+    overwrites: Vec<String>,
+    link_mode: LinkMode,
+    /// The bundle being installed.
+    assets: &'static Dir<'static>,
+    manifest: SetupManifest,
+    /// Print an NDJSON event for each change a real run makes.
+    events: bool,
+    counts: SetupCounts,
+}
+
+/// Changes a real setup run made.
+#[derive(Debug, Default, Serialize)]
+struct SetupCounts {
+    written: usize,
+    replaced: usize,
+    pruned: usize,
+    moved: usize,
+    linked: usize,
+}
+
+impl SetupFs {
+    fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            written: BTreeSet::new(),
+            removed: BTreeSet::new(),
+            plan: SetupPlan::default(),
+            backup_dir: unique_backup_dir(Path::new(SETUP_BACKUP_ROOT), unix_now_seconds()),
+            backups: Vec::new(),
+            touched: HashSet::new(),
+            overwrites: Vec::new(),
+            link_mode: LinkMode::platform_default(),
+            assets: &NEXUS_ASSETS,
+            manifest: SetupManifest::new(env!("CARGO_PKG_VERSION")),
+            events: false,
+            counts: SetupCounts::default(),
+        }
+    }
+
+    /// Print `event` as an NDJSON line when `--format json` streams a real
+    /// run: one per file written, pruned, moved, or linked, between the
+    /// `starting` and `completed` lines. `completed` carries the
+    /// [`SetupCounts`] totals.
+    fn emit(&self, event: Value) -> Result<()> {
+        if self.events {
+            print_json(&event)?;
+        }
+        Ok(())
+    }
+
+    /// Install from `bundle` instead of the compiled-in assets.
+    fn use_bundle(&mut self, bundle: SelectedBundle) {
+        if let Some((bundle, record)) = bundle {
+            self.assets = bundle.assets;
+            self.manifest.bundle = Some(record);
+        }
+    }
+
+    /// Whether step summaries are printed; a dry run prints only the plan.
+    fn reports(&self, format: OutputFormat) -> bool {
+        !self.dry_run && !format.is_structured()
+    }
+
+    fn into_plan(self, harness: &str, profile: &str) -> SetupPlan {
+        SetupPlan {
+            harness: harness.to_string(),
+            profile: profile.to_string(),
+            ..self.plan
+        }
+    }
+
+    fn written_at_or_under(&self, path: &Path) -> bool {
+        self.written.iter().any(|written| written.starts_with(path))
+    }
+
+    fn removed(&self, path: &Path) -> bool {
+        !self.written.contains(path) && self.removed.iter().any(|removed| path.starts_with(removed))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.written_at_or_under(path) || (!self.removed(path) && path_exists_or_symlink(path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.written.contains(path) || (!self.removed(path) && path.is_file())
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.written
+            .iter()
+            .any(|written| written != path && written.starts_with(path))
+            || (!self.removed(path) && path.is_dir())
+    }
+
+    /// Like `is_dir`, without following a symlink at `path`.
+    fn is_real_dir(&self, path: &Path) -> bool {
+        if self.written.contains(path) {
+            return false;
+        }
+        self.is_dir(path)
+            && fs::symlink_metadata(path).map_or(true, |metadata| metadata.file_type().is_dir())
+    }
+
+    fn is_symlink(&self, path: &Path) -> bool {
+        !self.written.contains(path)
+            && !self.removed(path)
+            && fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+    }
+
+    /// Paths of the entries directly inside `dir`, sorted.
+    fn entries(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = BTreeSet::new();
+        if !self.removed(dir) {
+            match fs::read_dir(dir) {
+                Ok(read_dir) => {
+                    for entry in read_dir {
+                        let path = entry?.path();
+                        if !self.removed(&path) {
+                            entries.insert(path);
+                        }
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(err).with_context(|| format!("Failed to read '{}'.", dir.display()))
+                }
+            }
+        }
+        for written in &self.written {
+            if let Some(first) = written
+                .strip_prefix(dir)
+                .ok()
+                .and_then(|rest| rest.components().next())
+            {
+                entries.insert(dir.join(first));
+            }
+        }
+        Ok(entries.into_iter().collect())
+    }
+
+    fn create_dir_all(&mut self, path: &Path) -> Result<()> {
+        if !self.dry_run {
+            fs::create_dir_all(path)
+                .with_context(|| format!("Failed to create '{}'.", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Write `contents` to `path`, returning whether it replaced an entry.
+    ///
+    /// A dry run reports each path once, by its state before setup.
+    fn write_file(&mut self, path: &Path, contents: &[u8]) -> Result<bool> {
+        self.manifest.record_file(path, contents);
+        self.write_untracked(path, contents)
+    }
+
+    /// Write a file the manifest does not track, such as a context template.
+    fn write_untracked(&mut self, path: &Path, contents: &[u8]) -> Result<bool> {
+        let replaced = self.exists(path);
+        if self.dry_run {
+            if self.written.insert(path.to_path_buf()) {
+                let display = path.display().to_string();
+                if replaced {
+                    // The config is merged rather than overwritten.
+                    if is_backed_up(path)
+                        && path != Path::new(NEXUS_CONFIG_PATH)
+                        && fs::read(path).is_ok_and(|current| current != contents)
+                    {
+                        self.overwrites.push(display.clone());
+                    }
+                    self.plan.replace.push(display);
+                } else {
+                    self.plan.create.push(display);
+                }
+            }
+        } else {
+            if replaced && self.touched.insert(path.to_path_buf()) {
+                self.back_up(path, contents)?;
+            }
+            self.touched.insert(path.to_path_buf());
+            fs::write(path, contents)
+                .with_context(|| format!("Failed to write '{}'.", path.display()))?;
+            if replaced {
+                self.counts.replaced += 1;
+            } else {
+                self.counts.written += 1;
+            }
+            self.emit(serde_json::json!({
+                "event": "file_written",
+                "path": path.display().to_string(),
+                "replaced": replaced,
+            }))?;
+        }
+        Ok(replaced)
+    }
+
+    /// Copy the `.nexus` file at `path` into this run's backup directory if
+    /// writing `contents` would change it.
+    fn back_up(&mut self, path: &Path, contents: &[u8]) -> Result<()> {
+        if !is_backed_up(path) {
+            return Ok(());
+        }
+        let Ok(relative) = path.strip_prefix(NEXUS_ROOT) else {
+            return Ok(());
+        };
+        let backup_path = self.backup_dir.join(relative);
+        if !path.is_file() || fs::read(path).is_ok_and(|current| current == contents) {
+            return Ok(());
+        }
+        if let Some(parent) = backup_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+        }
+        fs::copy(path, &backup_path).with_context(|| {
+            format!(
+                "Failed to back up '{}' to '{}'.",
+                path.display(),
+                backup_path.display()
+            )
+        })?;
+        self.backups.push((path.to_path_buf(), backup_path));
+        Ok(())
+    }
+
+    /// Drop backups of files that ended the run with their original content,
+    /// such as `config.json`, which is extracted and then rewritten.
+    fn discard_unchanged_backups(&mut self) -> Result<()> {
+        let mut kept = Vec::new();
+        for (path, backup_path) in self.backups.drain(..) {
+            if fs::read(&path).ok() == fs::read(&backup_path).ok() {
+                fs::remove_file(&backup_path)
+                    .with_context(|| format!("Failed to remove '{}'.", backup_path.display()))?;
+            } else {
+                kept.push((path, backup_path));
+            }
+        }
+        self.backups = kept;
+        if self.backups.is_empty() && self.backup_dir.is_dir() {
+            fs::remove_dir_all(&self.backup_dir)
+                .with_context(|| format!("Failed to remove '{}'.", self.backup_dir.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Remove a file, directory, or symlink at `path`.
+    fn remove(&mut self, path: &Path) -> Result<()> {
+        if self.dry_run {
+            self.plan.prune.push(path.display().to_string());
+            self.written.retain(|written| !written.starts_with(path));
+            self.removed.insert(path.to_path_buf());
+        } else {
+            remove_path(path).with_context(|| format!("Failed to remove '{}'.", path.display()))?;
+            self.counts.pruned += 1;
+            self.emit(serde_json::json!({
+                "event": "pruned",
+                "path": path.display().to_string(),
+            }))?;
+        }
+        Ok(())
+    }
+
+    /// Point `path` at `link_target`, returning whether it replaced an entry.
+    fn link(&mut self, link_target: &str, source: &Path, path: &Path) -> Result<bool> {
+        let replaced = self.exists(path);
+        self.manifest.record_link(path, link_target);
+        if self.dry_run {
+            self.plan.link.push(SetupLink {
+                path: path.display().to_string(),
+                target: link_target.to_string(),
+            });
+            self.written.insert(path.to_path_buf());
+        } else {
+            if replaced {
+                remove_path(path)?;
+            }
+            link_with_mode(self.link_mode, link_target, source, path)?;
+            self.emit_link(path, link_target, self.link_mode, replaced)?;
+        }
+        Ok(replaced)
+    }
+
+    fn emit_link(
+        &mut self,
+        path: &Path,
+        link_target: &str,
+        mode: LinkMode,
+        replaced: bool,
+    ) -> Result<()> {
+        self.counts.linked += 1;
+        self.emit(serde_json::json!({
+            "event": "linked",
+            "path": path.display().to_string(),
+            "target": link_target,
+            "mode": mode,
+            "replaced": replaced,
+        }))
+    }
+
+    /// Move the file or directory at `from` to `to`.
+    fn rename(&mut self, from: &Path, to: &Path) -> Result<()> {
+        if self.dry_run {
+            self.plan.prune.push(from.display().to_string());
+            self.plan.create.push(to.display().to_string());
+            self.removed.insert(from.to_path_buf());
+            self.written.insert(to.to_path_buf());
+        } else {
+            fs::rename(from, to).with_context(|| {
+                format!("Failed to move '{}' to '{}'.", from.display(), to.display())
+            })?;
+            self.counts.moved += 1;
+            self.emit(serde_json::json!({
+                "event": "moved",
+                "from": from.display().to_string(),
+                "to": to.display().to_string(),
+            }))?;
+        }
+        Ok(())
+    }
+
+    /// Point the symlink at `path`, which setup does not track, to
+    /// `link_target`.
+    fn relink(&mut self, link_target: &str, path: &Path) -> Result<()> {
+        if self.dry_run {
+            self.plan.link.push(SetupLink {
+                path: path.display().to_string(),
+                target: link_target.to_string(),
+            });
+            self.written.insert(path.to_path_buf());
+        } else {
+            let source = SetupManifest::link_source(&path.to_string_lossy(), link_target);
+            remove_path(path).with_context(|| format!("Failed to remove '{}'.", path.display()))?;
+            link_with_mode(LinkMode::Symlink, link_target, &source, path)?;
+            self.emit_link(path, link_target, LinkMode::Symlink, true)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run `setup rollback`: restore the files saved by the most recent setup and
+/// delete that backup, so repeated rollbacks walk back through older runs.
+pub fn run_setup_rollback(format: OutputFormat) -> Result<()> {
+    let backup_root = Path::new(SETUP_BACKUP_ROOT);
+    let Some(backup_dir) = latest_backup_dir(backup_root)? else {
+        bail!(CliError::not_found(format!(
+            "No setup backups found in {}. Remediation: backups are created when `opennexus setup` overwrites locally modified files.",
+            backup_root.display()
+        )));
+    };
+
+    let mut restored = Vec::<String>::new();
+    let mut stack = vec![backup_dir.clone()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read backup '{}'.", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let relative = path.strip_prefix(&backup_dir).unwrap_or(&path);
+            let target = Path::new(NEXUS_ROOT).join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if path_exists_or_symlink(&target) {
+                remove_path(&target)?;
+            }
+            fs::copy(&path, &target)
+                .with_context(|| format!("Failed to restore '{}'.", target.display()))?;
+            restored.push(target.display().to_string());
+        }
+    }
+    restored.sort();
+    fs::remove_dir_all(&backup_dir)
+        .with_context(|| format!("Failed to remove backup '{}'.", backup_dir.display()))?;
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "restored",
+            "backup": backup_dir.display().to_string(),
+            "files": restored,
+        }))?;
+    } else {
+        for path in &restored {
+            print_info(&format!("Restored {}", path));
+        }
+        print_success(&format!(
+            "Restored {} file(s) from {}",
+            restored.len(),
+            backup_dir.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Run `setup repair`: recreate the harness entries recorded in the setup
+/// manifest that are missing or, for copies and hard links, no longer match
+/// their `.nexus` source. Symlinks follow their source and only need it to
+/// exist.
+pub fn run_setup_repair(format: OutputFormat) -> Result<()> {
+    let manifest_path = Path::new(SETUP_MANIFEST_PATH);
+    let Some(manifest) = SetupManifest::read(manifest_path)? else {
+        bail!(CliError::not_found(format!(
+            "No setup manifest at {}. Remediation: run `opennexus setup` first.",
+            manifest_path.display()
+        )));
+    };
+    let mode = manifest.link_mode();
+
+    let mut repaired = Vec::new();
+    let mut missing_sources = Vec::new();
+    for (path, target) in &manifest.links {
+        let link = Path::new(path);
+        let source = SetupManifest::link_source(path, target);
+        if !source.exists() {
+            missing_sources.push(path.as_str());
+            continue;
+        }
+        if path_exists_or_symlink(link) {
+            if same_contents(link, &source) {
+                continue;
+            }
+            remove_path(link).with_context(|| format!("Failed to remove '{}'.", path))?;
+        } else if let Some(parent) = link.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+        }
+        link_with_mode(mode, target, &source, link)?;
+        repaired.push(path.as_str());
+    }
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "repaired",
+            "link_mode": mode,
+            "repaired": repaired,
+            "missing_sources": missing_sources,
+        }))?;
+    } else {
+        if repaired.is_empty() {
+            print_success(&format!(
+                "All {} harness link(s) match their sources",
+                manifest.links.len()
+            ));
+        } else {
+            print_success(&format!(
+                "Re-synced {} harness link(s): {}",
+                repaired.len(),
+                repaired.join(", ")
+            ));
+        }
+        if !missing_sources.is_empty() {
+            print_warning(&format!(
+                "Skipped {} link(s) whose .nexus source is gone; run `opennexus setup` to restore it: {}",
+                missing_sources.len(),
+                missing_sources.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Run `setup verify`: compare `.nexus` on disk with this CLI's bundle and the
+/// setup manifest, failing when anything drifted so CI can gate on it.
+pub fn run_setup_verify(format: OutputFormat) -> Result<()> {
+    if !Path::new(NEXUS_ROOT).is_dir() {
+        bail!(CliError::not_found(
+            "No .nexus directory here. Remediation: run `opennexus setup` in the repository root."
+        ));
+    }
+    let manifest = SetupManifest::read(Path::new(SETUP_MANIFEST_PATH))?;
+    let config = read_config_value(Path::new(NEXUS_CONFIG_PATH)).ok();
+    let profile = SetupProfile::resolve(None, config.as_ref())?;
+    let assets = match manifest
+        .as_ref()
+        .and_then(|manifest| manifest.bundle.as_ref())
+    {
+        Some(record) => resolve_bundle(&record.source, Some(&record.sha256))?.assets,
+        None => &NEXUS_ASSETS,
+    };
+    let mut bundled = SetupManifest::default();
+    let vars = resolve_template_vars(manifest.as_ref(), &TemplateVars::new());
+    record_bundled_files(assets, Path::new(NEXUS_ROOT), &profile, &vars, &mut bundled);
+    let cli_version = env!("CARGO_PKG_VERSION");
+
+    let mut issues = Vec::new();
+    match &manifest {
+        None => issues.push(DriftIssue {
+            path: SETUP_MANIFEST_PATH.to_string(),
+            kind: DriftKind::NoManifest,
+        }),
+        Some(manifest) if manifest.version != cli_version => issues.push(DriftIssue {
+            path: SETUP_MANIFEST_PATH.to_string(),
+            kind: DriftKind::VersionMismatch,
+        }),
+        Some(_) => {}
+    }
+    let recorded = manifest.clone().unwrap_or_default();
+    // The project config is the user's to edit, not a bundled asset.
+    let paths = bundled
+        .files
+        .keys()
+        .chain(recorded.files.keys())
+        .filter(|path| *path != NEXUS_CONFIG_PATH)
+        .collect::<BTreeSet<_>>();
+    for path in paths {
+        let on_disk = fs::read(path).ok().map(|contents| sha256_hex(&contents));
+        if let Some(kind) = file_drift(
+            bundled.files.get(path).map(String::as_str),
+            recorded.files.get(path).map(String::as_str),
+            on_disk.as_deref(),
+        ) {
+            issues.push(DriftIssue {
+                path: path.clone(),
+                kind,
+            });
+        }
+    }
+    for (path, target) in &recorded.links {
+        // `exists` follows the link, so a dangling link counts as broken.
+        let kind = if !Path::new(path).exists() {
+            DriftKind::BrokenLink
+        } else if !same_contents(Path::new(path), &SetupManifest::link_source(path, target)) {
+            DriftKind::StaleCopy
+        } else {
+            continue;
+        };
+        issues.push(DriftIssue {
+            path: path.clone(),
+            kind,
+        });
+    }
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": if issues.is_empty() { "ok" } else { "drift" },
+            "cli_version": cli_version,
+            "manifest_version": manifest.as_ref().map(|manifest| &manifest.version),
+            "issues": issues,
+        }))?;
+    } else if issues.is_empty() {
+        print_success(&format!(
+            "{} files and {} links match this CLI ({})",
+            bundled.files.len(),
+            recorded.links.len(),
+            cli_version
+        ));
+    } else {
+        for issue in &issues {
+            print_warning(&format!("{}: {}", drift_label(issue.kind), issue.path));
+        }
+    }
+
+    if !issues.is_empty() {
+        bail!(
+            "setup verify found {} issue(s). Remediation: run `opennexus setup` to restore the bundled assets; edited files are backed up first.",
+            issues.len()
+        );
+    }
+    Ok(())
+}
+
+fn drift_label(kind: DriftKind) -> &'static str {
+    match kind {
+        DriftKind::Missing => "missing",
+        DriftKind::Modified => "modified",
+        DriftKind::Outdated => "outdated",
+        DriftKind::BrokenLink => "broken link",
+        DriftKind::StaleCopy => "stale copy",
+        DriftKind::VersionMismatch => "set up by another CLI version",
+        DriftKind::NoManifest => "no setup manifest",
+    }
+}
+
+/// Record every file setup would extract from `dir` into `target_path`.
+fn record_bundled_files(
+    dir: &Dir,
+    target_path: &Path,
+    profile: &SetupProfile,
+    vars: &TemplateVars,
+    out: &mut SetupManifest,
+) {
+    for file in dir.files() {
+        if is_extracted(file.path(), profile) {
+            let file_path = target_path.join(file.path().file_name().unwrap_or_default());
+            out.record_file(&file_path, &render(file.contents(), vars));
+        }
+    }
+    for subdir in dir.dirs() {
+        let subdir_name = subdir.path().file_name().unwrap_or_default();
+        record_bundled_files(subdir, &target_path.join(subdir_name), profile, vars, out);
+    }
+}
+
+/// Whether setup extracts the bundled file at `relative` (to `.nexus/`).
+fn is_extracted(relative: &Path, profile: &SetupProfile) -> bool {
+    if relative == Path::new(BUNDLED_CONFIG_FILE) {
+        return false;
+    }
+    let mut components = relative.components();
+    let top = components.next();
+    if components.next().is_some()
+        && top.is_some_and(|top| {
+            UNEXTRACTED_ROOT_DIRS
+                .iter()
+                .any(|name| top.as_os_str() == *name)
+        })
+    {
+        return false;
+    }
+    profile.includes(&relative.to_string_lossy())
+}
+
+/// Remove the files and links recorded in the setup manifest, then the
+/// manifest itself. Files edited since setup are kept and listed.
+pub fn remove_installed_assets(format: OutputFormat) -> Result<()> {
+    let manifest_path = Path::new(SETUP_MANIFEST_PATH);
+    let Some(manifest) = SetupManifest::read(manifest_path)? else {
+        bail!(CliError::not_found(format!(
+            "No setup manifest at {}. Remediation: run from the project root, or remove .nexus and the harness links by hand.",
+            manifest_path.display()
+        )));
+    };
+
+    let mut links_removed = 0;
+    for path in manifest.links.keys().map(Path::new) {
+        if fs::symlink_metadata(path).is_ok() {
+            remove_path(path)?;
+            links_removed += 1;
+        }
+    }
+    let mut files_removed = 0;
+    let mut kept = Vec::new();
+    for path in manifest.files.keys() {
+        if !Path::new(path).exists() {
+            continue;
+        }
+        if manifest.is_unmodified(path) {
+            fs::remove_file(path).with_context(|| format!("Failed to remove '{}'.", path))?;
+            remove_empty_parents(Path::new(path));
+            files_removed += 1;
+        } else {
+            kept.push(path.as_str());
+        }
+    }
+    fs::remove_file(manifest_path)
+        .with_context(|| format!("Failed to remove '{}'.", manifest_path.display()))?;
+    remove_gitignore_block()?;
+
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "assets_removed",
+            "files_removed": files_removed,
+            "links_removed": links_removed,
+            "kept": kept,
+        }))?;
+    } else {
+        print_success(&format!(
+            "Removed {} setup file(s) and {} harness link(s)",
+            files_removed, links_removed
+        ));
+        if !kept.is_empty() {
+            print_info(&format!(
+                "Kept {} locally modified file(s): {}",
+                kept.len(),
+                kept.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
 
-/// Run the setup command.
-///
-/// This extracts the bundled .nexus directory to the current working directory.
-/// Existing files are overwritten to keep assets up to date.
-pub fn run_setup(format: OutputFormat, harness: &str) -> Result<()> {
-    if format == OutputFormat::Json {
-        println!(r#"{{"status": "starting"}}"#);
+/// Drop setup's managed block from `.gitignore`, removing the file if nothing
+/// else is left in it.
+pub fn remove_gitignore_block() -> Result<()> {
+    let path = Path::new(GITIGNORE_PATH);
+    let Ok(existing) = fs::read_to_string(path) else {
+        return Ok(());
+    };
+    let updated = render_gitignore(&existing, &[]);
+    if updated == existing {
+        return Ok(());
+    }
+    if updated.is_empty() {
+        fs::remove_file(path)
     } else {
-        print_info(&format!("Setting up OpenNexus (harness: {})...", harness));
+        fs::write(path, updated)
     }
+    .with_context(|| format!("Failed to update '{}'.", path.display()))
+}
 
-    // Extract bundled .nexus directory
-    extract_nexus_directory(format)?;
-
-    // Remove bundled context seed files while keeping the context directory.
-    let (_seeded_context_files_removed, _seeded_context_dirs_removed) =
-        prune_embedded_context_seed(format)?;
-
-    // Persist selected harness configuration
-    write_nexus_config(format, harness)?;
-
-    let is_opencode_harness = harness.eq_ignore_ascii_case("opencode");
-
-    if is_opencode_harness {
-        // Remove stale command files that no longer exist in embedded assets
-        let (_nexus_removed, _opencode_removed) = prune_stale_command_files(format)?;
+/// Remove the now-empty directories above `path`, stopping at `.nexus`.
+fn remove_empty_parents(path: &Path) {
+    let root = Path::new(NEXUS_ROOT);
+    for dir in path.ancestors().skip(1) {
+        if dir == root || !dir.starts_with(root) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
 
-        // Create symlinks in .opencode/command/ for all nexus commands
-        let (_symlinks_created, _symlinks_skipped) = create_command_symlinks(format)?;
+/// Backup directory name for a run started at unix `seconds`; sorts by time
+/// and avoids `:` so it is a valid path everywhere.
+fn backup_name(seconds: i64) -> String {
+    format_unix_rfc3339(seconds).replace(':', "")
+}
 
-        // Remove stale tool files that no longer exist in embedded assets
-        let (_nexus_tools_removed, _opencode_tools_removed) = prune_stale_tool_files(format)?;
+/// This run's backup directory under `root`, suffixed when an earlier run in
+/// the same second already used the name.
+fn unique_backup_dir(root: &Path, seconds: i64) -> PathBuf {
+    let name = backup_name(seconds);
+    let mut dir = root.join(&name);
+    let mut suffix = 1;
+    while dir.exists() {
+        dir = root.join(format!("{}-{}", name, suffix));
+        suffix += 1;
+    }
+    dir
+}
 
-        // Create symlinks in .opencode/tools/ for all nexus tools
-        let (_tool_symlinks_created, _tool_symlinks_replaced) = create_tool_symlinks(format)?;
+fn latest_backup_dir(backup_root: &Path) -> Result<Option<PathBuf>> {
+    if !backup_root.is_dir() {
+        return Ok(None);
+    }
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(backup_root)
+        .with_context(|| format!("Failed to read '{}'.", backup_root.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs.into_iter().max())
+}
 
-        // Remove stale skill entries in .opencode/skills
-        let (_stale_skill_entries_removed, _missing_embedded_skills) =
-            prune_stale_skill_entries(format)?;
+/// Whether setup can prompt: text output with a terminal on both ends.
+pub fn setup_is_interactive(format: OutputFormat) -> bool {
+    !format.is_structured() && std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
 
-        // Create symlinks in .opencode/skills/ for all nexus skills
-        let (_skill_symlinks_created, _skill_symlinks_replaced) = create_skill_symlinks(format)?;
+/// Run the interactive `setup` wizard: pick harness integrations and context
+/// templates, confirm overwriting changed assets, then install.
+pub fn run_setup_wizard(format: OutputFormat) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let integrations = INTEGRATION_TARGETS
+        .iter()
+        .map(|target| format!("{} ({})", target.root_dir(), target.name))
+        .collect::<Vec<_>>();
+    let defaults = INTEGRATION_TARGETS
+        .iter()
+        .map(|target| target.name == default_harness())
+        .collect::<Vec<_>>();
+    let Some(chosen) = MultiSelect::with_theme(&theme)
+        .with_prompt("Editor integrations to link (space to toggle)")
+        .items(&integrations)
+        .defaults(&defaults)
+        .interact_opt()
+        .context("Failed to read integration selection")?
+    else {
+        print_info("Setup cancelled; nothing was written.");
+        return Ok(());
+    };
+    if chosen.is_empty() {
+        bail!("No editor integration selected. Remediation: select at least one, or run `opennexus setup --harness <name>`.");
+    }
+    let templates = MultiSelect::with_theme(&theme)
+        .with_prompt("Context project templates to copy into .nexus/context (space to toggle)")
+        .items(&CONTEXT_TEMPLATES)
+        .interact_opt()
+        .context("Failed to read template selection")?
+        .unwrap_or_default();
+    let previous = SetupManifest::read(Path::new(SETUP_MANIFEST_PATH))?;
+    let defaults = resolve_template_vars(previous.as_ref(), &TemplateVars::new());
+    let mut template_vars = TemplateVars::new();
+    for (name, prompt) in [
+        ("project_name", "Project name for asset templates"),
+        ("prefix", "Context ID prefix for asset templates"),
+    ] {
+        let value: String = Input::with_theme(&theme)
+            .with_prompt(prompt)
+            .default(defaults[name].clone())
+            .interact_text()
+            .context("Failed to read template variable")?;
+        template_vars.insert(name.to_string(), value);
+    }
 
-        // Remove stale rule entries in .opencode/rules
-        let (_stale_rule_entries_removed, _missing_embedded_rules) =
-            prune_stale_rule_entries(format)?;
+    let integrations = chosen
+        .into_iter()
+        .map(|index| INTEGRATION_TARGETS[index].name.to_string())
+        .collect::<Vec<_>>();
+    // The default harness if it was picked, so its tools and rules are linked.
+    let harness = integrations
+        .iter()
+        .find(|name| *name == default_harness())
+        .unwrap_or(&integrations[0])
+        .clone();
+    let selection = SetupSelection {
+        harness,
+        integrations,
+        profile: None,
+        link_mode: None,
+        template_vars,
+        templates: templates
+            .into_iter()
+            .map(|index| CONTEXT_TEMPLATES[index].to_string())
+            .collect(),
+        from: None,
+        sha256: None,
+        no_gitignore: false,
+        no_hooks: false,
+    };
 
-        // Create symlinks in .opencode/rules/ for all nexus rules
-        let (_rule_symlinks_created, _rule_symlinks_replaced) = create_rule_symlinks(format)?;
-    } else if harness.eq_ignore_ascii_case("claude") {
-        let (_created, _replaced) = create_claude_command_symlinks(format)?;
-    } else if format != OutputFormat::Json {
+    let (profile, targets, bundle) = resolve_setup_selection(&selection)?;
+    let mut planned = SetupFs::new(true);
+    planned.use_bundle(bundle);
+    install(format, &selection, &profile, &targets, &mut planned)?;
+    if !planned.overwrites.is_empty() {
         print_info(&format!(
-            "Harness '{}' selected: skipped .opencode command/skill/rule linkage",
-            harness
+            "Setup would overwrite {} changed file(s), backing them up to {}:",
+            planned.overwrites.len(),
+            SETUP_BACKUP_ROOT
         ));
+        for path in &planned.overwrites {
+            println!("  {}", path);
+        }
+        let confirmed = Confirm::with_theme(&theme)
+            .with_prompt("Overwrite them?")
+            .default(false)
+            .interact_opt()
+            .context("Failed to read overwrite confirmation")?
+            .unwrap_or(false);
+        if !confirmed {
+            print_info("Setup cancelled; nothing was written.");
+            return Ok(());
+        }
     }
 
-    // Remove legacy .nexus/rules directory if present
-    remove_legacy_rules_directory(format)?;
-
-    if format == OutputFormat::Json {
-        println!(r#"{{"status": "completed"}}"#);
-    } else {
-        println!();
-        print_success("OpenNexus setup complete!");
-    }
-
-    Ok(())
+    run_setup(format, &selection, false)
 }
 
 pub fn resolve_setup_harness(format: OutputFormat, harness: Option<String>) -> Result<String> {
@@ -98,10 +1536,7 @@ pub fn resolve_setup_harness(format: OutputFormat, harness: Option<String>) -> R
         return Ok(harness);
     }
 
-    if format == OutputFormat::Json
-        || !std::io::stdin().is_terminal()
-        || !std::io::stdout().is_terminal()
-    {
+    if !setup_is_interactive(format) {
         return Ok(default_harness().to_string());
     }
 
@@ -122,7 +1557,13 @@ fn default_harness() -> &'static str {
     SUPPORTED_HARNESSES[0]
 }
 
-fn write_nexus_config(format: OutputFormat, harness: &str) -> Result<()> {
+fn write_nexus_config(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    selection: &SetupSelection,
+    profile: &str,
+) -> Result<()> {
+    let harness = selection.harness.as_str();
     let config_path = Path::new(".nexus/config.json");
     let docs_sync_state_path = Path::new(".nexus/docs-sync-state.json");
 
@@ -143,6 +1584,19 @@ fn write_nexus_config(format: OutputFormat, harness: &str) -> Result<()> {
         .expect("config object should exist after reset");
 
     obj.insert("harness".to_string(), Value::String(harness.to_string()));
+    obj.insert(
+        "setup_profile".to_string(),
+        Value::String(profile.to_string()),
+    );
+    if selection.no_gitignore {
+        obj.insert("gitignore".to_string(), Value::Bool(false));
+    }
+    if !selection.integrations.is_empty() {
+        obj.insert(
+            "integrations".to_string(),
+            Value::from(selection.integrations.clone()),
+        );
+    }
     obj.insert(
         "version".to_string(),
         Value::String(env!("CARGO_PKG_VERSION").to_string()),
@@ -152,13 +1606,12 @@ fn write_nexus_config(format: OutputFormat, harness: &str) -> Result<()> {
     obj.insert("marketplace".to_string(), Value::Object(Map::new()));
 
     let serialized = serde_json::to_string_pretty(&config).context("Failed to serialize config")?;
-    fs::write(config_path, format!("{serialized}\n"))
-        .with_context(|| format!("Failed to write '{}'.", config_path.display()))?;
+    setup_fs.write_file(config_path, format!("{serialized}\n").as_bytes())?;
 
-    if docs_sync_state_path.exists() {
-        let _ = fs::remove_file(docs_sync_state_path);
+    if setup_fs.exists(docs_sync_state_path) {
+        let _ = setup_fs.remove(docs_sync_state_path);
     }
-    if format != OutputFormat::Json {
+    if setup_fs.reports(format) {
         print_success(&format!(
             "Configured harness '{}' in .nexus/config.json",
             harness
@@ -168,18 +1621,86 @@ fn write_nexus_config(format: OutputFormat, harness: &str) -> Result<()> {
     Ok(())
 }
 
+/// Remove files listed in the previous manifest that this run did not extract,
+/// keeping any that were edited since.
+fn prune_dropped_files(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    previous: &SetupManifest,
+) -> Result<usize> {
+    let mut removed = 0;
+    let mut kept = Vec::new();
+    for path in previous.dropped_files(&setup_fs.manifest) {
+        // Rewritten by `write_nexus_config` later in the run.
+        if path == NEXUS_CONFIG_PATH || !setup_fs.exists(Path::new(path)) {
+            continue;
+        }
+        if previous.is_unmodified(path) {
+            setup_fs.remove(Path::new(path))?;
+            removed += 1;
+        } else {
+            kept.push(path);
+        }
+    }
+
+    if setup_fs.reports(format) && removed > 0 {
+        print_success(&format!(
+            "Removed {} file(s) this version or profile no longer installs",
+            removed
+        ));
+    }
+    if setup_fs.reports(format) && !kept.is_empty() {
+        print_info(&format!(
+            "Kept {} locally modified file(s) no longer installed: {}",
+            kept.len(),
+            kept.join(", ")
+        ));
+    }
+    Ok(removed)
+}
+
+/// Remove links listed in the previous manifest that this run did not make.
+fn prune_dropped_links(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    previous: &SetupManifest,
+) -> Result<usize> {
+    let mut removed = 0;
+    for path in previous.dropped_links(&setup_fs.manifest) {
+        let path = Path::new(path);
+        // Copies and hard links are plain entries, so whatever is there is ours.
+        let is_link = if previous.link_mode() == LinkMode::Symlink {
+            setup_fs.is_symlink(path)
+        } else {
+            setup_fs.exists(path)
+        };
+        if is_link {
+            setup_fs.remove(path)?;
+            removed += 1;
+        }
+    }
+
+    if setup_fs.reports(format) && removed > 0 {
+        print_success(&format!("Removed {} stale harness link(s)", removed));
+    }
+    Ok(removed)
+}
+
 /// Remove stale command files from .nexus/ai_harness/commands and .opencode/command.
 ///
 /// Any command file not present in the embedded `.nexus/ai_harness/commands` assets is deleted.
-fn prune_stale_command_files(format: OutputFormat) -> Result<(usize, usize)> {
+fn prune_stale_command_files(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+) -> Result<(usize, usize)> {
     let nexus_commands_dir = Path::new(".nexus/ai_harness/commands");
     let opencode_command_dir = Path::new(".opencode/command");
 
-    if !nexus_commands_dir.exists() {
+    if !setup_fs.exists(nexus_commands_dir) {
         return Ok((0, 0));
     }
 
-    let Some(embedded_commands_dir) = NEXUS_ASSETS.get_dir("ai_harness/commands") else {
+    let Some(embedded_commands_dir) = setup_fs.assets.get_dir("ai_harness/commands") else {
         return Ok((0, 0));
     };
 
@@ -190,12 +1711,8 @@ fn prune_stale_command_files(format: OutputFormat) -> Result<(usize, usize)> {
         .collect();
 
     let mut nexus_removed = 0;
-    for entry in fs::read_dir(nexus_commands_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        let metadata = fs::symlink_metadata(&path)?;
-
-        if metadata.file_type().is_dir() {
+    for path in setup_fs.entries(nexus_commands_dir)? {
+        if setup_fs.is_real_dir(&path) {
             continue;
         }
 
@@ -215,19 +1732,15 @@ fn prune_stale_command_files(format: OutputFormat) -> Result<(usize, usize)> {
         }
 
         if !allowed_files.contains(&file_name) {
-            fs::remove_file(&path)?;
+            setup_fs.remove(&path)?;
             nexus_removed += 1;
         }
     }
 
     let mut opencode_removed = 0;
-    if opencode_command_dir.exists() {
-        for entry in fs::read_dir(opencode_command_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            let metadata = fs::symlink_metadata(&path)?;
-
-            if metadata.file_type().is_dir() {
+    if setup_fs.exists(opencode_command_dir) {
+        for path in setup_fs.entries(opencode_command_dir)? {
+            if setup_fs.is_real_dir(&path) {
                 continue;
             }
 
@@ -239,13 +1752,13 @@ fn prune_stale_command_files(format: OutputFormat) -> Result<(usize, usize)> {
             };
 
             if !allowed_files.contains(&file_name) {
-                fs::remove_file(&path)?;
+                setup_fs.remove(&path)?;
                 opencode_removed += 1;
             }
         }
     }
 
-    if format != OutputFormat::Json && (nexus_removed > 0 || opencode_removed > 0) {
+    if setup_fs.reports(format) && (nexus_removed > 0 || opencode_removed > 0) {
         print_success(&format!(
             "Removed stale commands ({} from .nexus/ai_harness/commands, {} from .opencode/command)",
             nexus_removed, opencode_removed
@@ -255,78 +1768,22 @@ fn prune_stale_command_files(format: OutputFormat) -> Result<(usize, usize)> {
     Ok((nexus_removed, opencode_removed))
 }
 
-fn create_claude_command_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
-    let claude_command_dir = Path::new(".claude/commands");
-    let nexus_commands_dir = Path::new(".nexus/ai_harness/commands");
-
-    if !nexus_commands_dir.exists() {
-        return Ok((0, 0));
-    }
-
-    if !claude_command_dir.exists() {
-        fs::create_dir_all(claude_command_dir)?;
-    }
-
-    let mut symlinks_created = 0;
-    let mut symlinks_replaced = 0;
-
-    for entry in fs::read_dir(nexus_commands_dir)? {
-        let entry = entry?;
-        let source_path = entry.path();
-
-        if !source_path.is_file() {
-            continue;
-        }
-
-        let file_name = match source_path.file_name() {
-            Some(name) => name,
-            None => continue,
-        };
-
-        let file_name_str = file_name.to_string_lossy().to_string();
-        if !is_command_entry_file(&file_name_str) {
-            continue;
-        }
-
-        let symlink_path = claude_command_dir.join(file_name);
-
-        if path_exists_or_symlink(&symlink_path) {
-            remove_path(&symlink_path)?;
-            symlinks_replaced += 1;
-        }
-
-        let target = format!("../../.nexus/ai_harness/commands/{}", file_name_str);
-
-        #[cfg(unix)]
-        symlink(&target, &symlink_path)?;
-        #[cfg(not(unix))]
-        fs::copy(&source_path, &symlink_path)?;
-
-        symlinks_created += 1;
-    }
-
-    if format != OutputFormat::Json && (symlinks_created > 0 || symlinks_replaced > 0) {
-        print_success(&format!(
-            "Created {} symlinks in .claude/commands/ ({} replaced)",
-            symlinks_created, symlinks_replaced
-        ));
-    }
-
-    Ok((symlinks_created, symlinks_replaced))
-}
-
 /// Extract the bundled .nexus directory to the current working directory.
 ///
 /// This recursively extracts setup-managed files and directories from the embedded
-/// NEXUS_ASSETS to `.nexus/` in the current directory. Existing files are
+/// bundle to `.nexus/` in the current directory. Existing files are
 /// overwritten to keep assets up to date. The root `context/` folder is created
 /// but no bundled context files are extracted.
-fn extract_nexus_directory(format: OutputFormat) -> Result<()> {
+fn extract_nexus_directory(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    profile: &SetupProfile,
+) -> Result<()> {
     let nexus_dir = Path::new(".nexus");
-    let dir_exists = nexus_dir.exists();
+    let dir_exists = setup_fs.exists(nexus_dir);
 
     if !dir_exists {
-        fs::create_dir_all(nexus_dir)?;
+        setup_fs.create_dir_all(nexus_dir)?;
     }
 
     // Extract all files from the embedded directory
@@ -334,19 +1791,29 @@ fn extract_nexus_directory(format: OutputFormat) -> Result<()> {
     let mut files_replaced = 0;
 
     let context_dir = nexus_dir.join("context");
-    if !context_dir.exists() {
-        fs::create_dir_all(&context_dir)?;
+    if !setup_fs.exists(&context_dir) {
+        setup_fs.create_dir_all(&context_dir)?;
     }
 
+    let mut journal = SetupJournal::open(Path::new(SETUP_JOURNAL_PATH), !setup_fs.dry_run)?;
+    if !journal.completed.is_empty() && setup_fs.reports(format) {
+        print_info(&format!(
+            "Resuming interrupted setup ({} files already extracted)",
+            journal.completed.len()
+        ));
+    }
     extract_dir_recursive(
-        &NEXUS_ASSETS,
+        setup_fs.assets,
         nexus_dir,
         &mut files_written,
         &mut files_replaced,
-        true,
+        &mut journal,
+        setup_fs,
+        profile,
     )?;
+    journal.finish()?;
 
-    if format != OutputFormat::Json {
+    if setup_fs.reports(format) {
         if dir_exists && files_written == 0 {
             print_info(&format!(
                 ".nexus directory already exists ({} files replaced)",
@@ -371,23 +1838,33 @@ fn extract_dir_recursive(
     target_path: &Path,
     files_written: &mut usize,
     files_replaced: &mut usize,
-    is_root: bool,
+    journal: &mut SetupJournal,
+    setup_fs: &mut SetupFs,
+    profile: &SetupProfile,
 ) -> Result<()> {
-    // Create the target directory if it doesn't exist
-    if !target_path.exists() {
-        fs::create_dir_all(target_path)?;
-    }
-
-    // Extract all files in this directory
+    // Extract the files in this directory the profile selects
     for file in dir.files() {
+        if !is_extracted(file.path(), profile) {
+            continue;
+        }
+        // Create the target directory if it doesn't exist
+        if !setup_fs.exists(target_path) {
+            setup_fs.create_dir_all(target_path)?;
+        }
         let file_path = target_path.join(file.path().file_name().unwrap_or_default());
+        let journal_key = file.path().to_string_lossy().into_owned();
+        if journal.completed.contains(&journal_key) && setup_fs.exists(&file_path) {
+            let contents = render(file.contents(), &setup_fs.manifest.template_vars);
+            setup_fs.manifest.record_file(&file_path, &contents);
+            continue;
+        }
 
-        if file_path.exists() {
+        let contents = render(file.contents(), &setup_fs.manifest.template_vars).into_owned();
+        if setup_fs.write_file(&file_path, &contents)? {
             *files_replaced += 1;
         }
-
-        fs::write(&file_path, file.contents())?;
         *files_written += 1;
+        journal.record(journal_key)?;
     }
 
     // Recursively extract subdirectories
@@ -395,38 +1872,167 @@ fn extract_dir_recursive(
         let subdir_name = subdir.path().file_name().unwrap_or_default();
         let subdir_path = target_path.join(subdir_name);
 
-        if is_root && (subdir_name == "marketplace" || subdir_name == "context") {
-            continue;
+        extract_dir_recursive(
+            subdir,
+            &subdir_path,
+            files_written,
+            files_replaced,
+            journal,
+            setup_fs,
+            profile,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Progress journal that lets an interrupted extraction resume.
+///
+/// Extracted paths are appended in chunks of `SETUP_JOURNAL_CHUNK`; a rerun
+/// skips journaled files that still exist, and a finished setup deletes it.
+/// A journal opened without `persist` (for a dry run) is read but never
+/// written.
+struct SetupJournal {
+    path: PathBuf,
+    persist: bool,
+    completed: HashSet<String>,
+    pending: Vec<String>,
+}
+
+impl SetupJournal {
+    fn open(path: &Path, persist: bool) -> Result<Self> {
+        let completed = if path.exists() {
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read setup journal '{}'.", path.display()))?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            persist,
+            completed,
+            pending: Vec::new(),
+        })
+    }
+
+    fn record(&mut self, relative_path: String) -> Result<()> {
+        self.pending.push(relative_path);
+        if self.pending.len() >= SETUP_JOURNAL_CHUNK {
+            self.flush()?;
         }
+        Ok(())
+    }
 
-        extract_dir_recursive(subdir, &subdir_path, files_written, files_replaced, false)?;
+    fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() || !self.persist {
+            return Ok(());
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open setup journal '{}'.", self.path.display()))?;
+        for entry in self.pending.drain(..) {
+            writeln!(file, "{}", entry)?;
+            self.completed.insert(entry);
+        }
+        Ok(())
     }
 
-    Ok(())
+    fn finish(self) -> Result<()> {
+        if self.persist && self.path.exists() {
+            fs::remove_file(&self.path).with_context(|| {
+                format!("Failed to remove setup journal '{}'.", self.path.display())
+            })?;
+        }
+        Ok(())
+    }
 }
 
 /// Remove legacy bundled context content from `.nexus/context` while preserving
 /// user-created files and always keeping the root context directory.
-fn prune_embedded_context_seed(format: OutputFormat) -> Result<(usize, usize)> {
+/// Copy the selected bundled context projects into `.nexus/context/`,
+/// leaving existing files alone. Copies are not tracked by the manifest.
+fn copy_context_templates(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    templates: &[String],
+) -> Result<()> {
+    let mut copied = 0;
+    for name in templates {
+        let Some(template) = CONTEXT_TEMPLATES
+            .contains(&name.as_str())
+            .then(|| setup_fs.assets.get_dir(format!("context/{name}")))
+            .flatten()
+        else {
+            bail!(
+                "Unknown context template '{}'. Remediation: use one of {}.",
+                name,
+                CONTEXT_TEMPLATES.join(", ")
+            );
+        };
+        copy_template_dir(setup_fs, template, &mut copied)?;
+    }
+
+    if setup_fs.reports(format) && !templates.is_empty() {
+        print_success(&format!(
+            "Copied {} context template file(s) ({})",
+            copied,
+            templates.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+fn copy_template_dir(setup_fs: &mut SetupFs, dir: &Dir, copied: &mut usize) -> Result<()> {
+    for file in dir.files() {
+        let path = Path::new(NEXUS_ROOT).join(file.path());
+        if setup_fs.exists(&path) {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            if !setup_fs.exists(parent) {
+                setup_fs.create_dir_all(parent)?;
+            }
+        }
+        let contents = render(file.contents(), &setup_fs.manifest.template_vars).into_owned();
+        setup_fs.write_untracked(&path, &contents)?;
+        *copied += 1;
+    }
+    for subdir in dir.dirs() {
+        copy_template_dir(setup_fs, subdir, copied)?;
+    }
+    Ok(())
+}
+
+fn prune_embedded_context_seed(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+) -> Result<(usize, usize)> {
     let context_root = Path::new(".nexus/context");
-    if !context_root.exists() {
+    if !setup_fs.exists(context_root) {
         return Ok((0, 0));
     }
 
-    let Some(embedded_context_dir) = NEXUS_ASSETS.get_dir("context") else {
+    let Some(embedded_context_dir) = setup_fs.assets.get_dir("context") else {
         return Ok((0, 0));
     };
 
     let mut files_removed = 0;
     let mut dirs_removed = 0;
     prune_context_seed_recursive(
+        setup_fs,
         embedded_context_dir,
         context_root,
         &mut files_removed,
         &mut dirs_removed,
     )?;
 
-    if format != OutputFormat::Json && (files_removed > 0 || dirs_removed > 0) {
+    if setup_fs.reports(format) && (files_removed > 0 || dirs_removed > 0) {
         print_success(&format!(
             "Removed bundled context seed content ({} files, {} directories)",
             files_removed, dirs_removed
@@ -437,6 +2043,7 @@ fn prune_embedded_context_seed(format: OutputFormat) -> Result<(usize, usize)> {
 }
 
 fn prune_context_seed_recursive(
+    setup_fs: &mut SetupFs,
     embedded_dir: &Dir,
     target_dir: &Path,
     files_removed: &mut usize,
@@ -449,16 +2056,11 @@ fn prune_context_seed_recursive(
         };
 
         let target_path = target_dir.join(file_name);
-        if !path_exists_or_symlink(&target_path) {
-            continue;
-        }
-
-        let metadata = fs::symlink_metadata(&target_path)?;
-        if metadata.file_type().is_dir() {
+        if !setup_fs.exists(&target_path) || setup_fs.is_real_dir(&target_path) {
             continue;
         }
 
-        remove_path(&target_path)?;
+        setup_fs.remove(&target_path)?;
         *files_removed += 1;
     }
 
@@ -469,25 +2071,24 @@ fn prune_context_seed_recursive(
         };
 
         let subdir_path = target_dir.join(subdir_name);
-        if !path_exists_or_symlink(&subdir_path) {
+        if !setup_fs.exists(&subdir_path) {
             continue;
         }
 
-        let metadata = fs::symlink_metadata(&subdir_path)?;
-        if metadata.file_type().is_symlink() {
-            fs::remove_file(&subdir_path)?;
+        if setup_fs.is_symlink(&subdir_path) {
+            setup_fs.remove(&subdir_path)?;
             *dirs_removed += 1;
             continue;
         }
 
-        if !metadata.file_type().is_dir() {
+        if !setup_fs.is_real_dir(&subdir_path) {
             continue;
         }
 
-        prune_context_seed_recursive(subdir, &subdir_path, files_removed, dirs_removed)?;
+        prune_context_seed_recursive(setup_fs, subdir, &subdir_path, files_removed, dirs_removed)?;
 
-        if fs::read_dir(&subdir_path)?.next().is_none() {
-            fs::remove_dir(&subdir_path)?;
+        if setup_fs.entries(&subdir_path)?.is_empty() {
+            setup_fs.remove(&subdir_path)?;
             *dirs_removed += 1;
         }
     }
@@ -496,74 +2097,109 @@ fn prune_context_seed_recursive(
 }
 
 /// Create symlinks in .opencode/command/ for all files in .nexus/ai_harness/commands/.
-fn create_command_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
-    let opencode_command_dir = Path::new(".opencode/command");
-    let nexus_commands_dir = Path::new(".nexus/ai_harness/commands");
-
-    if !nexus_commands_dir.exists() {
+/// Link every bundled command into `target`'s command directory.
+fn create_command_symlinks(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    target: &IntegrationTarget,
+) -> Result<(usize, usize)> {
+    let command_dir = Path::new(target.command_dir);
+    let nexus_commands_dir = Path::new(NEXUS_COMMANDS_DIR);
+
+    if !setup_fs.exists(nexus_commands_dir) {
         return Ok((0, 0));
     }
 
-    if !opencode_command_dir.exists() {
-        fs::create_dir_all(opencode_command_dir)?;
+    if !setup_fs.exists(command_dir) {
+        setup_fs.create_dir_all(command_dir)?;
     }
 
     let mut symlinks_created = 0;
     let mut symlinks_replaced = 0;
 
-    for entry in fs::read_dir(nexus_commands_dir)? {
-        let entry = entry?;
-        let source_path = entry.path();
-
-        if !source_path.is_file() {
+    for source_path in setup_fs.entries(nexus_commands_dir)? {
+        if !setup_fs.is_file(&source_path) {
             continue;
         }
 
-        let file_name = match source_path.file_name() {
-            Some(name) => name,
-            None => continue,
+        let Some(file_name) = source_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
         };
+        if !is_command_entry_file(&file_name) {
+            continue;
+        }
 
-        let symlink_path = opencode_command_dir.join(file_name);
-
-        if path_exists_or_symlink(&symlink_path) {
-            remove_path(&symlink_path)?;
+        let symlink_path = command_dir.join(&file_name);
+        if setup_fs.link(&target.link_target(&file_name), &source_path, &symlink_path)? {
             symlinks_replaced += 1;
         }
-
-        let target = format!(
-            "../../.nexus/ai_harness/commands/{}",
-            file_name.to_string_lossy()
-        );
-
-        #[cfg(unix)]
-        symlink(&target, &symlink_path)?;
-        #[cfg(not(unix))]
-        std::fs::copy(&source_path, &symlink_path)?;
-
         symlinks_created += 1;
     }
 
-    if format != OutputFormat::Json && (symlinks_created > 0 || symlinks_replaced > 0) {
+    if setup_fs.reports(format) && (symlinks_created > 0 || symlinks_replaced > 0) {
         print_success(&format!(
-            "Created {} symlinks in .opencode/command/ ({} replaced)",
-            symlinks_created, symlinks_replaced
+            "Created {} links in {}/ ({} replaced)",
+            symlinks_created, target.command_dir, symlinks_replaced
         ));
     }
 
     Ok((symlinks_created, symlinks_replaced))
 }
 
+/// Remove links in `target`'s command directory that point at bundled
+/// commands setup no longer installs. Other entries are the user's own.
+fn prune_stale_target_links(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+    target: &IntegrationTarget,
+) -> Result<usize> {
+    let command_dir = Path::new(target.command_dir);
+    if !setup_fs.is_real_dir(command_dir) {
+        return Ok(0);
+    }
+
+    let link_prefix = target.link_target("");
+    let mut removed = 0;
+    for path in setup_fs.entries(command_dir)? {
+        let Ok(link) = fs::read_link(&path) else {
+            continue;
+        };
+        let Some(file_name) = link
+            .to_str()
+            .and_then(|link| link.strip_prefix(&link_prefix))
+        else {
+            continue;
+        };
+        if !setup_fs.is_file(&Path::new(NEXUS_COMMANDS_DIR).join(file_name))
+            || !is_command_entry_file(file_name)
+        {
+            setup_fs.remove(&path)?;
+            removed += 1;
+        }
+    }
+
+    if setup_fs.reports(format) && removed > 0 {
+        print_success(&format!(
+            "Removed {} stale command link(s) from {}/",
+            removed, target.command_dir
+        ));
+    }
+    Ok(removed)
+}
+
 /// Remove stale tool files from .nexus/tools and stale nexus-managed entries from .opencode/tools.
-fn prune_stale_tool_files(format: OutputFormat) -> Result<(usize, usize)> {
+fn prune_stale_tool_files(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<(usize, usize)> {
     let nexus_tools_dir = Path::new(".nexus/tools");
     let opencode_tools_dir = Path::new(".opencode/tools");
 
-    if !nexus_tools_dir.exists() {
+    if !setup_fs.exists(nexus_tools_dir) {
         return Ok((0, 0));
     }
 
-    let Some(embedded_tools_dir) = NEXUS_ASSETS.get_dir("tools") else {
+    let Some(embedded_tools_dir) = setup_fs.assets.get_dir("tools") else {
         return Ok((0, 0));
     };
 
@@ -575,12 +2211,8 @@ fn prune_stale_tool_files(format: OutputFormat) -> Result<(usize, usize)> {
         .collect();
 
     let mut nexus_removed = 0;
-    for entry in fs::read_dir(nexus_tools_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        let metadata = fs::symlink_metadata(&path)?;
-
-        if metadata.file_type().is_dir() {
+    for path in setup_fs.entries(nexus_tools_dir)? {
+        if setup_fs.is_real_dir(&path) {
             continue;
         }
 
@@ -592,19 +2224,15 @@ fn prune_stale_tool_files(format: OutputFormat) -> Result<(usize, usize)> {
         };
 
         if !allowed_files.contains(&file_name) {
-            fs::remove_file(&path)?;
+            setup_fs.remove(&path)?;
             nexus_removed += 1;
         }
     }
 
     let mut opencode_removed = 0;
-    if opencode_tools_dir.exists() {
-        for entry in fs::read_dir(opencode_tools_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            let metadata = fs::symlink_metadata(&path)?;
-
-            if metadata.file_type().is_dir() {
+    if setup_fs.exists(opencode_tools_dir) {
+        for path in setup_fs.entries(opencode_tools_dir)? {
+            if setup_fs.is_real_dir(&path) {
                 continue;
             }
 
@@ -619,20 +2247,20 @@ fn prune_stale_tool_files(format: OutputFormat) -> Result<(usize, usize)> {
                 continue;
             }
 
-            if metadata.file_type().is_symlink() {
-                fs::remove_file(&path)?;
+            if setup_fs.is_symlink(&path) {
+                setup_fs.remove(&path)?;
                 opencode_removed += 1;
                 continue;
             }
 
             // Preserve user-managed regular files.
-            if path.is_file() {
+            if setup_fs.is_file(&path) {
                 continue;
             }
         }
     }
 
-    if format != OutputFormat::Json && (nexus_removed > 0 || opencode_removed > 0) {
+    if setup_fs.reports(format) && (nexus_removed > 0 || opencode_removed > 0) {
         print_success(&format!(
             "Removed stale tools ({} from .nexus/tools, {} from .opencode/tools)",
             nexus_removed, opencode_removed
@@ -643,26 +2271,23 @@ fn prune_stale_tool_files(format: OutputFormat) -> Result<(usize, usize)> {
 }
 
 /// Create symlinks in .opencode/tools/ for all files in .nexus/tools/.
-fn create_tool_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
+fn create_tool_symlinks(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<(usize, usize)> {
     let opencode_tools_dir = Path::new(".opencode/tools");
     let nexus_tools_dir = Path::new(".nexus/tools");
 
-    if !nexus_tools_dir.exists() {
+    if !setup_fs.exists(nexus_tools_dir) {
         return Ok((0, 0));
     }
 
-    if !opencode_tools_dir.exists() {
-        fs::create_dir_all(opencode_tools_dir)?;
+    if !setup_fs.exists(opencode_tools_dir) {
+        setup_fs.create_dir_all(opencode_tools_dir)?;
     }
 
     let mut symlinks_created = 0;
     let mut symlinks_replaced = 0;
 
-    for entry in fs::read_dir(nexus_tools_dir)? {
-        let entry = entry?;
-        let source_path = entry.path();
-
-        if !source_path.is_file() {
+    for source_path in setup_fs.entries(nexus_tools_dir)? {
+        if !setup_fs.is_file(&source_path) {
             continue;
         }
 
@@ -678,24 +2303,17 @@ fn create_tool_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
 
         let symlink_path = opencode_tools_dir.join(file_name);
 
-        if path_exists_or_symlink(&symlink_path) {
-            remove_path(&symlink_path)?;
-            symlinks_replaced += 1;
-        }
-
         let target = format!("../../.nexus/tools/{}", file_name.to_string_lossy());
 
-        #[cfg(unix)]
-        symlink(&target, &symlink_path)?;
-        #[cfg(not(unix))]
-        std::fs::copy(&source_path, &symlink_path)?;
-
+        if setup_fs.link(&target, &source_path, &symlink_path)? {
+            symlinks_replaced += 1;
+        }
         symlinks_created += 1;
     }
 
-    if format != OutputFormat::Json && (symlinks_created > 0 || symlinks_replaced > 0) {
+    if setup_fs.reports(format) && (symlinks_created > 0 || symlinks_replaced > 0) {
         print_success(&format!(
-            "Created {} symlinks in .opencode/tools/ ({} replaced)",
+            "Created {} links in .opencode/tools/ ({} replaced)",
             symlinks_created, symlinks_replaced
         ));
     }
@@ -740,21 +2358,17 @@ fn is_command_entry_file(file_name: &str) -> bool {
     true
 }
 
-fn path_exists_or_symlink(path: &Path) -> bool {
-    path.exists() || fs::symlink_metadata(path).is_ok()
-}
-
 /// Remove legacy top-level .nexus/rules directory.
-fn remove_legacy_rules_directory(format: OutputFormat) -> Result<bool> {
+fn remove_legacy_rules_directory(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<bool> {
     let rules_dir = Path::new(".nexus/rules");
 
-    if !rules_dir.exists() {
+    if !setup_fs.exists(rules_dir) {
         return Ok(false);
     }
 
-    fs::remove_dir_all(rules_dir)?;
+    setup_fs.remove(rules_dir)?;
 
-    if format != OutputFormat::Json {
+    if setup_fs.reports(format) {
         print_success("Removed legacy .nexus/rules directory");
     }
 
@@ -762,13 +2376,13 @@ fn remove_legacy_rules_directory(format: OutputFormat) -> Result<bool> {
 }
 
 /// Remove stale rule entries from .opencode/rules that are no longer in embedded assets.
-fn prune_stale_rule_entries(format: OutputFormat) -> Result<(usize, bool)> {
+fn prune_stale_rule_entries(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<(usize, bool)> {
     let opencode_rules_dir = Path::new(".opencode/rules");
-    if !opencode_rules_dir.exists() {
+    if !setup_fs.exists(opencode_rules_dir) {
         return Ok((0, false));
     }
 
-    let Some(embedded_rules_dir) = NEXUS_ASSETS.get_dir("ai_harness/rules") else {
+    let Some(embedded_rules_dir) = setup_fs.assets.get_dir("ai_harness/rules") else {
         return Ok((0, true));
     };
 
@@ -779,9 +2393,7 @@ fn prune_stale_rule_entries(format: OutputFormat) -> Result<(usize, bool)> {
         .collect();
 
     let mut removed = 0;
-    for entry in fs::read_dir(opencode_rules_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    for path in setup_fs.entries(opencode_rules_dir)? {
         let Some(name) = path
             .file_name()
             .map(|file_name| file_name.to_string_lossy().to_string())
@@ -793,11 +2405,11 @@ fn prune_stale_rule_entries(format: OutputFormat) -> Result<(usize, bool)> {
             continue;
         }
 
-        remove_path(&path)?;
+        setup_fs.remove(&path)?;
         removed += 1;
     }
 
-    if format != OutputFormat::Json && removed > 0 {
+    if setup_fs.reports(format) && removed > 0 {
         print_success(&format!(
             "Removed {} stale entries from .opencode/rules",
             removed
@@ -808,13 +2420,16 @@ fn prune_stale_rule_entries(format: OutputFormat) -> Result<(usize, bool)> {
 }
 
 /// Remove stale skill entries from .opencode/skills that are no longer in embedded assets.
-fn prune_stale_skill_entries(format: OutputFormat) -> Result<(usize, bool)> {
+fn prune_stale_skill_entries(
+    format: OutputFormat,
+    setup_fs: &mut SetupFs,
+) -> Result<(usize, bool)> {
     let opencode_skills_dir = Path::new(".opencode/skills");
-    if !opencode_skills_dir.exists() {
+    if !setup_fs.exists(opencode_skills_dir) {
         return Ok((0, false));
     }
 
-    let Some(embedded_skills_dir) = NEXUS_ASSETS.get_dir("ai_harness/skills") else {
+    let Some(embedded_skills_dir) = setup_fs.assets.get_dir("ai_harness/skills") else {
         return Ok((0, true));
     };
 
@@ -825,9 +2440,7 @@ fn prune_stale_skill_entries(format: OutputFormat) -> Result<(usize, bool)> {
         .collect();
 
     let mut removed = 0;
-    for entry in fs::read_dir(opencode_skills_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    for path in setup_fs.entries(opencode_skills_dir)? {
         let Some(name) = path
             .file_name()
             .map(|file_name| file_name.to_string_lossy().to_string())
@@ -839,11 +2452,11 @@ fn prune_stale_skill_entries(format: OutputFormat) -> Result<(usize, bool)> {
             continue;
         }
 
-        remove_path(&path)?;
+        setup_fs.remove(&path)?;
         removed += 1;
     }
 
-    if format != OutputFormat::Json && removed > 0 {
+    if setup_fs.reports(format) && removed > 0 {
         print_success(&format!(
             "Removed {} stale entries from .opencode/skills",
             removed
@@ -854,25 +2467,23 @@ fn prune_stale_skill_entries(format: OutputFormat) -> Result<(usize, bool)> {
 }
 
 /// Create symlinks in .opencode/skills/ for all directories in .nexus/ai_harness/skills/.
-fn create_skill_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
+fn create_skill_symlinks(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<(usize, usize)> {
     let opencode_skills_dir = Path::new(".opencode/skills");
     let nexus_skills_dir = Path::new(".nexus/ai_harness/skills");
 
-    if !nexus_skills_dir.exists() {
+    if !setup_fs.exists(nexus_skills_dir) {
         return Ok((0, 0));
     }
 
-    if !opencode_skills_dir.exists() {
-        fs::create_dir_all(opencode_skills_dir)?;
+    if !setup_fs.exists(opencode_skills_dir) {
+        setup_fs.create_dir_all(opencode_skills_dir)?;
     }
 
     let mut symlinks_created = 0;
     let mut symlinks_replaced = 0;
 
-    for entry in fs::read_dir(nexus_skills_dir)? {
-        let entry = entry?;
-        let source_path = entry.path();
-        if !source_path.is_dir() {
+    for source_path in setup_fs.entries(nexus_skills_dir)? {
+        if !setup_fs.is_dir(&source_path) {
             continue;
         }
 
@@ -882,37 +2493,26 @@ fn create_skill_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
         };
 
         let skill_file = source_path.join("SKILL.md");
-        if !skill_file.exists() {
+        if !setup_fs.exists(&skill_file) {
             continue;
         }
 
         let symlink_path = opencode_skills_dir.join(skill_name);
 
-        if path_exists_or_symlink(&symlink_path) {
-            remove_path(&symlink_path)?;
-            symlinks_replaced += 1;
-        }
-
         let target = format!(
             "../../.nexus/ai_harness/skills/{}",
             skill_name.to_string_lossy()
         );
 
-        #[cfg(unix)]
-        symlink(&target, &symlink_path)?;
-
-        #[cfg(not(unix))]
-        {
-            fs::create_dir_all(&symlink_path)?;
-            fs::copy(&skill_file, symlink_path.join("SKILL.md"))?;
+        if setup_fs.link(&target, &source_path, &symlink_path)? {
+            symlinks_replaced += 1;
         }
-
         symlinks_created += 1;
     }
 
-    if format != OutputFormat::Json && (symlinks_created > 0 || symlinks_replaced > 0) {
+    if setup_fs.reports(format) && (symlinks_created > 0 || symlinks_replaced > 0) {
         print_success(&format!(
-            "Created {} symlinks in .opencode/skills/ ({} replaced)",
+            "Created {} links in .opencode/skills/ ({} replaced)",
             symlinks_created, symlinks_replaced
         ));
     }
@@ -921,25 +2521,23 @@ fn create_skill_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
 }
 
 /// Create symlinks in .opencode/rules/ for all directories in .nexus/ai_harness/rules/.
-fn create_rule_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
+fn create_rule_symlinks(format: OutputFormat, setup_fs: &mut SetupFs) -> Result<(usize, usize)> {
     let opencode_rules_dir = Path::new(".opencode/rules");
     let nexus_rules_dir = Path::new(".nexus/ai_harness/rules");
 
-    if !nexus_rules_dir.exists() {
+    if !setup_fs.exists(nexus_rules_dir) {
         return Ok((0, 0));
     }
 
-    if !opencode_rules_dir.exists() {
-        fs::create_dir_all(opencode_rules_dir)?;
+    if !setup_fs.exists(opencode_rules_dir) {
+        setup_fs.create_dir_all(opencode_rules_dir)?;
     }
 
     let mut symlinks_created = 0;
     let mut symlinks_replaced = 0;
 
-    for entry in fs::read_dir(nexus_rules_dir)? {
-        let entry = entry?;
-        let source_path = entry.path();
-        if !source_path.is_dir() {
+    for source_path in setup_fs.entries(nexus_rules_dir)? {
+        if !setup_fs.is_dir(&source_path) {
             continue;
         }
 
@@ -950,42 +2548,26 @@ fn create_rule_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
 
         let rule_file = source_path.join("RULE.md");
         let legacy_skill_file = source_path.join("SKILL.md");
-        if !rule_file.exists() && !legacy_skill_file.exists() {
+        if !setup_fs.exists(&rule_file) && !setup_fs.exists(&legacy_skill_file) {
             continue;
         }
 
         let symlink_path = opencode_rules_dir.join(rule_name);
 
-        if path_exists_or_symlink(&symlink_path) {
-            remove_path(&symlink_path)?;
-            symlinks_replaced += 1;
-        }
-
         let target = format!(
             "../../.nexus/ai_harness/rules/{}",
             rule_name.to_string_lossy()
         );
 
-        #[cfg(unix)]
-        symlink(&target, &symlink_path)?;
-
-        #[cfg(not(unix))]
-        {
-            fs::create_dir_all(&symlink_path)?;
-            if rule_file.exists() {
-                fs::copy(&rule_file, symlink_path.join("RULE.md"))?;
-            }
-            if legacy_skill_file.exists() {
-                fs::copy(&legacy_skill_file, symlink_path.join("SKILL.md"))?;
-            }
+        if setup_fs.link(&target, &source_path, &symlink_path)? {
+            symlinks_replaced += 1;
         }
-
         symlinks_created += 1;
     }
 
-    if format != OutputFormat::Json && (symlinks_created > 0 || symlinks_replaced > 0) {
+    if setup_fs.reports(format) && (symlinks_created > 0 || symlinks_replaced > 0) {
         print_success(&format!(
-            "Created {} symlinks in .opencode/rules/ ({} replaced)",
+            "Created {} links in .opencode/rules/ ({} replaced)",
             symlinks_created, symlinks_replaced
         ));
     }
@@ -993,12 +2575,68 @@ fn create_rule_symlinks(format: OutputFormat) -> Result<(usize, usize)> {
     Ok((symlinks_created, symlinks_replaced))
 }
 
-fn remove_path(path: &Path) -> Result<()> {
-    let metadata = fs::symlink_metadata(path)?;
-    if metadata.file_type().is_dir() {
-        fs::remove_dir_all(path)?;
-    } else {
-        fs::remove_file(path)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn dry_run_records_changes_and_overlays_them_without_writing() {
+        let dir = tempdir().expect("tempdir");
+        let commands = dir.path().join("commands");
+        fs::create_dir_all(&commands).expect("commands dir");
+        let existing = commands.join("old.md");
+        fs::write(&existing, "old").expect("existing");
+
+        let mut setup_fs = SetupFs::new(true);
+        let created = commands.join("new.md");
+        assert!(!setup_fs.write_file(&created, b"new").expect("create"));
+        assert!(setup_fs.write_file(&existing, b"old").expect("replace"));
+        setup_fs.remove(&existing).expect("prune");
+        let link = dir.path().join("links").join("new.md");
+        assert!(!setup_fs
+            .link("../commands/new.md", &created, &link)
+            .expect("link"));
+
+        assert!(setup_fs.is_file(&created));
+        assert!(!setup_fs.exists(&existing));
+        assert!(setup_fs.is_dir(&dir.path().join("links")));
+        assert_eq!(
+            setup_fs.entries(&commands).expect("entries"),
+            vec![created.clone()]
+        );
+        assert!(!created.exists());
+        assert!(existing.exists());
+        assert!(!link.exists());
+
+        let plan = setup_fs.into_plan("opencode", "full");
+        assert_eq!(plan.create, vec![created.display().to_string()]);
+        assert_eq!(plan.replace, vec![existing.display().to_string()]);
+        assert_eq!(plan.prune, vec![existing.display().to_string()]);
+        assert_eq!(
+            plan.link,
+            vec![SetupLink {
+                path: link.display().to_string(),
+                target: "../commands/new.md".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn latest_backup_is_the_newest_timestamped_directory() {
+        let dir = tempdir().expect("tempdir");
+        assert_eq!(latest_backup_dir(dir.path()).expect("empty"), None);
+
+        let older = backup_name(1_700_000_000);
+        let newer = backup_name(1_700_000_060);
+        assert_eq!(older, "2023-11-14T221320Z");
+        for name in [&newer, &older] {
+            fs::create_dir_all(dir.path().join(name)).expect("backup dir");
+        }
+        fs::write(dir.path().join("stray.txt"), "x").expect("stray file");
+        assert_eq!(
+            latest_backup_dir(dir.path()).expect("latest"),
+            Some(dir.path().join(newer))
+        );
     }
-    Ok(())
 }
@@ -0,0 +1,212 @@
+//! Interactive `shell` command.
+//!
+//! Each line is parsed like the arguments to `opennexus` and run through the
+//! same dispatch, so startup work (user config, onboarding, logging) happens
+//! once per session. Global flags are fixed when the shell starts; lines that
+//! repeat them are rejected. History is kept in
+//! `$XDG_DATA_HOME/opennexus/shell_history` unless stateless, and Tab
+//! completes through the same engine as `__complete`.
+
+use anyhow::{Context, Result};
+use clap::error::ErrorKind;
+use clap::{Command, CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Editor, Helper};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cli::{Cli, Commands};
+use crate::commands::complete::{complete_words, RepoCompletionSources};
+use crate::config::stateless::is_stateless;
+use crate::output::{print_error, print_info, report_error, structured_output};
+use crate::utils::paths::data_dir;
+
+const PROMPT: &str = "nexus> ";
+
+/// Outcome of one line typed at the prompt.
+#[derive(Debug)]
+enum ShellLine {
+    Skip,
+    Exit,
+    Run(Commands),
+    Message(String),
+    /// A clap parse error, already rendered with its own `error:` prefix.
+    Usage(String),
+    Invalid(String),
+}
+
+/// Run `shell`, passing each parsed command to `execute`.
+pub fn run_shell(mut execute: impl FnMut(Commands) -> Result<()>) -> Result<()> {
+    let mut editor = Editor::<ShellHelper, DefaultHistory>::new()
+        .context("Failed to start the interactive shell.")?;
+    let mut command = Cli::command();
+    command.build();
+    editor.set_helper(Some(ShellHelper { command }));
+    let history = history_path();
+    if let Some(path) = &history {
+        let _ = editor.load_history(path);
+    }
+
+    print_info("Type a command without `opennexus`, `help` for the command list, or `exit`.");
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err).context("Failed to read from the interactive shell."),
+        };
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+        match parse_line(&line) {
+            ShellLine::Skip => {}
+            ShellLine::Exit => break,
+            ShellLine::Message(message) => print!("{}", message),
+            ShellLine::Usage(message) => eprint!("{}", message),
+            ShellLine::Invalid(message) => print_error(&message),
+            ShellLine::Run(command) => {
+                if let Err(err) = execute(command) {
+                    report_error(&err, structured_output());
+                }
+            }
+        }
+    }
+
+    if let Some(path) = &history {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = editor.save_history(path);
+    }
+    Ok(())
+}
+
+fn parse_line(line: &str) -> ShellLine {
+    let line = line.trim();
+    if line.is_empty() {
+        return ShellLine::Skip;
+    }
+    if matches!(line, "exit" | "quit") {
+        return ShellLine::Exit;
+    }
+    let Some(words) = shlex::split(line) else {
+        return ShellLine::Invalid("Unbalanced quotes in command line.".to_string());
+    };
+    let cli = match Cli::try_parse_from(std::iter::once("opennexus".to_string()).chain(words)) {
+        Ok(cli) => cli,
+        Err(err)
+            if matches!(
+                err.kind(),
+                ErrorKind::DisplayHelp | ErrorKind::DisplayVersion
+            ) =>
+        {
+            return ShellLine::Message(err.render().to_string())
+        }
+        Err(err) => return ShellLine::Usage(err.render().to_string()),
+    };
+    if cli.has_global_flags() {
+        return ShellLine::Invalid(
+            "Global flags are fixed for the session. Remediation: exit and restart `opennexus shell` with them."
+                .to_string(),
+        );
+    }
+    match cli.command {
+        None => ShellLine::Message(Cli::command().render_long_help().to_string()),
+        Some(Commands::Shell) => {
+            ShellLine::Invalid("Already in the interactive shell.".to_string())
+        }
+        Some(command) => ShellLine::Run(command),
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    if is_stateless() {
+        return None;
+    }
+    Some(data_dir()?.join("shell_history"))
+}
+
+struct ShellHelper {
+    command: Command,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _context: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let start = before
+            .rfind(char::is_whitespace)
+            .map_or(0, |index| index + 1);
+        let mut words = before[..start]
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        words.push(before[start..].to_string());
+        let candidates = complete_words(&self.command, &words, &RepoCompletionSources)
+            .into_iter()
+            .map(|candidate| Pair {
+                display: if candidate.description.is_empty() {
+                    candidate.value.clone()
+                } else {
+                    format!("{}  {}", candidate.value, candidate.description)
+                },
+                replacement: candidate.value,
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shell_lines_like_command_arguments() {
+        assert!(matches!(parse_line("   "), ShellLine::Skip));
+        assert!(matches!(parse_line("quit"), ShellLine::Exit));
+        assert!(matches!(
+            parse_line("config get 'defaults.format'"),
+            ShellLine::Run(Commands::Config { .. })
+        ));
+        assert!(matches!(parse_line("help"), ShellLine::Message(_)));
+        assert!(matches!(parse_line("shell"), ShellLine::Invalid(_)));
+        assert!(matches!(
+            parse_line("status --format json"),
+            ShellLine::Invalid(_)
+        ));
+        assert!(matches!(
+            parse_line("config get \"open"),
+            ShellLine::Invalid(_)
+        ));
+        assert!(matches!(
+            parse_line("config get --bogus"),
+            ShellLine::Usage(_)
+        ));
+        assert!(matches!(
+            parse_line("lint --fix"),
+            ShellLine::Run(Commands::External(_))
+        ));
+    }
+}
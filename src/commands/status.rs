@@ -0,0 +1,174 @@
+//! Status command summarizing the repository in one view.
+//!
+//! Combines the project config, per-project context counts by index status
+//! (`done`, `pending`, `frozen`), and the orchestration runs still marked
+//! running, so one command answers "where does this repository stand".
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::adapters::orchestration_store::RunningRun;
+use crate::cli::OutputFormat;
+use crate::commands::context::{context_status, open_existing_store};
+use crate::config::nexus::{read_config_value, NEXUS_CONFIG_PATH};
+use crate::features::context::index::collect_context_entries;
+use crate::output::{format_timestamp, print_json, print_tsv_row};
+
+const CONTEXT_ROOT: &str = ".nexus/context";
+
+#[derive(Debug, Serialize)]
+struct StatusPayload {
+    cli_version: &'static str,
+    setup_version: Option<String>,
+    harness: Option<String>,
+    projects: Vec<ProjectCounts>,
+    running: Vec<RunningRun>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+struct ProjectCounts {
+    project: String,
+    done: usize,
+    pending: usize,
+    frozen: usize,
+    total: usize,
+}
+
+/// Run `status`.
+pub fn run_status(format: OutputFormat) -> Result<()> {
+    let config = read_config_value(Path::new(NEXUS_CONFIG_PATH)).ok();
+    let config_string = |key: &str| {
+        config
+            .as_ref()
+            .and_then(|value| value.get(key)?.as_str().map(str::to_string))
+    };
+    let store = open_existing_store()?;
+    let context_root = Path::new(CONTEXT_ROOT);
+    let projects = if context_root.is_dir() {
+        collect_context_entries(context_root, None, |context_id, frozen| {
+            context_status(store.as_ref(), context_id, frozen).to_string()
+        })?
+        .into_iter()
+        .map(|(project, entries)| {
+            count_statuses(project, entries.iter().map(|entry| entry.status.as_str()))
+        })
+        .collect()
+    } else {
+        Vec::new()
+    };
+    let running = match &store {
+        Some(store) => store.list_running_runs()?,
+        None => Vec::new(),
+    };
+    let payload = StatusPayload {
+        cli_version: env!("CARGO_PKG_VERSION"),
+        setup_version: config_string("version"),
+        harness: config_string("harness"),
+        projects,
+        running,
+    };
+
+    if format.is_structured() {
+        return print_json(&payload);
+    }
+    if format == OutputFormat::Tsv {
+        for counts in &payload.projects {
+            print_tsv_row(&[
+                &counts.project,
+                &counts.done.to_string(),
+                &counts.pending.to_string(),
+                &counts.frozen.to_string(),
+                &counts.total.to_string(),
+            ]);
+        }
+        return Ok(());
+    }
+    print_status_text(&payload);
+    Ok(())
+}
+
+fn count_statuses<'a>(project: String, statuses: impl Iterator<Item = &'a str>) -> ProjectCounts {
+    let mut counts = ProjectCounts {
+        project,
+        ..ProjectCounts::default()
+    };
+    for status in statuses {
+        match status {
+            "done" => counts.done += 1,
+            "frozen" => counts.frozen += 1,
+            _ => counts.pending += 1,
+        }
+        counts.total += 1;
+    }
+    counts
+}
+
+fn print_status_text(payload: &StatusPayload) {
+    println!(
+        "Harness: {}  Setup: {}  CLI: {}",
+        payload.harness.as_deref().unwrap_or("not configured"),
+        payload.setup_version.as_deref().unwrap_or("not run"),
+        payload.cli_version
+    );
+    println!();
+    if payload.projects.is_empty() {
+        println!("No contexts under {}.", CONTEXT_ROOT);
+    } else {
+        let width = payload
+            .projects
+            .iter()
+            .map(|counts| counts.project.len())
+            .max()
+            .unwrap_or_default()
+            .max("PROJECT".len());
+        println!(
+            "{:<width$}  {:>5}  {:>7}  {:>6}  {:>5}",
+            "PROJECT", "DONE", "PENDING", "FROZEN", "TOTAL"
+        );
+        for counts in &payload.projects {
+            println!(
+                "{:<width$}  {:>5}  {:>7}  {:>6}  {:>5}",
+                counts.project, counts.done, counts.pending, counts.frozen, counts.total
+            );
+        }
+    }
+    println!();
+    if payload.running.is_empty() {
+        println!("No orchestration runs in progress.");
+        return;
+    }
+    println!("Running:");
+    for run in &payload.running {
+        println!(
+            "  run {} {} {} (started {})",
+            run.run_id,
+            run.pipeline_name,
+            run.context_file,
+            format_timestamp(run.started_at)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_statuses_per_project() {
+        let counts = count_statuses(
+            "app".to_string(),
+            ["done", "pending", "frozen", "done"].into_iter(),
+        );
+        assert_eq!(
+            counts,
+            ProjectCounts {
+                project: "app".to_string(),
+                done: 2,
+                pending: 1,
+                frozen: 1,
+                total: 4,
+            }
+        );
+    }
+}
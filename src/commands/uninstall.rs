@@ -1,41 +1,216 @@
-//! Uninstall command for removing OpenNexus via cargo.
+//! Uninstall command for removing OpenNexus.
+//!
+//! The binary is removed the way the install metadata says it was
+//! installed: `cargo uninstall` of the recorded package, or deleting the
+//! release binary `update --method binary` wrote.
+//!
+//! `--purge` also deletes what the CLI keeps outside the binary: the data
+//! directory (logs, bundle cache, update check, shell history), the cache
+//! the npm and Python wrappers download binaries into, and the user config.
+//! With `--project` it deletes this project's `.nexus` directory and the
+//! harness links setup recorded as well. `--dry-run` only lists them.
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::cli::OutputFormat;
-use crate::output::{print_error, print_info, print_success};
+use crate::cli::{OutputFormat, UpdateMethod};
+use crate::commands::setup::{remove_gitignore_block, remove_installed_assets};
+use crate::config::user::user_config_path;
+use crate::features::install_metadata::{cargo_uninstall_args, InstallMetadata};
+use crate::features::setup_manifest::{SetupManifest, SETUP_MANIFEST_PATH};
+use crate::output::{print_error, print_info, print_json, print_success};
+use crate::utils::paths::{cache_dir, data_dir, path_exists_or_symlink, remove_path};
+
+/// One path `--purge` removes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct PurgeItem {
+    path: PathBuf,
+    /// `data`, `cache`, `config`, `harness_link`, or `project`.
+    kind: &'static str,
+}
 
 /// Run the uninstall command.
-pub fn run_uninstall(format: OutputFormat) -> Result<()> {
-    if format == OutputFormat::Json {
-        println!(
-            r#"{{"status":"starting","command":"cargo uninstall --package opennexus --bin opennexus"}}"#
-        );
+///
+/// With `remove_assets`, the files and links recorded in this project's setup
+/// manifest are removed first. With `purge`, the global directories (and,
+/// with `project`, this project's assets) are deleted before the binary is
+/// uninstalled; `dry_run` only reports them. The install metadata is read
+/// before the purge deletes the data directory it lives in.
+pub fn run_uninstall(
+    format: OutputFormat,
+    remove_assets: bool,
+    purge: bool,
+    project: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if dry_run {
+        return report_purge(format, &purge_items(project)?, true);
+    }
+    let exe =
+        std::env::current_exe().context("Failed to locate the running OpenNexus executable.")?;
+    let metadata = InstallMetadata::for_executable(&exe);
+    if remove_assets {
+        remove_installed_assets(format)?;
+    }
+    if purge {
+        let items = purge_items(project)?;
+        for item in &items {
+            remove_path(&item.path)?;
+        }
+        if project {
+            remove_empty_link_dirs(&items);
+            remove_gitignore_block()?;
+        }
+        report_purge(format, &items, false)?;
+    }
+
+    match metadata.method {
+        UpdateMethod::Cargo => uninstall_with_cargo(format, &metadata)?,
+        UpdateMethod::Binary => remove_binary(format, &metadata)?,
+    }
+    InstallMetadata::clear()?;
+    if format.is_structured() {
+        print_json(&serde_json::json!({ "status": "completed" }))?;
     } else {
-        print_info("Uninstalling OpenNexus via cargo...");
+        print_success("OpenNexus uninstalled successfully");
+    }
+    Ok(())
+}
+
+fn uninstall_with_cargo(format: OutputFormat, metadata: &InstallMetadata) -> Result<()> {
+    let args = cargo_uninstall_args(metadata);
+    let command = format!("cargo {}", args.join(" "));
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "starting",
+            "method": UpdateMethod::Cargo,
+            "command": command,
+        }))?;
+    } else {
+        print_info(&format!("Uninstalling OpenNexus via `{}`...", command));
     }
 
     let status = Command::new("cargo")
-        .args(["uninstall", "--package", "opennexus", "--bin", "opennexus"])
+        .args(&args)
         .status()
         .context("Failed to run cargo. Is Rust/cargo installed?")?;
 
     if status.success() {
-        if format == OutputFormat::Json {
-            println!(r#"{{"status":"completed"}}"#);
-        } else {
-            print_success("OpenNexus uninstalled successfully");
-        }
         Ok(())
     } else {
-        if format == OutputFormat::Json {
-            println!(
-                r#"{{"status":"failed","message":"cargo uninstall returned non-zero exit code"}}"#
-            );
+        if format.is_structured() {
+            print_json(&serde_json::json!({
+                "status": "failed",
+                "message": "cargo uninstall returned non-zero exit code",
+            }))?;
         } else {
             print_error("Uninstall failed: cargo uninstall returned non-zero exit code");
         }
         anyhow::bail!("Uninstall failed")
     }
 }
+
+/// Delete the release binary at the recorded path. On Unix the running
+/// process keeps its image after the file is gone.
+fn remove_binary(format: OutputFormat, metadata: &InstallMetadata) -> Result<()> {
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "starting",
+            "method": UpdateMethod::Binary,
+            "path": metadata.path,
+        }))?;
+    } else {
+        print_info(&format!(
+            "Removing OpenNexus binary {}...",
+            metadata.path.display()
+        ));
+    }
+    fs::remove_file(&metadata.path).with_context(|| {
+        format!(
+            "Failed to remove '{}'. Remediation: delete it manually, or rerun with permission to write its directory.",
+            metadata.path.display()
+        )
+    })
+}
+
+/// The paths `--purge` removes that exist, project ones first.
+fn purge_items(project: bool) -> Result<Vec<PurgeItem>> {
+    let mut items = Vec::new();
+    if project {
+        if let Some(manifest) = SetupManifest::read(Path::new(SETUP_MANIFEST_PATH))? {
+            items.extend(manifest.links.keys().map(|path| PurgeItem {
+                path: PathBuf::from(path),
+                kind: "harness_link",
+            }));
+        }
+        items.push(PurgeItem {
+            path: PathBuf::from(".nexus"),
+            kind: "project",
+        });
+    }
+    let global = [
+        (data_dir(), "data"),
+        (cache_dir(), "cache"),
+        (
+            user_config_path().and_then(|path| Some(path.parent()?.to_path_buf())),
+            "config",
+        ),
+    ];
+    items.extend(
+        global
+            .into_iter()
+            .filter_map(|(path, kind)| Some(PurgeItem { path: path?, kind })),
+    );
+    items.retain(|item| path_exists_or_symlink(&item.path));
+    Ok(items)
+}
+
+/// Remove harness directories, such as `.opencode/command/` and then
+/// `.opencode/`, that removing the links emptied.
+fn remove_empty_link_dirs(items: &[PurgeItem]) {
+    for item in items.iter().filter(|item| item.kind == "harness_link") {
+        for dir in item.path.ancestors().skip(1) {
+            if dir.as_os_str().is_empty() || fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+fn report_purge(format: OutputFormat, items: &[PurgeItem], dry_run: bool) -> Result<()> {
+    if format.is_structured() {
+        for item in items {
+            print_json(&serde_json::json!({
+                "event": if dry_run { "would_remove" } else { "removed" },
+                "path": item.path,
+                "kind": item.kind,
+            }))?;
+        }
+        if dry_run {
+            print_json(&serde_json::json!({ "status": "planned", "items": items.len() }))?;
+        }
+        return Ok(());
+    }
+    if items.is_empty() {
+        print_info("Nothing to purge");
+        return Ok(());
+    }
+    for item in items {
+        let message = format!("{} ({})", item.path.display(), item.kind);
+        if dry_run {
+            print_info(&format!("Would remove {}", message));
+        } else {
+            print_success(&format!("Removed {}", message));
+        }
+    }
+    if dry_run {
+        print_info(&format!(
+            "Dry run: {} item(s) would be removed and the binary uninstalled.",
+            items.len()
+        ));
+    }
+    Ok(())
+}
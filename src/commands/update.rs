@@ -1,39 +1,431 @@
-//! Update command for upgrading OpenNexus via cargo.
+//! Update command for upgrading OpenNexus via cargo or a release binary.
 
 use anyhow::{Context, Result};
-use std::process::Command;
+use clap::ValueEnum;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::path::Path;
+use std::process::{Command, Stdio};
 
-use crate::cli::OutputFormat;
-use crate::output::{print_error, print_info, print_success};
+use crate::cli::{OutputFormat, UpdateChannel, UpdateMethod};
+use crate::commands::setup::setup_is_interactive;
+use crate::config::nexus::{read_config_value, NEXUS_CONFIG_PATH, SUPPORTED_HARNESSES};
+use crate::config::stateless::is_stateless;
+use crate::config::user::{load_user_config, save_user_config, user_config_path};
+use crate::features::install_metadata::{cargo_bin_dir, InstallMetadata, BINARY_NAME};
+use crate::features::self_update::{
+    cargo_install_args, download_asset, fetch_changelog, fetch_release, read_update_check,
+    replace_executable, write_update_check, ChangelogEntry, ReleaseTarget, UpdateCheck,
+    NIGHTLY_TAG,
+};
+use crate::features::setup_manifest::{SetupManifest, SETUP_MANIFEST_PATH};
+use crate::output::{print_error, print_info, print_json, print_success, print_warning};
+use crate::utils::text::version_older;
+use crate::utils::time::unix_now_seconds;
 
 /// Run the update command.
-pub fn run_update(format: OutputFormat) -> Result<()> {
-    if format == OutputFormat::Json {
-        println!(r#"{{"status":"starting","command":"cargo install opennexus --bin opennexus"}}"#);
+///
+/// Without `method`, the binary updates the way the install metadata says it
+/// was installed; without a record, binaries in cargo's bin directory update
+/// through `cargo install` and any other install through the release binary.
+/// The metadata is rewritten once the update succeeds, so `uninstall`
+/// removes what this update installed. The
+/// release is `version` if given, else the latest on `channel`, which
+/// defaults to the channel remembered in the user config. A channel passed
+/// explicitly is remembered once the update succeeds.
+///
+/// When this project's `.nexus` assets were installed by an older version
+/// than the new binary, its setup is rerun with the new binary if
+/// `sync_assets` is set or the user agrees at the prompt.
+pub fn run_update(
+    format: OutputFormat,
+    method: Option<UpdateMethod>,
+    version: Option<&str>,
+    channel: Option<UpdateChannel>,
+    sync_assets: bool,
+) -> Result<()> {
+    let exe =
+        std::env::current_exe().context("Failed to locate the running OpenNexus executable.")?;
+    let metadata = InstallMetadata::for_executable(&exe);
+    let method = method.unwrap_or(metadata.method);
+    let resolved_channel = resolve_channel(channel)?;
+    let target = match version {
+        Some(version) => ReleaseTarget::Version(version),
+        None => ReleaseTarget::Channel(resolved_channel),
+    };
+    let version = match method {
+        UpdateMethod::Cargo => update_with_cargo(format, &metadata.package, target)?,
+        UpdateMethod::Binary => match update_from_release(format, &exe, target)? {
+            Some(version) => Some(version),
+            None => return Ok(()),
+        },
+    };
+    if let Some(channel) = channel {
+        remember_channel(channel)?;
+    }
+
+    // Project assets stay at the version setup recorded until setup reruns.
+    let assets_version = SetupManifest::read(Path::new(SETUP_MANIFEST_PATH))
+        .ok()
+        .flatten()
+        .map(|manifest| manifest.version);
+    let installed = installed_version(&exe).or_else(|| version.clone());
+    record_install(&metadata, method, &exe, installed.as_deref());
+    let outdated = assets_version
+        .as_deref()
+        .zip(installed.as_deref())
+        .is_some_and(|(assets, installed)| version_older(assets, installed));
+
+    let changelog = update_changelog(version.as_deref().or(installed.as_deref()));
+    if !format.is_structured() {
+        match &version {
+            Some(version) => print_success(&format!("OpenNexus updated to {}", version)),
+            None => print_success("OpenNexus updated successfully"),
+        }
+        print_changelog(&changelog);
+    }
+    let sync = outdated && (sync_assets || confirm_sync(format, assets_version.as_deref())?);
+    if sync {
+        sync_project_assets(format, &exe)?;
+    } else if outdated && !format.is_structured() {
+        print_info(&format!(
+            "This project's .nexus assets were installed by {}. Run `opennexus setup` to refresh them.",
+            assets_version.as_deref().unwrap_or_default()
+        ));
+    }
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "completed",
+            "method": method,
+            "channel": resolved_channel,
+            "version": version,
+            "assets_version": assets_version,
+            "assets_synced": sync,
+            "changelog": changelog,
+        }))?;
+    }
+    Ok(())
+}
+
+/// Record how the binary was just installed. `cargo install` writes to
+/// cargo's bin directory whatever `exe` was. The update itself succeeded,
+/// so a failed write is only a warning; stateless mode writes nothing.
+fn record_install(
+    metadata: &InstallMetadata,
+    method: UpdateMethod,
+    exe: &Path,
+    installed: Option<&str>,
+) {
+    if is_stateless() {
+        return;
+    }
+    let path = match method {
+        UpdateMethod::Cargo => cargo_bin_dir()
+            .map(|bin| bin.join(format!("{}{}", BINARY_NAME, std::env::consts::EXE_SUFFIX)))
+            .unwrap_or_else(|| exe.to_path_buf()),
+        UpdateMethod::Binary => exe.to_path_buf(),
+    };
+    let record = InstallMetadata {
+        package: metadata.package.clone(),
+        method,
+        path,
+        version: installed.unwrap_or(&metadata.version).to_string(),
+    };
+    if let Err(err) = record.save() {
+        print_warning(&format!("Could not record the install: {:#}", err));
+    }
+}
+
+/// Release notes from the running version up to `installed`. A failed
+/// lookup only costs the notes, so it is a warning.
+fn update_changelog(installed: Option<&str>) -> Vec<ChangelogEntry> {
+    let current = env!("CARGO_PKG_VERSION");
+    let Some(installed) = installed else {
+        return Vec::new();
+    };
+    if installed != NIGHTLY_TAG && !version_older(current, installed) {
+        return Vec::new();
+    }
+    fetch_changelog(current, installed).unwrap_or_else(|err| {
+        print_warning(&format!("Could not fetch release notes: {:#}", err));
+        Vec::new()
+    })
+}
+
+fn print_changelog(changelog: &[ChangelogEntry]) {
+    if changelog.is_empty() {
+        return;
+    }
+    println!();
+    println!("What's new:");
+    for entry in changelog {
+        println!();
+        println!("## {}", entry.version);
+        if !entry.notes.is_empty() {
+            println!();
+            println!("{}", entry.notes);
+        }
+    }
+    println!();
+}
+
+/// The version `exe` reports, from `<exe> --version`.
+fn installed_version(exe: &Path) -> Option<String> {
+    let output = Command::new(exe).arg("--version").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.split_whitespace().last()?.to_string())
+}
+
+/// Ask whether to refresh outdated project assets; never asks in
+/// structured output or without a terminal.
+fn confirm_sync(format: OutputFormat, assets_version: Option<&str>) -> Result<bool> {
+    if !setup_is_interactive(format) {
+        return Ok(false);
+    }
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "This project's .nexus assets were installed by {}. Refresh them now?",
+            assets_version.unwrap_or("an older version")
+        ))
+        .default(true)
+        .interact()
+        .context("Failed to read confirmation")
+}
+
+/// Rerun this project's setup with the updated binary at `exe`, keeping the
+/// harness from `.nexus/config.json`; setup reuses the rest of its choices
+/// from the manifest and config.
+fn sync_project_assets(format: OutputFormat, exe: &Path) -> Result<()> {
+    let harness = read_config_value(Path::new(NEXUS_CONFIG_PATH))
+        .ok()
+        .and_then(|config| config.get("harness")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| SUPPORTED_HARNESSES[0].to_string());
+    let mut command = Command::new(exe);
+    if let Some(name) = format.to_possible_value() {
+        command.args(["--format", name.get_name()]);
+    }
+    let status = command
+        .args(["setup", "--harness", &harness])
+        .status()
+        .context("Failed to run the updated OpenNexus binary.")?;
+    if !status.success() {
+        anyhow::bail!(
+            "OpenNexus was updated, but refreshing this project's .nexus assets failed. Remediation: run `opennexus setup`."
+        );
+    }
+    Ok(())
+}
+
+/// Report whether a newer release than this binary is published on
+/// `channel` (default: the remembered one), refreshing the cached lookup the
+/// startup notice reads.
+pub fn run_update_check(format: OutputFormat, channel: Option<UpdateChannel>) -> Result<()> {
+    let channel = resolve_channel(channel)?;
+    let release = fetch_release(ReleaseTarget::Channel(channel))?;
+    let check = UpdateCheck {
+        checked_at: unix_now_seconds(),
+        latest: release.version().to_string(),
+    };
+    // The answer stands without the cache; it only saves a later lookup.
+    if !is_stateless() {
+        let _ = write_update_check(&check);
+    }
+    let current = env!("CARGO_PKG_VERSION");
+    let available = check.update_available(current);
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "checked",
+            "channel": channel,
+            "current": current,
+            "latest": check.latest,
+            "update_available": available,
+        }))?;
+    } else if available {
+        print_info(&format!(
+            "OpenNexus {} is available (installed: {}). Run `opennexus update` to install it.",
+            check.latest, current
+        ));
+    } else {
+        print_success(&format!("OpenNexus {} is up to date", current));
+    }
+    Ok(())
+}
+
+/// Print a one-line notice when the cached lookup found a newer release,
+/// and refresh the lookup once it is a day old. The refresh runs as a
+/// detached `update --check` so it outlives short commands; the cache is
+/// stamped first, so an offline machine tries again a day later rather than
+/// on every run.
+pub fn notify_available_update() {
+    let now = unix_now_seconds();
+    let cached = read_update_check();
+    if let Some(check) = &cached {
+        let current = env!("CARGO_PKG_VERSION");
+        if check.update_available(current) {
+            print_info(&format!(
+                "OpenNexus {} is available (installed: {}). Run `opennexus update`.",
+                check.latest, current
+            ));
+        }
+    }
+    if !cached.as_ref().is_none_or(|check| check.is_stale(now)) {
+        return;
+    }
+    let stamp = UpdateCheck {
+        checked_at: now,
+        latest: cached.map_or_else(
+            || env!("CARGO_PKG_VERSION").to_string(),
+            |check| check.latest,
+        ),
+    };
+    if write_update_check(&stamp).is_err() {
+        return;
+    }
+    if let Ok(exe) = std::env::current_exe() {
+        let mut command = Command::new(exe);
+        command
+            .args(["--quiet", "--color", "never", "update", "--check"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        // Keep Ctrl-C in the terminal from reaching the check.
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let _ = command.spawn();
+    }
+}
+
+/// The channel passed, else the one in the user config, else stable.
+fn resolve_channel(channel: Option<UpdateChannel>) -> Result<UpdateChannel> {
+    if let Some(channel) = channel {
+        return Ok(channel);
+    }
+    let Some(path) = user_config_path() else {
+        return Ok(UpdateChannel::default());
+    };
+    Ok(load_user_config(&path)?
+        .and_then(|config| config.update_channel)
+        .unwrap_or_default())
+}
+
+fn remember_channel(channel: UpdateChannel) -> Result<()> {
+    let Some(path) = user_config_path() else {
+        return Ok(());
+    };
+    let mut config = load_user_config(&path)?.unwrap_or_default();
+    if config.update_channel.unwrap_or_default() == channel {
+        return Ok(());
+    }
+    config.update_channel = Some(channel);
+    save_user_config(&path, &config)
+}
+
+/// Reinstall from crates.io, or build nightly from the repository. Beta
+/// resolves to the newest release's version first. Cargo does not report
+/// the version it built, so only a known version is returned.
+fn update_with_cargo(
+    format: OutputFormat,
+    package: &str,
+    target: ReleaseTarget,
+) -> Result<Option<String>> {
+    let beta;
+    let target = match target {
+        ReleaseTarget::Channel(UpdateChannel::Beta) => {
+            beta = fetch_release(target)?;
+            ReleaseTarget::Version(beta.version())
+        }
+        target => target,
+    };
+    let args = cargo_install_args(package, target);
+    let command = format!("cargo {}", args.join(" "));
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "starting",
+            "method": UpdateMethod::Cargo,
+            "command": command,
+        }))?;
     } else {
-        print_info("Updating OpenNexus via cargo...");
+        print_info(&format!("Updating OpenNexus via `{}`...", command));
     }
 
     let status = Command::new("cargo")
-        .args(["install", "opennexus", "--bin", "opennexus", "--force"])
+        .args(&args)
         .status()
-        .context("Failed to run cargo. Is Rust/cargo installed?")?;
+        .context("Failed to run cargo. Is Rust/cargo installed? Remediation: run `opennexus update --method binary`.")?;
 
-    if status.success() {
-        if format == OutputFormat::Json {
-            println!(r#"{{"status":"completed"}}"#);
+    if !status.success() {
+        if format.is_structured() {
+            print_json(&serde_json::json!({
+                "status": "failed",
+                "message": "cargo install returned non-zero exit code",
+            }))?;
         } else {
-            print_success("OpenNexus updated successfully");
+            print_error("Update failed: cargo install returned non-zero exit code");
         }
-        Ok(())
-    } else {
-        if format == OutputFormat::Json {
-            println!(
-                r#"{{"status":"failed","message":"cargo install returned non-zero exit code"}}"#
+        anyhow::bail!("Update failed");
+    }
+    Ok(match target {
+        ReleaseTarget::Version(version) => Some(version.trim_start_matches('v').to_string()),
+        ReleaseTarget::Channel(_) => None,
+    })
+}
+
+/// Replace `exe` with the binary of the release `target` selects, returning
+/// its version, or `None` after reporting that this binary is already that
+/// version.
+fn update_from_release(
+    format: OutputFormat,
+    exe: &Path,
+    target: ReleaseTarget,
+) -> Result<Option<String>> {
+    let release = fetch_release(target)?;
+    let current = env!("CARGO_PKG_VERSION");
+    if let ReleaseTarget::Channel(channel) = target {
+        if release.tag_name != NIGHTLY_TAG && version_older(release.version(), current) {
+            let channel = channel
+                .to_possible_value()
+                .map_or_else(String::new, |value| value.get_name().to_string());
+            anyhow::bail!(
+                "The latest {} release, {}, is older than the installed {}. Remediation: pass --version {} to downgrade on purpose, or --channel beta to stay on prereleases.",
+                channel,
+                release.version(),
+                current,
+                release.version()
             );
+        }
+    }
+    if release.version() == current {
+        if format.is_structured() {
+            print_json(&serde_json::json!({
+                "status": "up_to_date",
+                "method": UpdateMethod::Binary,
+                "version": current,
+            }))?;
         } else {
-            print_error("Update failed: cargo install returned non-zero exit code");
+            print_success(&format!(
+                "OpenNexus {} is already the selected release",
+                current
+            ));
         }
-        anyhow::bail!("Update failed")
+        return Ok(None);
+    }
+
+    let asset = release.platform_asset()?;
+    if format.is_structured() {
+        print_json(&serde_json::json!({
+            "status": "starting",
+            "method": UpdateMethod::Binary,
+            "release": release.tag_name,
+            "asset": asset.name,
+        }))?;
+    } else {
+        print_info(&format!(
+            "Updating OpenNexus {} to {} from release asset {}...",
+            current,
+            release.version(),
+            asset.name
+        ));
     }
+    let contents = download_asset(asset)?;
+    replace_executable(exe, &contents)?;
+    Ok(Some(release.version().to_string()))
 }
@@ -0,0 +1,134 @@
+//! Dotted-key access into JSON config documents, e.g. `defaults.format` or
+//! `retention.max_age_days`, for `config get/set/unset/list`.
+
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+
+pub fn get_key<'a>(document: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(document, |value, segment| value.get(segment))
+}
+
+/// Set `key`, creating intermediate objects as needed.
+pub fn set_key(document: &mut Value, key: &str, new_value: Value) -> Result<()> {
+    let segments = key.split('.').collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        bail!(
+            "Invalid key '{}'. Remediation: use dot-separated names such as `defaults.format`.",
+            key
+        );
+    }
+    let (last, parents) = segments.split_last().expect("split yields a segment");
+    let mut current = document;
+    for (depth, segment) in parents.iter().enumerate() {
+        if !current.is_object() {
+            bail!(
+                "'{}' is not an object. Remediation: unset it before setting nested keys.",
+                segments[..depth].join(".")
+            );
+        }
+        current = current
+            .as_object_mut()
+            .expect("checked object")
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    let Some(object) = current.as_object_mut() else {
+        bail!(
+            "'{}' is not an object. Remediation: unset it before setting nested keys.",
+            parents.join(".")
+        );
+    };
+    object.insert(last.to_string(), new_value);
+    Ok(())
+}
+
+/// Remove `key`, returning the previous value when it was set.
+pub fn unset_key(document: &mut Value, key: &str) -> Option<Value> {
+    let (parent, last) = match key.rsplit_once('.') {
+        Some((parent, last)) => (
+            parent
+                .split('.')
+                .try_fold(document, |value, segment| value.get_mut(segment))?,
+            last,
+        ),
+        None => (document, key),
+    };
+    parent.as_object_mut()?.remove(last)
+}
+
+/// Leaf values keyed by dotted path; arrays count as leaves.
+pub fn flatten_keys(document: &Value) -> Vec<(String, &Value)> {
+    fn walk<'a>(prefix: &str, value: &'a Value, entries: &mut Vec<(String, &'a Value)>) {
+        match value.as_object() {
+            Some(object) if !object.is_empty() || prefix.is_empty() => {
+                for (key, child) in object {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    walk(&path, child, entries);
+                }
+            }
+            _ => entries.push((prefix.to_string(), value)),
+        }
+    }
+    let mut entries = Vec::new();
+    walk("", document, &mut entries);
+    entries
+}
+
+/// Interpret a command-line value as JSON (numbers, booleans, arrays,
+/// objects, `null`), falling back to a plain string.
+pub fn parse_value(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Render a value for text output: strings bare, everything else as JSON.
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sets_gets_unsets_and_flattens_dotted_keys() {
+        let mut document = json!({ "harness": "opencode" });
+        set_key(&mut document, "defaults.max_iterations", parse_value("5")).expect("set");
+        set_key(&mut document, "defaults.format", parse_value("json")).expect("set");
+        assert_eq!(
+            get_key(&document, "defaults.max_iterations"),
+            Some(&json!(5))
+        );
+        assert_eq!(get_key(&document, "defaults.format"), Some(&json!("json")));
+        assert!(set_key(&mut document, "harness.name", json!("x")).is_err());
+        assert!(set_key(&mut document, "defaults..format", json!("x")).is_err());
+
+        let flattened = flatten_keys(&document)
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key, display_value(value)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flattened,
+            vec![
+                "defaults.format=json",
+                "defaults.max_iterations=5",
+                "harness=opencode"
+            ]
+        );
+
+        assert_eq!(
+            unset_key(&mut document, "defaults.format"),
+            Some(json!("json"))
+        );
+        assert_eq!(unset_key(&mut document, "defaults.format"), None);
+        assert_eq!(unset_key(&mut document, "missing.key"), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! Layered defaults for flags that were not passed explicitly.
+//!
+//! Each setting takes the highest-precedence value that is set, in order:
+//! the explicit CLI flag (applied by the caller), `NEXUS_*` environment
+//! variables, the `defaults` section of `.nexus/config.json`, then the user
+//! config in `~/.config/opennexus/config.json`. Built-in defaults apply last.
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::path::Path;
+
+use crate::config::nexus::{load_nexus_config, DefaultsConfig, NEXUS_CONFIG_PATH};
+use crate::config::user::UserConfig;
+
+pub const FORMAT_ENV: &str = "NEXUS_FORMAT";
+pub const COLOR_ENV: &str = "NEXUS_COLOR";
+pub const MAX_ITERATIONS_ENV: &str = "NEXUS_MAX_ITERATIONS";
+pub const TIMEOUT_SECONDS_ENV: &str = "NEXUS_TIMEOUT_SECONDS";
+pub const GRAPH_HOST_ENV: &str = "NEXUS_GRAPH_HOST";
+pub const GRAPH_PORT_ENV: &str = "NEXUS_GRAPH_PORT";
+
+impl DefaultsConfig {
+    /// Keep every value `higher` sets and fall back to `self` for the rest.
+    pub fn overlay(self, higher: DefaultsConfig) -> DefaultsConfig {
+        DefaultsConfig {
+            color: higher.color.or(self.color),
+            format: higher.format.or(self.format),
+            max_iterations: higher.max_iterations.or(self.max_iterations),
+            timeout_seconds: higher.timeout_seconds.or(self.timeout_seconds),
+            graph_host: higher.graph_host.or(self.graph_host),
+            graph_port: higher.graph_port.or(self.graph_port),
+        }
+    }
+}
+
+/// Merge the user, project, and environment layers below the CLI flags.
+pub fn resolve_defaults(user: &UserConfig) -> Result<DefaultsConfig> {
+    let user_layer = DefaultsConfig {
+        color: user.color,
+        format: user.format,
+        ..DefaultsConfig::default()
+    };
+    let env_layer = env_defaults(|name| std::env::var(name).ok())?;
+    Ok(user_layer
+        .overlay(project_defaults(Path::new(NEXUS_CONFIG_PATH)))
+        .overlay(env_layer))
+}
+
+/// The `defaults` section of the project config. An unreadable or invalid
+/// config contributes nothing here; `config doctor` and the commands that
+/// load it report the problem.
+fn project_defaults(path: &Path) -> DefaultsConfig {
+    load_nexus_config(path)
+        .map(|config| config.defaults)
+        .unwrap_or_default()
+}
+
+fn env_defaults(lookup: impl Fn(&str) -> Option<String>) -> Result<DefaultsConfig> {
+    let value = |name: &str| {
+        lookup(name)
+            .map(|raw| raw.trim().to_string())
+            .filter(|raw| !raw.is_empty())
+    };
+    Ok(DefaultsConfig {
+        color: value(COLOR_ENV)
+            .map(|raw| parse_env(COLOR_ENV, &raw, |raw| ValueEnum::from_str(raw, true)))
+            .transpose()?,
+        format: value(FORMAT_ENV)
+            .map(|raw| parse_env(FORMAT_ENV, &raw, |raw| ValueEnum::from_str(raw, true)))
+            .transpose()?,
+        max_iterations: value(MAX_ITERATIONS_ENV)
+            .map(|raw| parse_env(MAX_ITERATIONS_ENV, &raw, str::parse))
+            .transpose()?,
+        timeout_seconds: value(TIMEOUT_SECONDS_ENV)
+            .map(|raw| parse_env(TIMEOUT_SECONDS_ENV, &raw, str::parse))
+            .transpose()?,
+        graph_host: value(GRAPH_HOST_ENV),
+        graph_port: value(GRAPH_PORT_ENV)
+            .map(|raw| parse_env(GRAPH_PORT_ENV, &raw, str::parse))
+            .transpose()?,
+    })
+}
+
+fn parse_env<T, E>(name: &str, raw: &str, parse: impl Fn(&str) -> Result<T, E>) -> Result<T> {
+    parse(raw).map_err(|_| {
+        anyhow!(
+            "Invalid value '{}' in {}. Remediation: fix or unset the variable.",
+            raw,
+            name
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ColorChoice, OutputFormat};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn higher_layers_override_lower_ones_per_key() {
+        let dir = tempdir().expect("tempdir");
+        let config = dir.path().join("config.json");
+        fs::write(
+            &config,
+            r#"{ "defaults": { "format": "tsv", "max_iterations": 5, "timeout_seconds": 120 } }"#,
+        )
+        .expect("config");
+        let user = DefaultsConfig {
+            color: Some(ColorChoice::Never),
+            format: Some(OutputFormat::Json),
+            ..DefaultsConfig::default()
+        };
+        let env = env_defaults(|name| match name {
+            MAX_ITERATIONS_ENV => Some(" 7 ".to_string()),
+            FORMAT_ENV => Some(String::new()),
+            GRAPH_PORT_ENV => Some("9000".to_string()),
+            _ => None,
+        })
+        .expect("env");
+
+        let resolved = user.overlay(project_defaults(&config)).overlay(env);
+        assert_eq!(
+            resolved,
+            DefaultsConfig {
+                color: Some(ColorChoice::Never),
+                format: Some(OutputFormat::Tsv),
+                max_iterations: Some(7),
+                timeout_seconds: Some(120),
+                graph_host: None,
+                graph_port: Some(9000),
+            }
+        );
+        assert_eq!(
+            project_defaults(&dir.path().join("missing.json")),
+            DefaultsConfig::default()
+        );
+
+        let error = env_defaults(|name| (name == COLOR_ENV).then(|| "sometimes".to_string()))
+            .expect_err("invalid color");
+        assert!(error.to_string().contains("NEXUS_COLOR"));
+    }
+}
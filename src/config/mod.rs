@@ -1 +1,6 @@
+pub mod keys;
+pub mod layered;
+pub mod nexus;
 pub mod opencode;
+pub mod stateless;
+pub mod user;
@@ -0,0 +1,662 @@
+//! Typed schema, validation, and migration for `.nexus/config.json`.
+//!
+//! Validation walks the raw JSON against a static field table so errors can
+//! name the exact key path; the typed `NexusConfig` is only built once the
+//! document passes.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::cli::{ColorChoice, OutputFormat};
+use crate::services::vcs::{VcsKind, SUPPORTED_VCS};
+use crate::utils::text::closest_match;
+
+pub const NEXUS_CONFIG_PATH: &str = ".nexus/config.json";
+pub const LEGACY_DOCS_SYNC_STATE_PATH: &str = ".nexus/docs-sync-state.json";
+pub const SUPPORTED_HARNESSES: [&str; 1] = ["opencode"];
+const SUPPORTED_COLORS: [&str; 3] = ["auto", "always", "never"];
+const SUPPORTED_FORMATS: [&str; 5] = ["text", "json", "tsv", "yaml", "ndjson"];
+
+/// Typed view of the keys the CLI reads; other keys are checked by the schema table.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NexusConfig {
+    #[serde(default)]
+    pub harness: Option<String>,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub presets: BTreeMap<String, OrchestrationPreset>,
+    /// Shell commands run after a pipeline succeeds, keyed by pipeline name.
+    #[serde(default)]
+    pub post_processors: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub context_index: ContextIndexConfig,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// Version-control backend; detected from the repository when unset.
+    #[serde(default)]
+    pub vcs: Option<VcsKind>,
+    /// Project defaults for global flags; see `config::layered`.
+    #[serde(default)]
+    pub defaults: DefaultsConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ContextIndexConfig {
+    /// Regenerate project `index.md` tables after commands that add contexts.
+    #[serde(default)]
+    pub auto: bool,
+}
+
+/// Limits for local run history, enforced lazily and by `clean --history`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct RetentionConfig {
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+}
+
+impl RetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_age_days.is_none() && self.max_size_mb.is_none()
+    }
+}
+
+/// Defaults for flags that were not passed explicitly, shared by the user
+/// config, `.nexus/config.json`, and `NEXUS_*` environment variables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefaultsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorChoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<OutputFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_port: Option<u16>,
+}
+
+/// Named orchestration flags stored under `presets.<name>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrchestrationPreset {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_discovery_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_dependency_bypass: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigIssueSeverity {
+    Error,
+    Deprecated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigIssue {
+    pub path: String,
+    pub severity: ConfigIssueSeverity,
+    pub message: String,
+}
+
+enum FieldKind {
+    String,
+    Bool,
+    Integer,
+    StringList,
+    Enum(&'static [&'static str]),
+    Object(&'static [Field]),
+    Map(&'static FieldKind),
+    Any,
+}
+
+struct Field {
+    name: &'static str,
+    kind: FieldKind,
+}
+
+const SKILL_PROJECT_FIELDS: &[Field] = &[
+    Field {
+        name: "include_folders",
+        kind: FieldKind::StringList,
+    },
+    Field {
+        name: "exclude_folders",
+        kind: FieldKind::StringList,
+    },
+    Field {
+        name: "updated_at",
+        kind: FieldKind::String,
+    },
+];
+
+const SKILL_GENERATION_FIELDS: &[Field] = &[Field {
+    name: "projects",
+    kind: FieldKind::Map(&FieldKind::Object(SKILL_PROJECT_FIELDS)),
+}];
+
+const AUDIT_FIELDS: &[Field] = &[Field {
+    name: "enabled",
+    kind: FieldKind::Bool,
+}];
+
+const CONTEXT_INDEX_FIELDS: &[Field] = &[Field {
+    name: "auto",
+    kind: FieldKind::Bool,
+}];
+
+const RETENTION_FIELDS: &[Field] = &[
+    Field {
+        name: "max_age_days",
+        kind: FieldKind::Integer,
+    },
+    Field {
+        name: "max_size_mb",
+        kind: FieldKind::Integer,
+    },
+];
+
+const DEFAULTS_FIELDS: &[Field] = &[
+    Field {
+        name: "color",
+        kind: FieldKind::Enum(&SUPPORTED_COLORS),
+    },
+    Field {
+        name: "format",
+        kind: FieldKind::Enum(&SUPPORTED_FORMATS),
+    },
+    Field {
+        name: "max_iterations",
+        kind: FieldKind::Integer,
+    },
+    Field {
+        name: "timeout_seconds",
+        kind: FieldKind::Integer,
+    },
+    Field {
+        name: "graph_host",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "graph_port",
+        kind: FieldKind::Integer,
+    },
+];
+
+const PRESET_FIELDS: &[Field] = &[
+    Field {
+        name: "pipeline_file",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "max_iterations",
+        kind: FieldKind::Integer,
+    },
+    Field {
+        name: "timeout_seconds",
+        kind: FieldKind::Integer,
+    },
+    Field {
+        name: "rule_file",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "test_command",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "test_discovery_command",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "model",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "allow_dependency_bypass",
+        kind: FieldKind::Bool,
+    },
+    Field {
+        name: "overwrite",
+        kind: FieldKind::Bool,
+    },
+];
+
+const ROOT_FIELDS: &[Field] = &[
+    Field {
+        name: "harness",
+        kind: FieldKind::Enum(&SUPPORTED_HARNESSES),
+    },
+    Field {
+        name: "version",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "setup_profile",
+        kind: FieldKind::String,
+    },
+    Field {
+        name: "setup_profiles",
+        kind: FieldKind::Map(&FieldKind::StringList),
+    },
+    Field {
+        name: "integrations",
+        kind: FieldKind::StringList,
+    },
+    Field {
+        name: "gitignore",
+        kind: FieldKind::Bool,
+    },
+    Field {
+        name: "marketplace",
+        kind: FieldKind::Map(&FieldKind::Any),
+    },
+    Field {
+        name: "skill_generation",
+        kind: FieldKind::Object(SKILL_GENERATION_FIELDS),
+    },
+    Field {
+        name: "audit",
+        kind: FieldKind::Object(AUDIT_FIELDS),
+    },
+    Field {
+        name: "presets",
+        kind: FieldKind::Map(&FieldKind::Object(PRESET_FIELDS)),
+    },
+    Field {
+        name: "post_processors",
+        kind: FieldKind::Map(&FieldKind::StringList),
+    },
+    Field {
+        name: "context_index",
+        kind: FieldKind::Object(CONTEXT_INDEX_FIELDS),
+    },
+    Field {
+        name: "retention",
+        kind: FieldKind::Object(RETENTION_FIELDS),
+    },
+    Field {
+        name: "vcs",
+        kind: FieldKind::Enum(&SUPPORTED_VCS),
+    },
+    Field {
+        name: "defaults",
+        kind: FieldKind::Object(DEFAULTS_FIELDS),
+    },
+    Field {
+        name: "docs_sync_state",
+        kind: FieldKind::Any,
+    },
+];
+
+const DEPRECATED_ROOT_KEYS: &[(&str, &str)] = &[(
+    "docs_sync_state",
+    "moved to `marketplace.fumadocs.sync_state`",
+)];
+
+/// Load and validate `.nexus/config.json`; a missing file yields defaults.
+pub fn load_nexus_config(path: &Path) -> Result<NexusConfig> {
+    if !path.exists() {
+        return Ok(NexusConfig::default());
+    }
+    let value = read_config_value(path)?;
+    let errors = validate_config_value(&value)
+        .into_iter()
+        .filter(|issue| issue.severity == ConfigIssueSeverity::Error)
+        .map(|issue| format!("{}: {}", issue.path, issue.message))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        bail!(
+            "Invalid '{}':\n  - {}\nRemediation: fix the listed keys, then run `opennexus config doctor`.",
+            path.display(),
+            errors.join("\n  - ")
+        );
+    }
+    serde_json::from_value(value).with_context(|| format!("Failed to load '{}'.", path.display()))
+}
+
+pub fn read_config_value(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read '{}'.", path.display()))?;
+    serde_json::from_str(&content).with_context(|| {
+        format!(
+            "'{}' is not valid JSON. Remediation: fix the syntax error and retry.",
+            path.display()
+        )
+    })
+}
+
+/// Write a config document back as indented JSON.
+pub fn write_config_value(path: &Path, value: &Value) -> Result<()> {
+    let serialized = serde_json::to_string_pretty(value).context("Failed to serialize config")?;
+    fs::write(path, format!("{serialized}\n"))
+        .with_context(|| format!("Failed to write '{}'.", path.display()))
+}
+
+/// Check a raw config document against the schema.
+pub fn validate_config_value(value: &Value) -> Vec<ConfigIssue> {
+    let mut issues = Vec::<ConfigIssue>::new();
+    validate_object(value, ROOT_FIELDS, "", &mut issues);
+    if let Some(object) = value.as_object() {
+        for (key, replacement) in DEPRECATED_ROOT_KEYS {
+            if object.contains_key(*key) {
+                issues.push(ConfigIssue {
+                    path: key.to_string(),
+                    severity: ConfigIssueSeverity::Deprecated,
+                    message: format!("deprecated; {}", replacement),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Rewrite deprecated config shapes in place and describe each change.
+///
+/// `legacy_docs_sync_state` is the parsed `.nexus/docs-sync-state.json`, if any.
+pub fn migrate_deprecated_config(
+    config: &mut Value,
+    legacy_docs_sync_state: Option<Value>,
+) -> Vec<String> {
+    let mut migrations = Vec::<String>::new();
+    let Some(root) = config.as_object_mut() else {
+        return migrations;
+    };
+
+    if let Some(state) = root.remove("docs_sync_state") {
+        if insert_fumadocs_sync_state(root, state) {
+            migrations
+                .push("Moved `docs_sync_state` to `marketplace.fumadocs.sync_state`.".to_string());
+        } else {
+            migrations.push(
+                "Removed `docs_sync_state`; `marketplace.fumadocs.sync_state` is already set."
+                    .to_string(),
+            );
+        }
+    }
+
+    if let Some(state) = legacy_docs_sync_state {
+        if insert_fumadocs_sync_state(root, state) {
+            migrations.push(format!(
+                "Imported `{}` into `marketplace.fumadocs.sync_state`.",
+                LEGACY_DOCS_SYNC_STATE_PATH
+            ));
+        } else {
+            migrations.push(format!(
+                "Dropped `{}`; `marketplace.fumadocs.sync_state` is already set.",
+                LEGACY_DOCS_SYNC_STATE_PATH
+            ));
+        }
+    }
+
+    migrations
+}
+
+fn insert_fumadocs_sync_state(root: &mut Map<String, Value>, state: Value) -> bool {
+    let marketplace = root
+        .entry("marketplace")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !marketplace.is_object() {
+        *marketplace = Value::Object(Map::new());
+    }
+    let fumadocs = marketplace
+        .as_object_mut()
+        .expect("marketplace object")
+        .entry("fumadocs")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !fumadocs.is_object() {
+        *fumadocs = Value::Object(Map::new());
+    }
+    let fumadocs = fumadocs.as_object_mut().expect("fumadocs object");
+    if fumadocs.contains_key("sync_state") {
+        return false;
+    }
+    fumadocs.insert("sync_state".to_string(), state);
+    true
+}
+
+fn validate_object(value: &Value, fields: &[Field], path: &str, issues: &mut Vec<ConfigIssue>) {
+    let Some(object) = value.as_object() else {
+        push_type_error(value, "an object", path, issues);
+        return;
+    };
+    let names = fields.iter().map(|field| field.name).collect::<Vec<_>>();
+    for (key, child) in object {
+        let child_path = join_path(path, key);
+        match fields.iter().find(|field| field.name == key) {
+            Some(field) => validate_kind(child, &field.kind, &child_path, issues),
+            None => {
+                let suggestion = closest_match(key, &names)
+                    .map(|name| format!("; did you mean `{}`?", name))
+                    .unwrap_or_default();
+                issues.push(ConfigIssue {
+                    path: child_path,
+                    severity: ConfigIssueSeverity::Error,
+                    message: format!("unknown key{}", suggestion),
+                });
+            }
+        }
+    }
+}
+
+fn validate_kind(value: &Value, kind: &FieldKind, path: &str, issues: &mut Vec<ConfigIssue>) {
+    match kind {
+        FieldKind::String => {
+            if !value.is_string() {
+                push_type_error(value, "a string", path, issues);
+            }
+        }
+        FieldKind::Bool => {
+            if !value.is_boolean() {
+                push_type_error(value, "a boolean", path, issues);
+            }
+        }
+        FieldKind::Integer => {
+            if !value.is_u64() {
+                push_type_error(value, "a non-negative integer", path, issues);
+            }
+        }
+        FieldKind::StringList => match value.as_array() {
+            Some(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if !item.is_string() {
+                        push_type_error(item, "a string", &format!("{}[{}]", path, index), issues);
+                    }
+                }
+            }
+            None => push_type_error(value, "an array of strings", path, issues),
+        },
+        FieldKind::Enum(allowed) => match value.as_str() {
+            Some(found) if allowed.contains(&found) => {}
+            Some(found) => {
+                let suggestion = closest_match(found, allowed)
+                    .map(|name| format!("; did you mean '{}'?", name))
+                    .unwrap_or_default();
+                issues.push(ConfigIssue {
+                    path: path.to_string(),
+                    severity: ConfigIssueSeverity::Error,
+                    message: format!(
+                        "invalid value '{}', expected one of: {}{}",
+                        found,
+                        allowed.join(", "),
+                        suggestion
+                    ),
+                });
+            }
+            None => push_type_error(value, "a string", path, issues),
+        },
+        FieldKind::Object(fields) => validate_object(value, fields, path, issues),
+        FieldKind::Map(inner) => match value.as_object() {
+            Some(entries) => {
+                for (key, child) in entries {
+                    validate_kind(child, inner, &join_path(path, key), issues);
+                }
+            }
+            None => push_type_error(value, "an object", path, issues),
+        },
+        FieldKind::Any => {}
+    }
+}
+
+fn push_type_error(value: &Value, expected: &str, path: &str, issues: &mut Vec<ConfigIssue>) {
+    issues.push(ConfigIssue {
+        path: if path.is_empty() {
+            "(root)".to_string()
+        } else {
+            path.to_string()
+        },
+        severity: ConfigIssueSeverity::Error,
+        message: format!("expected {}, found {}", expected, json_type_name(value)),
+    });
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn validation_reports_unknown_keys_types_and_enum_values() {
+        let issues = validate_config_value(&json!({
+            "harnes": "opencode",
+            "harness": "opencod",
+            "audit": { "enabled": "yes" },
+            "skill_generation": { "projects": { "app": { "include_folders": ["src", 3] } } },
+        }));
+        let messages = issues
+            .iter()
+            .map(|issue| format!("{}: {}", issue.path, issue.message))
+            .collect::<Vec<_>>();
+
+        assert!(messages.contains(&"harnes: unknown key; did you mean `harness`?".to_string()));
+        assert!(messages.contains(
+            &"harness: invalid value 'opencod', expected one of: opencode; did you mean 'opencode'?"
+                .to_string()
+        ));
+        assert!(messages.contains(&"audit.enabled: expected a boolean, found string".to_string()));
+        assert!(messages.contains(
+            &"skill_generation.projects.app.include_folders[1]: expected a string, found number"
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn presets_validate_and_load_typed() {
+        let issues = validate_config_value(&json!({
+            "presets": { "fast": { "max_iterations": "2", "modle": "x" } }
+        }));
+        let messages = issues
+            .iter()
+            .map(|issue| format!("{}: {}", issue.path, issue.message))
+            .collect::<Vec<_>>();
+        assert!(messages.contains(
+            &"presets.fast.max_iterations: expected a non-negative integer, found string"
+                .to_string()
+        ));
+        assert!(messages
+            .contains(&"presets.fast.modle: unknown key; did you mean `model`?".to_string()));
+
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("config.json");
+        write_config_value(
+            &path,
+            &json!({ "presets": { "fast": { "max_iterations": 1, "overwrite": true } } }),
+        )
+        .expect("write config");
+        let config = load_nexus_config(&path).expect("valid config");
+        let preset = &config.presets["fast"];
+        assert_eq!(preset.max_iterations, Some(1));
+        assert!(preset.overwrite);
+        assert_eq!(preset.model, None);
+    }
+
+    #[test]
+    fn repository_config_passes_validation() {
+        let value = read_config_value(Path::new(NEXUS_CONFIG_PATH)).expect("read repo config");
+        assert!(validate_config_value(&value).is_empty());
+    }
+
+    #[test]
+    fn load_rejects_invalid_config_with_precise_errors() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("config.json");
+        fs::write(&path, "{\"audit\":{\"enabeld\":true}}").expect("write config");
+        let err = load_nexus_config(&path).expect_err("invalid config");
+        assert!(err
+            .to_string()
+            .contains("audit.enabeld: unknown key; did you mean `enabled`?"));
+
+        fs::write(
+            &path,
+            "{\"harness\":\"opencode\",\"audit\":{\"enabled\":true}}",
+        )
+        .expect("write config");
+        let config = load_nexus_config(&path).expect("valid config");
+        assert_eq!(config.harness.as_deref(), Some("opencode"));
+        assert!(config.audit.enabled);
+    }
+
+    #[test]
+    fn migrates_deprecated_docs_sync_state_locations() {
+        let state = json!({ "docs_path": "docs", "last_synced_commit": "abc", "synced_at": "t" });
+        let mut config = json!({ "harness": "opencode", "docs_sync_state": state.clone() });
+        let deprecated = validate_config_value(&config);
+        assert_eq!(deprecated.len(), 1);
+        assert_eq!(deprecated[0].severity, ConfigIssueSeverity::Deprecated);
+
+        let migrations = migrate_deprecated_config(&mut config, Some(json!({ "docs_path": "x" })));
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(config["marketplace"]["fumadocs"]["sync_state"], state);
+        assert!(config.get("docs_sync_state").is_none());
+        assert!(validate_config_value(&config).is_empty());
+    }
+}
@@ -5,6 +5,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::services::ralph_fs::RalphPaths;
+use crate::utils::paths::home_dir;
 
 pub fn ensure_ralph_opencode_config(
     paths: &RalphPaths,
@@ -79,8 +80,8 @@ fn load_plugins() -> Vec<String> {
 
 fn candidate_configs() -> Vec<PathBuf> {
     let mut files = Vec::new();
-    if let Some(home) = std::env::var_os("HOME") {
-        files.push(Path::new(&home).join(".config/opencode/opencode.json"));
+    if let Some(home) = home_dir() {
+        files.push(home.join(".config").join("opencode").join("opencode.json"));
     }
     files.push(Path::new(".ralph/opencode.json").to_path_buf());
     files.push(Path::new(".opencode/opencode.json").to_path_buf());
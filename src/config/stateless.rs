@@ -0,0 +1,43 @@
+//! Stateless mode for ephemeral CI containers and read-only filesystems.
+//!
+//! Enabled with the global `--stateless` flag or `NEXUS_STATELESS=1`. While
+//! active, run history, caches, and resume breadcrumbs are kept in memory (or
+//! dropped) instead of being written under `.nexus/` or `.ralph/`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const STATELESS_ENV: &str = "NEXUS_STATELESS";
+
+static STATELESS: AtomicBool = AtomicBool::new(false);
+
+/// Turn on stateless mode for the rest of the process.
+pub fn enable_stateless_mode() {
+    STATELESS.store(true, Ordering::Relaxed);
+}
+
+/// Whether local state writes are disabled by flag or environment.
+pub fn is_stateless() -> bool {
+    STATELESS.load(Ordering::Relaxed)
+        || stateless_requested(std::env::var(STATELESS_ENV).ok().as_deref())
+}
+
+fn stateless_requested(value: Option<&str>) -> bool {
+    matches!(
+        value.map(|raw| raw.trim().to_ascii_lowercase()).as_deref(),
+        Some("1" | "true" | "yes" | "on")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_values_enable_stateless_mode() {
+        assert!(stateless_requested(Some("1")));
+        assert!(stateless_requested(Some(" TRUE ")));
+        assert!(!stateless_requested(Some("0")));
+        assert!(!stateless_requested(Some("")));
+        assert!(!stateless_requested(None));
+    }
+}
@@ -0,0 +1,99 @@
+//! Machine-level preferences in `~/.config/opennexus/config.json`.
+//!
+//! Unlike `.nexus/config.json`, this file is per user rather than per repo.
+//! It is written by first-run onboarding and supplies defaults for global
+//! flags that were not passed explicitly.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{ColorChoice, OutputFormat, Role, UpdateChannel};
+use crate::utils::paths::home_dir;
+
+/// Top-level keys `config set --global` accepts.
+pub const USER_CONFIG_KEYS: [&str; 5] =
+    ["color", "format", "role", "update_channel", "update_check"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserConfig {
+    /// Default for `--color`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorChoice>,
+    /// Default for `--format`; unset keeps text on a terminal and tsv when piped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<OutputFormat>,
+    /// `viewer` pins this machine to read-only commands regardless of `--role`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    /// `true` opts in to a once-a-day check for a newer release, noted on
+    /// stderr when one is out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_check: Option<bool>,
+    /// Release channel `update` follows; set by `update --channel`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_channel: Option<UpdateChannel>,
+}
+
+/// `$XDG_CONFIG_HOME/opennexus/config.json`, falling back to `~/.config` (`%USERPROFILE%` on Windows).
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))?;
+    Some(base.join("opennexus").join("config.json"))
+}
+
+/// Load the user config; `None` when the file has never been written.
+pub fn load_user_config(path: &Path) -> Result<Option<UserConfig>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read user config '{}'.", path.display()))?;
+    let config = serde_json::from_str(&content).with_context(|| {
+        format!(
+            "Invalid user config '{}'. Remediation: fix the JSON or delete the file to re-run onboarding.",
+            path.display()
+        )
+    })?;
+    Ok(Some(config))
+}
+
+pub fn save_user_config(path: &Path, config: &UserConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+    }
+    let serialized = serde_json::to_string_pretty(config)?;
+    fs::write(path, format!("{}\n", serialized))
+        .with_context(|| format!("Failed to write user config '{}'.", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn round_trips_preferences_and_reports_missing_file() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp.path().join("opennexus/config.json");
+        assert_eq!(load_user_config(&path).expect("load missing"), None);
+
+        let config = UserConfig {
+            color: Some(ColorChoice::Never),
+            format: Some(OutputFormat::Json),
+            role: None,
+            update_check: None,
+            update_channel: None,
+        };
+        save_user_config(&path, &config).expect("save");
+        assert_eq!(
+            fs::read_to_string(&path).expect("read"),
+            "{\n  \"color\": \"never\",\n  \"format\": \"json\"\n}\n"
+        );
+        assert_eq!(load_user_config(&path).expect("load"), Some(config));
+    }
+}
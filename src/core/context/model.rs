@@ -17,6 +17,8 @@ pub struct ContextImplementOptions {
     pub resume_checkpoint: Option<PathBuf>,
     pub allow_dependency_bypass: bool,
     pub overwrite: bool,
+    /// Re-author red tests even for actions whose cached hashes are unchanged.
+    pub force_regenerate_tests: bool,
     pub run_id: Option<i64>,
 }
 
@@ -32,6 +34,36 @@ pub struct ContextBackfillOptions {
     pub all: bool,
 }
 
+/// Which tests from a context's Next Actions the test runner discovers.
+#[derive(Debug, Clone)]
+pub struct ContextTestStatusReport {
+    pub context_file: PathBuf,
+    pub command_name: &'static str,
+    pub context_id: String,
+    /// Each test id with whether `cargo test -- --list` reported it.
+    pub tests: Vec<(String, bool)>,
+}
+
+impl ContextTestStatusReport {
+    pub fn discovered_count(&self) -> usize {
+        self.tests
+            .iter()
+            .filter(|(_, discovered)| *discovered)
+            .count()
+    }
+}
+
+/// What a backfill audited: one context, or with `all` every valid spec.
+#[derive(Debug, Clone)]
+pub struct ContextBackfillReport {
+    pub all: bool,
+    /// Valid specs audited, including ones that failed to run.
+    pub contexts_audited: usize,
+    pub results: Vec<BackfillContextResult>,
+    /// Parse and execution failures from `all`, one line each.
+    pub partial_errors: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextParseResult {
     pub context_id: String,
@@ -39,6 +71,8 @@ pub struct ContextParseResult {
     pub next_actions: Vec<ContextNextAction>,
     pub test_runner: Option<String>,
     pub language: Option<String>,
+    /// Set by `frozen: true` frontmatter; pipelines refuse to run without `--unfreeze`.
+    pub frozen: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,3 +156,23 @@ pub enum ContextLoopOutcome {
     MaxIterationsReached,
     TimeoutReached,
 }
+
+impl ContextLoopOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::MaxIterationsReached => "max iterations reached",
+            Self::TimeoutReached => "timeout reached",
+        }
+    }
+}
+
+/// What an implement run produced, used to write action outcomes back to the spec.
+#[derive(Debug, Clone, Default)]
+pub struct ContextImplementSummary {
+    pub tests: Vec<String>,
+    /// `(test_id, path)` for test files generated during the run.
+    pub generated_tests: Vec<(String, PathBuf)>,
+    pub terminal_outcome: Option<ContextLoopOutcome>,
+    pub tests_passed: bool,
+}
@@ -0,0 +1,191 @@
+//! Exit codes and the structured error envelope.
+//!
+//! Handlers keep returning `anyhow` errors; the ones that need a specific exit
+//! code raise (or wrap themselves in) a `CliError`. `classify` also maps I/O
+//! not-found errors and network failures found anywhere in the chain, so
+//! most call sites need no changes.
+
+use serde::Serialize;
+use std::fmt;
+
+/// Documented process exit codes. `2` is reserved for argument errors, which
+/// clap reports before any command runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Failed,
+    NotFound,
+    Unreachable,
+    WorkflowFailed,
+    Forbidden,
+}
+
+impl ErrorCode {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Failed => 1,
+            Self::NotFound => 3,
+            Self::Unreachable => 4,
+            Self::WorkflowFailed => 5,
+            Self::Forbidden => 6,
+        }
+    }
+}
+
+/// An error with a stable code; its message is shown like any other error.
+#[derive(Debug)]
+pub struct CliError {
+    pub code: ErrorCode,
+    message: String,
+    /// Overrides `code.exit_code()`, e.g. to pass a plugin's status through.
+    exit_code: Option<u8>,
+}
+
+impl CliError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            exit_code: None,
+        }
+    }
+
+    pub fn with_exit_code(mut self, exit_code: u8) -> Self {
+        self.exit_code = Some(exit_code);
+        self
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorEnvelope {
+    pub error: ErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    pub exit_code: u8,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// The code for `error`: the outermost `CliError` in its chain, else an
+/// inferred code for missing files and network failures, else `Failed`.
+pub fn classify(error: &anyhow::Error) -> ErrorCode {
+    if let Some(cli_error) = error.downcast_ref::<CliError>() {
+        return cli_error.code;
+    }
+    for cause in error.chain() {
+        if let Some(cli_error) = cause.downcast_ref::<CliError>() {
+            return cli_error.code;
+        }
+        if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            if io_error.kind() == std::io::ErrorKind::NotFound {
+                return ErrorCode::NotFound;
+            }
+        }
+        if let Some(http_error) = cause.downcast_ref::<reqwest::Error>() {
+            if http_error.is_connect() || http_error.is_timeout() {
+                return ErrorCode::Unreachable;
+            }
+        }
+    }
+    ErrorCode::Failed
+}
+
+/// The process exit code for `error`: an explicit `CliError` override, else
+/// the code of `classify`.
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<CliError>()?.exit_code)
+        .unwrap_or_else(|| classify(error).exit_code())
+}
+
+/// Whether retrying might succeed: the server was unreachable, timed out, or
+/// answered with a 5xx or `429 Too Many Requests`.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    classify(error) == ErrorCode::Unreachable
+        || error.chain().any(|cause| {
+            cause
+                .downcast_ref::<reqwest::Error>()
+                .and_then(reqwest::Error::status)
+                .is_some_and(|status| {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                })
+        })
+}
+
+/// Build the envelope, splitting the first `Remediation:` in the chain into
+/// `suggestion`.
+pub fn error_envelope(error: &anyhow::Error) -> ErrorEnvelope {
+    let code = classify(error);
+    let mut messages = Vec::<String>::new();
+    let mut suggestion = None;
+    for cause in error.chain() {
+        let text = cause.to_string();
+        match text.split_once("Remediation:") {
+            Some((message, remediation)) if suggestion.is_none() => {
+                suggestion = Some(remediation.trim().to_string());
+                messages.push(message.trim().to_string());
+            }
+            _ => messages.push(text.trim().to_string()),
+        }
+    }
+    messages.retain(|message| !message.is_empty());
+    ErrorEnvelope {
+        error: ErrorBody {
+            code,
+            exit_code: exit_code(error),
+            message: messages.join(": "),
+            suggestion,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn classifies_chains_and_splits_remediation() {
+        let missing = std::fs::read_to_string("/definitely/missing/file")
+            .context("Unable to read context. Remediation: check the --context-file path.")
+            .unwrap_err();
+        let envelope = error_envelope(&missing);
+        assert_eq!(envelope.error.code, ErrorCode::NotFound);
+        assert_eq!(envelope.error.exit_code, 3);
+        assert!(envelope
+            .error
+            .message
+            .starts_with("Unable to read context.: "));
+        assert_eq!(
+            envelope.error.suggestion.as_deref(),
+            Some("check the --context-file path.")
+        );
+
+        let wrapped = anyhow!(CliError::new(ErrorCode::WorkflowFailed, "step red failed"))
+            .context("Pipeline 'default' failed.");
+        assert_eq!(classify(&wrapped), ErrorCode::WorkflowFailed);
+        assert_eq!(exit_code(&wrapped), 5);
+        assert_eq!(classify(&anyhow!("plain")), ErrorCode::Failed);
+
+        let plugin = anyhow!(CliError::new(ErrorCode::Failed, "plugin failed").with_exit_code(42));
+        assert_eq!(exit_code(&plugin), 42);
+        assert_eq!(error_envelope(&plugin).error.exit_code, 42);
+    }
+}
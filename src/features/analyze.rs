@@ -0,0 +1,486 @@
+//! Heuristic onboarding analysis for repositories without Nexus setup.
+//!
+//! The analyzer only reads the filesystem: it counts source files by
+//! extension, reads manifest markers, and treats source subdirectories or
+//! workspace members as module boundaries. The resulting plan maps the repo
+//! onto `.nexus/context/<project>/<feature>/index.md` files.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::features::context::test_runner::{detect_repo_toolchain, ToolchainKind};
+use crate::utils::time::{format_unix_date, unix_now_seconds};
+
+const MAX_WALK_DEPTH: usize = 6;
+const SKIPPED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "dist",
+    "build",
+    "vendor",
+    "venv",
+    "__pycache__",
+];
+const WORKSPACE_DIRS: &[&str] = &["crates", "packages", "apps", "libs", "services"];
+const SOURCE_ROOTS: &[&str] = &["src", "lib", "app", "pkg", "internal"];
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct LanguageCount {
+    pub language: String,
+    pub files: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ModuleBoundary {
+    pub name: String,
+    pub path: String,
+    pub source_files: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoAnalysis {
+    pub project: String,
+    pub nexus_present: bool,
+    pub toolchain: String,
+    pub languages: Vec<LanguageCount>,
+    pub test_frameworks: Vec<String>,
+    pub modules: Vec<ModuleBoundary>,
+    pub docs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub feature: Option<String>,
+    pub exists: bool,
+    #[serde(skip)]
+    pub contents: String,
+}
+
+/// Scan `repo_root` and summarize languages, test tooling, modules, and docs.
+pub fn analyze_repository(
+    repo_root: &Path,
+    project_override: Option<&str>,
+) -> Result<RepoAnalysis> {
+    if !repo_root.is_dir() {
+        anyhow::bail!("Cannot analyze '{}': not a directory.", repo_root.display());
+    }
+
+    let project = match project_override {
+        Some(name) => slugify(name),
+        None => {
+            let canonical = repo_root
+                .canonicalize()
+                .with_context(|| format!("Failed to resolve '{}'.", repo_root.display()))?;
+            canonical
+                .file_name()
+                .map(|name| slugify(&name.to_string_lossy()))
+                .unwrap_or_else(|| "project".to_string())
+        }
+    };
+
+    let mut extension_counts = BTreeMap::<String, usize>::new();
+    count_source_files(repo_root, 0, &mut extension_counts)?;
+    let mut languages = extension_counts
+        .into_iter()
+        .map(|(language, files)| LanguageCount { language, files })
+        .collect::<Vec<_>>();
+    languages.sort_by(|left, right| {
+        right
+            .files
+            .cmp(&left.files)
+            .then(left.language.cmp(&right.language))
+    });
+
+    Ok(RepoAnalysis {
+        project,
+        nexus_present: repo_root.join(".nexus").exists(),
+        toolchain: detect_repo_toolchain(repo_root).as_str().to_string(),
+        languages,
+        test_frameworks: detect_test_frameworks(repo_root),
+        modules: detect_module_boundaries(repo_root)?,
+        docs: detect_docs(repo_root),
+    })
+}
+
+/// Propose index files for the detected project and module boundaries.
+pub fn build_onboarding_plan(repo_root: &Path, analysis: &RepoAnalysis) -> Vec<PlannedFile> {
+    let created = format_unix_date(unix_now_seconds());
+    let project_dir = PathBuf::from(".nexus/context").join(&analysis.project);
+    let mut plan = Vec::<PlannedFile>::new();
+
+    let project_index = project_dir.join("index.md");
+    plan.push(PlannedFile {
+        exists: repo_root.join(&project_index).exists(),
+        path: project_index.to_string_lossy().to_string(),
+        feature: None,
+        contents: render_project_index(analysis, &created),
+    });
+
+    for module in &analysis.modules {
+        let feature_index = project_dir.join(&module.name).join("index.md");
+        plan.push(PlannedFile {
+            exists: repo_root.join(&feature_index).exists(),
+            path: feature_index.to_string_lossy().to_string(),
+            feature: Some(module.name.clone()),
+            contents: render_feature_index(&analysis.project, module, &created),
+        });
+    }
+
+    plan
+}
+
+/// Write planned files that do not exist yet and return their paths.
+pub fn apply_onboarding_plan(repo_root: &Path, plan: &[PlannedFile]) -> Result<Vec<String>> {
+    let mut written = Vec::<String>::new();
+    for file in plan.iter().filter(|file| !file.exists) {
+        let target = repo_root.join(&file.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+        }
+        fs::write(&target, &file.contents)
+            .with_context(|| format!("Failed to write '{}'.", target.display()))?;
+        written.push(file.path.clone());
+    }
+    Ok(written)
+}
+
+fn count_source_files(
+    dir: &Path,
+    depth: usize,
+    counts: &mut BTreeMap<String, usize>,
+) -> Result<()> {
+    if depth > MAX_WALK_DEPTH {
+        return Ok(());
+    }
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read '{}'.", dir.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if !is_skipped_dir(&path) {
+                count_source_files(&path, depth + 1, counts)?;
+            }
+            continue;
+        }
+        if let Some(language) = language_for_path(&path) {
+            *counts.entry(language.to_string()).or_default() += 1;
+        }
+    }
+    Ok(())
+}
+
+fn is_skipped_dir(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_str())
+}
+
+fn language_for_path(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "go" => "go",
+        "java" => "java",
+        "kt" => "kotlin",
+        "rb" => "ruby",
+        "cs" => "csharp",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "hpp" => "cpp",
+        "swift" => "swift",
+        _ => return None,
+    };
+    Some(language)
+}
+
+fn detect_test_frameworks(repo_root: &Path) -> Vec<String> {
+    let mut frameworks = Vec::<String>::new();
+    if detect_repo_toolchain(repo_root) == ToolchainKind::Rust {
+        frameworks.push("cargo test".to_string());
+    }
+
+    let python_markers = ["pyproject.toml", "requirements.txt", "setup.cfg"]
+        .iter()
+        .filter_map(|name| fs::read_to_string(repo_root.join(name)).ok())
+        .collect::<Vec<_>>();
+    if repo_root.join("pytest.ini").exists()
+        || repo_root.join("conftest.py").exists()
+        || python_markers
+            .iter()
+            .any(|content| content.contains("pytest"))
+    {
+        frameworks.push("pytest".to_string());
+    }
+
+    if let Ok(package_json) = fs::read_to_string(repo_root.join("package.json")) {
+        for framework in ["vitest", "jest", "mocha", "playwright"] {
+            if package_json.contains(&format!("\"{}", framework))
+                || package_json.contains(&format!("@{}/", framework))
+            {
+                frameworks.push(framework.to_string());
+            }
+        }
+    }
+
+    if repo_root.join("go.mod").exists() {
+        frameworks.push("go test".to_string());
+    }
+    frameworks
+}
+
+fn detect_module_boundaries(repo_root: &Path) -> Result<Vec<ModuleBoundary>> {
+    let mut modules = Vec::<ModuleBoundary>::new();
+    for workspace_dir in WORKSPACE_DIRS {
+        collect_child_modules(repo_root, Path::new(workspace_dir), &mut modules)?;
+    }
+    if modules.is_empty() {
+        for source_root in SOURCE_ROOTS {
+            collect_child_modules(repo_root, Path::new(source_root), &mut modules)?;
+        }
+    }
+    if modules.is_empty() {
+        let mut counts = BTreeMap::<String, usize>::new();
+        count_source_files(repo_root, 0, &mut counts)?;
+        let source_files = counts.values().sum::<usize>();
+        if source_files > 0 {
+            modules.push(ModuleBoundary {
+                name: "core".to_string(),
+                path: ".".to_string(),
+                source_files,
+            });
+        }
+    }
+    modules.sort_by(|left, right| left.name.cmp(&right.name));
+    modules.dedup_by(|left, right| left.name == right.name);
+    Ok(modules)
+}
+
+fn collect_child_modules(
+    repo_root: &Path,
+    relative_dir: &Path,
+    modules: &mut Vec<ModuleBoundary>,
+) -> Result<()> {
+    let dir = repo_root.join(relative_dir);
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let entries =
+        fs::read_dir(&dir).with_context(|| format!("Failed to read '{}'.", dir.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || is_skipped_dir(&path) {
+            continue;
+        }
+        let mut counts = BTreeMap::<String, usize>::new();
+        count_source_files(&path, 1, &mut counts)?;
+        let source_files = counts.values().sum::<usize>();
+        if source_files == 0 {
+            continue;
+        }
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        modules.push(ModuleBoundary {
+            name: slugify(&dir_name),
+            path: relative_dir.join(&dir_name).to_string_lossy().to_string(),
+            source_files,
+        });
+    }
+    Ok(())
+}
+
+fn detect_docs(repo_root: &Path) -> Vec<String> {
+    let mut docs = Vec::<String>::new();
+    if let Ok(entries) = fs::read_dir(repo_root) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let upper = name.to_ascii_uppercase();
+            let is_doc_file = entry.path().is_file()
+                && ["README", "CHANGELOG", "CONTRIBUTING", "ARCHITECTURE"]
+                    .iter()
+                    .any(|prefix| upper.starts_with(prefix));
+            let is_doc_dir = entry.path().is_dir() && (name == "docs" || name == "doc");
+            if is_doc_file || is_doc_dir || name == "mkdocs.yml" {
+                docs.push(name);
+            }
+        }
+    }
+    docs.sort();
+    docs
+}
+
+fn render_project_index(analysis: &RepoAnalysis, created: &str) -> String {
+    let languages = if analysis.languages.is_empty() {
+        "no recognized".to_string()
+    } else {
+        analysis
+            .languages
+            .iter()
+            .map(|entry| entry.language.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut features = String::new();
+    for module in &analysis.modules {
+        features.push_str(&format!(
+            "| `{}` | `.nexus/context/{}/{}/` | Module boundary detected at `{}` |\n",
+            module.name, analysis.project, module.name, module.path
+        ));
+    }
+
+    format!(
+        "---\nproject_id: {project}\ntitle: {title}\ncreated: \"{created}\"\nstatus: draft\ndependencies: []\n---\n\n# {project}\n\n## Overview\n\nProposed by `opennexus analyze` from {languages} sources. Replace this overview with the project purpose before adding context files.\n\n## Features\n\n| Feature | Path | Purpose |\n|---------|------|---------|\n{features}",
+        project = analysis.project,
+        title = title_case(&analysis.project),
+        created = created,
+        languages = languages,
+        features = features,
+    )
+}
+
+fn render_feature_index(project: &str, module: &ModuleBoundary, created: &str) -> String {
+    format!(
+        "---\nproject_id: {project}-{feature}\ntitle: {title}\ncreated: \"{created}\"\nstatus: draft\ndependencies:\n  - {project}\n---\n\n# {project} {feature}\n\n## Scope\n\nOwns the `{path}` module ({files} source files). Refine this scope before adding context files.\n\n## Context Files\n\n| ID | Title |\n|----|-------|\n",
+        project = project,
+        feature = module.name,
+        title = title_case(&format!("{}-{}", project, module.name)),
+        created = created,
+        path = module.path,
+        files = module.source_files,
+    )
+}
+
+fn slugify(value: &str) -> String {
+    let mut slug = String::new();
+    for character in value.trim().chars() {
+        if character.is_ascii_alphanumeric() {
+            slug.push(character.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "project".to_string()
+    } else {
+        slug
+    }
+}
+
+fn title_case(slug: &str) -> String {
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, relative: &str, contents: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create parent");
+        fs::write(path, contents).expect("write file");
+    }
+
+    #[test]
+    fn analyzes_rust_repo_with_source_modules() {
+        let dir = tempdir().expect("tempdir");
+        write(dir.path(), "Cargo.toml", "[package]\nname='x'\n");
+        write(dir.path(), "README.md", "# x\n");
+        write(dir.path(), "src/main.rs", "fn main() {}\n");
+        write(dir.path(), "src/billing/mod.rs", "\n");
+        write(dir.path(), "src/billing/invoice.rs", "\n");
+        write(dir.path(), "src/Auth Flow/mod.rs", "\n");
+        write(dir.path(), "target/debug/build.rs", "\n");
+
+        let analysis = analyze_repository(dir.path(), Some("Demo App")).expect("analyze");
+        assert_eq!(analysis.project, "demo-app");
+        assert!(!analysis.nexus_present);
+        assert_eq!(analysis.toolchain, "rust");
+        assert_eq!(
+            analysis.languages,
+            vec![LanguageCount {
+                language: "rust".to_string(),
+                files: 4,
+            }]
+        );
+        assert_eq!(analysis.test_frameworks, vec!["cargo test".to_string()]);
+        assert_eq!(analysis.docs, vec!["README.md".to_string()]);
+        let names = analysis
+            .modules
+            .iter()
+            .map(|module| module.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["auth-flow", "billing"]);
+    }
+
+    #[test]
+    fn prefers_workspace_members_and_detects_node_frameworks() {
+        let dir = tempdir().expect("tempdir");
+        write(
+            dir.path(),
+            "package.json",
+            "{\"devDependencies\":{\"vitest\":\"1\",\"@playwright/test\":\"1\"}}",
+        );
+        write(dir.path(), "packages/web/index.ts", "\n");
+        write(dir.path(), "packages/docs/README.md", "\n");
+        write(dir.path(), "src/shared/util.ts", "\n");
+
+        let analysis = analyze_repository(dir.path(), Some("mono")).expect("analyze");
+        assert_eq!(analysis.toolchain, "node");
+        assert_eq!(
+            analysis.test_frameworks,
+            vec!["vitest".to_string(), "playwright".to_string()]
+        );
+        assert_eq!(
+            analysis.modules,
+            vec![ModuleBoundary {
+                name: "web".to_string(),
+                path: "packages/web".to_string(),
+                source_files: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn plan_apply_skips_existing_index_files() {
+        let dir = tempdir().expect("tempdir");
+        write(dir.path(), "src/api/handler.py", "\n");
+        write(dir.path(), ".nexus/context/svc/api/index.md", "existing\n");
+
+        let analysis = analyze_repository(dir.path(), Some("svc")).expect("analyze");
+        assert!(analysis.nexus_present);
+        let plan = build_onboarding_plan(dir.path(), &analysis);
+        assert_eq!(plan.len(), 2);
+        assert!(!plan[0].exists);
+        assert!(plan[0]
+            .contents
+            .contains("| `api` | `.nexus/context/svc/api/` |"));
+        assert!(plan[1].exists);
+
+        let written = apply_onboarding_plan(dir.path(), &plan).expect("apply");
+        assert_eq!(written, vec![".nexus/context/svc/index.md".to_string()]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".nexus/context/svc/api/index.md"))
+                .expect("read existing"),
+            "existing\n"
+        );
+    }
+}
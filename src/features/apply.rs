@@ -0,0 +1,362 @@
+//! Declarative desired-state manifests for projects and contexts.
+//!
+//! A manifest lists projects and the contexts each should contain. Planning
+//! diffs it against `.nexus/context/`: missing projects and contexts are
+//! created as stubs, title/frozen drift in existing frontmatter is updated,
+//! and (with `prune`) contexts absent from the manifest are deleted. Context
+//! bodies are never rewritten, so hand-written outcomes and actions survive.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::scan::scan_valid_context_specs;
+use crate::utils::paths::to_slash;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default)]
+    pub projects: Vec<ManifestProject>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestProject {
+    pub id: String,
+    #[serde(default)]
+    pub contexts: Vec<ManifestContext>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestContext {
+    pub id: String,
+    pub feature: String,
+    pub title: String,
+    #[serde(default)]
+    pub frozen: bool,
+    /// Seed actions for newly created contexts; ignored for existing ones.
+    #[serde(default)]
+    pub next_actions: Vec<ManifestAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestAction {
+    pub description: String,
+    pub test: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedChange {
+    pub action: ChangeAction,
+    /// `project` or `context`.
+    pub kind: &'static str,
+    pub id: String,
+    pub path: String,
+    /// Frontmatter keys an update changes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+}
+
+pub fn load_manifest(path: &Path) -> Result<Manifest> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Unable to read manifest '{}'.", path.display()))?;
+    serde_yaml::from_str(&content).with_context(|| {
+        format!(
+            "Invalid manifest '{}'. Remediation: manifests support `projects[].id` and `projects[].contexts[]` with id, feature, title, frozen, and next_actions.",
+            path.display()
+        )
+    })
+}
+
+/// Diff `manifest` against the contexts under `context_root`.
+pub fn plan_manifest(
+    context_root: &Path,
+    manifest: &Manifest,
+    prune: bool,
+) -> Result<Vec<PlannedChange>> {
+    let existing = existing_contexts(context_root)?;
+    let mut plan = Vec::<PlannedChange>::new();
+    for project in &manifest.projects {
+        let project_dir = context_root.join(&project.id);
+        if !project_dir.join("index.md").is_file() {
+            plan.push(PlannedChange {
+                action: ChangeAction::Create,
+                kind: "project",
+                id: project.id.clone(),
+                path: to_slash(&project_dir.join("index.md")),
+                fields: Vec::new(),
+            });
+        }
+        for context in &project.contexts {
+            match existing.get(&context.id) {
+                Some(path) => {
+                    let content = fs::read_to_string(path)
+                        .with_context(|| format!("Unable to read '{}'.", path.display()))?;
+                    let fields = drifted_fields(&content, context);
+                    if !fields.is_empty() {
+                        plan.push(PlannedChange {
+                            action: ChangeAction::Update,
+                            kind: "context",
+                            id: context.id.clone(),
+                            path: to_slash(path),
+                            fields,
+                        });
+                    }
+                }
+                None if context.next_actions.is_empty() => bail!(
+                    "Context '{}' does not exist yet and the manifest gives it no next_actions. Remediation: add at least one `{{ description, test }}` entry so the created context is valid.",
+                    context.id
+                ),
+                None => plan.push(PlannedChange {
+                    action: ChangeAction::Create,
+                    kind: "context",
+                    id: context.id.clone(),
+                    path: to_slash(&new_context_path(context_root, &project.id, context)),
+                    fields: Vec::new(),
+                }),
+            }
+        }
+    }
+
+    if prune {
+        let declared = manifest
+            .projects
+            .iter()
+            .flat_map(|project| project.contexts.iter().map(|context| context.id.as_str()))
+            .collect::<Vec<_>>();
+        for project in &manifest.projects {
+            let project_dir = context_root.join(&project.id);
+            for (id, path) in &existing {
+                if path.starts_with(&project_dir) && !declared.contains(&id.as_str()) {
+                    plan.push(PlannedChange {
+                        action: ChangeAction::Delete,
+                        kind: "context",
+                        id: id.clone(),
+                        path: to_slash(path),
+                        fields: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(plan)
+}
+
+/// Apply a plan produced by `plan_manifest` for the same manifest.
+pub fn apply_plan(manifest: &Manifest, plan: &[PlannedChange]) -> Result<()> {
+    let contexts = manifest
+        .projects
+        .iter()
+        .flat_map(|project| {
+            project
+                .contexts
+                .iter()
+                .map(move |context| (context.id.as_str(), (project.id.as_str(), context)))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    for change in plan {
+        let path = Path::new(&change.path);
+        match (change.action, change.kind) {
+            (ChangeAction::Create, "project") => write_file(
+                path,
+                &format!("---\nproject_id: {0}\n---\n\n# {0}\n", change.id),
+            )?,
+            (ChangeAction::Create, _) => {
+                let Some((project, context)) = contexts.get(change.id.as_str()) else {
+                    bail!("Context '{}' is not in the manifest.", change.id);
+                };
+                write_file(path, &render_context_stub(project, context))?;
+            }
+            (ChangeAction::Update, _) => {
+                let Some((_, context)) = contexts.get(change.id.as_str()) else {
+                    bail!("Context '{}' is not in the manifest.", change.id);
+                };
+                let mut content = fs::read_to_string(path)
+                    .with_context(|| format!("Unable to read '{}'.", path.display()))?;
+                for field in &change.fields {
+                    let value = match field.as_str() {
+                        "title" => context.title.clone(),
+                        _ => context.frozen.to_string(),
+                    };
+                    content = set_frontmatter_value(&content, field, &value);
+                }
+                write_file(path, &content)?;
+            }
+            (ChangeAction::Delete, _) => fs::remove_file(path)
+                .with_context(|| format!("Failed to delete '{}'.", path.display()))?,
+        }
+    }
+    Ok(())
+}
+
+fn existing_contexts(context_root: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut contexts = BTreeMap::new();
+    if !context_root.is_dir() {
+        return Ok(contexts);
+    }
+    for path in scan_valid_context_specs(context_root)?.valid_context_files {
+        contexts.insert(parse_context_file(&path)?.context_id, path);
+    }
+    Ok(contexts)
+}
+
+fn drifted_fields(content: &str, context: &ManifestContext) -> Vec<String> {
+    let title = frontmatter_value(content, "title").unwrap_or_default();
+    let frozen = frontmatter_value(content, "frozen").is_some_and(|value| value == "true");
+    let mut fields = Vec::new();
+    if title != context.title {
+        fields.push("title".to_string());
+    }
+    if frozen != context.frozen {
+        fields.push("frozen".to_string());
+    }
+    fields
+}
+
+fn frontmatter_value(content: &str, key: &str) -> Option<String> {
+    content
+        .lines()
+        .skip(1)
+        .take_while(|line| line.trim() != "---")
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .map(|value| value.trim().trim_matches('"').to_string())
+}
+
+/// Replace `key:` in the frontmatter, or add it before the closing `---`.
+fn set_frontmatter_value(content: &str, key: &str, value: &str) -> String {
+    let rendered = format!("{}: {}", key, value);
+    let mut lines = content.lines().map(str::to_string).collect::<Vec<_>>();
+    let closing = lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim() == "---")
+        .map(|offset| offset + 1)
+        .unwrap_or(lines.len());
+    let prefix = format!("{}:", key);
+    match lines[1..closing]
+        .iter()
+        .position(|line| line.starts_with(&prefix))
+    {
+        Some(offset) => lines[offset + 1] = rendered,
+        None => lines.insert(closing, rendered),
+    }
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+fn new_context_path(context_root: &Path, project: &str, context: &ManifestContext) -> PathBuf {
+    let slug = context
+        .title
+        .to_ascii_lowercase()
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    context_root
+        .join(project)
+        .join(&context.feature)
+        .join(format!("{}-{}.md", context.id, slug))
+}
+
+fn render_context_stub(project: &str, context: &ManifestContext) -> String {
+    let mut stub = format!(
+        "---\ncontext_id: {}\ntitle: {}\nproject: {}\nfeature: {}\n",
+        context.id, context.title, project, context.feature
+    );
+    if context.frozen {
+        stub.push_str("frozen: true\n");
+    }
+    stub.push_str(&format!(
+        "---\n\n# {}: {}\n\n## Desired Outcome\n\nTODO\n\n## Next Actions\n\n| Description | Test |\n|-------------|------|\n",
+        context.id, context.title
+    ));
+    for action in &context.next_actions {
+        stub.push_str(&format!("| {} | `{}` |\n", action.description, action.test));
+    }
+    stub
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write '{}'.", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn plans_and_applies_creates_updates_and_prunes() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path().join(".nexus").join("context");
+        write_file(
+            &root.join("app").join("index.md"),
+            "---\nproject_id: app\n---\n",
+        )
+        .expect("index");
+        write_file(
+            &root.join("app").join("core").join("APP_001-login.md"),
+            "---\ncontext_id: APP_001\ntitle: Login\n---\n\n## Next Actions\n\n| Description | Test |\n|-------------|------|\n| Log in | `logs_in` |\n",
+        )
+        .expect("existing context");
+        write_file(
+            &root.join("app").join("core").join("APP_009-old.md"),
+            "---\ncontext_id: APP_009\ntitle: Old\n---\n\n## Next Actions\n\n| Description | Test |\n|-------------|------|\n| Old | `old` |\n",
+        )
+        .expect("stale context");
+
+        let manifest: Manifest = serde_yaml::from_str(
+            "projects:\n  - id: app\n    contexts:\n      - { id: APP_001, feature: core, title: Sign In, frozen: true }\n      - id: APP_002\n        feature: core\n        title: Sign Out\n        next_actions:\n          - { description: Clear session, test: clears_session }\n",
+        )
+        .expect("manifest");
+        let plan = plan_manifest(&root, &manifest, true).expect("plan");
+        let summary = plan
+            .iter()
+            .map(|change| (change.action, change.id.as_str(), change.fields.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    ChangeAction::Update,
+                    "APP_001",
+                    vec!["title".to_string(), "frozen".to_string()]
+                ),
+                (ChangeAction::Create, "APP_002", vec![]),
+                (ChangeAction::Delete, "APP_009", vec![]),
+            ]
+        );
+
+        apply_plan(&manifest, &plan).expect("apply");
+        assert!(plan_manifest(&root, &manifest, true)
+            .expect("replan")
+            .is_empty());
+        let created =
+            parse_context_file(&root.join("app").join("core").join("APP_002-sign-out.md"))
+                .expect("parse created");
+        assert_eq!(created.tests, vec!["clears_session"]);
+        assert!(serde_yaml::from_str::<Manifest>("labels: [x]\n").is_err());
+    }
+}
@@ -0,0 +1,284 @@
+//! Lint rules for harness assets (commands, skills, rules) under `ai_harness/`.
+//!
+//! The same rules run against the assets embedded at build time and against a
+//! user-extracted `.nexus/` tree, so callers supply file contents plus an
+//! existence check for resolving relative links.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Asset subtree linted relative to the `.nexus/` root.
+pub const HARNESS_ASSET_ROOT: &str = "ai_harness";
+
+#[derive(Debug, Clone)]
+pub struct AssetFile {
+    /// Path relative to the `.nexus/` root, using `/` separators.
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssetLintIssue {
+    pub path: String,
+    pub line: usize,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+/// Lint every Markdown asset; `exists` resolves `.nexus/`-relative paths.
+pub fn lint_assets(files: &[AssetFile], exists: impl Fn(&str) -> bool) -> Vec<AssetLintIssue> {
+    let mut issues = Vec::<AssetLintIssue>::new();
+    for file in files.iter().filter(|file| file.path.ends_with(".md")) {
+        let frontmatter = match split_frontmatter(&file.content) {
+            Some(frontmatter) => frontmatter,
+            None => {
+                issues.push(issue(
+                    file,
+                    1,
+                    "frontmatter",
+                    "missing YAML frontmatter delimited by '---'".to_string(),
+                ));
+                continue;
+            }
+        };
+        let fields = match serde_yaml::from_str::<serde_yaml::Value>(frontmatter) {
+            Ok(fields) => fields,
+            Err(err) => {
+                issues.push(issue(
+                    file,
+                    1,
+                    "frontmatter",
+                    format!("frontmatter is not valid YAML: {}", err),
+                ));
+                continue;
+            }
+        };
+        for key in required_keys(&file.path) {
+            let present = fields
+                .get(*key)
+                .and_then(serde_yaml::Value::as_str)
+                .is_some_and(|value| !value.trim().is_empty());
+            if !present {
+                issues.push(issue(
+                    file,
+                    1,
+                    "frontmatter",
+                    format!("frontmatter key '{}' is required", key),
+                ));
+            }
+        }
+
+        let declared = declared_placeholders(&fields);
+        let mut in_fence = false;
+        for (index, line) in file.content.lines().enumerate() {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            for captures in placeholder_pattern().captures_iter(line) {
+                let name = &captures[1];
+                if !declared.contains(name) {
+                    issues.push(issue(
+                        file,
+                        index + 1,
+                        "placeholder",
+                        format!(
+                            "placeholder '{{{{{}}}}}' is not listed under frontmatter `placeholders`",
+                            name
+                        ),
+                    ));
+                }
+            }
+            for captures in link_pattern().captures_iter(line) {
+                let target = &captures[1];
+                let Some(resolved) = resolve_relative_link(&file.path, target) else {
+                    continue;
+                };
+                if !exists(&resolved) {
+                    issues.push(issue(
+                        file,
+                        index + 1,
+                        "link",
+                        format!("relative link '{}' does not resolve", target),
+                    ));
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Read Markdown assets under `<nexus_root>/ai_harness` from disk.
+pub fn collect_assets_from_dir(nexus_root: &Path) -> Result<Vec<AssetFile>> {
+    let mut files = Vec::<AssetFile>::new();
+    let mut stack = vec![nexus_root.join(HARNESS_ASSET_ROOT)];
+    while let Some(dir) = stack.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir)
+            .with_context(|| format!("Unable to read asset directory '{}'.", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Unable to read asset '{}'.", path.display()))?;
+            let relative = path.strip_prefix(nexus_root).unwrap_or(&path);
+            files.push(AssetFile {
+                path: relative_slash_path(relative),
+                content,
+            });
+        }
+    }
+    files.sort_by(|left, right| left.path.cmp(&right.path));
+    Ok(files)
+}
+
+fn required_keys(path: &str) -> &'static [&'static str] {
+    if path.starts_with("ai_harness/commands/") {
+        &["description"]
+    } else if path.starts_with("ai_harness/skills/") && path.ends_with("/SKILL.md") {
+        &["name", "description"]
+    } else {
+        &[]
+    }
+}
+
+fn split_frontmatter(content: &str) -> Option<&str> {
+    let rest = content.strip_prefix("---")?;
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))?;
+    let end = rest
+        .match_indices("\n---")
+        .map(|(index, _)| index)
+        .find(|index| {
+            let after = &rest[index + 4..];
+            after.is_empty() || after.starts_with('\n') || after.starts_with("\r\n")
+        })?;
+    Some(&rest[..end])
+}
+
+fn declared_placeholders(fields: &serde_yaml::Value) -> BTreeSet<String> {
+    match fields.get("placeholders") {
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(serde_yaml::Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(serde_yaml::Value::String(list)) => list
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+/// Resolve a Markdown link target against the asset's directory; `None` for
+/// external, anchor-only, or absolute targets.
+fn resolve_relative_link(asset_path: &str, target: &str) -> Option<String> {
+    let target = target.split('#').next().unwrap_or_default();
+    if target.is_empty() || target.starts_with('/') || target.contains("://") {
+        return None;
+    }
+    if target.starts_with("mailto:") {
+        return None;
+    }
+    let base = Path::new(asset_path).parent().unwrap_or(Path::new(""));
+    let mut resolved = PathBuf::new();
+    for component in base.join(target).components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+    Some(relative_slash_path(&resolved))
+}
+
+fn relative_slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn issue(file: &AssetFile, line: usize, rule: &'static str, message: String) -> AssetLintIssue {
+    AssetLintIssue {
+        path: file.path.clone(),
+        line,
+        rule,
+        message,
+    }
+}
+
+fn placeholder_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").expect("regex"))
+}
+
+fn link_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\[[^\]]*\]\(([^)\s]+)\)").expect("regex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(path: &str, content: &str) -> AssetFile {
+        AssetFile {
+            path: path.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_frontmatter_placeholder_and_link_issues() {
+        let files = vec![
+            asset("ai_harness/commands/missing.md", "# No frontmatter\n"),
+            asset(
+                "ai_harness/commands/ok.md",
+                "---\ndescription: Fine\nplaceholders: [context]\n---\n\nUse {{context}} and see [guide](../skills/guide/SKILL.md).\n\n```\n{{ignored}} [x](nowhere.md)\n```\n",
+            ),
+            asset(
+                "ai_harness/commands/bad.md",
+                "---\ndescription: \"\"\n---\n\nUse {{target}}; read [notes](notes.md#intro) and [site](https://example.com).\n",
+            ),
+        ];
+        let known = ["ai_harness/skills/guide/SKILL.md"];
+        let issues = lint_assets(&files, |path| known.contains(&path));
+
+        let summary = issues
+            .iter()
+            .map(|issue| (issue.path.as_str(), issue.rule))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("ai_harness/commands/missing.md", "frontmatter"),
+                ("ai_harness/commands/bad.md", "frontmatter"),
+                ("ai_harness/commands/bad.md", "placeholder"),
+                ("ai_harness/commands/bad.md", "link"),
+            ]
+        );
+        assert_eq!(issues[3].line, 5);
+    }
+}
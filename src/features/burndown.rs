@@ -0,0 +1,156 @@
+//! Burndown and cumulative-flow series over next-action history.
+//!
+//! An action enters scope when a run first snapshots it, is completed when a
+//! successful run that included it finishes, and leaves scope if it is dropped
+//! from its context before ever completing. Series are bucketed by UTC day.
+
+use serde::Serialize;
+
+use crate::adapters::orchestration_store::ActionHistoryRow;
+use crate::cli::BurndownChart;
+use crate::utils::time::format_unix_date;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const CHART_HEIGHT: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BurndownPoint {
+    pub date: String,
+    pub scope: usize,
+    pub completed: usize,
+    pub remaining: usize,
+}
+
+/// Daily series from the first recorded action (or the last `days` days) to `now`.
+pub fn burndown_series(
+    rows: &[ActionHistoryRow],
+    now: i64,
+    days: Option<u32>,
+) -> Vec<BurndownPoint> {
+    let Some(first_seen) = rows.iter().map(|row| row.first_seen_at).min() else {
+        return Vec::new();
+    };
+    let today = now.div_euclid(SECONDS_PER_DAY);
+    let mut start = first_seen.div_euclid(SECONDS_PER_DAY);
+    if let Some(days) = days {
+        start = start.max(today - i64::from(days.max(1)) + 1);
+    }
+
+    (start..=today)
+        .map(|day| {
+            let end = (day + 1) * SECONDS_PER_DAY;
+            let before_end = |at: Option<i64>| at.is_some_and(|at| at < end);
+            let (mut scope, mut completed) = (0usize, 0usize);
+            for row in rows.iter().filter(|row| row.first_seen_at < end) {
+                if before_end(row.completed_at) {
+                    scope += 1;
+                    completed += 1;
+                } else if !before_end(row.removed_at) {
+                    scope += 1;
+                }
+            }
+            BurndownPoint {
+                date: format_unix_date(day * SECONDS_PER_DAY),
+                scope,
+                completed,
+                remaining: scope - completed,
+            }
+        })
+        .collect()
+}
+
+/// Render `points` as a Unicode column chart, one column per day.
+///
+/// `Burndown` plots remaining actions; `Flow` stacks completed (`█`) under
+/// remaining (`░`) so the top edge traces total scope.
+pub fn render_chart(points: &[BurndownPoint], chart: BurndownChart) -> String {
+    let max = points
+        .iter()
+        .map(|point| point.scope)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let label_width = max.to_string().len();
+    let scaled = |value: usize| (value * CHART_HEIGHT).div_ceil(max);
+
+    let mut lines = Vec::<String>::new();
+    for level in (1..=CHART_HEIGHT).rev() {
+        let label = if level == CHART_HEIGHT {
+            max.to_string()
+        } else {
+            String::new()
+        };
+        let columns = points
+            .iter()
+            .map(|point| match chart {
+                BurndownChart::Burndown if scaled(point.remaining) >= level => '█',
+                BurndownChart::Flow if scaled(point.completed) >= level => '█',
+                BurndownChart::Flow if scaled(point.scope) >= level => '░',
+                _ => ' ',
+            })
+            .collect::<String>();
+        lines.push(format!("{:>label_width$} │{}", label, columns));
+    }
+    lines.push(format!("{:>label_width$} └{}", 0, "─".repeat(points.len())));
+    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+        let span = points.len().max(first.date.len() + last.date.len() + 1);
+        lines.push(format!(
+            "{:label_width$}  {}{}{}",
+            "",
+            first.date,
+            " ".repeat(span - first.date.len() - last.date.len()),
+            if points.len() > 1 {
+                last.date.as_str()
+            } else {
+                ""
+            }
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        first_seen_day: i64,
+        completed_day: Option<i64>,
+        removed_day: Option<i64>,
+    ) -> ActionHistoryRow {
+        ActionHistoryRow {
+            context_id: "BRN_001".to_string(),
+            action_key: format!("{}::{:?}", first_seen_day, completed_day),
+            first_seen_at: first_seen_day * SECONDS_PER_DAY + 60,
+            completed_at: completed_day.map(|day| day * SECONDS_PER_DAY + 120),
+            removed_at: removed_day.map(|day| day * SECONDS_PER_DAY + 120),
+        }
+    }
+
+    #[test]
+    fn series_counts_scope_completion_and_removal_per_day() {
+        let rows = vec![
+            row(0, Some(1), None),
+            row(0, None, Some(2)),
+            row(1, Some(2), None),
+            row(2, None, None),
+        ];
+        let series = burndown_series(&rows, 3 * SECONDS_PER_DAY, None);
+        let counts = series
+            .iter()
+            .map(|point| (point.scope, point.completed, point.remaining))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![(2, 0, 2), (3, 1, 2), (3, 2, 1), (3, 2, 1)]);
+        assert_eq!(series[0].date, "1970-01-01");
+        assert_eq!(
+            burndown_series(&rows, 3 * SECONDS_PER_DAY, Some(2)).len(),
+            2
+        );
+
+        let chart = render_chart(&series, BurndownChart::Flow);
+        let lines = chart.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "3 │ ░░░");
+        assert_eq!(lines[CHART_HEIGHT - 1], "  │░███");
+        assert!(lines.last().unwrap().contains("1970-01-01"));
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::{bail, Context, Result};
 use std::collections::BTreeSet;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 use super::parser::parse_context_file;
 use super::reporting::{
@@ -12,10 +12,14 @@ use super::scan::scan_valid_context_specs;
 use super::test_discovery::{discover_tests_from_runner, is_test_discovered};
 use crate::adapters::context_observability::ContextObservabilityStore;
 use crate::core::context::model::{
-    BackfillContextResult, BackfillTaskResult, ContextBackfillOptions, ContextTaskStatus,
+    BackfillContextResult, BackfillTaskResult, ContextBackfillOptions, ContextBackfillReport,
+    ContextTaskStatus,
 };
+use crate::services::hooks::{fire_hook, HookEvent};
 
-pub(crate) fn run_context_backfill(options: &ContextBackfillOptions) -> Result<()> {
+pub(crate) fn run_context_backfill(
+    options: &ContextBackfillOptions,
+) -> Result<ContextBackfillReport> {
     if options.all {
         return run_context_backfill_all();
     }
@@ -35,24 +39,17 @@ pub(crate) fn run_context_backfill(options: &ContextBackfillOptions) -> Result<(
     store.persist_backfill_context_result(&result).context(
         "Backfill could not persist run results. Remediation: verify SQLite file permissions under .nexus/context/ and retry.",
     )?;
+    fire_action_completed_hooks(&result);
 
-    print_backfill_context_summary(&result);
-
-    if result.failed_count() > 0 || result.missing_count() > 0 {
-        bail!(
-            "Backfill finished with incomplete task states for context '{}': implemented={}, failed={}, missing={}. Remediation: implement missing behavior and rerun `opennexus context backfill --context-file {}`.",
-            result.context_id,
-            result.implemented_count(),
-            result.failed_count(),
-            result.missing_count(),
-            result.context_file.display()
-        );
-    }
-
-    Ok(())
+    Ok(ContextBackfillReport {
+        all: false,
+        contexts_audited: 1,
+        results: vec![result],
+        partial_errors: Vec::new(),
+    })
 }
 
-fn run_context_backfill_all() -> Result<()> {
+fn run_context_backfill_all() -> Result<ContextBackfillReport> {
     let discovered = discover_tests_from_runner().context(
         "Global backfill test discovery failed. Remediation: run `cargo test -- --list` locally and resolve compiler/test harness issues.",
     )?;
@@ -79,6 +76,8 @@ fn run_context_backfill_all() -> Result<()> {
                         context_file.display(),
                         err
                     ));
+                } else {
+                    fire_action_completed_hooks(&result);
                 }
                 context_results.push(result);
             }
@@ -90,15 +89,51 @@ fn run_context_backfill_all() -> Result<()> {
         }
     }
 
-    let aggregate = aggregate_backfill_counts(&context_results);
+    Ok(ContextBackfillReport {
+        all: true,
+        contexts_audited: scan.valid_context_files.len(),
+        results: context_results,
+        partial_errors: format_partial_audit_errors(&scan.parse_errors, &execution_errors),
+    })
+}
+
+pub(crate) fn print_backfill_report(report: &ContextBackfillReport) {
+    if !report.all {
+        report
+            .results
+            .iter()
+            .for_each(print_backfill_context_summary);
+        return;
+    }
+    let aggregate = aggregate_backfill_counts(&report.results);
     print_backfill_global_summary(
-        scan.valid_context_files.len(),
+        report.contexts_audited,
         aggregate.implemented,
         aggregate.failed,
         aggregate.missing,
     );
+}
+
+/// Fail when the backfill left tasks failed or missing, or could not audit
+/// every spec.
+pub(crate) fn ensure_backfill_complete(report: &ContextBackfillReport) -> Result<()> {
+    if !report.all {
+        for result in &report.results {
+            if result.failed_count() > 0 || result.missing_count() > 0 {
+                bail!(
+                    "Backfill finished with incomplete task states for context '{}': implemented={}, failed={}, missing={}. Remediation: implement missing behavior and rerun `opennexus context backfill --context-file {}`.",
+                    result.context_id,
+                    result.implemented_count(),
+                    result.failed_count(),
+                    result.missing_count(),
+                    result.context_file.display()
+                );
+            }
+        }
+        return Ok(());
+    }
 
-    let partial_errors = format_partial_audit_errors(&scan.parse_errors, &execution_errors);
+    let partial_errors = &report.partial_errors;
     if !partial_errors.is_empty() {
         bail!(
             "Global backfill completed with partial-audit errors:\n{}\nRemediation: fix invalid context specs and execution failures, then rerun `opennexus context backfill --all`.",
@@ -106,6 +141,7 @@ fn run_context_backfill_all() -> Result<()> {
         );
     }
 
+    let aggregate = aggregate_backfill_counts(&report.results);
     if aggregate.failed > 0 || aggregate.missing > 0 {
         bail!(
             "Global backfill detected incomplete task states: implemented={}, failed={}, missing={}. Remediation: address failing/missing tests and rerun `opennexus context backfill --all`.",
@@ -142,6 +178,24 @@ fn run_backfill_for_context(
     })
 }
 
+fn fire_action_completed_hooks(result: &BackfillContextResult) {
+    for task in &result.tasks {
+        if task.status != ContextTaskStatus::Implemented {
+            continue;
+        }
+        fire_hook(
+            HookEvent::ActionCompleted,
+            serde_json::json!({
+                "source": "backfill",
+                "context_id": result.context_id,
+                "context_file": result.context_file.to_string_lossy(),
+                "test_id": task.test_id,
+                "status": task.status.as_str(),
+            }),
+        );
+    }
+}
+
 fn run_backfill_task(test_id: &str, discovered: &BTreeSet<String>) -> Result<BackfillTaskResult> {
     if !is_test_discovered(test_id, discovered) {
         return Ok(BackfillTaskResult {
@@ -156,6 +210,8 @@ fn run_backfill_task(test_id: &str, discovered: &BTreeSet<String>) -> Result<Bac
 
     let status = Command::new("cargo")
         .args(["test", test_id, "--", "--exact"])
+        // Keep stdout free for the structured summary.
+        .stdout(Stdio::from(std::io::stderr()))
         .status()
         .with_context(|| {
             format!(
@@ -8,9 +8,11 @@ use super::steps::{
     VerifyTestDiscoveryStep,
 };
 use super::workflow_state::{ContextWorkflowState, StepContext};
-use crate::core::context::model::ContextImplementOptions;
+use crate::core::context::model::{ContextImplementOptions, ContextImplementSummary};
 
-pub(crate) fn run_context_implement(options: &ContextImplementOptions) -> Result<()> {
+pub(crate) fn run_context_implement(
+    options: &ContextImplementOptions,
+) -> Result<ContextImplementSummary> {
     let mut state = ContextWorkflowState::new();
     let ctx = StepContext { options };
     let pipeline = build_pipeline(options.pipeline_steps.as_deref())?;
@@ -33,7 +35,20 @@ pub(crate) fn run_context_implement(options: &ContextImplementOptions) -> Result
     } else {
         pipeline.run_from(start_index, &mut state, &ctx)?
     };
-    Ok(())
+    Ok(ContextImplementSummary {
+        tests: state
+            .parsed
+            .as_ref()
+            .map(|parsed| parsed.tests.clone())
+            .unwrap_or_default(),
+        generated_tests: state
+            .generated_files
+            .iter()
+            .map(|file| (file.test_id.clone(), file.path.clone()))
+            .collect(),
+        terminal_outcome: state.terminal_outcome,
+        tests_passed: state.last_validation_passed,
+    })
 }
 
 fn resolve_resume_index(
@@ -91,6 +106,15 @@ fn default_pipeline_step_ids() -> Vec<String> {
     ]
 }
 
+/// Default steps up to and including test scaffolding, with no agent stages.
+pub(crate) fn scaffold_only_step_ids() -> Vec<String> {
+    let mut ids = default_pipeline_step_ids();
+    if let Some(index) = ids.iter().position(|id| id == "generate_scaffold") {
+        ids.truncate(index + 1);
+    }
+    ids
+}
+
 fn build_step(id: &str) -> Result<Box<dyn super::pipeline::WorkflowStep>> {
     match id {
         "parse_context" => Ok(Box::new(ParseContextStep)),
@@ -0,0 +1,218 @@
+//! Generated `## Context Files` tables for project `index.md` files.
+//!
+//! Only the `## Context Files` section is rewritten; frontmatter, overview,
+//! and any other hand-written sections are preserved. A project without an
+//! `index.md` gets a minimal one.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path};
+
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::scan::{context_project, scan_valid_context_specs};
+
+const SECTION_HEADING: &str = "## Context Files";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextIndexEntry {
+    pub context_id: String,
+    pub feature: String,
+    pub title: String,
+    pub status: String,
+    pub actions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectIndexUpdate {
+    pub project: String,
+    pub path: String,
+    pub contexts: usize,
+    pub changed: bool,
+}
+
+/// Regenerate the index of every project (or only `project`) under `context_root`.
+///
+/// `status_for` maps a context id and its `frozen` flag to the status shown
+/// in the table.
+pub fn regenerate_context_indexes(
+    context_root: &Path,
+    project: Option<&str>,
+    status_for: impl Fn(&str, bool) -> String,
+) -> Result<Vec<ProjectIndexUpdate>> {
+    let projects = collect_context_entries(context_root, project, status_for)?;
+    let mut updates = Vec::<ProjectIndexUpdate>::new();
+    for (name, entries) in projects {
+        let path = context_root.join(&name).join("index.md");
+        let existing = fs::read_to_string(&path)
+            .unwrap_or_else(|_| format!("---\nproject_id: {name}\n---\n\n# {name}\n"));
+        let updated = replace_context_section(&existing, &render_context_table(&entries));
+        let changed = updated != existing;
+        if changed {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+            }
+            fs::write(&path, &updated)
+                .with_context(|| format!("Failed to write '{}'.", path.display()))?;
+        }
+        updates.push(ProjectIndexUpdate {
+            project: name,
+            path: path.display().to_string(),
+            contexts: entries.len(),
+            changed,
+        });
+    }
+    Ok(updates)
+}
+
+/// Index entries per project, sorted by feature then context id, without
+/// touching any `index.md`. A requested `project` is present even if empty.
+pub fn collect_context_entries(
+    context_root: &Path,
+    project: Option<&str>,
+    status_for: impl Fn(&str, bool) -> String,
+) -> Result<BTreeMap<String, Vec<ContextIndexEntry>>> {
+    let scan = scan_valid_context_specs(context_root)?;
+    let mut projects = BTreeMap::<String, Vec<ContextIndexEntry>>::new();
+    for path in &scan.valid_context_files {
+        let Some((name, feature)) = project_and_feature(context_root, path) else {
+            continue;
+        };
+        if project.is_some_and(|wanted| wanted != name) {
+            continue;
+        }
+        let parsed = parse_context_file(path)?;
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Unable to read '{}'.", path.display()))?;
+        projects.entry(name).or_default().push(ContextIndexEntry {
+            status: status_for(&parsed.context_id, parsed.frozen),
+            title: context_title(&content, &parsed.context_id),
+            actions: parsed.next_actions.len(),
+            context_id: parsed.context_id,
+            feature,
+        });
+    }
+    if let Some(wanted) = project {
+        projects.entry(wanted.to_string()).or_default();
+    }
+    for entries in projects.values_mut() {
+        entries.sort_by(|left, right| {
+            (&left.feature, &left.context_id).cmp(&(&right.feature, &right.context_id))
+        });
+    }
+    Ok(projects)
+}
+
+fn render_context_table(entries: &[ContextIndexEntry]) -> String {
+    let mut table = String::from(
+        "| ID | Feature | Title | Status | Actions |\n|----|---------|-------|--------|---------|\n",
+    );
+    for entry in entries {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            entry.context_id,
+            entry.feature,
+            entry.title.replace('|', "\\|"),
+            entry.status,
+            entry.actions
+        ));
+    }
+    table
+}
+
+/// Replace the body of `## Context Files` up to the next `## ` heading, or
+/// append the section when it is missing.
+fn replace_context_section(content: &str, table: &str) -> String {
+    let section = format!("{}\n\n{}", SECTION_HEADING, table);
+    let lines = content.lines().collect::<Vec<_>>();
+    let Some(start) = lines.iter().position(|line| line.trim() == SECTION_HEADING) else {
+        return format!("{}\n\n{}", content.trim_end(), section);
+    };
+    let end = lines
+        .iter()
+        .skip(start + 1)
+        .position(|line| line.starts_with("## "))
+        .map(|offset| start + 1 + offset);
+
+    let mut updated = lines[..start].join("\n");
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(&section);
+    if let Some(end) = end {
+        updated.push('\n');
+        updated.push_str(&lines[end..].join("\n"));
+        if content.ends_with('\n') {
+            updated.push('\n');
+        }
+    }
+    updated
+}
+
+fn project_and_feature(context_root: &Path, path: &Path) -> Option<(String, String)> {
+    let project = context_project(context_root, path)?;
+    let relative = path.strip_prefix(context_root).ok()?;
+    let rest = relative.components().skip(1).collect::<Vec<_>>();
+    let feature = match rest.as_slice() {
+        [Component::Normal(feature), _, ..] => feature.to_string_lossy().to_string(),
+        _ => "-".to_string(),
+    };
+    Some((project, feature))
+}
+
+fn context_title(content: &str, context_id: &str) -> String {
+    let from_frontmatter = content
+        .lines()
+        .take_while(|line| !line.starts_with("# "))
+        .find_map(|line| line.strip_prefix("title:"))
+        .map(|title| title.trim().trim_matches('"').to_string());
+    let from_heading = || {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .map(|heading| {
+                let heading = heading.trim();
+                heading
+                    .strip_prefix(context_id)
+                    .map(|rest| rest.trim_start_matches([':', ' ']).to_string())
+                    .unwrap_or_else(|| heading.to_string())
+            })
+    };
+    from_frontmatter
+        .filter(|title| !title.is_empty())
+        .or_else(from_heading)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_only_the_context_files_section() {
+        let entries = vec![ContextIndexEntry {
+            context_id: "CDD_001".to_string(),
+            feature: "cdd".to_string(),
+            title: "Rule | Gate".to_string(),
+            status: "done".to_string(),
+            actions: 3,
+        }];
+        let table = render_context_table(&entries);
+        let content = "---\nproject_id: app\n---\n\n# app\n\n## Context Files\n\n| ID | Title |\n|----|-------|\n| OLD_001 | Old |\n\n## Overview\n\nKeep me.\n";
+        let updated = replace_context_section(content, &table);
+        assert_eq!(
+            updated,
+            "---\nproject_id: app\n---\n\n# app\n\n## Context Files\n\n| ID | Feature | Title | Status | Actions |\n|----|---------|-------|--------|---------|\n| CDD_001 | cdd | Rule \\| Gate | done | 3 |\n\n## Overview\n\nKeep me.\n"
+        );
+        assert_eq!(replace_context_section(&updated, &table), updated);
+
+        let appended = replace_context_section("# app\n", &table);
+        assert!(appended.starts_with("# app\n\n## Context Files\n\n| ID |"));
+        assert_eq!(
+            context_title("# CDD_002: Status Command\n", "CDD_002"),
+            "Status Command"
+        );
+    }
+}
@@ -1,16 +1,26 @@
 mod backfill;
 mod checkpoint;
 mod implement;
+pub mod index;
+pub mod outcome;
 pub mod parser;
 mod pipeline;
+pub mod refs;
 mod reporting;
+pub mod reservation;
 mod rules;
 pub mod runtime;
-mod scan;
+pub mod scaffold;
+pub mod scan;
 mod status;
 mod steps;
+pub(crate) mod test_cache;
 mod test_discovery;
-mod test_runner;
+pub(crate) mod test_runner;
+pub mod translate;
 mod workflow_state;
 
+pub(crate) use backfill::{ensure_backfill_complete, print_backfill_report};
+pub(crate) use implement::scaffold_only_step_ids;
 pub use runtime::{run_context_backfill, run_context_implement, run_context_test_status};
+pub(crate) use status::print_test_status_report;
@@ -0,0 +1,153 @@
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+use crate::core::context::model::ContextImplementSummary;
+
+/// Outcome cell text per test id for a finished implement run.
+pub fn action_outcomes(summary: &ContextImplementSummary, run_id: i64) -> BTreeMap<String, String> {
+    let status = if summary.tests_passed {
+        "passed".to_string()
+    } else {
+        format!(
+            "failing ({})",
+            summary
+                .terminal_outcome
+                .map(|outcome| outcome.as_str())
+                .unwrap_or("incomplete")
+        )
+    };
+    summary
+        .tests
+        .iter()
+        .map(|test_id| {
+            let generated = summary
+                .generated_tests
+                .iter()
+                .find(|(generated_id, _)| generated_id == test_id)
+                .map(|(_, path)| format!(" in `{}`", path.display()))
+                .unwrap_or_default();
+            (
+                test_id.clone(),
+                format!("{} in run {}{}", status, run_id, generated),
+            )
+        })
+        .collect()
+}
+
+/// Write `outcomes` into the Outcome column of the Next Actions table,
+/// adding the column when the table does not have one yet.
+pub fn apply_action_outcomes(content: &str, outcomes: &BTreeMap<String, String>) -> Result<String> {
+    let mut lines = content.lines().map(str::to_string).collect::<Vec<_>>();
+    let section_index = lines
+        .iter()
+        .position(|line| line.trim() == "## Next Actions")
+        .ok_or_else(|| anyhow!("Missing required '## Next Actions' section."))?;
+    let header_index = (section_index + 1..lines.len())
+        .find(|index| {
+            let trimmed = lines[*index].trim();
+            trimmed.starts_with('|') && trimmed.contains("Description") && trimmed.contains("Test")
+        })
+        .ok_or_else(|| {
+            anyhow!("Next Actions section must include a table with a 'Test' column.")
+        })?;
+
+    let mut header = table_cells(&lines[header_index]);
+    let outcome_column = match header
+        .iter()
+        .position(|cell| cell.eq_ignore_ascii_case("Outcome"))
+    {
+        Some(index) => index,
+        None => {
+            header.push("Outcome".to_string());
+            lines[header_index] = table_row(&header);
+            if let Some(separator) = lines.get_mut(header_index + 1) {
+                *separator = format!("{}---------|", separator.trim_end());
+            }
+            header.len() - 1
+        }
+    };
+
+    for line in lines.iter_mut().skip(header_index + 2) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || !trimmed.starts_with('|') {
+            break;
+        }
+        let mut cells = table_cells(line);
+        if cells.len() < 2 {
+            continue;
+        }
+        let raw_test_id = cells[1].trim_matches('`');
+        let test_id = raw_test_id
+            .strip_prefix("test_")
+            .unwrap_or(raw_test_id)
+            .to_string();
+        if cells.len() <= outcome_column {
+            cells.resize(outcome_column + 1, String::new());
+        }
+        if let Some(outcome) = outcomes.get(&test_id) {
+            cells[outcome_column] = outcome.replace('|', "\\|");
+        }
+        *line = table_row(&cells);
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Ok(updated)
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    line.trim()
+        .trim_matches('|')
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+fn table_row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::context::model::ContextLoopOutcome;
+    use std::path::PathBuf;
+
+    #[test]
+    fn adds_outcome_column_and_fills_matching_rows() {
+        let content = "---\ncontext_id: CDD_100\n---\n\n## Next Actions\n\n| Description | Test |\n|-------------|------|\n| One | `alpha_test` |\n| Two | `test_beta_test` |\n\n## Notes\n";
+        let summary = ContextImplementSummary {
+            tests: vec!["alpha_test".to_string(), "beta_test".to_string()],
+            generated_tests: vec![(
+                "alpha_test".to_string(),
+                PathBuf::from("tests/alpha_test.py"),
+            )],
+            terminal_outcome: Some(ContextLoopOutcome::Success),
+            tests_passed: true,
+        };
+        let updated =
+            apply_action_outcomes(content, &action_outcomes(&summary, 7)).expect("apply outcomes");
+        assert!(
+            updated.contains("| Description | Test | Outcome |\n|-------------|------|---------|")
+        );
+        assert!(
+            updated.contains("| One | `alpha_test` | passed in run 7 in `tests/alpha_test.py` |")
+        );
+        assert!(updated.contains("| Two | `test_beta_test` | passed in run 7 |"));
+        assert!(updated.ends_with("## Notes\n"));
+
+        let failing = ContextImplementSummary {
+            tests_passed: false,
+            terminal_outcome: Some(ContextLoopOutcome::MaxIterationsReached),
+            generated_tests: Vec::new(),
+            ..summary
+        };
+        let rerun = apply_action_outcomes(&updated, &action_outcomes(&failing, 8)).expect("rerun");
+        assert!(
+            rerun.contains("| One | `alpha_test` | failing (max iterations reached) in run 8 |")
+        );
+        assert_eq!(rerun.matches("Outcome").count(), 1);
+    }
+}
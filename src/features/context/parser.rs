@@ -5,13 +5,14 @@ use std::fs;
 use std::path::Path;
 
 use crate::core::context::model::{ContextNextAction, ContextParseResult};
+use crate::core::error::CliError;
 
 pub fn parse_context_file(path: &Path) -> Result<ContextParseResult> {
     if !path.exists() {
-        bail!(
+        bail!(CliError::not_found(format!(
             "Context file '{}' does not exist. Pass --context-file with a valid path.",
             path.display()
-        );
+        )));
     }
     if !path.is_file() {
         bail!(
@@ -40,12 +41,15 @@ pub fn parse_context_content(content: &str) -> Result<ContextParseResult> {
         .collect();
     let language = extract_frontmatter_value(content, "language");
     let test_runner = extract_frontmatter_value(content, "test_runner");
+    let frozen = extract_frontmatter_value(content, "frozen")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
     Ok(ContextParseResult {
         context_id,
         tests,
         next_actions,
         test_runner,
         language,
+        frozen,
     })
 }
 
@@ -221,6 +225,25 @@ context_id: CDD_100
         assert_eq!(parsed.next_actions[1].test_id, "beta_test");
         assert!(parsed.language.is_none());
         assert!(parsed.test_runner.is_none());
+        assert!(!parsed.frozen);
+    }
+
+    #[test]
+    fn reads_frozen_flag_from_frontmatter() {
+        let content = r#"---
+context_id: CDD_101
+frozen: true
+---
+
+## Next Actions
+
+| Description | Test |
+|-------------|------|
+| One | `alpha_test` |
+"#;
+
+        let parsed = parse_context_content(content).expect("parse should succeed");
+        assert!(parsed.frozen);
     }
 
     #[test]
@@ -7,6 +7,8 @@ use crate::adapters::orchestration_store::{
     default_orchestration_database_path, OrchestrationStore, StepAttemptPersistence,
     TraceRecordInput,
 };
+use crate::core::error::{CliError, ErrorCode};
+use crate::services::progress::{emit_progress, ProgressEvent};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum StepOutcome {
@@ -100,8 +102,15 @@ impl WorkflowPipeline {
         state: &mut ContextWorkflowState,
         ctx: &StepContext,
     ) -> Result<StepOutcome> {
-        for step in self.steps.iter().skip(start_index) {
+        let total = self.steps.len();
+        for (index, step) in self.steps.iter().enumerate().skip(start_index) {
             let step_id = step.id();
+            emit_progress(ProgressEvent::StepStarted {
+                run_id: ctx.options.run_id,
+                step_id: step_id.to_string(),
+                index: index + 1,
+                total,
+            });
             let step_input_json = step_io_snapshot_json(state, step.requires());
             let conversations_before = state.agent_conversations.len();
             let outcome = match step.run(state, ctx) {
@@ -130,12 +139,19 @@ impl WorkflowPipeline {
                     if let Some(checkpoint_path) = &ctx.options.checkpoint_file {
                         let _ = save_checkpoint(checkpoint_path, &state.to_checkpoint());
                     }
-                    return Err(err).with_context(|| {
+                    emit_progress(ProgressEvent::StepFinished {
+                        run_id: ctx.options.run_id,
+                        step_id: step_id.to_string(),
+                        status: "failed",
+                        error: Some(err.to_string()),
+                    });
+                    return Err(err).context(CliError::new(
+                        ErrorCode::WorkflowFailed,
                         format!(
                             "Pipeline step '{}' failed. To resume, rerun from this step after fixing the cause.",
                             step_id
-                        )
-                    });
+                        ),
+                    ));
                 }
             };
 
@@ -162,6 +178,12 @@ impl WorkflowPipeline {
             if let Some(checkpoint_path) = &ctx.options.checkpoint_file {
                 save_checkpoint(checkpoint_path, &state.to_checkpoint())?;
             }
+            emit_progress(ProgressEvent::StepFinished {
+                run_id: ctx.options.run_id,
+                step_id: step_id.to_string(),
+                status: "success",
+                error: None,
+            });
             if outcome == StepOutcome::Stop {
                 return Ok(StepOutcome::Stop);
             }
@@ -355,6 +377,7 @@ mod tests {
             resume_checkpoint: None,
             allow_dependency_bypass: false,
             overwrite: false,
+            force_regenerate_tests: false,
             run_id: None,
         }
     }
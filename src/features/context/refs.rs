@@ -0,0 +1,201 @@
+//! Rewriting of file references in context documents after repository moves.
+//!
+//! Contexts reference repository files as backtick code spans (the same spans
+//! the graph and file reservations read). A remap pattern may contain `*`
+//! (within one path segment) and `**` (across segments); each wildcard in
+//! `--from` is substituted into the matching wildcard of `--to`, so
+//! `src/old/**` -> `src/new/**` moves a whole tree.
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::features::context::translate::context_markdown_files;
+
+#[derive(Debug, Clone)]
+pub struct ReferenceRemap {
+    from: Regex,
+    to: Vec<ToSegment>,
+}
+
+#[derive(Debug, Clone)]
+enum ToSegment {
+    Literal(String),
+    Wildcard(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemappedContext {
+    pub file: String,
+    pub replacements: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemapReport {
+    pub scanned: usize,
+    pub contexts: Vec<RemappedContext>,
+}
+
+impl ReferenceRemap {
+    /// Build a remap; `from` and `to` must use the same wildcards in order.
+    pub fn new(from: &str, to: &str) -> Result<Self> {
+        let (from_regex, from_wildcards) = compile_from(from)?;
+        let (segments, to_wildcards) = split_to(to);
+        if from_wildcards != to_wildcards {
+            bail!(
+                "--from '{}' and --to '{}' must use the same wildcards in the same order. Remediation: e.g. --from 'src/old/**' --to 'src/new/**'.",
+                from,
+                to
+            );
+        }
+        Ok(Self {
+            from: from_regex,
+            to: segments,
+        })
+    }
+
+    /// The remapped path, or `None` when `path` does not match `--from`.
+    pub fn apply(&self, path: &str) -> Option<String> {
+        let captures = self.from.captures(path)?;
+        Some(
+            self.to
+                .iter()
+                .map(|segment| match segment {
+                    ToSegment::Literal(text) => text.as_str(),
+                    ToSegment::Wildcard(index) => captures
+                        .get(index + 1)
+                        .map(|capture| capture.as_str())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Rewrite matching backtick references in `content`, returning the new
+    /// content and the number of references changed.
+    pub fn rewrite(&self, content: &str) -> (String, usize) {
+        let mut replacements = 0usize;
+        let mut rewritten = String::with_capacity(content.len());
+        for (index, part) in content.split('`').enumerate() {
+            if index > 0 {
+                rewritten.push('`');
+            }
+            let is_code_span = index % 2 == 1 && !part.contains(char::is_whitespace);
+            let (prefix, path) = match part.strip_prefix("./") {
+                Some(path) => ("./", path),
+                None => ("", part),
+            };
+            match is_code_span.then(|| self.apply(path)).flatten() {
+                Some(remapped) if remapped != path => {
+                    replacements += 1;
+                    rewritten.push_str(prefix);
+                    rewritten.push_str(&remapped);
+                }
+                _ => rewritten.push_str(part),
+            }
+        }
+        (rewritten, replacements)
+    }
+}
+
+/// Apply `remap` to every context under `context_root`; `dry_run` only reports.
+pub fn remap_context_references(
+    context_root: &Path,
+    remap: &ReferenceRemap,
+    dry_run: bool,
+) -> Result<RemapReport> {
+    let mut report = RemapReport::default();
+    for path in context_markdown_files(context_root)? {
+        report.scanned += 1;
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read '{}'.", path.display()))?;
+        let (rewritten, replacements) = remap.rewrite(&content);
+        if replacements == 0 {
+            continue;
+        }
+        if !dry_run {
+            fs::write(&path, rewritten)
+                .with_context(|| format!("Failed to write '{}'.", path.display()))?;
+        }
+        report.contexts.push(RemappedContext {
+            file: path.display().to_string(),
+            replacements,
+        });
+    }
+    Ok(report)
+}
+
+fn compile_from(pattern: &str) -> Result<(Regex, Vec<&'static str>)> {
+    let (segments, wildcards) = split_to(pattern);
+    let mut regex = String::from("^");
+    for segment in &segments {
+        match segment {
+            ToSegment::Literal(text) => regex.push_str(&regex::escape(text)),
+            ToSegment::Wildcard(index) if wildcards[*index] == "**" => regex.push_str("(.*)"),
+            ToSegment::Wildcard(_) => regex.push_str("([^/]*)"),
+        }
+    }
+    regex.push('$');
+    let compiled =
+        Regex::new(&regex).with_context(|| format!("Invalid --from pattern '{}'.", pattern))?;
+    Ok((compiled, wildcards))
+}
+
+/// Split a pattern into literal text and numbered `*`/`**` wildcards.
+fn split_to(pattern: &str) -> (Vec<ToSegment>, Vec<&'static str>) {
+    let mut segments = Vec::<ToSegment>::new();
+    let mut wildcards = Vec::<&'static str>::new();
+    let mut rest = pattern.trim_start_matches("./");
+    while !rest.is_empty() {
+        let wildcard = if rest.starts_with("**") {
+            Some("**")
+        } else if rest.starts_with('*') {
+            Some("*")
+        } else {
+            None
+        };
+        match wildcard {
+            Some(wildcard) => {
+                segments.push(ToSegment::Wildcard(wildcards.len()));
+                wildcards.push(wildcard);
+                rest = &rest[wildcard.len()..];
+            }
+            None => {
+                let next = rest.find('*').unwrap_or(rest.len());
+                segments.push(ToSegment::Literal(rest[..next].to_string()));
+                rest = &rest[next..];
+            }
+        }
+    }
+    (segments, wildcards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaps_exact_and_wildcard_references() {
+        let exact = ReferenceRemap::new("src/cli.rs", "src/app/cli.rs").expect("exact");
+        let (content, count) =
+            exact.rewrite("Edit `src/cli.rs` and `./src/cli.rs`, not `src/cli.rs.bak`.\n");
+        assert_eq!(
+            content,
+            "Edit `src/app/cli.rs` and `./src/app/cli.rs`, not `src/cli.rs.bak`.\n"
+        );
+        assert_eq!(count, 2);
+
+        let tree = ReferenceRemap::new("src/old/**", "crates/core/src/**").expect("tree");
+        assert_eq!(
+            tree.apply("src/old/a/b.rs").as_deref(),
+            Some("crates/core/src/a/b.rs")
+        );
+        let ext = ReferenceRemap::new("src/*.js", "src/*.ts").expect("ext");
+        assert_eq!(ext.apply("src/index.js").as_deref(), Some("src/index.ts"));
+        assert_eq!(ext.apply("src/lib/index.js"), None);
+
+        assert!(ReferenceRemap::new("src/**", "lib/main.rs").is_err());
+    }
+}
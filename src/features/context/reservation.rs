@@ -0,0 +1,119 @@
+//! File reservations for concurrent orchestration runs.
+//!
+//! Every running run reserves the repository files its context snapshot
+//! references in backticks. A new run whose file set overlaps an active run
+//! on another context is warned about (or blocked with `--exclusive-files`)
+//! so parallel agents do not edit the same files.
+
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReservationConflict {
+    pub run_id: i64,
+    pub context_file: String,
+    pub files: Vec<String>,
+}
+
+/// Repository file paths referenced in backticks by a context document.
+///
+/// `.nexus/` paths, URLs, and identifiers without a directory or extension are
+/// ignored so test ids and code symbols do not reserve anything.
+pub fn referenced_files(content: &str) -> BTreeSet<String> {
+    code_span_pattern()
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let candidate = captures[1].trim_start_matches("./");
+            looks_like_path(candidate).then(|| candidate.to_string())
+        })
+        .collect()
+}
+
+/// Overlaps between `files` and the reservations of active runs on other contexts.
+///
+/// `active_runs` holds `(run_id, context_file, context_snapshot)` rows.
+pub fn reservation_conflicts(
+    files: &BTreeSet<String>,
+    context_file: &str,
+    active_runs: &[(i64, String, String)],
+) -> Vec<ReservationConflict> {
+    if files.is_empty() {
+        return Vec::new();
+    }
+    active_runs
+        .iter()
+        .filter(|(_, active_context, _)| active_context != context_file)
+        .filter_map(|(run_id, active_context, snapshot)| {
+            let overlap = referenced_files(snapshot)
+                .intersection(files)
+                .cloned()
+                .collect::<Vec<_>>();
+            (!overlap.is_empty()).then(|| ReservationConflict {
+                run_id: *run_id,
+                context_file: active_context.clone(),
+                files: overlap,
+            })
+        })
+        .collect()
+}
+
+fn looks_like_path(candidate: &str) -> bool {
+    if candidate.is_empty()
+        || candidate.starts_with(".nexus/")
+        || candidate.starts_with('-')
+        || candidate.starts_with('/')
+        || candidate.contains("://")
+        || candidate.contains(':')
+        || candidate.contains(['(', ')', '{', '}', '<', '>', '*', '=', '"', '\''])
+    {
+        return false;
+    }
+    let file_name = candidate.rsplit('/').next().unwrap_or(candidate);
+    let has_extension = file_name.rsplit_once('.').is_some_and(|(stem, extension)| {
+        !stem.is_empty()
+            && extension.starts_with(|c: char| c.is_ascii_alphabetic())
+            && extension.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    has_extension || (candidate.contains('/') && !candidate.ends_with('/'))
+}
+
+fn code_span_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"`([^`\s]+)`").expect("code span pattern"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overlapping_files_from_other_active_contexts() {
+        let content = "Touch `src/cli.rs`, `./src/main.rs`, and `Cargo.toml`.\nIgnore `alpha_test`, `std::fs`, `.nexus/context/app/CDD_001.md`, `--format`, and `https://example.com/a.rs`.\n";
+        let files = referenced_files(content);
+        assert_eq!(
+            files.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["Cargo.toml", "src/cli.rs", "src/main.rs"]
+        );
+
+        let active = vec![
+            (4, "ctx/a.md".to_string(), "Edit `src/cli.rs`.".to_string()),
+            (5, "ctx/b.md".to_string(), "Edit `README.md`.".to_string()),
+            (
+                6,
+                "ctx/self.md".to_string(),
+                "Edit `src/main.rs`.".to_string(),
+            ),
+        ];
+        let conflicts = reservation_conflicts(&files, "ctx/self.md", &active);
+        assert_eq!(
+            conflicts,
+            vec![ReservationConflict {
+                run_id: 4,
+                context_file: "ctx/a.md".to_string(),
+                files: vec!["src/cli.rs".to_string()],
+            }]
+        );
+    }
+}
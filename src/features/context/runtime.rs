@@ -4,17 +4,20 @@ use super::backfill::run_context_backfill as run_backfill;
 use super::implement::run_context_implement as run_implement;
 use super::status::run_context_test_status as run_status;
 use crate::core::context::model::{
-    ContextBackfillOptions, ContextImplementOptions, ContextTestStatusOptions,
+    ContextBackfillOptions, ContextBackfillReport, ContextImplementOptions,
+    ContextImplementSummary, ContextTestStatusOptions, ContextTestStatusReport,
 };
 
-pub fn run_context_test_status(options: &ContextTestStatusOptions) -> Result<()> {
+pub fn run_context_test_status(
+    options: &ContextTestStatusOptions,
+) -> Result<ContextTestStatusReport> {
     run_status(options)
 }
 
-pub fn run_context_backfill(options: &ContextBackfillOptions) -> Result<()> {
+pub fn run_context_backfill(options: &ContextBackfillOptions) -> Result<ContextBackfillReport> {
     run_backfill(options)
 }
 
-pub fn run_context_implement(options: &ContextImplementOptions) -> Result<()> {
+pub fn run_context_implement(options: &ContextImplementOptions) -> Result<ContextImplementSummary> {
     run_implement(options)
 }
@@ -0,0 +1,149 @@
+//! Stub test files and module skeletons for a context's Next Actions.
+//!
+//! Test stubs reuse the orchestration scaffold (one failing test per action,
+//! named after its test id); module skeletons are created for source files the
+//! context references in backticks that do not exist yet. Existing files are
+//! never touched, so rerunning only fills in what is still missing.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::core::context::model::ContextParseResult;
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::reservation::referenced_files;
+use crate::features::context::steps::{derive_context_test_output_dir, generate_test_scaffold};
+use crate::features::context::test_runner::{resolve_test_runner_plan, ToolchainKind};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScaffoldReport {
+    pub context_id: String,
+    pub tests_created: Vec<PathBuf>,
+    pub modules_created: Vec<PathBuf>,
+}
+
+impl ScaffoldReport {
+    pub fn created_files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.tests_created.iter().chain(&self.modules_created)
+    }
+}
+
+/// Create missing test stubs and referenced module skeletons for `context_file`.
+pub fn scaffold_context(context_file: &Path) -> Result<ScaffoldReport> {
+    let parsed = parse_context_file(context_file)?;
+    let content = fs::read_to_string(context_file)
+        .with_context(|| format!("Unable to read '{}'.", context_file.display()))?;
+    let toolchain = resolve_test_runner_plan(Path::new("."), &parsed, None, None, None)
+        .map(|plan| plan.toolchain)
+        .unwrap_or(ToolchainKind::Unknown);
+
+    let output_dir = derive_context_test_output_dir(context_file)?;
+    let existing_tests = existing_files(&output_dir);
+    let tests_created = generate_test_scaffold(context_file, &parsed, toolchain)?
+        .into_iter()
+        .map(|generated| generated.path)
+        .filter(|path| !existing_tests.contains(path))
+        .collect::<Vec<_>>();
+
+    let mut modules_created = Vec::<PathBuf>::new();
+    for file in referenced_files(&content) {
+        let path = PathBuf::from(&file);
+        let Some(skeleton) = module_skeleton(&path, &parsed) else {
+            continue;
+        };
+        if path.exists() || !is_repository_relative(&path) {
+            continue;
+        }
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+        }
+        fs::write(&path, skeleton)
+            .with_context(|| format!("Failed to write '{}'.", path.display()))?;
+        modules_created.push(path);
+    }
+
+    Ok(ScaffoldReport {
+        context_id: parsed.context_id,
+        tests_created,
+        modules_created,
+    })
+}
+
+/// Skeleton content for a source file, or `None` for non-code references.
+fn module_skeleton(path: &Path, parsed: &ContextParseResult) -> Option<String> {
+    let comment = match path.extension().and_then(|ext| ext.to_str())? {
+        "rs" => "//!",
+        "go" | "js" | "jsx" | "ts" | "tsx" | "java" | "kt" | "swift" | "c" | "cpp" | "h" => "//",
+        "py" | "rb" | "sh" => "#",
+        _ => return None,
+    };
+    let mut skeleton = format!(
+        "{} Scaffolded by `opennexus scaffold` for {}.\n{}\n{} Pending actions:\n",
+        comment, parsed.context_id, comment, comment
+    );
+    for action in &parsed.next_actions {
+        skeleton.push_str(&format!(
+            "{} - {} (`{}`)\n",
+            comment, action.description, action.test_id
+        ));
+    }
+    if path.extension().and_then(|ext| ext.to_str()) == Some("go") {
+        let package = path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("main");
+        skeleton.push_str(&format!("\npackage {}\n", package));
+    }
+    Some(skeleton)
+}
+
+fn existing_files(dir: &Path) -> BTreeSet<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_repository_relative(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::context::model::ContextNextAction;
+
+    #[test]
+    fn builds_skeletons_for_code_references_only() {
+        let parsed = ContextParseResult {
+            context_id: "CDD_007".to_string(),
+            tests: vec!["login_succeeds".to_string()],
+            next_actions: vec![ContextNextAction {
+                description: "Accept valid credentials".to_string(),
+                test_id: "login_succeeds".to_string(),
+            }],
+            test_runner: None,
+            language: None,
+            frozen: false,
+        };
+
+        let rust = module_skeleton(Path::new("src/auth/login.rs"), &parsed).expect("rust");
+        assert!(rust.starts_with("//! Scaffolded by `opennexus scaffold` for CDD_007.\n"));
+        assert!(rust.contains("//! - Accept valid credentials (`login_succeeds`)\n"));
+        let go = module_skeleton(Path::new("internal/auth/login.go"), &parsed).expect("go");
+        assert!(go.ends_with("\npackage auth\n"));
+        assert!(module_skeleton(Path::new("README.md"), &parsed).is_none());
+        assert!(!is_repository_relative(Path::new("../outside.rs")));
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{bail, Context, Result};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use super::parser::parse_context_file;
 
@@ -54,6 +54,16 @@ pub(crate) fn scan_valid_context_specs(root: &Path) -> Result<ContextScanResult>
     })
 }
 
+/// The project a context belongs to: the first path component under
+/// `context_root`, which is the file name for a context directly under it.
+pub(crate) fn context_project(context_root: &Path, context_file: &Path) -> Option<String> {
+    let relative = context_file.strip_prefix(context_root).ok()?;
+    match relative.components().next()? {
+        Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+        _ => None,
+    }
+}
+
 fn is_context_markdown_candidate(path: &Path) -> bool {
     if path.file_name().and_then(|name| name.to_str()) == Some("index.md") {
         return false;
@@ -69,6 +79,20 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn context_project_uses_first_component_under_context_root() {
+        let root = Path::new(".nexus/context");
+        assert_eq!(
+            context_project(root, Path::new(".nexus/context/nexus-cli/cdd/CDD_001.md")).as_deref(),
+            Some("nexus-cli")
+        );
+        assert_eq!(
+            context_project(root, Path::new(".nexus/context/CDD_001.md")).as_deref(),
+            Some("CDD_001.md")
+        );
+        assert!(context_project(root, Path::new("elsewhere/CDD_001.md")).is_none());
+    }
+
     #[test]
     fn context_backfill_all_scans_and_filters_valid_context_specs() {
         let temp = tempdir().expect("tempdir");
@@ -2,38 +2,46 @@ use anyhow::{bail, Result};
 
 use super::parser::parse_context_file;
 use super::test_discovery::{discover_tests_from_runner, is_test_discovered};
-use crate::core::context::model::ContextTestStatusOptions;
+use crate::core::context::model::{ContextTestStatusOptions, ContextTestStatusReport};
 
-pub(crate) fn run_context_test_status(options: &ContextTestStatusOptions) -> Result<()> {
-    let resolved = resolve_test_status_command_name(&options.command_name)?;
+pub(crate) fn run_context_test_status(
+    options: &ContextTestStatusOptions,
+) -> Result<ContextTestStatusReport> {
+    let command_name = resolve_test_status_command_name(&options.command_name)?;
     let parsed = parse_context_file(&options.context_file)?;
     let discovered = discover_tests_from_runner()?;
 
-    println!("Context file: {}", options.context_file.display());
-    println!("Command id: {}", resolved);
-    println!("Context id: {}", parsed.context_id);
-    println!("Tests from Next Actions:");
+    let tests = parsed
+        .tests
+        .iter()
+        .map(|test_id| (test_id.clone(), is_test_discovered(test_id, &discovered)))
+        .collect();
+    Ok(ContextTestStatusReport {
+        context_file: options.context_file.clone(),
+        command_name,
+        context_id: parsed.context_id,
+        tests,
+    })
+}
 
-    let mut found = 0usize;
-    for test_id in &parsed.tests {
-        let is_discovered = is_test_discovered(test_id, &discovered);
-        if is_discovered {
-            found += 1;
-        }
+pub(crate) fn print_test_status_report(report: &ContextTestStatusReport) {
+    println!("Context file: {}", report.context_file.display());
+    println!("Command id: {}", report.command_name);
+    println!("Context id: {}", report.context_id);
+    println!("Tests from Next Actions:");
+    for (test_id, discovered) in &report.tests {
         println!(
             "- {:<9} {}",
-            if is_discovered {
-                "DISCOVERED"
-            } else {
-                "MISSING"
-            },
+            if *discovered { "DISCOVERED" } else { "MISSING" },
             test_id
         );
     }
-
-    println!("Discovered: {}/{} tests.", found, parsed.tests.len());
+    println!(
+        "Discovered: {}/{} tests.",
+        report.discovered_count(),
+        report.tests.len()
+    );
     println!("Warning: test existence and discovery do not guarantee behavioral correctness.");
-    Ok(())
 }
 
 pub(crate) fn resolve_test_status_command_name(raw: &str) -> Result<&'static str> {
@@ -16,5 +16,6 @@ pub(crate) use generate_scaffold::GenerateScaffoldStep;
 pub(crate) use parse_context::ParseContextStep;
 pub(crate) use red_test_author::RedTestAuthorStep;
 pub(crate) use resolve_test_runner::ResolveTestRunnerStep;
+pub(crate) use support::{derive_context_test_output_dir, generate_test_scaffold};
 pub(crate) use verify_red_tests::VerifyRedTestsStep;
 pub(crate) use verify_test_discovery::VerifyTestDiscoveryStep;
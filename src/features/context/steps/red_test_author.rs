@@ -1,9 +1,16 @@
 use anyhow::{Context, Result};
+use std::fs;
 
+use crate::adapters::orchestration_store::{
+    default_orchestration_database_path, ActionTestCacheEntry, OrchestrationStore,
+};
 use crate::features::context::pipeline::{StepContract, StepOutcome, WorkflowStep};
 use crate::features::context::reporting::{log_stage_result, log_stage_start};
 use crate::features::context::steps::support::{build_red_test_author_prompt, run_agent_stage};
-use crate::features::context::workflow_state::{ContextWorkflowState, StepContext, WorkflowEvent};
+use crate::features::context::test_cache::{action_hash, file_hash};
+use crate::features::context::workflow_state::{
+    ContextWorkflowState, GeneratedTestFile, StepContext, WorkflowEvent,
+};
 
 pub(crate) struct RedTestAuthorStep;
 
@@ -23,12 +30,64 @@ impl WorkflowStep for RedTestAuthorStep {
             .context("Runner plan missing in workflow state")?;
 
         log_stage_start("red_test_author", 0, &ctx.options.context_file);
+        let context_content = fs::read_to_string(&ctx.options.context_file).with_context(|| {
+            format!(
+                "Unable to read context file '{}'.",
+                ctx.options.context_file.display()
+            )
+        })?;
+        let store = OrchestrationStore::open(&default_orchestration_database_path())?;
+
+        let mut stale = Vec::<(GeneratedTestFile, String)>::new();
+        let mut skipped = 0usize;
+        for file in &state.generated_files {
+            let hash = parsed
+                .next_actions
+                .iter()
+                .find(|action| action.test_id == file.test_id)
+                .map(|action| action_hash(&context_content, action))
+                .unwrap_or_default();
+            let unchanged = !ctx.options.force_regenerate_tests
+                && store
+                    .cached_action_test(&parsed.context_id, &file.test_id)?
+                    .is_some_and(|entry| {
+                        entry.action_hash == hash
+                            && entry.test_file == file.path.display().to_string()
+                            && file_hash(&file.path).as_deref() == Some(entry.test_hash.as_str())
+                    });
+            if unchanged {
+                skipped += 1;
+            } else {
+                stale.push((file.clone(), hash));
+            }
+        }
+
+        if stale.is_empty() {
+            log_stage_result(
+                "red_test_author",
+                true,
+                &format!("regenerated=0 skipped={} (actions unchanged)", skipped),
+            );
+            state.events.push(WorkflowEvent {
+                step_id: self.id().to_string(),
+                message: format!(
+                    "skipped red test authoring for {} unchanged actions",
+                    skipped
+                ),
+            });
+            return Ok(StepOutcome::Continue);
+        }
+
+        let stale_files = stale
+            .iter()
+            .map(|(file, _)| file.clone())
+            .collect::<Vec<GeneratedTestFile>>();
         let prompt = build_red_test_author_prompt(
             &ctx.options.context_file,
             parsed,
             state.selected_rule.as_deref(),
             runner_plan,
-            &state.generated_files,
+            &stale_files,
         )?;
         let conversation = run_agent_stage(
             "red_test_author",
@@ -38,15 +97,37 @@ impl WorkflowStep for RedTestAuthorStep {
         .with_context(|| {
             "red_test_author stage failed. Remediation: ensure OpenCode is installed/authenticated and retry."
         })?;
+
+        for (file, hash) in &stale {
+            if let Some(test_hash) = file_hash(&file.path) {
+                store.record_action_test(
+                    &parsed.context_id,
+                    &file.test_id,
+                    &ActionTestCacheEntry {
+                        action_hash: hash.clone(),
+                        test_file: file.path.display().to_string(),
+                        test_hash,
+                    },
+                )?;
+            }
+        }
         log_stage_result(
             "red_test_author",
             true,
-            "agent generated test content for scaffold files",
+            &format!(
+                "agent generated test content for scaffold files regenerated={} skipped={}",
+                stale.len(),
+                skipped
+            ),
         );
 
         state.events.push(WorkflowEvent {
             step_id: self.id().to_string(),
-            message: "completed red test authoring".to_string(),
+            message: format!(
+                "completed red test authoring (regenerated={} skipped={})",
+                stale.len(),
+                skipped
+            ),
         });
         state.agent_conversations.push(conversation);
         Ok(StepOutcome::Continue)
@@ -11,6 +11,7 @@ use crate::features::context::test_runner::{
     run_test_with_plan, run_test_with_plan_capture, TestRunnerPlan, ToolchainKind,
 };
 use crate::features::context::workflow_state::{AgentConversationRecord, GeneratedTestFile};
+use crate::output::propagate_color_choice;
 
 #[derive(Debug, Clone)]
 pub(crate) struct RedTestClassification {
@@ -166,6 +167,8 @@ pub(crate) fn generate_test_scaffold(
     Ok(generated_files)
 }
 
+/// Where a context's generated tests live: `.../context/<project>/<feature>/<Context>.md`
+/// maps to `tests/<project>/<feature>/<context>/`, each segment sanitized.
 pub(crate) fn derive_context_test_output_dir(context_file: &Path) -> Result<PathBuf> {
     let mut components = Vec::<String>::new();
     let mut found_context_root = false;
@@ -244,16 +247,15 @@ pub(crate) fn run_agent_stage(
         None,
     );
     let started = Instant::now();
-    let output = Command::new(&invocation.command)
-        .args(&invocation.args)
-        .envs(&invocation.env)
-        .output()
-        .with_context(|| {
-            format!(
-                "Failed to execute {} agent command '{}'.",
-                stage, invocation.command
-            )
-        })?;
+    let mut command = Command::new(&invocation.command);
+    command.args(&invocation.args).envs(&invocation.env);
+    propagate_color_choice(&mut command);
+    let output = command.output().with_context(|| {
+        format!(
+            "Failed to execute {} agent command '{}'.",
+            stage, invocation.command
+        )
+    })?;
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -472,11 +474,24 @@ fn looks_like_syntax_or_collection_failure(output: &str, markers: &[String]) ->
 
 #[cfg(test)]
 mod tests {
-    use super::estimate_token_usage;
+    use super::{derive_context_test_output_dir, estimate_token_usage};
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn token_estimate_is_non_zero_for_non_empty_payloads() {
         let estimate = estimate_token_usage("hello", "world", "");
         assert!(estimate > 0);
     }
+
+    #[test]
+    fn test_output_dir_mirrors_project_feature_and_context() {
+        assert_eq!(
+            derive_context_test_output_dir(Path::new(
+                ".nexus/context/sample/cdd/CDD_900-sample-implement.md"
+            ))
+            .expect("under context/"),
+            PathBuf::from("tests/sample/cdd/cdd_900-sample-implement")
+        );
+        assert!(derive_context_test_output_dir(Path::new("notes/CDD_900.md")).is_err());
+    }
 }
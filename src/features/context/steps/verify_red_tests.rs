@@ -69,6 +69,15 @@ impl WorkflowStep for VerifyRedTestsStep {
         }
 
         if !non_behavioral.is_empty() {
+            if let Some(parsed) = state.parsed.as_ref() {
+                let store = OrchestrationStore::open(&default_orchestration_database_path())?;
+                for record in classifications
+                    .iter()
+                    .filter(|record| !record.is_behavioral)
+                {
+                    store.forget_action_test(&parsed.context_id, &record.test_id)?;
+                }
+            }
             anyhow::bail!(
                 "Red gate blocked: non-behavioral failures detected. {}",
                 non_behavioral.join(" | ")
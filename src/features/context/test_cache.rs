@@ -0,0 +1,74 @@
+//! Per-action fingerprints for skipping unchanged red-test authoring.
+//!
+//! An action's hash covers its Next Actions row plus the context body outside
+//! the Next Actions section, so editing one row only invalidates that action
+//! while editing the spec itself invalidates all of them.
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::core::context::model::ContextNextAction;
+
+pub(crate) fn action_hash(context_content: &str, action: &ContextNextAction) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(context_body(context_content).as_bytes());
+    hasher.update(b"\naction:");
+    hasher.update(action.test_id.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(action.description.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// SHA-256 of a test file's current content; `None` when it cannot be read.
+pub(crate) fn file_hash(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    Some(format!("{:x}", Sha256::digest(&content)))
+}
+
+fn context_body(content: &str) -> String {
+    let mut in_next_actions = false;
+    content
+        .lines()
+        .filter(|line| {
+            if line.starts_with("## ") {
+                in_next_actions = line.trim() == "## Next Actions";
+            }
+            !in_next_actions
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(test_id: &str, description: &str) -> ContextNextAction {
+        ContextNextAction {
+            description: description.to_string(),
+            test_id: test_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn row_edits_only_change_that_action_hash() {
+        let original = "# Spec\n\nBody.\n\n## Next Actions\n\n| Description | Test |\n|---|---|\n| Login | `login_test` |\n| Logout | `logout_test` |\n";
+        let edited_row = original.replace("| Logout |", "| Sign out |");
+        let edited_body = original.replace("Body.", "Body changed.");
+
+        let login = action("login_test", "Login");
+        assert_eq!(
+            action_hash(original, &login),
+            action_hash(&edited_row, &login)
+        );
+        assert_ne!(
+            action_hash(original, &action("logout_test", "Logout")),
+            action_hash(&edited_row, &action("logout_test", "Sign out"))
+        );
+        assert_ne!(
+            action_hash(original, &login),
+            action_hash(&edited_body, &login)
+        );
+    }
+}
@@ -340,6 +340,11 @@ fn detect_toolchain(
         }
     }
 
+    detect_repo_toolchain(repo_root)
+}
+
+/// Detect the primary toolchain from manifest files at `repo_root`.
+pub(crate) fn detect_repo_toolchain(repo_root: &Path) -> ToolchainKind {
     if repo_root.join("Cargo.toml").exists() {
         return ToolchainKind::Rust;
     }
@@ -371,6 +376,7 @@ mod tests {
             }],
             test_runner: None,
             language: None,
+            frozen: false,
         }
     }
 
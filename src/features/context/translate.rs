@@ -0,0 +1,310 @@
+//! Prefix and project translation for contexts imported from other repositories.
+//!
+//! `marketplace install` copies foreign context folders verbatim; this module
+//! then rewrites context ids (`ABC_001` -> `XYZ_001`), `project:` frontmatter,
+//! and `.nexus/context/<project>/` paths in place, and reports references that
+//! do not resolve to any context in the local tree.
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextTranslation {
+    pub prefixes: BTreeMap<String, String>,
+    pub projects: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnresolvedLink {
+    pub file: String,
+    pub context_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TranslationReport {
+    pub translated_files: usize,
+    pub unresolved_links: Vec<UnresolvedLink>,
+}
+
+impl ContextTranslation {
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty() && self.projects.is_empty()
+    }
+
+    /// Drop identity entries so unchanged prefixes/projects are not rewritten.
+    pub fn without_identity(mut self) -> Self {
+        self.prefixes.retain(|from, to| from != to);
+        self.projects.retain(|from, to| from != to);
+        self
+    }
+}
+
+/// Parse a `FROM=TO` mapping flag value.
+pub fn parse_mapping(raw: &str, flag: &str) -> Result<(String, String)> {
+    let Some((from, to)) = raw.split_once('=') else {
+        bail!(
+            "Invalid {} value '{}'. Remediation: use FROM=TO, for example {}=ABC=XYZ.",
+            flag,
+            raw,
+            flag
+        );
+    };
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() {
+        bail!(
+            "Invalid {} value '{}'. Remediation: both sides of FROM=TO must be non-empty.",
+            flag,
+            raw
+        );
+    }
+    Ok((from.to_string(), to.to_string()))
+}
+
+/// Context id prefixes declared by `context_id:` frontmatter in `files`.
+pub fn declared_prefixes(files: &[PathBuf]) -> BTreeSet<String> {
+    files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| declared_context_id(&content))
+        .filter_map(|id| id.split_once('_').map(|(prefix, _)| prefix.to_string()))
+        .collect()
+}
+
+/// Markdown files under `root`, sorted.
+pub fn context_markdown_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::<PathBuf>::new();
+    if !root.exists() {
+        return Ok(files);
+    }
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir)
+            .with_context(|| format!("Unable to read context directory '{}'.", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Translate imported context directories in place and report unresolved links.
+///
+/// `installed_dirs` are the top-level project folders the install just wrote
+/// under `context_root`; a mapped project folder is merged into its local name.
+pub fn translate_installed_contexts(
+    context_root: &Path,
+    installed_dirs: &[PathBuf],
+    translation: &ContextTranslation,
+) -> Result<TranslationReport> {
+    let mut report = TranslationReport::default();
+    let mut translated_dirs = Vec::<PathBuf>::new();
+
+    for dir in installed_dirs {
+        let target_dir = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| translation.projects.get(name))
+            .map(|local| context_root.join(local))
+            .unwrap_or_else(|| dir.clone());
+
+        for source in context_markdown_files(dir)? {
+            let relative = source.strip_prefix(dir).unwrap_or(&source);
+            let file_name = relative
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let target = target_dir
+                .join(relative)
+                .with_file_name(translate_file_name(file_name, translation));
+            let content = fs::read_to_string(&source)
+                .with_context(|| format!("Unable to read '{}'.", source.display()))?;
+            let translated = translate_content(&content, translation);
+            if target != source && target.exists() {
+                bail!(
+                    "Translating '{}' would overwrite '{}'. Remediation: map the prefix or project to a name without existing contexts.",
+                    source.display(),
+                    target.display()
+                );
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+            }
+            fs::write(&target, &translated)
+                .with_context(|| format!("Failed to write '{}'.", target.display()))?;
+            if target != source {
+                fs::remove_file(&source)
+                    .with_context(|| format!("Failed to remove '{}'.", source.display()))?;
+            }
+            if translated != content || target != source {
+                report.translated_files += 1;
+            }
+        }
+        if target_dir != *dir {
+            move_remaining_files(dir, &target_dir)?;
+        }
+        translated_dirs.push(target_dir);
+    }
+
+    let defined = context_markdown_files(context_root)?
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| declared_context_id(&content))
+        .collect::<BTreeSet<_>>();
+    for dir in &translated_dirs {
+        for path in context_markdown_files(dir)? {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Unable to read '{}'.", path.display()))?;
+            let referenced = context_id_pattern()
+                .find_iter(&content)
+                .map(|found| found.as_str().to_string())
+                .collect::<BTreeSet<_>>();
+            for context_id in referenced.difference(&defined) {
+                report.unresolved_links.push(UnresolvedLink {
+                    file: path.display().to_string(),
+                    context_id: context_id.clone(),
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn translate_content(content: &str, translation: &ContextTranslation) -> String {
+    let mut translated = context_id_pattern()
+        .replace_all(content, |captures: &regex::Captures<'_>| {
+            let prefix = &captures[1];
+            match translation.prefixes.get(prefix) {
+                Some(local) => format!("{}_{}", local, &captures[2]),
+                None => captures[0].to_string(),
+            }
+        })
+        .into_owned();
+    for (foreign, local) in &translation.projects {
+        let project_line = Regex::new(&format!(
+            r#"(?m)^project:\s*"?{}"?\s*$"#,
+            regex::escape(foreign)
+        ))
+        .expect("escaped project pattern");
+        translated = project_line
+            .replace_all(&translated, format!("project: {}", local).as_str())
+            .into_owned();
+        translated = translated.replace(
+            &format!(".nexus/context/{}/", foreign),
+            &format!(".nexus/context/{}/", local),
+        );
+    }
+    translated
+}
+
+fn translate_file_name(file_name: &str, translation: &ContextTranslation) -> String {
+    match file_name.split_once('_') {
+        Some((prefix, rest)) => match translation.prefixes.get(prefix) {
+            Some(local) => format!("{}_{}", local, rest),
+            None => file_name.to_string(),
+        },
+        None => file_name.to_string(),
+    }
+}
+
+fn move_remaining_files(source_dir: &Path, target_dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(source_dir)? {
+        let path = entry?.path();
+        let target = target_dir.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            fs::create_dir_all(&target)?;
+            move_remaining_files(&path, &target)?;
+        } else if !target.exists() {
+            fs::rename(&path, &target).with_context(|| {
+                format!(
+                    "Failed to move '{}' to '{}'.",
+                    path.display(),
+                    target.display()
+                )
+            })?;
+        }
+    }
+    fs::remove_dir_all(source_dir)
+        .with_context(|| format!("Failed to remove '{}'.", source_dir.display()))
+}
+
+fn declared_context_id(content: &str) -> Option<String> {
+    content
+        .lines()
+        .take_while(|line| !line.starts_with("# "))
+        .find_map(|line| line.strip_prefix("context_id:"))
+        .map(|value| value.trim().trim_matches('"').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn context_id_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\b([A-Z][A-Z0-9]{1,7})_(\d{3,})\b").expect("context id pattern")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn translation() -> ContextTranslation {
+        ContextTranslation {
+            prefixes: BTreeMap::from([("DOC".to_string(), "WEB".to_string())]),
+            projects: BTreeMap::from([("fumadocs".to_string(), "site".to_string())]),
+        }
+    }
+
+    #[test]
+    fn translates_ids_projects_and_paths() {
+        let content = "---\ncontext_id: DOC_002\nproject: fumadocs\ndepends_on:\n  contexts:\n    - id: DOC_001\n---\n\nSee `.nexus/context/fumadocs/setup/DOC_001-init.md` and CLI_003.\n";
+        let translated = translate_content(content, &translation());
+        assert!(translated.contains("context_id: WEB_002"));
+        assert!(translated.contains("project: site"));
+        assert!(translated.contains("- id: WEB_001"));
+        assert!(translated.contains(".nexus/context/site/setup/WEB_001-init.md"));
+        assert!(translated.contains("CLI_003"));
+        assert_eq!(
+            translate_file_name("DOC_002-theme.md", &translation()),
+            "WEB_002-theme.md"
+        );
+        assert!(parse_mapping("DOC", "--map-prefix").is_err());
+    }
+
+    #[test]
+    fn moves_mapped_project_and_reports_unresolved_links() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path().join("context");
+        let imported = root.join("fumadocs");
+        fs::create_dir_all(imported.join("setup")).expect("create dirs");
+        fs::write(
+            imported.join("setup/DOC_001-init.md"),
+            "---\ncontext_id: DOC_001\nproject: fumadocs\n---\n\nDepends on DOC_009.\n",
+        )
+        .expect("write context");
+
+        let report =
+            translate_installed_contexts(&root, std::slice::from_ref(&imported), &translation())
+                .expect("translate");
+        assert_eq!(report.translated_files, 1);
+        assert!(!imported.exists());
+        let moved = root.join("site/setup/WEB_001-init.md");
+        assert!(fs::read_to_string(&moved)
+            .expect("read moved")
+            .contains("context_id: WEB_001"));
+        assert_eq!(report.unresolved_links.len(), 1);
+        assert_eq!(report.unresolved_links[0].context_id, "WEB_009");
+    }
+}
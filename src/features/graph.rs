@@ -0,0 +1,271 @@
+//! Context graph model for `graph serve`.
+//!
+//! Nodes are projects, contexts, and referenced files; edges record project
+//! membership, `depends_on` frontmatter, and backtick file references found in
+//! context bodies. The graph is rebuilt from disk on every request, so the
+//! served page only has to poll the fingerprint to live-reload.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use crate::features::context::parser::parse_context_file;
+use crate::features::context::scan::{context_project, scan_valid_context_specs};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GraphNode {
+    pub id: String,
+    pub kind: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContextGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    pub parse_errors: Vec<String>,
+}
+
+/// Build the graph for every valid context spec under `context_root`.
+pub fn build_context_graph(context_root: &Path) -> Result<ContextGraph> {
+    let scan = scan_valid_context_specs(context_root)?;
+    let mut nodes = BTreeSet::<GraphNode>::new();
+    let mut edges = BTreeSet::<GraphEdge>::new();
+
+    for context_file in &scan.valid_context_files {
+        let parsed = parse_context_file(context_file)?;
+        let content = fs::read_to_string(context_file).with_context(|| {
+            format!("Unable to read context file '{}'.", context_file.display())
+        })?;
+        let frontmatter = frontmatter_value(&content);
+        let context_node = format!("context:{}", parsed.context_id);
+        let title = frontmatter
+            .get("title")
+            .and_then(|value| value.as_str())
+            .map(|title| format!("{}: {}", parsed.context_id, title))
+            .unwrap_or_else(|| parsed.context_id.clone());
+        nodes.insert(node(&context_node, "context", &title));
+
+        let project = frontmatter
+            .get("project")
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+            .or_else(|| context_project(context_root, context_file));
+        if let Some(project) = project {
+            let project_node = format!("project:{}", project);
+            nodes.insert(node(&project_node, "project", &project));
+            edges.insert(edge(&project_node, &context_node, "contains"));
+        }
+
+        let depends_on = frontmatter.get("depends_on");
+        for context_id in dependency_ids(depends_on.and_then(|value| value.get("contexts"))) {
+            let target = format!("context:{}", context_id);
+            nodes.insert(node(&target, "context", &context_id));
+            edges.insert(edge(&context_node, &target, "depends_on"));
+        }
+        for project in dependency_ids(depends_on.and_then(|value| value.get("projects"))) {
+            let target = format!("project:{}", project);
+            nodes.insert(node(&target, "project", &project));
+            edges.insert(edge(&context_node, &target, "depends_on"));
+        }
+
+        for reference in file_references(&content) {
+            let target = format!("file:{}", reference);
+            nodes.insert(node(&target, "file", &reference));
+            edges.insert(edge(&context_node, &target, "references"));
+        }
+    }
+
+    // Dependency targets seen before their own spec keep the richer label.
+    let mut deduped = Vec::<GraphNode>::new();
+    for candidate in nodes {
+        match deduped.last_mut() {
+            Some(last) if last.id == candidate.id => {
+                if candidate.label.len() > last.label.len() {
+                    *last = candidate;
+                }
+            }
+            _ => deduped.push(candidate),
+        }
+    }
+
+    Ok(ContextGraph {
+        nodes: deduped,
+        edges: edges.into_iter().collect(),
+        parse_errors: scan.parse_errors,
+    })
+}
+
+/// Cheap change marker for live reload: file count plus newest modification time.
+pub fn context_tree_fingerprint(context_root: &Path) -> String {
+    let mut stack = vec![context_root.to_path_buf()];
+    let mut files = 0usize;
+    let mut newest = 0u128;
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            files += 1;
+            let modified = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_nanos())
+                .unwrap_or(0);
+            newest = newest.max(modified);
+        }
+    }
+    format!("{}-{}", files, newest)
+}
+
+fn node(id: &str, kind: &str, label: &str) -> GraphNode {
+    GraphNode {
+        id: id.to_string(),
+        kind: kind.to_string(),
+        label: label.to_string(),
+    }
+}
+
+fn edge(source: &str, target: &str, kind: &str) -> GraphEdge {
+    GraphEdge {
+        source: source.to_string(),
+        target: target.to_string(),
+        kind: kind.to_string(),
+    }
+}
+
+fn frontmatter_value(content: &str) -> serde_yaml::Value {
+    let trimmed = content.trim_start();
+    let Some(rest) = trimmed.strip_prefix("---") else {
+        return serde_yaml::Value::Null;
+    };
+    let Some(end) = rest.find("\n---") else {
+        return serde_yaml::Value::Null;
+    };
+    serde_yaml::from_str(&rest[..end]).unwrap_or(serde_yaml::Value::Null)
+}
+
+/// Dependency entries are either plain ids or `{ id, why }` maps.
+fn dependency_ids(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    value
+        .and_then(|value| value.as_sequence())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    entry
+                        .as_str()
+                        .or_else(|| entry.get("id").and_then(|id| id.as_str()))
+                        .map(str::to_string)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Backtick spans that look like relative file paths (`dir/name.ext`).
+fn file_references(content: &str) -> BTreeSet<String> {
+    content
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .filter(|span| {
+            !span.contains(char::is_whitespace)
+                && span.contains('/')
+                && !span.contains("://")
+                && Path::new(span)
+                    .extension()
+                    .is_some_and(|ext| ext.to_str().is_some_and(|ext| !ext.is_empty()))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const NEXT_ACTIONS: &str =
+        "## Next Actions\n\n| Description | Test |\n|-------------|------|\n| Do thing | `do_thing` |\n";
+
+    #[test]
+    fn graph_links_projects_dependencies_and_file_references() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path().join(".nexus/context");
+        fs::create_dir_all(root.join("docs/site")).expect("create dirs");
+        fs::write(
+            root.join("docs/site/ZDO_001-home.md"),
+            format!(
+                "---\ncontext_id: ZDO_001\ntitle: Home\n---\n\n{}",
+                NEXT_ACTIONS
+            ),
+        )
+        .expect("write first");
+        fs::write(
+            root.join("docs/site/ZDO_002-layout.md"),
+            format!(
+                "---\ncontext_id: ZDO_002\nproject: docs\ndepends_on:\n  contexts:\n    - id: ZDO_001\n      why: needs home\n  projects:\n    - api\n---\n\nSee `_reference/layout.md` and `cargo test`.\n\n{}",
+                NEXT_ACTIONS
+            ),
+        )
+        .expect("write second");
+
+        let graph = build_context_graph(&root).expect("graph");
+        let ids = graph
+            .nodes
+            .iter()
+            .map(|node| node.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "context:ZDO_001",
+                "context:ZDO_002",
+                "file:_reference/layout.md",
+                "project:api",
+                "project:docs",
+            ]
+        );
+        assert_eq!(graph.nodes[0].label, "ZDO_001: Home");
+        assert!(graph
+            .edges
+            .contains(&edge("context:ZDO_002", "context:ZDO_001", "depends_on")));
+        assert!(graph
+            .edges
+            .contains(&edge("context:ZDO_002", "project:api", "depends_on")));
+        assert!(graph
+            .edges
+            .contains(&edge("project:docs", "context:ZDO_001", "contains")));
+        assert!(graph.edges.contains(&edge(
+            "context:ZDO_002",
+            "file:_reference/layout.md",
+            "references"
+        )));
+    }
+
+    #[test]
+    fn fingerprint_changes_when_context_files_change() {
+        let temp = tempdir().expect("tempdir");
+        let before = context_tree_fingerprint(temp.path());
+        fs::write(temp.path().join("CDD_001.md"), "---\n---\n").expect("write");
+        assert_ne!(before, context_tree_fingerprint(temp.path()));
+    }
+}
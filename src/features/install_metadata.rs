@@ -0,0 +1,173 @@
+//! How the running OpenNexus binary was installed.
+//!
+//! `update` records the crate name, the [`UpdateMethod`] that installed the
+//! binary, and where it lives in [`INSTALL_METADATA_FILE`] under the data
+//! directory, and `update` and `uninstall` both act on that record so they
+//! always target the same artifact. Installs that predate the record, or
+//! whose recorded path is not the running binary, are detected from where
+//! the binary lives.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::UpdateMethod;
+use crate::utils::paths::{data_dir, home_dir};
+
+/// Install record, relative to the data directory.
+pub const INSTALL_METADATA_FILE: &str = "install.json";
+
+/// The crate published to crates.io.
+pub const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// The binary target cargo installs and uninstalls.
+pub const BINARY_NAME: &str = "opennexus";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallMetadata {
+    /// Crate cargo installs the binary from.
+    pub package: String,
+    pub method: UpdateMethod,
+    /// The installed executable.
+    pub path: PathBuf,
+    /// Version the last install or update put there.
+    pub version: String,
+}
+
+impl InstallMetadata {
+    /// The record for `exe`: the stored one if it describes `exe`, or one
+    /// detected from whether `exe` lives in cargo's bin directory.
+    pub fn for_executable(exe: &Path) -> Self {
+        let recorded = metadata_path()
+            .and_then(|path| Self::read(&path).ok().flatten())
+            .filter(|metadata| same_file(&metadata.path, exe));
+        recorded.unwrap_or_else(|| Self {
+            package: PACKAGE_NAME.to_string(),
+            method: detect_update_method(exe, cargo_bin_dir().as_deref()),
+            path: exe.to_path_buf(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    /// Read the record at `path`; `None` when none was written.
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read install metadata '{}'.", path.display()))?;
+        let metadata = serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Install metadata '{}' is not valid JSON. Remediation: delete it; the next update rewrites it.",
+                path.display()
+            )
+        })?;
+        Ok(Some(metadata))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create '{}'.", parent.display()))?;
+        }
+        let serialized = serde_json::to_string_pretty(self)?;
+        fs::write(path, format!("{}\n", serialized))
+            .with_context(|| format!("Failed to write install metadata '{}'.", path.display()))
+    }
+
+    /// Store this record where [`InstallMetadata::for_executable`] finds it.
+    pub fn save(&self) -> Result<()> {
+        let path = metadata_path()
+            .context("Cannot locate the data directory. Remediation: set HOME or XDG_DATA_HOME.")?;
+        self.write(&path)
+    }
+
+    /// Forget the stored record, after the binary it describes is removed.
+    pub fn clear() -> Result<()> {
+        match metadata_path() {
+            Some(path) if path.exists() => fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove '{}'.", path.display())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `$XDG_DATA_HOME/opennexus/install.json`.
+pub fn metadata_path() -> Option<PathBuf> {
+    Some(data_dir()?.join(INSTALL_METADATA_FILE))
+}
+
+/// `$CARGO_HOME/bin`, falling back to `~/.cargo/bin`.
+pub fn cargo_bin_dir() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".cargo")))
+        .map(|cargo_home| cargo_home.join("bin"))
+}
+
+/// `Cargo` when `exe` lives in `cargo_bin`, `Binary` otherwise.
+pub fn detect_update_method(exe: &Path, cargo_bin: Option<&Path>) -> UpdateMethod {
+    if cargo_bin.is_some_and(|bin| exe.parent() == Some(bin)) {
+        UpdateMethod::Cargo
+    } else {
+        UpdateMethod::Binary
+    }
+}
+
+/// Arguments to `cargo uninstall` for `metadata`.
+pub fn cargo_uninstall_args(metadata: &InstallMetadata) -> Vec<String> {
+    [
+        "uninstall",
+        "--package",
+        &metadata.package,
+        "--bin",
+        BINARY_NAME,
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn same_file(left: &Path, right: &Path) -> bool {
+    left == right
+        || fs::canonicalize(left)
+            .ok()
+            .zip(fs::canonicalize(right).ok())
+            .is_some_and(|(left, right)| left == right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn round_trips_record_and_detects_method() {
+        let dir = tempdir().expect("tempdir");
+        let cargo_bin = dir.path().join(".cargo").join("bin");
+        assert_eq!(
+            detect_update_method(&cargo_bin.join(BINARY_NAME), Some(&cargo_bin)),
+            UpdateMethod::Cargo
+        );
+        assert_eq!(
+            detect_update_method(&dir.path().join(BINARY_NAME), Some(&cargo_bin)),
+            UpdateMethod::Binary
+        );
+
+        let metadata = InstallMetadata {
+            package: "opennexus".to_string(),
+            method: UpdateMethod::Cargo,
+            path: cargo_bin.join(BINARY_NAME),
+            version: "1.2.3".to_string(),
+        };
+        assert_eq!(
+            cargo_uninstall_args(&metadata).join(" "),
+            "uninstall --package opennexus --bin opennexus"
+        );
+        let path = dir.path().join("data").join(INSTALL_METADATA_FILE);
+        assert_eq!(InstallMetadata::read(&path).expect("missing"), None);
+        metadata.write(&path).expect("write");
+        assert_eq!(InstallMetadata::read(&path).expect("read"), Some(metadata));
+    }
+}
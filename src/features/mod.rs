@@ -1,2 +1,17 @@
+pub mod analyze;
+pub mod apply;
+pub mod assets_lint;
+pub mod burndown;
 pub mod context;
+pub mod graph;
+pub mod install_metadata;
 pub mod ralph;
+pub mod retention;
+pub mod self_update;
+pub mod setup_bundle;
+pub mod setup_gitignore;
+pub mod setup_integrations;
+pub mod setup_manifest;
+pub mod setup_migrations;
+pub mod setup_profile;
+pub mod setup_template;
@@ -9,16 +9,17 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::adapters::agents::{agent_label, build_invocation, resolve_command};
 use crate::config::opencode::ensure_ralph_opencode_config;
+use crate::core::error::{CliError, ErrorCode};
 use crate::core::ralph::model::{
     AgentType, IterationHistory, ParsedRunOptions, RalphHistory, RalphOperation, RalphState,
 };
 use crate::features::ralph::parser::{help_text, VERSION};
-use crate::services::git::{auto_commit, modified_since, snapshot_files};
 use crate::services::process::{run_command, ActiveChild};
 use crate::services::ralph_fs::{
     append_context, clear_context, clear_history, clear_state, load_context, load_history,
     load_state, save_history, save_state, RalphPaths,
 };
+use crate::services::vcs::{modified_since, open_vcs};
 use crate::utils::text::{format_duration_long, format_duration_short};
 
 pub fn execute(operation: RalphOperation) -> Result<()> {
@@ -237,6 +238,8 @@ fn run_loop(paths: &RalphPaths, mut options: ParsedRunOptions) -> Result<()> {
         init_state(&options)
     };
 
+    let vcs = open_vcs()?;
+
     if options.prompt.trim().is_empty() && state.prompt.trim().is_empty() {
         bail!(
             "Error: No prompt provided\nUsage: opennexus ralph \"Your task description\" [options]"
@@ -323,7 +326,7 @@ fn run_loop(paths: &RalphPaths, mut options: ParsedRunOptions) -> Result<()> {
         }
 
         let context_at_start = load_context(paths);
-        let snapshot_before = snapshot_files();
+        let snapshot_before = vcs.snapshot_files();
         let (agent, model) = resolve_agent_model(&state);
         let prompt = build_prompt(paths, &state, &context_at_start)?;
         let opencode_config = if agent == AgentType::Opencode
@@ -386,7 +389,7 @@ fn run_loop(paths: &RalphPaths, mut options: ParsedRunOptions) -> Result<()> {
                     && check_promise(&combined, &state.task_promise)
                     && !completion;
 
-                let snapshot_after = snapshot_files();
+                let snapshot_after = vcs.snapshot_files();
                 let modified = modified_since(&snapshot_before, &snapshot_after);
                 let errors = extract_errors(&combined);
                 let duration = ended.saturating_sub(started);
@@ -437,7 +440,13 @@ fn run_loop(paths: &RalphPaths, mut options: ParsedRunOptions) -> Result<()> {
                     clear_state(paths);
                     clear_history(paths);
                     clear_context(paths);
-                    std::process::exit(1);
+                    bail!(CliError::new(
+                        ErrorCode::WorkflowFailed,
+                        format!(
+                            "Ralph loop aborted after the agent emitted <promise>{}</promise>.",
+                            state.abort_promise
+                        )
+                    ));
                 }
 
                 if completion {
@@ -463,7 +472,10 @@ fn run_loop(paths: &RalphPaths, mut options: ParsedRunOptions) -> Result<()> {
                 }
 
                 if state.auto_commit {
-                    auto_commit(state.iteration);
+                    let message = format!("Ralph iteration {}: work in progress", state.iteration);
+                    if let Err(error) = vcs.commit_all(&message) {
+                        eprintln!("Warning: auto-commit failed: {:#}", error);
+                    }
                 }
             }
             Err(error) => {
@@ -0,0 +1,129 @@
+//! Retention policy for local orchestration history.
+//!
+//! Runs (with their traces, step payloads, and snapshots) older than
+//! `max_age_days` are pruned first; if the remaining payloads still exceed
+//! `max_size_mb`, the oldest runs go next. Running and pinned runs are never
+//! selected. The audit log is left alone because pruning would break its hash
+//! chain.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::adapters::orchestration_store::{OrchestrationStore, PrunableRun};
+use crate::config::nexus::RetentionConfig;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const BYTES_PER_MB: i64 = 1024 * 1024;
+/// Lazy startup pruning runs at most this often.
+const LAZY_PRUNE_INTERVAL_SECONDS: i64 = SECONDS_PER_DAY;
+const LAZY_PRUNE_MARKER: &str = ".nexus/.retention-checked";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PruneSummary {
+    pub runs_pruned: usize,
+    pub bytes_pruned: i64,
+    pub pinned_kept: i64,
+    pub dry_run: bool,
+}
+
+/// Runs to delete under `policy`, given prunable runs ordered oldest first.
+pub fn select_runs_to_prune(runs: &[PrunableRun], policy: &RetentionConfig, now: i64) -> Vec<i64> {
+    let cutoff = policy
+        .max_age_days
+        .map(|days| now - days as i64 * SECONDS_PER_DAY);
+    let (expired, kept): (Vec<_>, Vec<_>) = runs
+        .iter()
+        .partition(|run| cutoff.is_some_and(|cutoff| run.started_at < cutoff));
+    let mut selected = expired.iter().map(|run| run.run_id).collect::<Vec<_>>();
+
+    if let Some(max_mb) = policy.max_size_mb {
+        let budget = max_mb as i64 * BYTES_PER_MB;
+        let mut total = kept.iter().map(|run| run.payload_bytes).sum::<i64>();
+        for run in kept {
+            if total <= budget {
+                break;
+            }
+            total -= run.payload_bytes;
+            selected.push(run.run_id);
+        }
+    }
+    selected
+}
+
+/// Apply `policy` to `store`; `dry_run` only reports what would be pruned.
+pub fn prune_history(
+    store: &OrchestrationStore,
+    policy: &RetentionConfig,
+    now: i64,
+    dry_run: bool,
+) -> Result<PruneSummary> {
+    let runs = store.list_prunable_runs()?;
+    let selected = select_runs_to_prune(&runs, policy, now);
+    let bytes_pruned = runs
+        .iter()
+        .filter(|run| selected.contains(&run.run_id))
+        .map(|run| run.payload_bytes)
+        .sum();
+    let runs_pruned = if dry_run {
+        selected.len()
+    } else {
+        store.delete_runs(&selected)?
+    };
+    Ok(PruneSummary {
+        runs_pruned,
+        bytes_pruned,
+        pinned_kept: store.count_pinned_runs()?,
+        dry_run,
+    })
+}
+
+/// Whether lazy pruning is due, based on the marker written by the last check.
+pub fn lazy_prune_due(now: i64) -> bool {
+    fs::read_to_string(LAZY_PRUNE_MARKER)
+        .ok()
+        .and_then(|content| content.trim().parse::<i64>().ok())
+        .is_none_or(|checked_at| now - checked_at >= LAZY_PRUNE_INTERVAL_SECONDS)
+}
+
+pub fn record_lazy_prune(now: i64) -> Result<()> {
+    if Path::new(LAZY_PRUNE_MARKER)
+        .parent()
+        .is_some_and(|parent| parent.is_dir())
+    {
+        fs::write(LAZY_PRUNE_MARKER, format!("{}\n", now))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(run_id: i64, age_days: i64, megabytes: i64) -> PrunableRun {
+        PrunableRun {
+            run_id,
+            started_at: 100 * SECONDS_PER_DAY - age_days * SECONDS_PER_DAY,
+            payload_bytes: megabytes * BYTES_PER_MB,
+        }
+    }
+
+    #[test]
+    fn prunes_expired_runs_then_oldest_until_under_size() {
+        let now = 100 * SECONDS_PER_DAY;
+        let runs = vec![run(1, 40, 5), run(2, 20, 6), run(3, 10, 4), run(4, 1, 3)];
+        let by_age = RetentionConfig {
+            max_age_days: Some(30),
+            max_size_mb: None,
+        };
+        assert_eq!(select_runs_to_prune(&runs, &by_age, now), vec![1]);
+
+        let by_both = RetentionConfig {
+            max_age_days: Some(30),
+            max_size_mb: Some(8),
+        };
+        assert_eq!(select_runs_to_prune(&runs, &by_both, now), vec![1, 2]);
+        assert!(select_runs_to_prune(&runs, &RetentionConfig::default(), now).is_empty());
+    }
+}
@@ -0,0 +1,445 @@
+//! Self-update from the prebuilt binaries attached to GitHub releases.
+//!
+//! Every release publishes one executable per platform, named
+//! `opennexus-<target triple>` (plus `.exe` on Windows), and GitHub records
+//! the SHA-256 of each asset. `update --method binary` downloads the asset
+//! for this platform, refuses it unless the digest matches, and swaps it in
+//! for the running executable with a rename so a failed update never leaves
+//! a half-written binary behind.
+//!
+//! Releases are picked per [`ReleaseTarget`]: the `stable` channel is the
+//! latest full release, `beta` the newest one including prereleases, and
+//! `nightly` the rolling [`NIGHTLY_TAG`] release; `--version` names a tag.
+//! The cargo method installs the same version from crates.io, or builds
+//! nightly from the repository's default branch.
+//!
+//! After an update, [`fetch_changelog`] collects the release notes of every
+//! release between the old and new version.
+//!
+//! `update --check` and the opt-in startup notice compare this binary's
+//! version with the latest release. The notice reads the last lookup from
+//! [`UPDATE_CHECK_CACHE`] under the data directory and refreshes it in the
+//! background at most once a day.
+
+use anyhow::{bail, Context, Result};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cli::UpdateChannel;
+use crate::core::error::is_retryable;
+use crate::features::install_metadata::BINARY_NAME;
+use crate::features::setup_manifest::sha256_hex;
+use crate::services::retry::{retry_with_backoff_if, RetryBudget};
+use crate::utils::paths::data_dir;
+use crate::utils::text::version_older;
+
+/// GitHub releases API for this repository; `OPENNEXUS_RELEASES_URL`
+/// overrides it for mirrors.
+pub const RELEASES_API_URL: &str =
+    "https://api.github.com/repos/Alpha-Innovation-Labs/opennexus/releases";
+
+/// Git repository the cargo method builds nightly from.
+pub const REPOSITORY_GIT_URL: &str = "https://github.com/Alpha-Innovation-Labs/opennexus";
+
+/// Tag of the rolling release rebuilt from the default branch.
+pub const NIGHTLY_TAG: &str = "nightly";
+
+/// The release an update installs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseTarget<'a> {
+    Channel(UpdateChannel),
+    /// An exact version, with or without a `v` prefix.
+    Version(&'a str),
+}
+
+/// Last release lookup, relative to the data directory.
+pub const UPDATE_CHECK_CACHE: &str = "update-check.json";
+
+/// How long the startup notice trusts a cached lookup.
+pub const UPDATE_CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// A release lookup, as cached for the startup notice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateCheck {
+    /// Unix time of the lookup.
+    pub checked_at: i64,
+    /// Latest published version, without a `v` prefix.
+    pub latest: String,
+}
+
+impl UpdateCheck {
+    pub fn is_stale(&self, now: i64) -> bool {
+        now - self.checked_at >= UPDATE_CHECK_INTERVAL_SECS
+    }
+
+    /// Whether the latest release is newer than `current`.
+    pub fn update_available(&self, current: &str) -> bool {
+        version_older(current, &self.latest)
+    }
+}
+
+/// The cached lookup; `None` when there is none or it cannot be read.
+pub fn read_update_check() -> Option<UpdateCheck> {
+    let content = fs::read_to_string(data_dir()?.join(UPDATE_CHECK_CACHE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn write_update_check(check: &UpdateCheck) -> Result<()> {
+    let dir = data_dir()
+        .context("Cannot locate the data directory. Remediation: set HOME or XDG_DATA_HOME.")?;
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create '{}'.", dir.display()))?;
+    let path = dir.join(UPDATE_CHECK_CACHE);
+    let staged = tempfile::NamedTempFile::new_in(&dir)
+        .with_context(|| format!("Failed to create a file in '{}'.", dir.display()))?;
+    fs::write(staged.path(), serde_json::to_string(check)?)
+        .with_context(|| format!("Failed to write '{}'.", path.display()))?;
+    staged
+        .persist(&path)
+        .map_err(|err| err.error)
+        .with_context(|| format!("Failed to write '{}'.", path.display()))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub draft: bool,
+    /// Release notes, in Markdown.
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    /// The release version without the tag's `v` prefix.
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    /// The asset built for this platform.
+    pub fn platform_asset(&self) -> Result<&ReleaseAsset> {
+        let name = platform_asset_name()?;
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .with_context(|| {
+                format!(
+                    "Release {} has no '{}' asset. Remediation: run `opennexus update --method cargo`.",
+                    self.tag_name, name
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    /// `sha256:<hex>` as recorded by GitHub.
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+impl ReleaseAsset {
+    pub fn sha256(&self) -> Option<&str> {
+        self.digest.as_deref()?.strip_prefix("sha256:")
+    }
+}
+
+/// The release asset name for the platform this binary was built for.
+pub fn platform_asset_name() -> Result<String> {
+    let triple = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+        ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+        // Windows on ARM runs the x64 build under emulation.
+        ("windows", "x86_64" | "aarch64") => "x86_64-pc-windows-msvc",
+        (os, arch) => bail!(
+            "No prebuilt OpenNexus binary for {}/{}. Remediation: run `opennexus update --method cargo`.",
+            os,
+            arch
+        ),
+    };
+    Ok(format!(
+        "opennexus-{}{}",
+        triple,
+        std::env::consts::EXE_SUFFIX
+    ))
+}
+
+fn releases_url() -> String {
+    std::env::var("OPENNEXUS_RELEASES_URL")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| RELEASES_API_URL.to_string())
+}
+
+fn http_client(timeout: Duration) -> Result<Client> {
+    Client::builder()
+        .timeout(timeout)
+        .user_agent(concat!("opennexus/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("Failed to create HTTP client for self-update.")
+}
+
+/// The published release `target` selects.
+pub fn fetch_release(target: ReleaseTarget) -> Result<Release> {
+    let base = releases_url();
+    let base = base.trim_end_matches('/');
+    match target {
+        ReleaseTarget::Channel(UpdateChannel::Stable) => fetch_json(&format!("{}/latest", base)),
+        ReleaseTarget::Channel(UpdateChannel::Beta) => {
+            let releases = fetch_json::<Vec<Release>>(&format!("{}?per_page=30", base))?;
+            newest_beta(releases).context(
+                "No beta or stable release is published. Remediation: use `--channel stable`.",
+            )
+        }
+        ReleaseTarget::Channel(UpdateChannel::Nightly) => {
+            fetch_json(&format!("{}/tags/{}", base, NIGHTLY_TAG))
+        }
+        ReleaseTarget::Version(version) => {
+            let tag = format!("v{}", version.trim_start_matches('v'));
+            fetch_json(&format!("{}/tags/{}", base, tag)).with_context(|| {
+                format!(
+                    "No release {} is published. Remediation: check the version against the project's GitHub releases.",
+                    tag
+                )
+            })
+        }
+    }
+}
+
+/// The newest release in `releases` (newest first, as GitHub lists them)
+/// that is neither a draft nor the nightly build.
+fn newest_beta(releases: Vec<Release>) -> Option<Release> {
+    releases
+        .into_iter()
+        .find(|release| !release.draft && release.tag_name != NIGHTLY_TAG)
+}
+
+/// Release notes for one version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub notes: String,
+}
+
+/// Notes of the releases after `from` up to and including `to`, oldest
+/// first. Updating to nightly shows the nightly release's notes.
+pub fn fetch_changelog(from: &str, to: &str) -> Result<Vec<ChangelogEntry>> {
+    let base = releases_url();
+    let releases =
+        fetch_json::<Vec<Release>>(&format!("{}?per_page=100", base.trim_end_matches('/')))?;
+    Ok(changelog_between(releases, from, to))
+}
+
+fn changelog_between(releases: Vec<Release>, from: &str, to: &str) -> Vec<ChangelogEntry> {
+    let mut entries = releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter(|release| {
+            if to == NIGHTLY_TAG {
+                release.tag_name == NIGHTLY_TAG
+            } else {
+                release.tag_name != NIGHTLY_TAG
+                    && version_older(from, release.version())
+                    && !version_older(to, release.version())
+            }
+        })
+        .map(|release| ChangelogEntry {
+            version: release.version().to_string(),
+            notes: release.body.unwrap_or_default().trim().to_string(),
+        })
+        .collect::<Vec<_>>();
+    entries.reverse();
+    entries
+}
+
+fn fetch_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T> {
+    let client = http_client(Duration::from_secs(30))?;
+    retry_with_backoff_if("Release lookup", RetryBudget::NETWORK, is_retryable, || {
+        client
+            .get(url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json::<T>())
+            .with_context(|| format!("Failed to look up releases at '{}'.", url))
+    })
+}
+
+/// Arguments to `cargo install` of `package` for `target`. Beta versions are
+/// resolved against the releases first and passed as `Version`.
+pub fn cargo_install_args(package: &str, target: ReleaseTarget) -> Vec<String> {
+    let mut args = vec!["install".to_string()];
+    if target == ReleaseTarget::Channel(UpdateChannel::Nightly) {
+        args.extend(["--git".to_string(), REPOSITORY_GIT_URL.to_string()]);
+    }
+    args.extend([package, "--bin", BINARY_NAME, "--force"].map(String::from));
+    if let ReleaseTarget::Version(version) = target {
+        args.extend([
+            "--version".to_string(),
+            version.trim_start_matches('v').to_string(),
+        ]);
+    }
+    args
+}
+
+/// Download `asset` and check it against its published SHA-256.
+pub fn download_asset(asset: &ReleaseAsset) -> Result<Vec<u8>> {
+    let Some(expected) = asset.sha256() else {
+        bail!(
+            "Release asset '{}' has no published SHA-256, so it cannot be verified. Remediation: run `opennexus update --method cargo`.",
+            asset.name
+        );
+    };
+    let client = http_client(Duration::from_secs(300))?;
+    let bytes = retry_with_backoff_if(
+        "Release download",
+        RetryBudget::NETWORK,
+        is_retryable,
+        || {
+            client
+                .get(&asset.browser_download_url)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.bytes())
+                .with_context(|| format!("Failed to download '{}'.", asset.browser_download_url))
+        },
+    )?;
+    let actual = sha256_hex(&bytes);
+    if !actual.eq_ignore_ascii_case(expected) {
+        bail!(
+            "Downloaded '{}' has SHA-256 {} but the release lists {}. Nothing was replaced. Remediation: retry the update; if it persists, report it.",
+            asset.name,
+            actual,
+            expected
+        );
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Replace the executable at `exe` with `contents`. The new binary is
+/// written next to it and renamed over it; on Windows, where a running
+/// executable cannot be overwritten, the old one is first moved aside to
+/// `<exe>.old`.
+pub fn replace_executable(exe: &Path, contents: &[u8]) -> Result<()> {
+    let dir = exe.parent().unwrap_or(Path::new("."));
+    let mut staged = tempfile::Builder::new()
+        .prefix(".opennexus-update")
+        .tempfile_in(dir)
+        .with_context(|| {
+            format!(
+                "Failed to stage the new binary in '{}'. Remediation: rerun with write access to that directory.",
+                dir.display()
+            )
+        })?;
+    staged
+        .write_all(contents)
+        .and_then(|_| staged.as_file().sync_all())
+        .context("Failed to write the new binary.")?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(staged.path(), fs::Permissions::from_mode(0o755))
+            .context("Failed to mark the new binary executable.")?;
+    }
+    #[cfg(windows)]
+    {
+        let aside = exe.with_extension("exe.old");
+        let _ = fs::remove_file(&aside);
+        fs::rename(exe, &aside)
+            .with_context(|| format!("Failed to move '{}' aside.", exe.display()))?;
+    }
+    staged
+        .persist(exe)
+        .map_err(|err| err.error)
+        .with_context(|| format!("Failed to replace '{}'.", exe.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn selects_verified_asset_and_replaces_binary() {
+        let name = platform_asset_name().expect("supported test platform");
+        let release: Release = serde_json::from_value(serde_json::json!({
+            "tag_name": "v1.2.3",
+            "assets": [{
+                "name": name,
+                "browser_download_url": "https://example.invalid/bin",
+                "digest": "sha256:abc123",
+            }],
+        }))
+        .expect("release");
+        assert_eq!(release.version(), "1.2.3");
+        let asset = release.platform_asset().expect("asset");
+        assert_eq!(asset.sha256(), Some("abc123"));
+
+        let check = UpdateCheck {
+            checked_at: 1_000,
+            latest: release.version().to_string(),
+        };
+        assert!(check.update_available("1.2.2"));
+        assert!(!check.update_available("1.2.3"));
+        assert!(!check.update_available("1.10.0"));
+        assert!(!check.is_stale(1_000 + UPDATE_CHECK_INTERVAL_SECS - 1));
+        assert!(check.is_stale(1_000 + UPDATE_CHECK_INTERVAL_SECS));
+
+        let list = |tags: &[&str]| {
+            tags.iter()
+                .map(|tag| Release {
+                    tag_name: tag.to_string(),
+                    draft: *tag == "v2.0.0",
+                    body: Some(format!("notes for {tag}\n")),
+                    assets: Vec::new(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let beta = newest_beta(list(&["v2.0.0", NIGHTLY_TAG, "v1.3.0-beta.1", "v1.2.3"]));
+        assert_eq!(
+            beta.map(|release| release.tag_name).as_deref(),
+            Some("v1.3.0-beta.1")
+        );
+        let versions = |from, to| {
+            changelog_between(list(&["v1.3.0", "v1.2.3", "v1.2.0", "v1.1.0"]), from, to)
+                .into_iter()
+                .map(|entry| entry.version)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(versions("1.1.0", "1.2.3"), vec!["1.2.0", "1.2.3"]);
+        assert!(versions("1.3.0", "1.3.0").is_empty());
+        assert_eq!(
+            changelog_between(list(&[NIGHTLY_TAG, "v1.0.0"]), "1.0.0", NIGHTLY_TAG),
+            vec![ChangelogEntry {
+                version: NIGHTLY_TAG.to_string(),
+                notes: format!("notes for {NIGHTLY_TAG}"),
+            }]
+        );
+        assert_eq!(
+            cargo_install_args("opennexus", ReleaseTarget::Version("v1.4.2")).join(" "),
+            "install opennexus --bin opennexus --force --version 1.4.2"
+        );
+        assert_eq!(
+            cargo_install_args("opennexus", ReleaseTarget::Channel(UpdateChannel::Nightly))
+                .join(" "),
+            format!("install --git {REPOSITORY_GIT_URL} opennexus --bin opennexus --force")
+        );
+
+        let dir = tempdir().expect("tempdir");
+
+        let exe = dir.path().join("opennexus");
+        fs::write(&exe, b"old").expect("old binary");
+        replace_executable(&exe, b"new").expect("replace");
+        assert_eq!(fs::read(&exe).expect("read"), b"new");
+    }
+}
@@ -0,0 +1,474 @@
+//! Asset bundles `setup` installs from.
+//!
+//! Setup extracts the `.nexus` tree compiled into the binary unless
+//! `setup --from` names a team's own bundle: a git repository (`url#ref`
+//! picks a branch or tag), a tarball URL, or a local directory or tarball.
+//! The bundle is a `.nexus/` directory at the source's root, or the source
+//! itself. Remote bundles are cached under the data directory, outside
+//! stateless mode, and reused when the source cannot be fetched or a pinned
+//! digest already matches. The cache only saves fetches; setup works without it.
+//!
+//! A bundle is identified by [`bundle_digest`], a SHA-256 over its file
+//! paths and contents. `--sha256` pins it, and the setup manifest records it
+//! so later runs and `setup verify` read the same bundle.
+
+use anyhow::{bail, Context, Result};
+use include_dir::{Dir, DirEntry, File};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tempfile::TempDir;
+
+use crate::config::stateless::is_stateless;
+use crate::core::error::is_retryable;
+use crate::features::setup_manifest::sha256_hex;
+use crate::output::print_warning;
+use crate::services::retry::{retry_with_backoff, retry_with_backoff_if, RetryBudget};
+use crate::utils::paths::{copy_dir_recursive, data_dir, expand_home};
+use crate::utils::time::{format_unix_rfc3339, unix_now_seconds};
+
+/// `--from` value selecting the bundle compiled into the binary.
+pub const BUILTIN_BUNDLE: &str = "builtin";
+
+const TARBALL_SUFFIXES: [&str; 3] = [".tar.gz", ".tgz", ".tar"];
+
+/// The bundle a setup installed, as recorded in the setup manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleRecord {
+    /// The `--from` value.
+    pub source: String,
+    /// [`bundle_digest`] of the installed bundle.
+    pub sha256: String,
+    /// Whether `sha256` was pinned with `--sha256`; later runs keep the pin.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// An asset bundle loaded for one setup run.
+#[derive(Debug, Clone, Copy)]
+pub struct AssetBundle {
+    pub assets: &'static Dir<'static>,
+    pub digest: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleSource {
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+    Tarball(String),
+    Path(PathBuf),
+}
+
+impl BundleSource {
+    pub fn parse(raw: &str) -> Self {
+        let raw = raw.trim();
+        if let Some(url) = raw.strip_prefix("git+") {
+            return Self::git(url);
+        }
+        let is_remote = ["http://", "https://", "ssh://", "git://", "git@"]
+            .iter()
+            .any(|scheme| raw.starts_with(scheme));
+        if !is_remote {
+            let path = raw.strip_prefix("file://").unwrap_or(raw);
+            return Self::Path(expand_home(path).unwrap_or_else(|| PathBuf::from(path)));
+        }
+        let path = raw.split(['?', '#']).next().unwrap_or(raw);
+        if raw.starts_with("http") && TARBALL_SUFFIXES.iter().any(|s| path.ends_with(s)) {
+            return Self::Tarball(raw.to_string());
+        }
+        Self::git(raw)
+    }
+
+    fn git(raw: &str) -> Self {
+        let (url, reference) = match raw.split_once('#') {
+            Some((url, reference)) if !reference.is_empty() => (url, Some(reference.to_string())),
+            _ => (raw.trim_end_matches('#'), None),
+        };
+        Self::Git {
+            url: url.to_string(),
+            reference,
+        }
+    }
+}
+
+/// Load the bundle at `source`, failing unless its digest is `pin` when one
+/// is given.
+pub fn resolve_bundle(source: &str, pin: Option<&str>) -> Result<AssetBundle> {
+    let source = source.trim();
+    let parsed = BundleSource::parse(source);
+    if let BundleSource::Path(path) = &parsed {
+        if path.is_dir() {
+            return check_pin(source, load_bundle(&bundle_root(path))?, pin);
+        }
+        if !path.is_file() {
+            bail!(
+                "Asset bundle '{}' does not exist. Remediation: pass a git URL, a tarball URL, or a local directory or tarball to --from.",
+                source
+            );
+        }
+    }
+
+    let cache_dir = bundle_cache_dir(source);
+    let cached = cache_dir.as_deref().and_then(read_cached_bundle);
+    if let (Some(pin), Some(cached)) = (pin, cached) {
+        if cached.digest == pin {
+            return Ok(cached);
+        }
+    }
+
+    let fetched = match fetch_bundle(&parsed) {
+        Ok(fetched) => fetched,
+        Err(err) => match cached {
+            Some(cached) if pin.is_none() => {
+                print_warning(&format!(
+                    "Could not fetch asset bundle '{}' ({:#}); using the cached copy.",
+                    source, err
+                ));
+                return Ok(cached);
+            }
+            _ => return Err(err),
+        },
+    };
+    let root = bundle_root(&fetched.path().join("bundle"));
+    let bundle = check_pin(source, load_bundle(&root)?, pin)?;
+    if let Some(cache_dir) = &cache_dir {
+        if let Err(err) = store_cached_bundle(cache_dir, source, &root, bundle.digest) {
+            print_warning(&format!(
+                "Could not cache asset bundle '{}' ({:#}); later runs fetch it again.",
+                source, err
+            ));
+        }
+    }
+    Ok(bundle)
+}
+
+/// SHA-256 over every file path in `dir` with the SHA-256 of its contents.
+pub fn bundle_digest(dir: &Dir) -> String {
+    let mut files = Vec::new();
+    collect_file_hashes(dir, &mut files);
+    files.sort();
+    let mut hasher = Sha256::new();
+    for (path, hash) in files {
+        hasher.update(path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn collect_file_hashes(dir: &Dir, out: &mut Vec<(String, String)>) {
+    for file in dir.files() {
+        out.push((
+            file.path().to_string_lossy().replace('\\', "/"),
+            sha256_hex(file.contents()),
+        ));
+    }
+    for subdir in dir.dirs() {
+        collect_file_hashes(subdir, out);
+    }
+}
+
+fn check_pin(source: &str, bundle: AssetBundle, pin: Option<&str>) -> Result<AssetBundle> {
+    match pin {
+        Some(pin) if !pin.eq_ignore_ascii_case(bundle.digest) => bail!(
+            "Asset bundle '{}' has SHA-256 {}, expected {}. Remediation: check the source, or pass the new digest to --sha256 if the bundle changed on purpose.",
+            source,
+            bundle.digest,
+            pin
+        ),
+        _ => Ok(bundle),
+    }
+}
+
+/// The bundle inside a fetched tree: descend through a lone top-level
+/// directory (as in release tarballs), then into `.nexus/` if there is one.
+fn bundle_root(fetched: &Path) -> PathBuf {
+    let mut root = fetched.to_path_buf();
+    if let Ok(entries) = fs::read_dir(&root) {
+        let entries = entries
+            .flatten()
+            .filter(|entry| entry.file_name() != ".git")
+            .collect::<Vec<_>>();
+        if let [only] = entries.as_slice() {
+            if only.path().is_dir() && only.file_name() != ".nexus" {
+                root = only.path();
+            }
+        }
+    }
+    let nexus = root.join(".nexus");
+    if nexus.is_dir() {
+        nexus
+    } else {
+        root
+    }
+}
+
+/// Read the tree at `root` into a [`Dir`] like the compiled-in bundle. The
+/// tree lives for the rest of the process.
+fn load_bundle(root: &Path) -> Result<AssetBundle> {
+    let assets: &'static Dir<'static> = Box::leak(Box::new(load_dir(root, root)?));
+    if assets.entries().is_empty() {
+        bail!(
+            "Asset bundle at '{}' is empty. Remediation: point --from at a `.nexus` directory or a repository containing one.",
+            root.display()
+        );
+    }
+    let digest = Box::leak(bundle_digest(assets).into_boxed_str());
+    Ok(AssetBundle { assets, digest })
+}
+
+fn load_dir(root: &Path, dir: &Path) -> Result<Dir<'static>> {
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read asset bundle directory '{}'.", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read asset bundle directory '{}'.", dir.display()))?;
+    paths.sort();
+
+    let mut entries = Vec::new();
+    for path in paths {
+        if path.file_name().is_some_and(|name| name == ".git") {
+            continue;
+        }
+        let relative = leak_relative(root, &path);
+        if path.is_dir() {
+            entries.push(DirEntry::Dir(load_dir(root, &path)?));
+        } else if path.is_file() {
+            let contents = fs::read(&path)
+                .with_context(|| format!("Failed to read bundle file '{}'.", path.display()))?;
+            entries.push(DirEntry::File(File::new(
+                relative,
+                Box::leak(contents.into_boxed_slice()),
+            )));
+        }
+    }
+    Ok(Dir::new(
+        leak_relative(root, dir),
+        Box::leak(entries.into_boxed_slice()),
+    ))
+}
+
+fn leak_relative(root: &Path, path: &Path) -> &'static str {
+    let relative = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    Box::leak(relative.into_boxed_str())
+}
+
+/// Fetch `source` into a temporary directory.
+fn fetch_bundle(source: &BundleSource) -> Result<TempDir> {
+    let temp = TempDir::new().context("Failed to create temporary directory for asset bundle.")?;
+    match source {
+        BundleSource::Git { url, reference } => {
+            let checkout = temp.path().join("bundle");
+            retry_with_backoff(&format!("Clone of '{}'", url), RetryBudget::NETWORK, || {
+                if checkout.exists() {
+                    fs::remove_dir_all(&checkout).with_context(|| {
+                        format!("Failed to clear partial clone '{}'.", checkout.display())
+                    })?;
+                }
+                let mut command = Command::new("git");
+                command.args(["clone", "--depth", "1"]);
+                if let Some(reference) = reference {
+                    command.args(["--branch", reference]);
+                }
+                let output = command
+                    .arg(url)
+                    .arg(&checkout)
+                    .output()
+                    .context("Failed to invoke git while fetching asset bundle.")?;
+                if !output.status.success() {
+                    bail!(
+                        "Unable to clone asset bundle '{}': {}",
+                        url,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        BundleSource::Tarball(url) => {
+            let client = Client::builder()
+                .timeout(Duration::from_secs(60))
+                .build()
+                .context("Failed to create HTTP client for asset bundle download.")?;
+            let bytes = retry_with_backoff_if(
+                "Asset bundle download",
+                RetryBudget::NETWORK,
+                is_retryable,
+                || {
+                    client
+                        .get(url)
+                        .send()
+                        .and_then(|response| response.error_for_status())
+                        .and_then(|response| response.bytes())
+                        .with_context(|| format!("Failed to download asset bundle '{}'.", url))
+                },
+            )?;
+            let archive = temp.path().join(archive_name(url));
+            fs::write(&archive, &bytes)
+                .with_context(|| format!("Failed to save '{}'.", archive.display()))?;
+            extract_tarball(&archive, &temp.path().join("bundle"))?;
+        }
+        BundleSource::Path(path) => extract_tarball(path, &temp.path().join("bundle"))?,
+    }
+    Ok(temp)
+}
+
+fn archive_name(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    if path.ends_with(".tar") {
+        "bundle.tar"
+    } else {
+        "bundle.tar.gz"
+    }
+}
+
+fn extract_tarball(archive: &Path, target: &Path) -> Result<()> {
+    fs::create_dir_all(target)
+        .with_context(|| format!("Failed to create directory '{}'.", target.display()))?;
+    let name = archive.to_string_lossy();
+    let flags = if TARBALL_SUFFIXES[..2].iter().any(|s| name.ends_with(s)) {
+        "-xzf"
+    } else {
+        "-xf"
+    };
+    let output = Command::new("tar")
+        .arg(flags)
+        .arg(archive)
+        .arg("-C")
+        .arg(target)
+        .output()
+        .context("Failed to invoke tar while extracting asset bundle.")?;
+    if !output.status.success() {
+        bail!(
+            "Unable to extract asset bundle '{}': {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// `<data dir>/bundles/<hash of source>`; `None` in stateless mode or when
+/// there is no data directory.
+fn bundle_cache_dir(source: &str) -> Option<PathBuf> {
+    if is_stateless() {
+        return None;
+    }
+    Some(
+        data_dir()?
+            .join("bundles")
+            .join(&sha256_hex(source.as_bytes())[..16]),
+    )
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedBundle {
+    source: String,
+    sha256: String,
+    fetched_at: String,
+}
+
+/// The cached bundle, if it is intact.
+fn read_cached_bundle(cache_dir: &Path) -> Option<AssetBundle> {
+    let metadata = fs::read_to_string(cache_dir.join("bundle.json")).ok()?;
+    let metadata = serde_json::from_str::<CachedBundle>(&metadata).ok()?;
+    let bundle = load_bundle(&cache_dir.join("nexus")).ok()?;
+    (bundle.digest == metadata.sha256).then_some(bundle)
+}
+
+fn store_cached_bundle(cache_dir: &Path, source: &str, root: &Path, digest: &str) -> Result<()> {
+    if cache_dir.exists() {
+        fs::remove_dir_all(cache_dir).with_context(|| {
+            format!(
+                "Failed to clear cached asset bundle '{}'.",
+                cache_dir.display()
+            )
+        })?;
+    }
+    let nexus = cache_dir.join("nexus");
+    fs::create_dir_all(&nexus)
+        .with_context(|| format!("Failed to create directory '{}'.", nexus.display()))?;
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        if path.is_dir() {
+            copy_dir_recursive(&path, &nexus.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, nexus.join(entry.file_name()))
+                .with_context(|| format!("Failed to cache '{}'.", path.display()))?;
+        }
+    }
+    let metadata = CachedBundle {
+        source: source.to_string(),
+        sha256: digest.to_string(),
+        fetched_at: format_unix_rfc3339(unix_now_seconds()),
+    };
+    let metadata = serde_json::to_string_pretty(&metadata)
+        .context("Failed to serialize asset bundle metadata")?;
+    fs::write(cache_dir.join("bundle.json"), format!("{metadata}\n")).with_context(|| {
+        format!(
+            "Failed to write asset bundle cache '{}'.",
+            cache_dir.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_sources_and_loads_directory_bundles() {
+        assert_eq!(
+            BundleSource::parse("https://github.com/acme/nexus-assets#v2"),
+            BundleSource::Git {
+                url: "https://github.com/acme/nexus-assets".to_string(),
+                reference: Some("v2".to_string()),
+            }
+        );
+        assert_eq!(
+            BundleSource::parse("https://example.com/assets.tar.gz?token=1"),
+            BundleSource::Tarball("https://example.com/assets.tar.gz?token=1".to_string())
+        );
+        assert!(matches!(
+            BundleSource::parse("git+https://example.com/assets.tar"),
+            BundleSource::Git { .. }
+        ));
+        assert_eq!(
+            BundleSource::parse("./team-assets"),
+            BundleSource::Path(PathBuf::from("./team-assets"))
+        );
+
+        let dir = tempdir().expect("tempdir");
+        let commands = dir.path().join(".nexus/ai_harness/commands");
+        fs::create_dir_all(&commands).expect("commands");
+        fs::write(commands.join("team.md"), "# team").expect("command");
+        fs::create_dir_all(dir.path().join(".git")).expect("git");
+
+        let source = dir.path().to_string_lossy().into_owned();
+        let bundle = resolve_bundle(&source, None).expect("bundle");
+        let file = bundle
+            .assets
+            .get_file("ai_harness/commands/team.md")
+            .expect("file");
+        assert_eq!(file.contents(), b"# team");
+        assert!(resolve_bundle(&source, Some(bundle.digest)).is_ok());
+        let err = resolve_bundle(&source, Some("0000")).expect_err("pin");
+        assert!(err.to_string().contains("expected 0000"));
+    }
+}
@@ -0,0 +1,87 @@
+//! The `.gitignore` block maintained by `setup`.
+//!
+//! Setup keeps the paths it generates (backups, its journal, and the harness
+//! link directories) between two marker lines in the project's `.gitignore`.
+//! Each run rewrites only that block, so reruns are idempotent and anything
+//! outside the markers is left alone.
+
+pub const GITIGNORE_PATH: &str = ".gitignore";
+pub const GITIGNORE_BEGIN: &str = "# >>> opennexus setup (managed, edits are overwritten) >>>";
+pub const GITIGNORE_END: &str = "# <<< opennexus setup <<<";
+
+/// `existing` with the managed block set to `entries`, in place if it is
+/// already there and appended otherwise. No entries removes the block.
+pub fn render_gitignore(existing: &str, entries: &[String]) -> String {
+    let lines = existing.lines().collect::<Vec<_>>();
+    let begin = lines.iter().position(|line| line.trim() == GITIGNORE_BEGIN);
+    let end = begin.and_then(|begin| {
+        lines[begin..]
+            .iter()
+            .position(|line| line.trim() == GITIGNORE_END)
+            .map(|offset| begin + offset)
+    });
+    let block = (!entries.is_empty()).then(|| {
+        std::iter::once(GITIGNORE_BEGIN)
+            .chain(entries.iter().map(String::as_str))
+            .chain(std::iter::once(GITIGNORE_END))
+            .collect::<Vec<_>>()
+    });
+
+    let mut out = match (begin, end) {
+        (Some(begin), Some(end)) => {
+            let mut before = lines[..begin].to_vec();
+            let after = &lines[end + 1..];
+            if block.is_none() && before.last().is_some_and(|line| line.is_empty()) {
+                before.pop();
+            }
+            before
+                .into_iter()
+                .chain(block.into_iter().flatten())
+                .chain(after.iter().copied())
+                .collect::<Vec<_>>()
+        }
+        _ => {
+            let mut out = lines;
+            if let Some(block) = block {
+                if out.last().is_some_and(|line| !line.is_empty()) {
+                    out.push("");
+                }
+                out.extend(block);
+            }
+            out
+        }
+    }
+    .join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maintains_managed_block_idempotently() {
+        let entries = vec![
+            ".nexus/.backup/".to_string(),
+            ".opencode/command/".to_string(),
+        ];
+        let once = render_gitignore("target/\n", &entries);
+        assert_eq!(
+            once,
+            format!("target/\n\n{GITIGNORE_BEGIN}\n.nexus/.backup/\n.opencode/command/\n{GITIGNORE_END}\n")
+        );
+        assert_eq!(render_gitignore(&once, &entries), once);
+
+        let edited = format!("{once}dist/\n");
+        let narrowed = render_gitignore(&edited, &entries[..1]);
+        assert_eq!(
+            narrowed,
+            format!("target/\n\n{GITIGNORE_BEGIN}\n.nexus/.backup/\n{GITIGNORE_END}\ndist/\n")
+        );
+        assert_eq!(render_gitignore(&once, &[]), "target/\n");
+        assert_eq!(render_gitignore("", &[]), "");
+    }
+}
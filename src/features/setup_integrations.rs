@@ -0,0 +1,157 @@
+//! Editor integration targets for the bundled commands.
+//!
+//! Setup links every command in `.nexus/ai_harness/commands/` into the
+//! command directory of each selected target. The selection comes from the
+//! `integrations` list in `.nexus/config.json`, falling back to the target
+//! named like the harness. Adding an editor is one entry in
+//! [`INTEGRATION_TARGETS`].
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+/// Where setup links the bundled commands for one editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegrationTarget {
+    pub name: &'static str,
+    /// Command directory, relative to the project root.
+    pub command_dir: &'static str,
+}
+
+pub const INTEGRATION_TARGETS: [IntegrationTarget; 4] = [
+    IntegrationTarget {
+        name: "opencode",
+        command_dir: ".opencode/command",
+    },
+    IntegrationTarget {
+        name: "claude",
+        command_dir: ".claude/commands",
+    },
+    IntegrationTarget {
+        name: "cursor",
+        command_dir: ".cursor/commands",
+    },
+    IntegrationTarget {
+        name: "windsurf",
+        command_dir: ".windsurf/commands",
+    },
+];
+
+/// Bundled command directory the links point into.
+pub const NEXUS_COMMANDS_DIR: &str = ".nexus/ai_harness/commands";
+
+impl IntegrationTarget {
+    pub fn find(name: &str) -> Option<&'static IntegrationTarget> {
+        INTEGRATION_TARGETS
+            .iter()
+            .find(|target| target.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The editor's top-level directory, such as `.cursor`.
+    pub fn root_dir(&self) -> &'static str {
+        self.command_dir
+            .split_once('/')
+            .map_or(self.command_dir, |(root, _)| root)
+    }
+
+    /// Link target for the bundled command `file_name`, relative to
+    /// `command_dir`.
+    pub fn link_target(&self, file_name: &str) -> String {
+        let depth = self.command_dir.split('/').count();
+        format!(
+            "{}{}/{}",
+            "../".repeat(depth),
+            NEXUS_COMMANDS_DIR,
+            file_name
+        )
+    }
+}
+
+/// Resolve the targets for one setup run: `requested`, else the config's
+/// `integrations`, else the target named like `harness` (none if there is no
+/// such target).
+pub fn resolve_integrations(
+    requested: &[String],
+    config: Option<&Value>,
+    harness: &str,
+) -> Result<Vec<&'static IntegrationTarget>> {
+    let configured = config
+        .and_then(|config| config.get("integrations")?.as_array())
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        });
+    let names = if !requested.is_empty() {
+        requested.to_vec()
+    } else if let Some(configured) = configured {
+        configured
+    } else {
+        return Ok(IntegrationTarget::find(harness).into_iter().collect());
+    };
+
+    let mut targets = Vec::new();
+    for name in &names {
+        let Some(target) = IntegrationTarget::find(name) else {
+            bail!(
+                "Unknown integration '{}'. Remediation: use one of {} in `integrations` in .nexus/config.json.",
+                name,
+                INTEGRATION_TARGETS
+                    .iter()
+                    .map(|target| target.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        };
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_integrations_from_request_config_or_harness() {
+        let names = |targets: Vec<&IntegrationTarget>| {
+            targets
+                .into_iter()
+                .map(|target| target.name)
+                .collect::<Vec<_>>()
+        };
+        let config = serde_json::json!({ "integrations": ["cursor", "opencode", "cursor"] });
+
+        assert_eq!(
+            names(resolve_integrations(&[], None, "opencode").expect("harness")),
+            vec!["opencode"]
+        );
+        assert!(resolve_integrations(&[], None, "other")
+            .expect("unknown harness")
+            .is_empty());
+        assert_eq!(
+            names(resolve_integrations(&[], Some(&config), "opencode").expect("config")),
+            vec!["cursor", "opencode"]
+        );
+        assert_eq!(
+            names(
+                resolve_integrations(&["windsurf".to_string()], Some(&config), "opencode")
+                    .expect("requested")
+            ),
+            vec!["windsurf"]
+        );
+        let err = resolve_integrations(&["vim".to_string()], None, "opencode").expect_err("vim");
+        assert!(err
+            .to_string()
+            .contains("opencode, claude, cursor, windsurf"));
+
+        let cursor = IntegrationTarget::find("cursor").expect("cursor");
+        assert_eq!(cursor.root_dir(), ".cursor");
+        assert_eq!(
+            cursor.link_target("nexus-code.md"),
+            "../../.nexus/ai_harness/commands/nexus-code.md"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Installation manifest written by `setup`.
+//!
+//! `.nexus/.manifest.json` records every file setup extracted with its
+//! SHA-256, every harness link it made, and the CLI version that wrote them.
+//! The next setup prunes exactly what the bundle dropped, and
+//! `uninstall --remove-assets` removes exactly what setup installed. Files
+//! whose hash no longer matches were edited locally and are left in place.
+//! `setup verify` classifies each file against the bundle and the manifest
+//! with [`file_drift`]. Setups from a `--from` bundle record its source
+//! and digest.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::cli::LinkMode;
+use crate::features::setup_bundle::BundleRecord;
+use crate::features::setup_template::TemplateVars;
+
+pub const SETUP_MANIFEST_PATH: &str = ".nexus/.manifest.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupManifest {
+    /// CLI version that ran setup.
+    pub version: String,
+    /// Extracted file path to the SHA-256 of the content setup wrote.
+    pub files: BTreeMap<String, String>,
+    /// Harness link path to its link target.
+    #[serde(default)]
+    pub links: BTreeMap<String, String>,
+    /// How the links were made; absent in manifests from before link modes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_mode: Option<LinkMode>,
+    /// Template variable values the files were rendered with.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub template_vars: TemplateVars,
+    /// The `--from` bundle installed; absent for the compiled-in one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<BundleRecord>,
+    /// Layout migrations applied to this project, or found unnecessary.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<String>,
+}
+
+impl SetupManifest {
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Read the manifest at `path`; `None` when setup has not written one.
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read setup manifest '{}'.", path.display()))?;
+        let manifest = serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Setup manifest '{}' is not valid JSON. Remediation: delete it and rerun `opennexus setup`.",
+                path.display()
+            )
+        })?;
+        Ok(Some(manifest))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let serialized =
+            serde_json::to_string_pretty(self).context("Failed to serialize setup manifest")?;
+        Ok(format!("{serialized}\n"))
+    }
+
+    pub fn record_file(&mut self, path: &Path, contents: &[u8]) {
+        self.files.insert(manifest_key(path), sha256_hex(contents));
+    }
+
+    pub fn record_link(&mut self, path: &Path, target: &str) {
+        self.links.insert(manifest_key(path), target.to_string());
+    }
+
+    /// Files recorded here but not in `current`.
+    pub fn dropped_files<'a>(&'a self, current: &SetupManifest) -> Vec<&'a str> {
+        self.files
+            .keys()
+            .filter(|path| !current.files.contains_key(*path))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The mode the links were made with, assuming the platform default for
+    /// older manifests.
+    pub fn link_mode(&self) -> LinkMode {
+        self.link_mode.unwrap_or_else(LinkMode::platform_default)
+    }
+
+    /// The file or directory the link at `path` mirrors, from its recorded
+    /// relative target.
+    pub fn link_source(path: &str, target: &str) -> PathBuf {
+        Path::new(path)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .components()
+            .chain(Path::new(target).components())
+            .fold(PathBuf::new(), |mut source, component| {
+                match component {
+                    Component::ParentDir if source.file_name().is_some() => {
+                        source.pop();
+                    }
+                    component => source.push(component),
+                }
+                source
+            })
+    }
+
+    /// Links recorded here but not in `current`.
+    pub fn dropped_links<'a>(&'a self, current: &SetupManifest) -> Vec<&'a str> {
+        self.links
+            .keys()
+            .filter(|path| !current.links.contains_key(*path))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether the file at `path` still has the content setup wrote.
+    pub fn is_unmodified(&self, path: &str) -> bool {
+        let Some(expected) = self.files.get(path) else {
+            return false;
+        };
+        fs::read(path).is_ok_and(|contents| &sha256_hex(&contents) == expected)
+    }
+}
+
+/// How an installed asset differs from what setup would produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// Bundled or recorded but not on disk.
+    Missing,
+    /// Edited since setup wrote it.
+    Modified,
+    /// As setup wrote it, but this CLI bundles different content or none.
+    Outdated,
+    /// A recorded harness link that is gone or dangling.
+    BrokenLink,
+    /// A copied or hard-linked harness entry that no longer matches its
+    /// source; `setup repair` re-syncs it.
+    StaleCopy,
+    /// The manifest was written by another CLI version.
+    VersionMismatch,
+    /// No manifest, so edits cannot be told apart from old versions.
+    NoManifest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DriftIssue {
+    pub path: String,
+    pub kind: DriftKind,
+}
+
+/// Classify one file from its SHA-256 in this CLI's bundle, in the manifest,
+/// and on disk (`None` where absent).
+pub fn file_drift(
+    bundled: Option<&str>,
+    recorded: Option<&str>,
+    on_disk: Option<&str>,
+) -> Option<DriftKind> {
+    // A file this CLI no longer bundles is not missed once it is gone.
+    let on_disk = match on_disk {
+        Some(hash) => hash,
+        None => return bundled.map(|_| DriftKind::Missing),
+    };
+    let expected = recorded.or(bundled)?;
+    if on_disk != expected {
+        return Some(DriftKind::Modified);
+    }
+    match (bundled, recorded) {
+        (None, Some(_)) => Some(DriftKind::Outdated),
+        (Some(bundled), Some(recorded)) if bundled != recorded => Some(DriftKind::Outdated),
+        _ => None,
+    }
+}
+
+pub fn sha256_hex(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+/// Manifest keys use `/` on every platform.
+fn manifest_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn diffs_manifests_and_detects_local_edits() {
+        let dir = tempdir().expect("tempdir");
+        let kept = dir.path().join("kept.md");
+        let dropped = dir.path().join("dropped.md");
+        fs::write(&kept, "kept").expect("kept");
+        fs::write(&dropped, "dropped").expect("dropped");
+
+        let mut previous = SetupManifest::new("0.1.0");
+        previous.record_file(&kept, b"kept");
+        previous.record_file(&dropped, b"dropped");
+        previous.record_link(Path::new(".opencode/command/old.md"), "../../old.md");
+        let mut current = SetupManifest::new("0.2.0");
+        current.record_file(&kept, b"kept");
+
+        assert_eq!(
+            SetupManifest::link_source(
+                ".opencode/command/nexus-code.md",
+                "../../.nexus/ai_harness/commands/nexus-code.md"
+            ),
+            Path::new(".nexus/ai_harness/commands/nexus-code.md")
+        );
+
+        let dropped_key = manifest_key(&dropped);
+        assert_eq!(previous.dropped_files(&current), vec![dropped_key.as_str()]);
+        assert_eq!(
+            previous.dropped_links(&current),
+            vec![".opencode/command/old.md"]
+        );
+        assert!(previous.is_unmodified(&dropped_key));
+        fs::write(&dropped, "edited").expect("edit");
+        assert!(!previous.is_unmodified(&dropped_key));
+
+        let path = dir.path().join("manifest.json");
+        assert_eq!(SetupManifest::read(&path).expect("missing"), None);
+        fs::write(&path, previous.to_json().expect("json")).expect("write");
+        assert_eq!(SetupManifest::read(&path).expect("read"), Some(previous));
+    }
+
+    #[test]
+    fn classifies_file_drift() {
+        assert_eq!(file_drift(Some("a"), Some("a"), Some("a")), None);
+        assert_eq!(
+            file_drift(Some("a"), Some("a"), None),
+            Some(DriftKind::Missing)
+        );
+        assert_eq!(
+            file_drift(Some("a"), Some("a"), Some("b")),
+            Some(DriftKind::Modified)
+        );
+        assert_eq!(
+            file_drift(Some("b"), Some("a"), Some("a")),
+            Some(DriftKind::Outdated)
+        );
+        assert_eq!(
+            file_drift(None, Some("a"), Some("a")),
+            Some(DriftKind::Outdated)
+        );
+        assert_eq!(file_drift(None, Some("a"), None), None);
+        assert_eq!(
+            file_drift(Some("a"), None, Some("b")),
+            Some(DriftKind::Modified)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Migrations `setup` applies to projects laid out by older CLI versions.
+//!
+//! The layout version is the CLI version recorded by the last setup, in the
+//! setup manifest or else as `version` in `.nexus/config.json`. Every
+//! [`Migration`] newer than it that the manifest does not list as applied
+//! runs in order before the bundle is extracted. A migration only plans
+//! [`MigrationAction`]s from what is on disk, so running it on a tree that
+//! is already migrated plans nothing; setup records every migration id in
+//! the manifest once a run completes.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::features::setup_integrations::INTEGRATION_TARGETS;
+use crate::utils::text::version_older;
+
+/// Context projects lived here before they moved under `.nexus/`.
+pub const LEGACY_CONTEXT_DIR: &str = ".context";
+const CONTEXT_DIR: &str = ".nexus/context";
+
+/// Frontmatter keys renamed since, as (old, new).
+const LEGACY_FRONTMATTER_KEYS: [(&str, &str); 1] = [("id", "context_id")];
+
+/// Harness directories outside the integration targets that hold links.
+const OPENCODE_LINK_DIRS: [&str; 3] = [".opencode/skills", ".opencode/rules", ".opencode/tools"];
+
+/// One change a migration makes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationAction {
+    /// Move a file or directory; skipped when `to` already exists.
+    Move {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    /// Remove a directory the moves left empty.
+    RemoveEmptyDir(PathBuf),
+    Write {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    /// Point the symlink at `path` to `target`, relative to its directory.
+    Relink {
+        path: PathBuf,
+        target: String,
+    },
+}
+
+pub struct Migration {
+    pub id: &'static str,
+    /// Layouts written by CLI versions older than this need the migration.
+    pub since: &'static str,
+    pub description: &'static str,
+    pub plan: fn() -> Result<Vec<MigrationAction>>,
+}
+
+/// Every migration, in the order they apply.
+pub const MIGRATIONS: [Migration; 3] = [
+    Migration {
+        id: "context-frontmatter-keys",
+        since: "0.1.8",
+        description: "rename legacy frontmatter keys in context files",
+        plan: plan_frontmatter_keys,
+    },
+    Migration {
+        id: "context-into-nexus",
+        since: "0.1.8",
+        description: "move .context/ projects into .nexus/context/",
+        plan: plan_context_move,
+    },
+    Migration {
+        id: "relink-context-links",
+        since: "0.1.8",
+        description: "point harness links into .context/ at .nexus/context/",
+        plan: plan_context_relinks,
+    },
+];
+
+/// Migrations not in `applied` that a layout from `layout_version` needs; an
+/// unknown version predates them all.
+pub fn pending_migrations(
+    applied: &[String],
+    layout_version: Option<&str>,
+) -> Vec<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .filter(|migration| !applied.iter().any(|id| id == migration.id))
+        .filter(|migration| {
+            layout_version.is_none_or(|version| version_older(version, migration.since))
+        })
+        .collect()
+}
+
+fn plan_frontmatter_keys() -> Result<Vec<MigrationAction>> {
+    let mut actions = Vec::new();
+    for root in [LEGACY_CONTEXT_DIR, CONTEXT_DIR] {
+        let mut files = Vec::new();
+        collect_markdown(Path::new(root), &mut files)?;
+        for path in files {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read '{}'.", path.display()))?;
+            if let Some(rewritten) = rewrite_frontmatter_keys(&content) {
+                actions.push(MigrationAction::Write {
+                    path,
+                    contents: rewritten.into_bytes(),
+                });
+            }
+        }
+    }
+    Ok(actions)
+}
+
+fn collect_markdown(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read '{}'.", dir.display()))?;
+    paths.sort();
+    for path in paths {
+        if fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_dir()) {
+            collect_markdown(&path, out)?;
+        } else if path.extension().is_some_and(|extension| extension == "md") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// `content` with legacy top-level frontmatter keys renamed, or `None` if
+/// there is nothing to rename. A key is left alone when its replacement is
+/// already set.
+fn rewrite_frontmatter_keys(content: &str) -> Option<String> {
+    let body = content.strip_prefix("---\n")?;
+    let end = body.find("\n---")?;
+    let frontmatter = &body[..end];
+    let keys = frontmatter
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t']))
+        .filter_map(|line| Some(line.split_once(':')?.0.trim()))
+        .collect::<Vec<_>>();
+    let mut changed = false;
+    let lines = frontmatter
+        .lines()
+        .map(|line| {
+            for (old, new) in LEGACY_FRONTMATTER_KEYS {
+                if let Some(rest) = line
+                    .strip_prefix(old)
+                    .and_then(|rest| rest.strip_prefix(':'))
+                {
+                    if !keys.contains(&new) {
+                        changed = true;
+                        return format!("{new}:{rest}");
+                    }
+                }
+            }
+            line.to_string()
+        })
+        .collect::<Vec<_>>();
+    changed.then(|| format!("---\n{}{}", lines.join("\n"), &body[end..]))
+}
+
+fn plan_context_move() -> Result<Vec<MigrationAction>> {
+    let legacy = Path::new(LEGACY_CONTEXT_DIR);
+    let Ok(entries) = fs::read_dir(legacy) else {
+        return Ok(Vec::new());
+    };
+    let mut names = entries
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read '{}'.", legacy.display()))?;
+    names.sort();
+    let mut actions = names
+        .into_iter()
+        .map(|name| MigrationAction::Move {
+            from: legacy.join(&name),
+            to: Path::new(CONTEXT_DIR).join(&name),
+        })
+        .collect::<Vec<_>>();
+    actions.push(MigrationAction::RemoveEmptyDir(legacy.to_path_buf()));
+    Ok(actions)
+}
+
+fn plan_context_relinks() -> Result<Vec<MigrationAction>> {
+    let mut actions = Vec::new();
+    let dirs = INTEGRATION_TARGETS
+        .iter()
+        .map(|target| target.command_dir)
+        .chain(OPENCODE_LINK_DIRS);
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let Ok(target) = fs::read_link(&path) else {
+                continue;
+            };
+            if let Some(target) = relinked_target(&target) {
+                actions.push(MigrationAction::Relink { path, target });
+            }
+        }
+    }
+    Ok(actions)
+}
+
+/// `target` with its `.context` component replaced by `.nexus/context`.
+fn relinked_target(target: &Path) -> Option<String> {
+    let mut found = false;
+    let mut parts = Vec::new();
+    for component in target.components() {
+        match component {
+            Component::Normal(name) if name == LEGACY_CONTEXT_DIR && !found => {
+                found = true;
+                parts.push(CONTEXT_DIR.to_string());
+            }
+            component => parts.push(component.as_os_str().to_string_lossy().into_owned()),
+        }
+    }
+    found.then(|| parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_pending_migrations_and_rewrites_legacy_layouts() {
+        let all = MIGRATIONS.iter().map(|m| m.id).collect::<Vec<_>>();
+        let ids = |migrations: Vec<&Migration>| migrations.iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids(pending_migrations(&[], None)), all);
+        assert_eq!(ids(pending_migrations(&[], Some("0.1.7"))), all);
+        assert!(pending_migrations(&[], Some("0.1.8")).is_empty());
+        assert!(pending_migrations(&[], Some("v0.2.0-beta")).is_empty());
+        let applied = vec!["context-into-nexus".to_string()];
+        assert_eq!(
+            ids(pending_migrations(&applied, Some("0.1.0"))),
+            vec!["context-frontmatter-keys", "relink-context-links"]
+        );
+
+        assert_eq!(
+            rewrite_frontmatter_keys(
+                "---\nid: APP_001\ndepends_on:\n  - id: APP_000\n---\n# APP_001\n"
+            )
+            .as_deref(),
+            Some("---\ncontext_id: APP_001\ndepends_on:\n  - id: APP_000\n---\n# APP_001\n")
+        );
+        assert_eq!(
+            rewrite_frontmatter_keys("---\ncontext_id: APP_001\nid: old\n---\n"),
+            None
+        );
+        assert_eq!(rewrite_frontmatter_keys("# no frontmatter\n"), None);
+
+        assert_eq!(
+            relinked_target(Path::new("../../.context/app/skills/review")).as_deref(),
+            Some("../../.nexus/context/app/skills/review")
+        );
+        assert_eq!(
+            relinked_target(Path::new("../../.nexus/ai_harness/commands/x.md")),
+            None
+        );
+    }
+}
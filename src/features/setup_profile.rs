@@ -0,0 +1,158 @@
+//! Setup profiles selecting which bundled assets `setup` extracts.
+//!
+//! A profile is a list of patterns over paths relative to `.nexus/`: `*`
+//! matches within one path segment and a trailing `/**` matches everything
+//! under a directory. `full` extracts the whole bundle and `minimal` only the
+//! context workflow commands and pipelines; projects add their own
+//! under `setup_profiles` in `.nexus/config.json`. Files a profile leaves out
+//! are not extracted, and the setup manifest diff prunes them from earlier
+//! runs.
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub const DEFAULT_SETUP_PROFILE: &str = "full";
+pub const BUILTIN_SETUP_PROFILES: [&str; 2] = ["full", "minimal"];
+
+const MINIMAL_PATTERNS: [&str; 4] = [
+    ".gitkeep",
+    "orchestration/**",
+    "ai_harness/commands/nexus-code.md",
+    "ai_harness/commands/nexus-context-*.md",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupProfile {
+    pub name: String,
+    /// `None` selects every bundled file.
+    patterns: Option<Vec<String>>,
+}
+
+impl SetupProfile {
+    /// Resolve `requested`, else the `setup_profile` recorded in `config`,
+    /// else `full`. Profiles in the config's `setup_profiles` are looked up
+    /// before the built-in ones.
+    pub fn resolve(requested: Option<&str>, config: Option<&Value>) -> Result<Self> {
+        let name = requested
+            .or_else(|| config?.get("setup_profile")?.as_str())
+            .unwrap_or(DEFAULT_SETUP_PROFILE);
+        let custom = config
+            .and_then(|config| config.get("setup_profiles")?.as_object())
+            .map(|profiles| {
+                profiles
+                    .iter()
+                    .map(|(name, patterns)| {
+                        let patterns = patterns
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                            .collect::<Vec<_>>();
+                        (name.clone(), patterns)
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        let patterns = match (custom.get(name), name) {
+            (Some(patterns), _) => Some(patterns.clone()),
+            (None, "full") => None,
+            (None, "minimal") => Some(MINIMAL_PATTERNS.iter().map(|p| p.to_string()).collect()),
+            (None, _) => {
+                let mut available = BUILTIN_SETUP_PROFILES
+                    .iter()
+                    .map(|name| name.to_string())
+                    .chain(custom.keys().cloned())
+                    .collect::<Vec<_>>();
+                available.sort();
+                available.dedup();
+                bail!(
+                    "Unknown setup profile '{}'. Remediation: use one of {}, or define it under `setup_profiles` in .nexus/config.json.",
+                    name,
+                    available.join(", ")
+                );
+            }
+        };
+        Ok(Self {
+            name: name.to_string(),
+            patterns,
+        })
+    }
+
+    /// Whether the bundled file at `relative` (to `.nexus/`) is extracted.
+    pub fn includes(&self, relative: &str) -> bool {
+        let relative = relative.replace('\\', "/");
+        match &self.patterns {
+            None => true,
+            Some(patterns) => patterns
+                .iter()
+                .any(|pattern| pattern_matches(pattern, &relative)),
+        }
+    }
+}
+
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    if let Some(dir) = pattern.strip_suffix("/**") {
+        return path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('/'));
+    }
+    let pattern_segments = pattern.split('/').collect::<Vec<_>>();
+    let path_segments = path.split('/').collect::<Vec<_>>();
+    pattern_segments.len() == path_segments.len()
+        && pattern_segments
+            .iter()
+            .zip(&path_segments)
+            .all(|(pattern, segment)| segment_matches(pattern, segment))
+}
+
+/// Match one path segment against a pattern where `*` is any run of characters.
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = segment.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_builtin_and_config_profiles() {
+        let full = SetupProfile::resolve(None, None).expect("default");
+        assert_eq!(full.name, "full");
+        assert!(full.includes("ai_harness/commands/browser-test.md"));
+
+        let minimal = SetupProfile::resolve(Some("minimal"), None).expect("minimal");
+        assert!(minimal.includes("ai_harness/commands/nexus-context-create.md"));
+        assert!(minimal.includes("orchestration/pipelines.json"));
+        assert!(!minimal.includes("ai_harness/commands/browser-test.md"));
+        assert!(!minimal.includes("ai_harness/commands/nested/nexus-context-create.md"));
+
+        let config = serde_json::json!({
+            "setup_profile": "custom",
+            "setup_profiles": { "custom": ["config.json", "ai_harness/commands/*-review.md"] },
+        });
+        let custom = SetupProfile::resolve(None, Some(&config)).expect("custom");
+        assert_eq!(custom.name, "custom");
+        assert!(custom.includes("ai_harness/commands/nexus-context-review.md"));
+        assert!(!custom.includes("ai_harness/commands/nexus-code.md"));
+
+        let err = SetupProfile::resolve(Some("tiny"), Some(&config)).expect_err("unknown");
+        assert!(err.to_string().contains("custom, full, minimal"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Template variables substituted into bundled assets by `setup`.
+//!
+//! A bundled text file may contain `{{name}}` placeholders. Setup replaces
+//! the ones it has a value for and leaves any other `{{...}}` untouched, so
+//! assets can still show template syntax meant for other tools. The
+//! built-in variables default from the project directory and the clock;
+//! `--var name=value` and the wizard override them, and the values used are
+//! kept in the setup manifest so later runs and `setup verify` render the
+//! same bytes.
+
+use anyhow::{bail, Result};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::utils::time::format_unix_date;
+
+pub type TemplateVars = BTreeMap<String, String>;
+
+/// Defaults for the built-in variables: the name of `project_dir`, a prefix
+/// of its first three letters or digits in upper case, and the year of unix
+/// `now`.
+pub fn default_template_vars(project_dir: &Path, now: i64) -> TemplateVars {
+    let project_name = project_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "project".to_string());
+    let prefix = project_name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(3)
+        .collect::<String>()
+        .to_ascii_uppercase();
+    let year = format_unix_date(now)[..4].to_string();
+    TemplateVars::from([
+        ("project_name".to_string(), project_name),
+        ("prefix".to_string(), prefix),
+        ("year".to_string(), year),
+    ])
+}
+
+/// Parse one `--var name=value` argument.
+pub fn parse_template_var(raw: &str) -> Result<(String, String)> {
+    let Some((name, value)) = raw.split_once('=') else {
+        bail!("Expected NAME=VALUE, got '{}'.", raw);
+    };
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        bail!(
+            "Template variable names use lowercase letters, digits, and `_`; got '{}'.",
+            name
+        );
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Substitute `vars` into `contents`. Non-UTF-8 files and files without
+/// placeholders are returned as they are.
+pub fn render<'a>(contents: &'a [u8], vars: &TemplateVars) -> Cow<'a, [u8]> {
+    let Ok(text) = std::str::from_utf8(contents) else {
+        return Cow::Borrowed(contents);
+    };
+    if !text.contains("{{") {
+        return Cow::Borrowed(contents);
+    }
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after
+            .find("}}")
+            .and_then(|end| Some((vars.get(&after[..end])?, end)))
+        {
+            Some((value, end)) => {
+                rendered.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                rendered.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    Cow::Owned(rendered.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_known_placeholders_only() {
+        let mut vars = default_template_vars(Path::new("/work/open-nexus"), 1_700_000_000);
+        assert_eq!(vars["project_name"], "open-nexus");
+        assert_eq!(vars["prefix"], "OPE");
+        assert_eq!(vars["year"], "2023");
+
+        let (name, value) = parse_template_var("prefix=NEX").expect("var");
+        vars.insert(name, value);
+        assert!(parse_template_var("Prefix=NEX").is_err());
+        assert!(parse_template_var("prefix").is_err());
+
+        let rendered = render(
+            b"# {{project_name}} ({{prefix}}_001), {{year}} {{unknown}} {{",
+            &vars,
+        );
+        assert_eq!(
+            rendered.as_ref(),
+            b"# open-nexus (NEX_001), 2023 {{unknown}} {{".as_slice()
+        );
+        assert!(matches!(render(b"plain", &vars), Cow::Borrowed(_)));
+        assert!(matches!(
+            render(&[0xff, b'{', b'{'], &vars),
+            Cow::Borrowed(_)
+        ));
+    }
+}
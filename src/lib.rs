@@ -12,9 +12,18 @@ pub mod services;
 pub mod utils;
 
 pub use cli::{
-    Cli, Commands, MarketplaceCommands, OrchestrationCommand, OutputFormat, RalphCommand,
+    AssetsCommands, AuditCommands, BurndownChart, Cli, Commands, ConfigCommands, ConfigScope,
+    ContextCommands, GraphCommands, LinkMode, MarketplaceCommands, OrchestrationCommand,
+    OutputFormat, PluginCommands, PresetCommands, RalphCommand, RefsCommands, SetupCommands,
 };
 pub use commands::{
+    run_analyze, run_apply, run_assets_lint, run_audit_export, run_audit_verify, run_badge,
+    run_bug_report, run_burndown, run_clean, run_complete, run_config_doctor, run_config_get,
+    run_config_list, run_config_set, run_config_unset, run_context_backfill, run_context_implement,
+    run_context_index, run_context_test_status, run_doctor, run_graph_serve, run_learn,
     run_marketplace_install, run_marketplace_list, run_marketplace_search,
-    run_orchestration_pipeline, run_ralph, run_setup, run_uninstall, run_update,
+    run_orchestration_pipeline, run_plugin_list, run_preset_delete, run_preset_list,
+    run_preset_save, run_ralph, run_refs_remap, run_scaffold, run_setup, run_setup_repair,
+    run_setup_rollback, run_setup_verify, run_setup_wizard, run_shell, run_status, run_uninstall,
+    run_update, run_update_check, OrchestrationRunOptions,
 };
@@ -1,7 +1,10 @@
 //! Nexus CLI entry point.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::CommandFactory;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::ExitCode;
 
 mod adapters;
 mod app;
@@ -14,60 +17,313 @@ mod output;
 mod services;
 mod utils;
 
-use cli::{Cli, Commands, MarketplaceCommands};
+use crate::core::error::exit_code;
+use app::middleware::{CommandContext, MiddlewarePipeline};
+use cli::{
+    AssetsCommands, AuditCommands, Cli, Commands, ConfigCommands, ContextCommands, GraphCommands,
+    MarketplaceCommands, OutputFormat, PluginCommands, PresetCommands, RefsCommands, Role,
+    SetupCommands, DEFAULT_GRAPH_HOST, DEFAULT_GRAPH_PORT,
+};
 use commands::{
-    resolve_setup_harness, run_marketplace_install, run_marketplace_list, run_marketplace_search,
-    run_orchestration_pipeline, run_ralph, run_setup, run_uninstall, run_update,
+    apply_orchestration_preset, load_or_onboard_user_config, notify_available_update,
+    resolve_setup_harness, run_analyze, run_apply, run_assets_lint, run_audit_export,
+    run_audit_verify, run_badge, run_bug_report, run_burndown, run_clean, run_complete,
+    run_config_doctor, run_config_get, run_config_list, run_config_set, run_config_unset,
+    run_context_backfill, run_context_implement, run_context_index, run_context_test_status,
+    run_doctor, run_external_plugin, run_graph_serve, run_learn, run_marketplace_install,
+    run_marketplace_list, run_marketplace_search, run_orchestration_pipeline, run_plugin_list,
+    run_preset_delete, run_preset_list, run_preset_save, run_ralph, run_refs_remap, run_scaffold,
+    run_setup, run_setup_repair, run_setup_rollback, run_setup_verify, run_setup_wizard, run_shell,
+    run_status, run_uninstall, run_update, run_update_check, setup_is_interactive,
+    OrchestrationRunOptions, SetupSelection,
+};
+use config::layered::resolve_defaults;
+use config::nexus::{DefaultsConfig, OrchestrationPreset};
+use config::stateless::{enable_stateless_mode, is_stateless};
+use output::{
+    report_error, set_color_choice, set_document_format, set_iso_timestamps, set_json_query,
+    set_quiet, structured_output,
 };
+use services::logging::init_logging;
+use services::progress::open_progress_fd;
+use services::retry::set_max_attempts;
+use utils::query::JsonQuery;
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     // Parse CLI arguments
     let cli = Cli::parse_args();
-    let format = cli.format;
+    let structured = cli.format.is_some_and(OutputFormat::is_structured);
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            report_error(&error, structured || structured_output());
+            ExitCode::from(exit_code(&error))
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    if cli.stateless {
+        enable_stateless_mode();
+    }
+    init_logging(cli.verbose, cli.log_file.as_deref().map(Path::new))?;
+    set_quiet(cli.quiet);
+    if cli.no_retry {
+        set_max_attempts(1);
+    } else if let Some(attempts) = cli.retry_attempts {
+        set_max_attempts(attempts);
+    }
+    let user_config =
+        load_or_onboard_user_config(cli.format.is_some_and(OutputFormat::is_structured))?;
+    let defaults = resolve_defaults(&user_config)?;
+    let format = cli.output_format(defaults.format);
+    set_document_format(format);
+    set_color_choice(cli.color.or(defaults.color).unwrap_or_default());
+    set_iso_timestamps(cli.iso);
+    if let Some(expression) = cli.query.as_deref() {
+        if !format.is_structured() {
+            bail!(
+                "--query only applies to json, yaml, or ndjson output. Remediation: add --format json."
+            );
+        }
+        set_json_query(JsonQuery::parse(expression)?);
+    }
+    if let Some(fd) = cli.progress_fd {
+        open_progress_fd(fd)?;
+    }
+    if user_config.update_check == Some(true)
+        && !is_stateless()
+        && !format.is_structured()
+        && std::io::stderr().is_terminal()
+        && !matches!(cli.command, Some(Commands::Update { .. }))
+    {
+        notify_available_update();
+    }
+    let role = cli.role(user_config.role);
+    dispatch(cli.command, format, &defaults, role)
+}
 
-    // Route to appropriate command handler
-    let result = match cli.command {
+/// Run one command inside the middleware pipeline; `shell` calls back in here
+/// for every line.
+fn dispatch(
+    command: Option<Commands>,
+    format: OutputFormat,
+    defaults: &DefaultsConfig,
+    role: Role,
+) -> Result<()> {
+    let context = CommandContext::new(command.as_ref());
+
+    // Route to appropriate command handler inside the middleware pipeline
+    MiddlewarePipeline::standard(role).run(&context, || match command {
         None => {
             let mut cmd = Cli::command();
             cmd.print_long_help()?;
             println!();
             Ok(())
         }
-        Some(Commands::Setup { harness }) => {
+        Some(Commands::Setup {
+            command: Some(SetupCommands::Rollback),
+            ..
+        }) => run_setup_rollback(format),
+        Some(Commands::Setup {
+            command: Some(SetupCommands::Verify),
+            ..
+        }) => run_setup_verify(format),
+        Some(Commands::Setup {
+            command: Some(SetupCommands::Repair),
+            ..
+        }) => run_setup_repair(format),
+        Some(Commands::Setup {
+            command: None,
+            harness: None,
+            profile: None,
+            ref vars,
+            link_mode: None,
+            from: None,
+            sha256: None,
+            no_gitignore: false,
+            no_hooks: false,
+            dry_run: false,
+        }) if vars.is_empty() && setup_is_interactive(format) => run_setup_wizard(format),
+        Some(Commands::Setup {
+            command: None,
+            harness,
+            profile,
+            vars,
+            link_mode,
+            from,
+            sha256,
+            no_gitignore,
+            no_hooks,
+            dry_run,
+        }) => {
             let harness = resolve_setup_harness(format, harness)?;
-            run_setup(format, &harness)
+            let selection = SetupSelection {
+                link_mode,
+                template_vars: vars.into_iter().collect(),
+                from,
+                sha256,
+                no_gitignore,
+                no_hooks,
+                ..SetupSelection::new(harness, profile)
+            };
+            run_setup(format, &selection, dry_run)
         }
-        Some(Commands::Update) => run_update(format),
-        Some(Commands::Uninstall) => run_uninstall(format),
+        Some(Commands::Update {
+            check: true,
+            channel,
+            ..
+        }) => run_update_check(format, channel),
+        Some(Commands::Update {
+            method,
+            version,
+            channel,
+            sync_assets,
+            ..
+        }) => run_update(format, method, version.as_deref(), channel, sync_assets),
+        Some(Commands::Uninstall {
+            remove_assets,
+            purge,
+            project,
+            dry_run,
+        }) => run_uninstall(format, remove_assets, purge, project, dry_run),
         Some(Commands::Marketplace { command }) => match command {
             MarketplaceCommands::List => run_marketplace_list(format),
             MarketplaceCommands::Search { query } => run_marketplace_search(&query, format),
-            MarketplaceCommands::Install { target, package } => {
-                run_marketplace_install(&target, package.as_deref(), format)
-            }
+            MarketplaceCommands::Install {
+                target,
+                package,
+                map_prefix,
+                map_project,
+            } => run_marketplace_install(
+                &target,
+                package.as_deref(),
+                &map_prefix,
+                &map_project,
+                format,
+            ),
         },
         Some(Commands::Ralph(command)) => run_ralph(&command.args),
-        Some(Commands::Orchestration(command)) => run_orchestration_pipeline(
-            format,
-            &command.pipeline_name,
-            command.target_pipeline_name.as_deref(),
-            command.pipeline_file.as_deref(),
-            command.context_file.as_deref(),
-            command.max_iterations,
-            command.timeout_seconds,
-            command.rule_file.as_deref(),
-            command.test_command.as_deref(),
-            command.test_discovery_command.as_deref(),
-            command.model.as_deref(),
-            command.checkpoint_file.as_deref(),
-            command.resume_checkpoint.as_deref(),
-            command.allow_dependency_bypass,
-            command.overwrite,
-            command.run_id,
-            command.context_id.as_deref(),
-            command.pipeline_filter.as_deref(),
-        ),
-    };
-
-    result
+        Some(Commands::Orchestration(command)) => {
+            let mut command = apply_orchestration_preset(command)?;
+            command.max_iterations = command.max_iterations.or(defaults.max_iterations);
+            command.timeout_seconds = command.timeout_seconds.or(defaults.timeout_seconds);
+            run_orchestration_pipeline(&OrchestrationRunOptions::from(&command), format)
+        }
+        Some(Commands::Analyze {
+            path,
+            project,
+            apply,
+        }) => run_analyze(&path, project.as_deref(), apply, format),
+        Some(Commands::Apply {
+            manifest,
+            prune,
+            yes,
+            dry_run,
+        }) => run_apply(&manifest, prune, yes, dry_run, format),
+        Some(Commands::Learn { dir, reset }) => run_learn(&dir, reset, format),
+        Some(Commands::Badge { project, out }) => run_badge(project.as_deref(), &out, format),
+        Some(Commands::Status) => run_status(format),
+        Some(Commands::Doctor) => run_doctor(format),
+        Some(Commands::BugReport { out }) => run_bug_report(out.as_deref(), format),
+        Some(Commands::Scaffold {
+            context_id,
+            no_commit,
+        }) => run_scaffold(&context_id, no_commit, format),
+        Some(Commands::Burndown {
+            project,
+            days,
+            chart,
+        }) => run_burndown(project.as_deref(), days, chart, format),
+        Some(Commands::Clean {
+            history,
+            max_age_days,
+            max_size_mb,
+            dry_run,
+        }) => run_clean(history, max_age_days, max_size_mb, dry_run, format),
+        Some(Commands::Context { command }) => match command {
+            ContextCommands::Index { project } => run_context_index(project.as_deref(), format),
+            ContextCommands::Implement(mut command) => {
+                command.max_iterations = command.max_iterations.or(defaults.max_iterations);
+                command.timeout_seconds = command.timeout_seconds.or(defaults.timeout_seconds);
+                run_context_implement(&command, format)
+            }
+            ContextCommands::TestStatus { context_file } => {
+                run_context_test_status(&context_file, format)
+            }
+            ContextCommands::Backfill { context_file, all } => {
+                run_context_backfill(context_file.as_deref(), all, format)
+            }
+        },
+        Some(Commands::Refs { command }) => match command {
+            RefsCommands::Remap { from, to, dry_run } => {
+                run_refs_remap(&from, &to, dry_run, format)
+            }
+        },
+        Some(Commands::Audit { command }) => match command {
+            AuditCommands::Verify => run_audit_verify(format),
+            AuditCommands::Export { out } => run_audit_export(out.as_deref(), format),
+        },
+        Some(Commands::Config { command }) => match command {
+            ConfigCommands::Doctor { dry_run } => run_config_doctor(dry_run, format),
+            ConfigCommands::Get { key, scope } => run_config_get(&key, scope, format),
+            ConfigCommands::Set { key, value, scope } => {
+                run_config_set(&key, &value, scope, format)
+            }
+            ConfigCommands::Unset { key, scope } => run_config_unset(&key, scope, format),
+            ConfigCommands::List { scope } => run_config_list(scope, format),
+        },
+        Some(Commands::Preset { command }) => match command {
+            PresetCommands::Save {
+                name,
+                from_last,
+                pipeline_file,
+                max_iterations,
+                timeout_seconds,
+                rule_file,
+                test_command,
+                test_discovery_command,
+                model,
+                allow_dependency_bypass,
+                overwrite,
+            } => run_preset_save(
+                &name,
+                from_last,
+                OrchestrationPreset {
+                    pipeline_file,
+                    max_iterations,
+                    timeout_seconds,
+                    rule_file,
+                    test_command,
+                    test_discovery_command,
+                    model,
+                    allow_dependency_bypass,
+                    overwrite,
+                },
+                format,
+            ),
+            PresetCommands::List => run_preset_list(format),
+            PresetCommands::Delete { name } => run_preset_delete(&name, format),
+        },
+        Some(Commands::Graph { command }) => match command {
+            GraphCommands::Serve { host, port } => run_graph_serve(
+                host.or(defaults.graph_host.clone())
+                    .as_deref()
+                    .unwrap_or(DEFAULT_GRAPH_HOST),
+                port.or(defaults.graph_port).unwrap_or(DEFAULT_GRAPH_PORT),
+                format,
+            ),
+        },
+        Some(Commands::Assets { command }) => match command {
+            AssetsCommands::Lint { embedded } => run_assets_lint(embedded, format),
+        },
+        Some(Commands::Plugin { command }) => match command {
+            PluginCommands::List => run_plugin_list(format),
+        },
+        Some(Commands::External(args)) => run_external_plugin(&args, format),
+        Some(Commands::Shell) => {
+            run_shell(|command| dispatch(Some(command), format, defaults, role))
+        }
+        Some(Commands::Complete { words }) => run_complete(&words),
+    })
 }
@@ -1,22 +1,113 @@
 //! Output formatting utilities for the setup-only CLI.
 
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::IsTerminal;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::cli::{ColorChoice, OutputFormat};
+use crate::core::error::error_envelope;
+use crate::services::logging::OUTPUT_TARGET;
+use crate::utils::query::JsonQuery;
+use crate::utils::time::{format_relative, format_unix_rfc3339, unix_now_seconds};
+
+/// `--query` expression applied to every JSON document printed to stdout.
+static JSON_QUERY: OnceLock<JsonQuery> = OnceLock::new();
+
+/// Structured `--format` that `print_json` renders; unset behaves like `json`.
+static DOCUMENT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// `--color` choice; unset behaves like `auto`.
+static COLOR_CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+
+/// `--quiet`: suppress info and success messages.
+static QUIET: OnceLock<bool> = OnceLock::new();
+
+/// `--iso`: absolute UTC timestamps instead of relative times in text output.
+static ISO_TIMESTAMPS: OnceLock<bool> = OnceLock::new();
+
 /// ANSI color codes for terminal output.
 mod colors {
     pub const GREEN: &str = "\x1b[32m";
     pub const RED: &str = "\x1b[31m";
+    pub const YELLOW: &str = "\x1b[33m";
     pub const BLUE: &str = "\x1b[34m";
     pub const BOLD: &str = "\x1b[1m";
     pub const RESET: &str = "\x1b[0m";
 }
 
-/// Check if the terminal supports colors.
+/// Check if status messages on stderr should be colored.
 fn supports_color() -> bool {
-    // Simple check - in production, use a crate like `supports-color`
-    std::env::var("NO_COLOR").is_err() && std::env::var("TERM").map(|t| t != "dumb").unwrap_or(true)
+    color_enabled(
+        COLOR_CHOICE.get().copied().unwrap_or_default(),
+        std::io::stderr().is_terminal(),
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+/// `auto` colors only a terminal, and not when `NO_COLOR` is set to a
+/// non-empty value or `TERM=dumb`; `always` and `never` ignore all three.
+fn color_enabled(
+    choice: ColorChoice,
+    is_terminal: bool,
+    no_color: Option<&str>,
+    term: Option<&str>,
+) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            is_terminal && no_color.is_none_or(str::is_empty) && term != Some("dumb")
+        }
+    }
+}
+
+/// Export `NO_COLOR=1` to a child process when this process is not coloring
+/// its own output, so agents, hooks, and post-processors follow `--color`.
+pub fn propagate_color_choice(command: &mut Command) {
+    if !supports_color() {
+        command.env("NO_COLOR", "1");
+    }
+}
+
+/// Install the `--color` choice for this process.
+pub fn set_color_choice(choice: ColorChoice) {
+    let _ = COLOR_CHOICE.set(choice);
+}
+
+/// Install the `--quiet` choice for this process.
+pub fn set_quiet(enabled: bool) {
+    let _ = QUIET.set(enabled);
+}
+
+fn is_quiet() -> bool {
+    QUIET.get().copied().unwrap_or(false)
+}
+
+/// Install the `--iso` choice for this process.
+pub fn set_iso_timestamps(enabled: bool) {
+    let _ = ISO_TIMESTAMPS.set(enabled);
+}
+
+/// Render unix seconds for text output: relative (`3h ago`) by default,
+/// RFC 3339 UTC under `--iso`. JSON and TSV output keep raw unix seconds.
+pub fn format_timestamp(seconds: i64) -> String {
+    if ISO_TIMESTAMPS.get().copied().unwrap_or(false) {
+        format_unix_rfc3339(seconds)
+    } else {
+        format_relative(seconds, unix_now_seconds())
+    }
 }
 
 /// Print a success message (green checkmark).
 pub fn print_success(message: &str) {
+    tracing::info!(target: OUTPUT_TARGET, "{}", message);
+    if is_quiet() {
+        return;
+    }
     if supports_color() {
         eprintln!(
             "{}{}[OK]{} {}",
@@ -32,6 +123,10 @@ pub fn print_success(message: &str) {
 
 /// Print an info message (blue).
 pub fn print_info(message: &str) {
+    tracing::info!(target: OUTPUT_TARGET, "{}", message);
+    if is_quiet() {
+        return;
+    }
     if supports_color() {
         eprintln!(
             "{}{}[INFO]{} {}",
@@ -45,8 +140,25 @@ pub fn print_info(message: &str) {
     }
 }
 
+/// Print a warning message (yellow).
+pub fn print_warning(message: &str) {
+    tracing::warn!(target: OUTPUT_TARGET, "{}", message);
+    if supports_color() {
+        eprintln!(
+            "{}{}[WARN]{} {}",
+            colors::BOLD,
+            colors::YELLOW,
+            colors::RESET,
+            message
+        );
+    } else {
+        eprintln!("[WARN] {}", message);
+    }
+}
+
 /// Print an error message (red).
 pub fn print_error(message: &str) {
+    tracing::error!(target: OUTPUT_TARGET, "{}", message);
     if supports_color() {
         eprintln!(
             "{}{}[ERROR]{} {}",
@@ -59,3 +171,144 @@ pub fn print_error(message: &str) {
         eprintln!("[ERROR] {}", message);
     }
 }
+
+/// Install the `--query` expression for this process.
+pub fn set_json_query(query: JsonQuery) {
+    let _ = JSON_QUERY.set(query);
+}
+
+/// Install the structured `--format` (`json`, `yaml`, or `ndjson`) for this process.
+pub fn set_document_format(format: OutputFormat) {
+    if format.is_structured() {
+        let _ = DOCUMENT_FORMAT.set(format);
+    }
+}
+
+/// Whether a structured `--format` was installed for this process.
+pub fn structured_output() -> bool {
+    DOCUMENT_FORMAT.get().is_some()
+}
+
+/// Report a failed command on stderr: the `{"error": ...}` envelope as one
+/// JSON line for structured formats, otherwise anyhow's chain and backtrace.
+pub fn report_error(error: &anyhow::Error, structured: bool) {
+    if structured {
+        if let Ok(line) = serde_json::to_string(&error_envelope(error)) {
+            eprintln!("{}", line);
+            return;
+        }
+    }
+    eprintln!("Error: {:?}", error);
+}
+
+/// Print a structured document after applying `--query` if set: one JSON
+/// line, a `---`-led YAML document, or under `ndjson` one line per element
+/// when the document is an array.
+pub fn print_json(value: &impl Serialize) -> Result<()> {
+    println!(
+        "{}",
+        render_document(&queried(value)?, document_format(), false)?
+    );
+    Ok(())
+}
+
+/// Like `print_json`, but indents JSON output.
+pub fn print_json_pretty(value: &impl Serialize) -> Result<()> {
+    println!(
+        "{}",
+        render_document(&queried(value)?, document_format(), true)?
+    );
+    Ok(())
+}
+
+fn document_format() -> OutputFormat {
+    DOCUMENT_FORMAT.get().copied().unwrap_or(OutputFormat::Json)
+}
+
+fn render_document(value: &Value, format: OutputFormat, pretty: bool) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Yaml => format!("---\n{}", serde_yaml::to_string(value)?.trim_end()),
+        OutputFormat::Ndjson => match value {
+            Value::Array(items) => items
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            other => other.to_string(),
+        },
+        _ if pretty => serde_json::to_string_pretty(value)?,
+        _ => value.to_string(),
+    })
+}
+
+/// Print one tab-separated row; tabs and newlines inside fields become spaces.
+pub fn print_tsv_row(fields: &[&str]) {
+    println!("{}", tsv_row(fields));
+}
+
+fn tsv_row(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| field.replace(['\t', '\n', '\r'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+fn queried(value: &impl Serialize) -> Result<Value> {
+    let value = serde_json::to_value(value)?;
+    match JSON_QUERY.get() {
+        Some(query) => query.apply(&value),
+        None => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tsv_rows_flatten_embedded_separators() {
+        assert_eq!(
+            tsv_row(&["gen-code", "two\tcols", "multi\nline"]),
+            "gen-code\ttwo cols\tmulti line"
+        );
+    }
+
+    #[test]
+    fn documents_render_as_json_yaml_or_ndjson() {
+        let document = serde_json::json!([{ "id": 1 }, { "id": 2 }]);
+        assert_eq!(
+            render_document(&document, OutputFormat::Json, false).unwrap(),
+            r#"[{"id":1},{"id":2}]"#
+        );
+        assert_eq!(
+            render_document(&document, OutputFormat::Ndjson, true).unwrap(),
+            "{\"id\":1}\n{\"id\":2}"
+        );
+        assert_eq!(
+            render_document(
+                &serde_json::json!({ "status": "ok" }),
+                OutputFormat::Yaml,
+                false
+            )
+            .unwrap(),
+            "---\nstatus: ok"
+        );
+    }
+
+    #[test]
+    fn auto_color_requires_a_terminal_without_opt_outs() {
+        assert!(color_enabled(ColorChoice::Auto, true, None, Some("xterm")));
+        assert!(color_enabled(ColorChoice::Auto, true, Some(""), None));
+        assert!(!color_enabled(ColorChoice::Auto, false, None, None));
+        assert!(!color_enabled(ColorChoice::Auto, true, Some("1"), None));
+        assert!(!color_enabled(ColorChoice::Auto, true, None, Some("dumb")));
+        assert!(color_enabled(
+            ColorChoice::Always,
+            false,
+            Some("1"),
+            Some("dumb")
+        ));
+        assert!(!color_enabled(ColorChoice::Never, true, None, None));
+    }
+}
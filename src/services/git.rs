@@ -1,58 +1,88 @@
+//! Git backend for the `Vcs` abstraction.
+
+use anyhow::{bail, Context, Result};
 use std::collections::BTreeSet;
 use std::process::Command;
 
-pub fn snapshot_files() -> BTreeSet<String> {
-    let mut files = BTreeSet::new();
-    let tracked = Command::new("git").args(["ls-files"]).output();
-    if let Ok(output) = tracked {
-        if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
-                files.insert(line.to_string());
+use crate::services::vcs::Vcs;
+
+pub struct GitVcs;
+
+impl Vcs for GitVcs {
+    fn snapshot_files(&self) -> BTreeSet<String> {
+        let mut files = BTreeSet::new();
+        let tracked = Command::new("git").args(["ls-files"]).output();
+        if let Ok(output) = tracked {
+            if output.status.success() {
+                let text = String::from_utf8_lossy(&output.stdout);
+                for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    files.insert(line.to_string());
+                }
             }
         }
-    }
 
-    let changed = Command::new("git").args(["status", "--porcelain"]).output();
-    if let Ok(output) = changed {
-        if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            for line in text.lines() {
-                if line.len() > 3 {
-                    files.insert(line[3..].trim().to_string());
+        let changed = Command::new("git").args(["status", "--porcelain"]).output();
+        if let Ok(output) = changed {
+            if output.status.success() {
+                let text = String::from_utf8_lossy(&output.stdout);
+                for line in text.lines() {
+                    if line.len() > 3 {
+                        files.insert(line[3..].trim().to_string());
+                    }
                 }
             }
         }
+        files
     }
-    files
-}
 
-pub fn modified_since(before: &BTreeSet<String>, after: &BTreeSet<String>) -> Vec<String> {
-    let mut changed = Vec::new();
-    for item in before.union(after) {
-        if before.contains(item) != after.contains(item) {
-            changed.push(item.to_string());
+    fn commit_all(&self, message: &str) -> Result<()> {
+        let status = Command::new("git")
+            .args(["status", "--porcelain"])
+            .output()
+            .context("Failed to run git. Remediation: install git and run inside a repository.")?;
+        if !status.status.success() || status.stdout.is_empty() {
+            return Ok(());
         }
+        run_git(&["add", "-A"])?;
+        run_git(&["commit", "-m", message])
     }
-    changed.sort();
-    changed
-}
 
-pub fn auto_commit(iteration: usize) {
-    let status = Command::new("git").args(["status", "--porcelain"]).output();
-    let Ok(output) = status else {
-        return;
-    };
-    if !output.status.success() || output.stdout.is_empty() {
-        return;
+    fn commit_on_new_branch(&self, branch: &str, paths: &[String], message: &str) -> Result<()> {
+        let exists = Command::new("git")
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/heads/{}", branch),
+            ])
+            .output()
+            .context("Failed to run git. Remediation: install git and run inside a repository.")?;
+        if exists.status.success() {
+            bail!(
+                "Branch '{}' already exists. Remediation: delete or rename it, or rerun with --no-commit.",
+                branch
+            );
+        }
+        run_git(&["checkout", "-b", branch])?;
+        let mut add = vec!["add", "--"];
+        add.extend(paths.iter().map(String::as_str));
+        run_git(&add)?;
+        run_git(&["commit", "-m", message])
     }
+}
 
-    let _ = Command::new("git").args(["add", "-A"]).status();
-    let _ = Command::new("git")
-        .args([
-            "commit",
-            "-m",
-            &format!("Ralph iteration {}: work in progress", iteration),
-        ])
-        .status();
+fn run_git(args: &[&str]) -> Result<()> {
+    tracing::debug!(?args, "running git");
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git. Remediation: install git and run inside a repository.")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
 }
@@ -0,0 +1,255 @@
+//! Executable event hooks under `.nexus/hooks/`.
+//!
+//! A hook is any executable file named after its event, or any executable in
+//! the event's `<event>.d/` directory, which run in name order. It receives
+//! one JSON payload on stdin; a failing hook is reported but never fails the
+//! command that triggered it.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+
+use crate::output::{print_error, propagate_color_choice};
+use crate::utils::paths::is_executable;
+
+const HOOKS_DIR: &str = ".nexus/hooks";
+
+/// Scripts `setup` runs after installing, in name order.
+pub const POST_SETUP_HOOKS_DIR: &str = ".nexus/hooks/post-setup.d";
+
+/// The outcome of one hook in a hook directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookRun {
+    pub hook: String,
+    /// `None` when the hook could not be started or was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    ContextCreated,
+    ActionCompleted,
+    WorkflowFinished,
+}
+
+impl HookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ContextCreated => "on-context-created",
+            Self::ActionCompleted => "on-action-completed",
+            Self::WorkflowFinished => "on-workflow-finished",
+        }
+    }
+}
+
+/// Invoke the hook for `event` if one is installed, reporting failures on stderr.
+pub fn fire_hook(event: HookEvent, payload: Value) {
+    if let Err(err) = run_hook_in(Path::new(HOOKS_DIR), event, payload) {
+        print_error(&format!("Hook '{}' failed: {:#}", event.as_str(), err));
+    }
+}
+
+/// Run every executable in `dir` in name order with `payload`, reporting
+/// each one's outcome. A missing directory runs nothing.
+pub fn run_hook_dir(dir: &Path, event: &str, payload: Value) -> Vec<HookRun> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut hooks = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path))
+        .collect::<Vec<_>>();
+    hooks.sort();
+    hooks
+        .into_iter()
+        .map(|hook_path| {
+            let hook = hook_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            match execute_hook(&hook_path, event, payload.clone()) {
+                Ok(status) => HookRun {
+                    hook,
+                    exit_code: status.code(),
+                    success: status.success(),
+                    error: None,
+                },
+                Err(err) => HookRun {
+                    hook,
+                    exit_code: None,
+                    success: false,
+                    error: Some(format!("{:#}", err)),
+                },
+            }
+        })
+        .collect()
+}
+
+fn run_hook_in(hooks_dir: &Path, event: HookEvent, payload: Value) -> Result<()> {
+    let hook_path = hooks_dir.join(event.as_str());
+    if !hook_path.is_file() {
+        return Ok(());
+    }
+    let status = execute_hook(&hook_path, event.as_str(), payload)?;
+    if !status.success() {
+        bail!(
+            "'{}' exited with status {}.",
+            hook_path.display(),
+            status.code().unwrap_or(-1)
+        );
+    }
+    Ok(())
+}
+
+/// Run the hook at `hook_path` with `payload` and `event` on stdin.
+fn execute_hook(hook_path: &Path, event: &str, payload: Value) -> Result<ExitStatus> {
+    let mut body = match payload {
+        Value::Object(map) => map,
+        other => {
+            let mut map = serde_json::Map::new();
+            map.insert("data".to_string(), other);
+            map
+        }
+    };
+    body.insert("event".to_string(), Value::String(event.to_string()));
+
+    tracing::debug!(hook = %hook_path.display(), "running hook");
+    let mut command = Command::new(hook_path);
+    propagate_color_choice(&mut command);
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| {
+            format!(
+                "Unable to execute '{}'. Remediation: make the hook executable (chmod +x).",
+                hook_path.display()
+            )
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Hooks that ignore stdin may exit before reading it; that is not an error.
+        if let Err(err) = stdin.write_all(Value::Object(body).to_string().as_bytes()) {
+            if err.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(err).with_context(|| {
+                    format!("Failed to send payload to '{}'.", hook_path.display())
+                });
+            }
+        }
+    }
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Failed to wait for '{}'.", hook_path.display()))?;
+
+    // Hook stdout is forwarded to stderr so JSON command output stays clean.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        eprint!("{}", stdout);
+    }
+    Ok(output.status)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    fn install_hook(dir: &Path, event: HookEvent, script: &str) {
+        let path = dir.join(event.as_str());
+        fs::write(&path, script).expect("write hook");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod hook");
+    }
+
+    #[test]
+    fn passes_payload_with_event_name_on_stdin() {
+        let dir = tempdir().expect("tempdir");
+        let capture = dir.path().join("payload.json");
+        install_hook(
+            dir.path(),
+            HookEvent::WorkflowFinished,
+            &format!("#!/bin/sh\ncat > '{}'\n", capture.display()),
+        );
+
+        run_hook_in(
+            dir.path(),
+            HookEvent::WorkflowFinished,
+            serde_json::json!({"run_id": 7, "status": "success"}),
+        )
+        .expect("hook should run");
+
+        let payload: Value =
+            serde_json::from_str(&fs::read_to_string(&capture).expect("read capture"))
+                .expect("payload json");
+        assert_eq!(payload["event"], "on-workflow-finished");
+        assert_eq!(payload["run_id"], 7);
+        assert_eq!(payload["status"], "success");
+    }
+
+    #[test]
+    fn missing_hook_is_a_no_op_and_failing_hook_errors() {
+        let dir = tempdir().expect("tempdir");
+        run_hook_in(dir.path(), HookEvent::ContextCreated, serde_json::json!({}))
+            .expect("missing hook is ignored");
+
+        install_hook(
+            dir.path(),
+            HookEvent::ActionCompleted,
+            "#!/bin/sh\nexit 3\n",
+        );
+        let err = run_hook_in(
+            dir.path(),
+            HookEvent::ActionCompleted,
+            serde_json::json!({}),
+        )
+        .expect_err("failing hook should error");
+        assert!(err.to_string().contains("exited with status 3"));
+    }
+
+    #[test]
+    fn runs_hook_directory_in_name_order() {
+        let dir = tempdir().expect("tempdir");
+        let log = dir.path().join("log");
+        let hooks = dir.path().join("post-setup.d");
+        fs::create_dir(&hooks).expect("hooks dir");
+        let write = |name: &str, script: String, mode: u32| {
+            let path = hooks.join(name);
+            fs::write(&path, script).expect("write hook");
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).expect("chmod hook");
+        };
+        write(
+            "20-fail",
+            format!("#!/bin/sh\necho fail >> '{}'\nexit 4\n", log.display()),
+            0o755,
+        );
+        write(
+            "10-ok",
+            format!(
+                "#!/bin/sh\ngrep -q post-setup && echo ok >> '{}'\n",
+                log.display()
+            ),
+            0o755,
+        );
+        write("README", "not a hook".to_string(), 0o644);
+
+        let runs = run_hook_dir(&hooks, "post-setup", serde_json::json!({}));
+        assert_eq!(
+            runs.iter()
+                .map(|run| (run.hook.as_str(), run.exit_code, run.success))
+                .collect::<Vec<_>>(),
+            vec![("10-ok", Some(0), true), ("20-fail", Some(4), false)]
+        );
+        assert_eq!(fs::read_to_string(&log).expect("log"), "ok\nfail\n");
+        assert!(run_hook_dir(&dir.path().join("missing"), "post-setup", Value::Null).is_empty());
+    }
+}
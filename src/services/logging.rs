@@ -0,0 +1,94 @@
+//! Debug logging through `tracing`.
+//!
+//! Every event at debug level and above is appended to a log file: the
+//! `--log-file` path, or `$XDG_DATA_HOME/opennexus/logs/cli.log` (falling back
+//! to `~/.local/share`) unless stateless. `-v` also mirrors info events to
+//! stderr and `-vv` debug events. Status messages from `output::print_*` are
+//! logged under `OUTPUT_TARGET` and never mirrored, since they are already on
+//! screen.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing_subscriber::filter::{filter_fn, LevelFilter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+use crate::config::stateless::is_stateless;
+use crate::utils::paths::data_dir;
+
+/// Target of the events `output::print_*` records for its status messages.
+pub const OUTPUT_TARGET: &str = "opennexus::output";
+
+/// `$XDG_DATA_HOME/opennexus/logs/cli.log`, falling back to `~/.local/share`.
+pub fn default_log_path() -> Option<PathBuf> {
+    Some(data_dir()?.join("logs").join("cli.log"))
+}
+
+/// Install the process-wide subscriber for `-v` count `verbose`.
+///
+/// An explicit `log_file` that cannot be opened is an error; the default log
+/// is skipped silently so a read-only home directory never blocks a command.
+pub fn init_logging(verbose: u8, log_file: Option<&Path>) -> Result<()> {
+    let file = match log_file {
+        Some(path) => Some(open_log_file(path).with_context(|| {
+            format!(
+                "Unable to open log file '{}'. Remediation: pass a writable --log-file path.",
+                path.display()
+            )
+        })?),
+        None if is_stateless() => None,
+        None => default_log_path().and_then(|path| open_log_file(&path).ok()),
+    };
+    let file_layer = file.map(|file| {
+        fmt::layer()
+            .with_writer(Mutex::new(file))
+            .with_filter(LevelFilter::DEBUG)
+    });
+    let stderr_layer = fmt::layer()
+        .with_writer(std::io::stderr)
+        .without_time()
+        .with_target(false)
+        .with_filter(stderr_level(verbose))
+        .with_filter(filter_fn(|metadata| metadata.target() != OUTPUT_TARGET));
+
+    tracing_subscriber::registry()
+        .with(file_layer)
+        .with(stderr_layer)
+        .try_init()
+        .context("Failed to install the logging subscriber.")
+}
+
+fn stderr_level(verbose: u8) -> LevelFilter {
+    match verbose {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+fn open_log_file(path: &Path) -> Result<File> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(OpenOptions::new().create(true).append(true).open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_maps_to_stderr_levels() {
+        assert_eq!(stderr_level(0), LevelFilter::OFF);
+        assert_eq!(stderr_level(1), LevelFilter::INFO);
+        assert_eq!(stderr_level(2), LevelFilter::DEBUG);
+        assert_eq!(stderr_level(5), LevelFilter::TRACE);
+    }
+}
@@ -1,3 +1,9 @@
 pub mod git;
+pub mod hooks;
+pub mod logging;
+pub mod post_processors;
 pub mod process;
+pub mod progress;
 pub mod ralph_fs;
+pub mod retry;
+pub mod vcs;
@@ -0,0 +1,90 @@
+//! Post-processors declared per pipeline under `post_processors` in
+//! `.nexus/config.json`, run after a pipeline succeeds.
+//!
+//! Each entry is a shell command (for example `cargo fmt` or
+//! `npx prettier --write $NEXUS_GENERATED_FILES`) run from the repository root.
+//! Failures are reported in the run summary but do not fail the run.
+
+use serde::Serialize;
+use std::process::Command;
+
+use crate::output::propagate_color_choice;
+
+/// Trailing output lines kept per post-processor for the run summary.
+const OUTPUT_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PostProcessorResult {
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// Last lines of combined stdout and stderr.
+    pub output: String,
+}
+
+/// Run `commands` in order with `env` exported to each.
+pub fn run_post_processors(
+    commands: &[String],
+    env: &[(&str, String)],
+) -> Vec<PostProcessorResult> {
+    commands
+        .iter()
+        .map(|command| {
+            tracing::debug!(command = %command, "running post-processor");
+            let mut process = Command::new("sh");
+            process
+                .arg("-c")
+                .arg(command)
+                .envs(env.iter().map(|(key, value)| (*key, value.as_str())));
+            propagate_color_choice(&mut process);
+            let output = process.output();
+            match output {
+                Ok(output) => {
+                    let combined = format!(
+                        "{}{}",
+                        String::from_utf8_lossy(&output.stdout),
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                    PostProcessorResult {
+                        command: command.clone(),
+                        success: output.status.success(),
+                        exit_code: output.status.code(),
+                        output: output_tail(&combined),
+                    }
+                }
+                Err(err) => PostProcessorResult {
+                    command: command.clone(),
+                    success: false,
+                    exit_code: None,
+                    output: format!("failed to start: {}", err),
+                },
+            }
+        })
+        .collect()
+}
+
+fn output_tail(output: &str) -> String {
+    let lines = output.lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..].join("\n")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_commands_with_env_and_reports_failures() {
+        let results = run_post_processors(
+            &[
+                "printf '%s' \"$NEXUS_PIPELINE\"".to_string(),
+                "echo broken >&2; exit 3".to_string(),
+            ],
+            &[("NEXUS_PIPELINE", "gen-code".to_string())],
+        );
+        assert_eq!(results[0].output, "gen-code");
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert_eq!(results[1].exit_code, Some(3));
+        assert_eq!(results[1].output, "broken");
+    }
+}
@@ -9,6 +9,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::adapters::agents::parse_tool_name;
 use crate::core::ralph::model::AgentType;
+use crate::output::propagate_color_choice;
 use crate::utils::text::format_duration_short;
 
 #[derive(Debug, Clone)]
@@ -50,6 +51,7 @@ pub fn run_command(
     interrupted: Arc<AtomicBool>,
     active: ActiveChild,
 ) -> Result<ProcessResult> {
+    tracing::debug!(command, ?args, stream, "launching agent");
     let mut cmd = Command::new(command);
     cmd.args(args)
         .stdin(Stdio::inherit())
@@ -58,6 +60,7 @@ pub fn run_command(
     for (key, value) in env {
         cmd.env(key, value);
     }
+    propagate_color_choice(&mut cmd);
 
     let child = cmd
         .spawn()
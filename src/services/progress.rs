@@ -0,0 +1,137 @@
+//! Structured progress events for wrapper tools (`--progress-fd <n>`).
+//!
+//! Each event is one JSON object per line written to a descriptor the caller
+//! opened, so stdout stays reserved for command results. Without
+//! `--progress-fd` every emit is a no-op.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
+
+use crate::utils::time::unix_now_seconds;
+
+static PROGRESS_SINK: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Route progress events to the already-open file descriptor `fd`.
+pub fn open_progress_fd(fd: u32) -> Result<()> {
+    if fd <= 1 {
+        bail!(
+            "--progress-fd {} would mix progress into stdin/stdout. Remediation: pass 2 or a descriptor opened by the caller, such as 3.",
+            fd
+        );
+    }
+    let file = open_descriptor(fd)?;
+    let _ = PROGRESS_SINK.set(Mutex::new(file));
+    Ok(())
+}
+
+#[cfg(unix)]
+fn open_descriptor(fd: u32) -> Result<File> {
+    OpenOptions::new()
+        .append(true)
+        .open(format!("/dev/fd/{}", fd))
+        .with_context(|| {
+            format!(
+                "File descriptor {} is not open for writing. Remediation: open it in the calling process (for example `3>progress.jsonl`).",
+                fd
+            )
+        })
+}
+
+#[cfg(not(unix))]
+fn open_descriptor(fd: u32) -> Result<File> {
+    bail!("--progress-fd {} is only supported on Unix platforms.", fd)
+}
+
+/// Events written to `--progress-fd`, tagged by `event`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event")]
+pub enum ProgressEvent {
+    #[serde(rename = "command.started")]
+    CommandStarted { command: String },
+    #[serde(rename = "command.finished")]
+    CommandFinished {
+        command: String,
+        status: &'static str,
+        error: Option<String>,
+    },
+    #[serde(rename = "step.started")]
+    StepStarted {
+        run_id: Option<i64>,
+        step_id: String,
+        index: usize,
+        total: usize,
+    },
+    #[serde(rename = "step.finished")]
+    StepFinished {
+        run_id: Option<i64>,
+        step_id: String,
+        status: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// Identifies this invocation in every event, so wrappers reading several
+/// processes' progress from one pipe can tell them apart.
+fn execution_id() -> &'static str {
+    static EXECUTION_ID: OnceLock<String> = OnceLock::new();
+    EXECUTION_ID.get_or_init(|| format!("{:x}-{:x}", unix_now_seconds(), std::process::id()))
+}
+
+/// Emit one progress event; write failures are ignored so progress never fails a command.
+pub fn emit_progress(event: ProgressEvent) {
+    let Some(sink) = PROGRESS_SINK.get() else {
+        return;
+    };
+    let Ok(line) = progress_line(&event, unix_now_seconds(), execution_id()) else {
+        return;
+    };
+    if let Ok(mut file) = sink.lock() {
+        let _ = writeln!(file, "{}", line);
+        let _ = file.flush();
+    }
+}
+
+fn progress_line(event: &ProgressEvent, timestamp: i64, execution_id: &str) -> Result<String> {
+    let mut body = serde_json::to_value(event)?;
+    if let Value::Object(map) = &mut body {
+        map.insert("timestamp".to_string(), Value::from(timestamp));
+        map.insert(
+            "execution_id".to_string(),
+            Value::String(execution_id.to_string()),
+        );
+    }
+    Ok(body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_lines_merge_event_timestamp_and_fields() {
+        let line = progress_line(
+            &ProgressEvent::StepFinished {
+                run_id: Some(7),
+                step_id: "red".to_string(),
+                status: "success",
+                error: None,
+            },
+            1_700_000_000,
+            "abc-1",
+        )
+        .expect("line");
+        let parsed: Value = serde_json::from_str(&line).expect("json line");
+        assert_eq!(parsed["event"], "step.finished");
+        assert_eq!(parsed["timestamp"], 1_700_000_000);
+        assert_eq!(parsed["execution_id"], "abc-1");
+        assert_eq!(parsed["step_id"], "red");
+        assert!(parsed.get("error").is_none());
+        assert!(!line.contains('\n'));
+        assert!(open_progress_fd(1).is_err());
+    }
+}
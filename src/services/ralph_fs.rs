@@ -2,6 +2,7 @@ use anyhow::Result;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::config::stateless::is_stateless;
 use crate::core::ralph::model::{RalphHistory, RalphState};
 
 #[derive(Debug, Clone)]
@@ -52,6 +53,9 @@ pub fn load_history(paths: &RalphPaths) -> RalphHistory {
 }
 
 pub fn save_history(paths: &RalphPaths, history: &RalphHistory) -> Result<()> {
+    if is_stateless() {
+        return Ok(());
+    }
     fs::create_dir_all(&paths.state_dir)?;
     fs::write(
         &paths.history_file,
@@ -0,0 +1,142 @@
+//! Retry-with-backoff for network fetches.
+//!
+//! Each fetch gets a bounded number of attempts and a total time budget;
+//! backoff doubles between attempts and a retry is skipped when its delay
+//! would overrun the budget. `--retry-attempts` and `--no-retry` override the
+//! attempt limit for every fetch.
+
+use anyhow::{Context, Result};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::output::print_info;
+
+/// `--retry-attempts`, or `1` for `--no-retry`; unset keeps each budget's own.
+static MAX_ATTEMPTS: OnceLock<u32> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryBudget {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub total: Duration,
+}
+
+impl RetryBudget {
+    /// Budget for registry downloads and repository clones.
+    pub const NETWORK: Self = Self {
+        max_attempts: 4,
+        initial_backoff: Duration::from_millis(500),
+        total: Duration::from_secs(60),
+    };
+}
+
+pub fn set_max_attempts(max_attempts: u32) {
+    let _ = MAX_ATTEMPTS.set(max_attempts.max(1));
+}
+
+/// Run `operation` until it succeeds or `budget` is spent; `label` names the
+/// fetch in retry notices and the final error.
+pub fn retry_with_backoff<T>(
+    label: &str,
+    budget: RetryBudget,
+    operation: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    retry_with_backoff_if(label, budget, |_| true, operation)
+}
+
+/// Like `retry_with_backoff`, but fail at once on errors `retryable` rejects,
+/// such as a 404 that no amount of waiting will fix.
+pub fn retry_with_backoff_if<T>(
+    label: &str,
+    budget: RetryBudget,
+    retryable: impl Fn(&anyhow::Error) -> bool,
+    mut operation: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let max_attempts = MAX_ATTEMPTS.get().copied().unwrap_or(budget.max_attempts);
+    let started = Instant::now();
+    let mut backoff = budget.initial_backoff;
+    let mut attempt = 1;
+    loop {
+        let err = match operation() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if !retryable(&err) {
+            return Err(err);
+        }
+        let out_of_budget = started.elapsed() + backoff > budget.total;
+        if attempt >= max_attempts || out_of_budget {
+            return Err(err).with_context(|| {
+                format!(
+                    "{} failed after {} attempt(s) within the {}s retry budget.",
+                    label,
+                    attempt,
+                    budget.total.as_secs()
+                )
+            });
+        }
+        print_info(&format!(
+            "{} failed (attempt {}/{}): {}. Retrying in {}ms.",
+            label,
+            attempt,
+            max_attempts,
+            err,
+            backoff.as_millis()
+        ));
+        std::thread::sleep(backoff);
+        backoff = backoff.saturating_mul(2);
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn instant_budget(max_attempts: u32) -> RetryBudget {
+        RetryBudget {
+            max_attempts,
+            initial_backoff: Duration::ZERO,
+            total: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn retries_until_success_and_stops_at_attempt_limit() {
+        let mut calls = 0;
+        let value = retry_with_backoff("fetch", instant_budget(3), || {
+            calls += 1;
+            if calls < 3 {
+                Err(anyhow!("flaky"))
+            } else {
+                Ok(calls)
+            }
+        })
+        .expect("third attempt succeeds");
+        assert_eq!(value, 3);
+
+        let mut calls = 0;
+        let err = retry_with_backoff("fetch", instant_budget(2), || -> Result<()> {
+            calls += 1;
+            Err(anyhow!("down"))
+        })
+        .expect_err("budget exhausted");
+        assert_eq!(calls, 2);
+        assert!(err.to_string().contains("after 2 attempt(s)"));
+
+        let mut calls = 0;
+        let err = retry_with_backoff_if(
+            "fetch",
+            instant_budget(3),
+            |err| err.to_string() != "not found",
+            || -> Result<()> {
+                calls += 1;
+                Err(anyhow!("not found"))
+            },
+        )
+        .expect_err("permanent failure");
+        assert_eq!(calls, 1);
+        assert_eq!(err.to_string(), "not found");
+    }
+}
@@ -0,0 +1,108 @@
+//! Version-control abstraction used by commit and diff features.
+//!
+//! Features talk to a `Vcs` instead of shelling out to git directly. The
+//! backend comes from `"vcs"` in `.nexus/config.json` when set, otherwise from
+//! the repository's metadata directory. Only git is implemented; jujutsu and
+//! sapling are detected so users get a clear message instead of silent git
+//! failures.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::config::nexus::{load_nexus_config, NEXUS_CONFIG_PATH};
+use crate::services::git::GitVcs;
+
+/// Values accepted by `"vcs"` in `.nexus/config.json`.
+pub const SUPPORTED_VCS: [&str; 3] = ["git", "jj", "sapling"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VcsKind {
+    #[serde(rename = "git")]
+    Git,
+    #[serde(rename = "jj")]
+    Jujutsu,
+    #[serde(rename = "sapling")]
+    Sapling,
+}
+
+impl VcsKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Git => "git",
+            Self::Jujutsu => "jj",
+            Self::Sapling => "sapling",
+        }
+    }
+}
+
+pub trait Vcs {
+    /// Tracked files plus files with uncommitted changes, relative to the root.
+    fn snapshot_files(&self) -> BTreeSet<String>;
+
+    /// Commit every pending change; a clean tree is not an error.
+    fn commit_all(&self, message: &str) -> Result<()>;
+
+    /// Create `branch` from the current revision and commit exactly `paths` on it.
+    fn commit_on_new_branch(&self, branch: &str, paths: &[String], message: &str) -> Result<()>;
+}
+
+/// Backend inferred from metadata directories; jujutsu wins in colocated
+/// repositories because `.git` there is only an export target.
+pub fn detect_vcs(root: &Path) -> Option<VcsKind> {
+    if root.join(".jj").is_dir() {
+        Some(VcsKind::Jujutsu)
+    } else if root.join(".sl").is_dir() || root.join(".hg").is_dir() {
+        Some(VcsKind::Sapling)
+    } else if root.join(".git").exists() {
+        Some(VcsKind::Git)
+    } else {
+        None
+    }
+}
+
+/// Open the configured or detected backend for the current directory.
+///
+/// Outside any repository this falls back to git, whose commands then fail
+/// quietly the same way they always have.
+pub fn open_vcs() -> Result<Box<dyn Vcs>> {
+    let configured = load_nexus_config(Path::new(NEXUS_CONFIG_PATH))?.vcs;
+    let kind = configured
+        .or_else(|| detect_vcs(Path::new(".")))
+        .unwrap_or(VcsKind::Git);
+    match kind {
+        VcsKind::Git => Ok(Box::new(GitVcs)),
+        other => bail!(
+            "The {} backend is not supported yet. Remediation: in a colocated repository set \"vcs\": \"git\" in {}, or run this command from a git checkout.",
+            other.as_str(),
+            NEXUS_CONFIG_PATH
+        ),
+    }
+}
+
+/// Paths present in exactly one of two snapshots.
+pub fn modified_since(before: &BTreeSet<String>, after: &BTreeSet<String>) -> Vec<String> {
+    before.symmetric_difference(after).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn detects_backend_from_metadata_directory() {
+        let dir = tempdir().expect("tempdir");
+        assert_eq!(detect_vcs(dir.path()), None);
+        fs::create_dir(dir.path().join(".git")).expect("git dir");
+        assert_eq!(detect_vcs(dir.path()), Some(VcsKind::Git));
+        fs::create_dir(dir.path().join(".jj")).expect("jj dir");
+        assert_eq!(detect_vcs(dir.path()), Some(VcsKind::Jujutsu));
+
+        let before = BTreeSet::from(["a.rs".to_string(), "b.rs".to_string()]);
+        let after = BTreeSet::from(["b.rs".to_string(), "c.rs".to_string()]);
+        assert_eq!(modified_since(&before, &after), vec!["a.rs", "c.rs"]);
+    }
+}
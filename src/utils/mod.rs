@@ -1 +1,4 @@
+pub mod paths;
+pub mod query;
 pub mod text;
+pub mod time;
@@ -0,0 +1,318 @@
+//! Platform-safe path helpers.
+//!
+//! Build paths with `Path::join` per segment rather than `/`-joined strings,
+//! and go through these helpers for the home directory and for links into
+//! `.nexus/`. By default those links are relative symlinks on Unix; elsewhere
+//! the source is copied, because Windows symlinks need Developer Mode or admin
+//! rights. [`link_with_mode`] makes the other [`LinkMode`]s.
+
+use anyhow::{bail, Context, Result};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::LinkMode;
+
+/// `$HOME`, falling back to `%USERPROFILE%` on Windows.
+pub fn home_dir() -> Option<PathBuf> {
+    home_dir_from(|name| std::env::var_os(name))
+}
+
+/// [`home_dir`] with variables read through `var` instead of the process
+/// environment.
+pub(crate) fn home_dir_from(var: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    ["HOME", "USERPROFILE"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// `$XDG_DATA_HOME/opennexus`, falling back to `~/.local/share/opennexus`.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".local").join("share")))?;
+    Some(base.join("opennexus"))
+}
+
+/// `$XDG_CACHE_HOME/opennexus`, falling back to `~/.cache/opennexus`; the
+/// npm and Python wrappers keep downloaded binaries here.
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".cache")))?;
+    Some(base.join("opennexus"))
+}
+
+/// Expand a leading `~/` or `~\` against the home directory.
+pub fn expand_home(input: &str) -> Option<PathBuf> {
+    let rest = input
+        .strip_prefix("~/")
+        .or_else(|| input.strip_prefix("~\\"))?;
+    Some(home_dir()?.join(rest))
+}
+
+/// Whether `input` is written as a filesystem path: absolute (including
+/// `C:\` and UNC forms on Windows) or explicitly relative with `./` or `.\`.
+pub fn is_explicit_path(input: &str) -> bool {
+    Path::new(input).is_absolute()
+        || input.starts_with('/')
+        || ["./", "../", ".\\", "..\\"]
+            .iter()
+            .any(|prefix| input.starts_with(prefix))
+}
+
+/// Render `path` with `/` separators, for paths stored in databases, context
+/// documents, or JSON output that must compare equal across platforms.
+pub fn to_slash(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Whether `path` exists, including dangling symlinks.
+pub fn path_exists_or_symlink(path: &Path) -> bool {
+    path.exists() || fs::symlink_metadata(path).is_ok()
+}
+
+/// Remove a file, directory, or symlink without following the link.
+pub fn remove_path(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    // Directory symlinks and junctions are removed as directories on Windows.
+    if cfg!(windows) && metadata.file_type().is_symlink() {
+        fs::remove_dir(path).or_else(|_| fs::remove_file(path))?;
+    } else if metadata.file_type().is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Point `target` at `source` with the platform's default [`LinkMode`].
+pub fn link_or_copy(link_target: &str, source: &Path, target: &Path) -> Result<()> {
+    link_with_mode(LinkMode::platform_default(), link_target, source, target)
+}
+
+/// Point `target` at `source` using `mode`. Symlinks point at the relative
+/// `link_target`; hard links and copies of a directory are made file by file,
+/// and junctions (Windows only) hard link files, which cannot be junctions.
+pub fn link_with_mode(
+    mode: LinkMode,
+    link_target: &str,
+    source: &Path,
+    target: &Path,
+) -> Result<()> {
+    match mode {
+        LinkMode::Symlink => symlink(link_target, source, target),
+        LinkMode::Hardlink => hard_link_recursive(source, target),
+        LinkMode::Junction if source.is_dir() => junction(source, target),
+        LinkMode::Junction => hard_link_recursive(source, target),
+        LinkMode::Copy if source.is_dir() => copy_dir_recursive(source, target),
+        LinkMode::Copy => fs::copy(source, target).map(|_| ()).with_context(|| {
+            format!(
+                "Failed to copy '{}' to '{}'.",
+                source.display(),
+                target.display()
+            )
+        }),
+    }
+}
+
+fn symlink(link_target: &str, source: &Path, target: &Path) -> Result<()> {
+    #[cfg(unix)]
+    let result = {
+        let _ = source;
+        std::os::unix::fs::symlink(link_target, target)
+    };
+    #[cfg(windows)]
+    let result = if source.is_dir() {
+        std::os::windows::fs::symlink_dir(link_target, target)
+    } else {
+        std::os::windows::fs::symlink_file(link_target, target)
+    };
+    #[cfg(not(any(unix, windows)))]
+    let result: std::io::Result<()> = {
+        let _ = source;
+        Err(std::io::ErrorKind::Unsupported.into())
+    };
+    result.with_context(|| {
+        format!(
+            "Failed to link '{}' -> '{}'. Remediation: enable Developer Mode for symlinks, or pass --link-mode hardlink, junction, or copy.",
+            target.display(),
+            link_target
+        )
+    })
+}
+
+fn hard_link_recursive(source: &Path, target: &Path) -> Result<()> {
+    if !source.is_dir() {
+        return fs::hard_link(source, target).with_context(|| {
+            format!(
+                "Failed to hard link '{}' to '{}'. Remediation: hard links need both paths on one volume; pass --link-mode copy otherwise.",
+                target.display(),
+                source.display()
+            )
+        });
+    }
+    fs::create_dir_all(target)
+        .with_context(|| format!("Failed to create directory '{}'.", target.display()))?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        hard_link_recursive(&entry.path(), &target.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+fn junction(source: &Path, target: &Path) -> Result<()> {
+    if !cfg!(windows) {
+        bail!("Junctions exist only on Windows. Remediation: pass --link-mode symlink.");
+    }
+    let status = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg("mklink")
+        .arg("/J")
+        .arg(target)
+        .arg(source)
+        .stdout(std::process::Stdio::null())
+        .status()
+        .context("Failed to run `mklink /J`.")?;
+    if !status.success() {
+        bail!(
+            "Failed to create junction '{}' -> '{}' ({}).",
+            target.display(),
+            source.display(),
+            status
+        );
+    }
+    Ok(())
+}
+
+/// Whether `path` (a file or directory tree) has the same contents as
+/// `source`. A symlink is in sync exactly when it resolves.
+pub fn same_contents(path: &Path, source: &Path) -> bool {
+    if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return path.exists();
+    }
+    if source.is_dir() {
+        let names = |dir: &Path| -> Option<Vec<_>> {
+            let mut names = fs::read_dir(dir)
+                .ok()?
+                .map(|entry| entry.map(|entry| entry.file_name()))
+                .collect::<std::io::Result<Vec<_>>>()
+                .ok()?;
+            names.sort();
+            Some(names)
+        };
+        return match (names(path), names(source)) {
+            (Some(ours), Some(theirs)) if ours == theirs => ours
+                .iter()
+                .all(|name| same_contents(&path.join(name), &source.join(name))),
+            _ => false,
+        };
+    }
+    matches!((fs::read(path), fs::read(source)), (Ok(ours), Ok(theirs)) if ours == theirs)
+}
+
+/// Whether `path` is a file the OS will run: any execute bit on Unix, an
+/// `.exe` extension elsewhere.
+pub fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+    if !metadata.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("exe"))
+    }
+}
+
+pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
+    fs::create_dir_all(target)
+        .with_context(|| format!("Failed to create directory '{}'.", target.display()))?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let source_path = entry.path();
+        let target_path = target.join(entry.file_name());
+        if source_path.is_dir() {
+            copy_dir_recursive(&source_path, &target_path)?;
+        } else {
+            fs::copy(&source_path, &target_path).with_context(|| {
+                format!(
+                    "Failed to copy '{}' to '{}'.",
+                    source_path.display(),
+                    target_path.display()
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn recognizes_unix_and_windows_path_forms() {
+        assert!(is_explicit_path("./pkg"));
+        assert!(is_explicit_path("..\\pkg"));
+        assert!(is_explicit_path(".\\pkg"));
+        assert!(is_explicit_path("/opt/pkg"));
+        assert!(!is_explicit_path("owner/repo"));
+        #[cfg(windows)]
+        {
+            assert!(is_explicit_path("C:\\Users\\dev\\pkg"));
+            assert!(is_explicit_path("\\\\server\\share\\pkg"));
+        }
+        assert_eq!(
+            to_slash(Path::new(".nexus\\context\\app\\index.md")),
+            ".nexus/context/app/index.md"
+        );
+        assert_eq!(expand_home("pkg"), None);
+        if let Some(home) = home_dir() {
+            assert_eq!(expand_home("~\\pkg"), Some(home.join("pkg")));
+        }
+    }
+
+    #[test]
+    fn link_or_copy_resolves_to_source_contents() {
+        let dir = tempdir().expect("tempdir");
+        let source = dir.path().join("source");
+        fs::create_dir_all(source.join("nested")).expect("source dirs");
+        fs::write(source.join("nested").join("SKILL.md"), "skill").expect("skill");
+        let links = dir.path().join("links");
+        fs::create_dir_all(&links).expect("links dir");
+
+        let target = links.join("skill");
+        link_or_copy("../source", &source, &target).expect("link dir");
+        assert_eq!(
+            fs::read_to_string(target.join("nested").join("SKILL.md")).expect("read"),
+            "skill"
+        );
+        assert!(path_exists_or_symlink(&target));
+        assert!(same_contents(&target, &source));
+        remove_path(&target).expect("remove");
+        assert!(!path_exists_or_symlink(&target));
+        assert!(source.join("nested").join("SKILL.md").is_file());
+
+        for mode in [LinkMode::Hardlink, LinkMode::Copy] {
+            let target = links.join(format!("{mode:?}"));
+            link_with_mode(mode, "../source", &source, &target).expect("link tree");
+            assert!(same_contents(&target, &source));
+        }
+        fs::write(source.join("nested").join("SKILL.md"), "edited").expect("edit");
+        assert!(same_contents(&links.join("Hardlink"), &source));
+        assert!(!same_contents(&links.join("Copy"), &source));
+    }
+}
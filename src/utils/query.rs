@@ -0,0 +1,810 @@
+//! JMESPath expressions for `--query`.
+//!
+//! Supports the subset scripts reach for: field and quoted-field access,
+//! indexes, `[*]`/`.*`/`[]` projections, `[?cond]` filters with comparisons
+//! and `&&`/`||`/`!`, multiselect lists and hashes, pipes, raw `'strings'`,
+//! backtick JSON literals, and the `length`, `keys`, `values`, `sort`,
+//! `contains`, `starts_with`, and `join` functions.
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone)]
+pub struct JsonQuery {
+    expression: String,
+    ast: Node,
+}
+
+impl JsonQuery {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let tokens = tokenize(expression).map_err(|err| invalid(expression, &err))?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let ast = parser
+            .expression(0)
+            .map_err(|err| invalid(expression, &err))?;
+        if parser.peek() != &Token::End {
+            return Err(invalid(
+                expression,
+                &format!("unexpected {}", parser.peek().describe()),
+            ));
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            ast,
+        })
+    }
+
+    pub fn apply(&self, value: &Value) -> Result<Value> {
+        evaluate(&self.ast, value)
+            .map_err(|err| anyhow!("Query '{}' failed: {}", self.expression, err))
+    }
+}
+
+fn invalid(expression: &str, reason: &str) -> anyhow::Error {
+    anyhow!(
+        "Invalid --query expression '{}': {}. Remediation: use JMESPath syntax such as \"runs[?status=='failed'].run_id\".",
+        expression,
+        reason
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    QuotedIdentifier(String),
+    Literal(Value),
+    Number(i64),
+    Dot,
+    Star,
+    Flatten,
+    Filter,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    Comma,
+    Colon,
+    Pipe,
+    Or,
+    And,
+    Not,
+    At,
+    Comparator(Comparator),
+    End,
+}
+
+impl Token {
+    fn binding_power(&self) -> u8 {
+        match self {
+            Token::Pipe => 1,
+            Token::Or => 2,
+            Token::And => 3,
+            Token::Comparator(_) => 5,
+            Token::Flatten => 9,
+            Token::Star => 20,
+            Token::Filter => 21,
+            Token::Dot => 40,
+            Token::Not => 45,
+            Token::LBrace => 50,
+            Token::LBracket => 55,
+            Token::LParen => 60,
+            _ => 0,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Token::Identifier(name) | Token::QuotedIdentifier(name) => format!("'{}'", name),
+            Token::Literal(value) => format!("literal {}", value),
+            Token::Number(number) => format!("number {}", number),
+            Token::End => "end of expression".to_string(),
+            other => format!("{:?}", other).to_lowercase(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Current,
+    Field(String),
+    Index(i64),
+    Literal(Value),
+    Subexpr(Box<Node>, Box<Node>),
+    Projection(Box<Node>, Box<Node>),
+    ObjectProjection(Box<Node>, Box<Node>),
+    FilterProjection(Box<Node>, Box<Node>, Box<Node>),
+    Flatten(Box<Node>),
+    Compare(Comparator, Box<Node>, Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+    Pipe(Box<Node>, Box<Node>),
+    MultiList(Vec<Node>),
+    MultiHash(Vec<(String, Node)>),
+    Function(String, Vec<Node>),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars = expression.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        let next = chars.get(index + 1).copied();
+        let (token, width) = match ch {
+            ' ' | '\t' | '\n' | '\r' => {
+                index += 1;
+                continue;
+            }
+            '.' => (Token::Dot, 1),
+            '*' => (Token::Star, 1),
+            '@' => (Token::At, 1),
+            ']' => (Token::RBracket, 1),
+            '{' => (Token::LBrace, 1),
+            '}' => (Token::RBrace, 1),
+            '(' => (Token::LParen, 1),
+            ')' => (Token::RParen, 1),
+            ',' => (Token::Comma, 1),
+            ':' => (Token::Colon, 1),
+            '[' => match next {
+                Some(']') => (Token::Flatten, 2),
+                Some('?') => (Token::Filter, 2),
+                _ => (Token::LBracket, 1),
+            },
+            '|' if next == Some('|') => (Token::Or, 2),
+            '|' => (Token::Pipe, 1),
+            '&' if next == Some('&') => (Token::And, 2),
+            '=' if next == Some('=') => (Token::Comparator(Comparator::Eq), 2),
+            '!' if next == Some('=') => (Token::Comparator(Comparator::Ne), 2),
+            '!' => (Token::Not, 1),
+            '<' if next == Some('=') => (Token::Comparator(Comparator::Le), 2),
+            '<' => (Token::Comparator(Comparator::Lt), 1),
+            '>' if next == Some('=') => (Token::Comparator(Comparator::Ge), 2),
+            '>' => (Token::Comparator(Comparator::Gt), 1),
+            '\'' | '"' | '`' => {
+                let end = find_closing(&chars, index, ch)?;
+                let raw = chars[index + 1..end].iter().collect::<String>();
+                let token = match ch {
+                    '\'' => Token::Literal(Value::String(raw.replace("\\'", "'"))),
+                    '"' => Token::QuotedIdentifier(
+                        serde_json::from_str::<String>(&format!("\"{}\"", raw))
+                            .map_err(|err| format!("bad quoted identifier: {}", err))?,
+                    ),
+                    _ => Token::Literal(
+                        serde_json::from_str(&raw.replace("\\`", "`"))
+                            .map_err(|err| format!("bad JSON literal `{}`: {}", raw, err))?,
+                    ),
+                };
+                (token, end + 1 - index)
+            }
+            '-' | '0'..='9' => {
+                let mut end = index + 1;
+                while end < chars.len() && chars[end].is_ascii_digit() {
+                    end += 1;
+                }
+                let raw = chars[index..end].iter().collect::<String>();
+                let number = raw
+                    .parse::<i64>()
+                    .map_err(|_| format!("bad number '{}'", raw))?;
+                (Token::Number(number), end - index)
+            }
+            ch if ch.is_ascii_alphabetic() || ch == '_' => {
+                let mut end = index + 1;
+                while end < chars.len() && (chars[end].is_ascii_alphanumeric() || chars[end] == '_')
+                {
+                    end += 1;
+                }
+                (
+                    Token::Identifier(chars[index..end].iter().collect()),
+                    end - index,
+                )
+            }
+            other => return Err(format!("unexpected character '{}'", other)),
+        };
+        tokens.push(token);
+        index += width;
+    }
+    tokens.push(Token::End);
+    Ok(tokens)
+}
+
+fn find_closing(chars: &[char], start: usize, quote: char) -> Result<usize, String> {
+    let mut index = start + 1;
+    while index < chars.len() {
+        if chars[index] == '\\' {
+            index += 2;
+            continue;
+        }
+        if chars[index] == quote {
+            return Ok(index);
+        }
+        index += 1;
+    }
+    Err(format!("unterminated {} quote", quote))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&Token::End)
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if token != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        let found = self.advance();
+        if found == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "expected {} but found {}",
+                expected.describe(),
+                found.describe()
+            ))
+        }
+    }
+
+    fn expression(&mut self, rbp: u8) -> Result<Node, String> {
+        let token = self.advance();
+        let mut left = self.nud(token)?;
+        while rbp < self.peek().binding_power() {
+            let token = self.advance();
+            left = self.led(token, left)?;
+        }
+        Ok(left)
+    }
+
+    fn nud(&mut self, token: Token) -> Result<Node, String> {
+        match token {
+            Token::Identifier(name) => {
+                if self.peek() == &Token::LParen {
+                    self.advance();
+                    return self.function_call(name);
+                }
+                Ok(Node::Field(name))
+            }
+            Token::QuotedIdentifier(name) => Ok(Node::Field(name)),
+            Token::Literal(value) => Ok(Node::Literal(value)),
+            Token::Number(number) => Ok(Node::Literal(Value::from(number))),
+            Token::At => Ok(Node::Current),
+            Token::Star => {
+                let right = self.projection_rhs(Token::Star.binding_power())?;
+                Ok(Node::ObjectProjection(
+                    Box::new(Node::Current),
+                    Box::new(right),
+                ))
+            }
+            Token::Flatten => {
+                let right = self.projection_rhs(Token::Flatten.binding_power())?;
+                Ok(Node::Projection(
+                    Box::new(Node::Flatten(Box::new(Node::Current))),
+                    Box::new(right),
+                ))
+            }
+            Token::Filter => self.filter(Node::Current),
+            Token::LBracket => match self.peek().clone() {
+                Token::Number(number) => {
+                    self.advance();
+                    self.expect(Token::RBracket)?;
+                    Ok(Node::Index(number))
+                }
+                Token::Star if self.peek_at(1) == &Token::RBracket => {
+                    self.advance();
+                    self.advance();
+                    let right = self.projection_rhs(Token::Star.binding_power())?;
+                    Ok(Node::Projection(Box::new(Node::Current), Box::new(right)))
+                }
+                _ => self.multi_list(),
+            },
+            Token::LBrace => self.multi_hash(),
+            Token::Not => Ok(Node::Not(Box::new(
+                self.expression(Token::Not.binding_power())?,
+            ))),
+            Token::LParen => {
+                let inner = self.expression(0)?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            other => Err(format!("unexpected {}", other.describe())),
+        }
+    }
+
+    fn led(&mut self, token: Token, left: Node) -> Result<Node, String> {
+        match token {
+            Token::Dot => {
+                if self.peek() == &Token::Star {
+                    self.advance();
+                    let right = self.projection_rhs(Token::Star.binding_power())?;
+                    return Ok(Node::ObjectProjection(Box::new(left), Box::new(right)));
+                }
+                let right = self.dot_rhs(Token::Dot.binding_power())?;
+                Ok(Node::Subexpr(Box::new(left), Box::new(right)))
+            }
+            Token::Pipe => Ok(Node::Pipe(
+                Box::new(left),
+                Box::new(self.expression(Token::Pipe.binding_power())?),
+            )),
+            Token::Or => Ok(Node::Or(
+                Box::new(left),
+                Box::new(self.expression(Token::Or.binding_power())?),
+            )),
+            Token::And => Ok(Node::And(
+                Box::new(left),
+                Box::new(self.expression(Token::And.binding_power())?),
+            )),
+            Token::Comparator(comparator) => Ok(Node::Compare(
+                comparator,
+                Box::new(left),
+                Box::new(self.expression(Token::Comparator(comparator).binding_power())?),
+            )),
+            Token::Flatten => {
+                let right = self.projection_rhs(Token::Flatten.binding_power())?;
+                Ok(Node::Projection(
+                    Box::new(Node::Flatten(Box::new(left))),
+                    Box::new(right),
+                ))
+            }
+            Token::Filter => self.filter(left),
+            Token::LBracket => match self.advance() {
+                Token::Number(number) => {
+                    self.expect(Token::RBracket)?;
+                    Ok(Node::Subexpr(Box::new(left), Box::new(Node::Index(number))))
+                }
+                Token::Star => {
+                    self.expect(Token::RBracket)?;
+                    let right = self.projection_rhs(Token::Star.binding_power())?;
+                    Ok(Node::Projection(Box::new(left), Box::new(right)))
+                }
+                other => Err(format!(
+                    "expected an index or '*' after '[' but found {}",
+                    other.describe()
+                )),
+            },
+            other => Err(format!("unexpected {}", other.describe())),
+        }
+    }
+
+    fn filter(&mut self, left: Node) -> Result<Node, String> {
+        let condition = self.expression(0)?;
+        self.expect(Token::RBracket)?;
+        let right = self.projection_rhs(Token::Filter.binding_power())?;
+        Ok(Node::FilterProjection(
+            Box::new(left),
+            Box::new(condition),
+            Box::new(right),
+        ))
+    }
+
+    fn projection_rhs(&mut self, rbp: u8) -> Result<Node, String> {
+        match self.peek() {
+            Token::Dot => {
+                self.advance();
+                self.dot_rhs(rbp)
+            }
+            Token::LBracket | Token::Filter | Token::Flatten => self.expression(rbp),
+            token if token.binding_power() < 10 => Ok(Node::Current),
+            other => Err(format!("unexpected {} after projection", other.describe())),
+        }
+    }
+
+    fn dot_rhs(&mut self, rbp: u8) -> Result<Node, String> {
+        match self.peek() {
+            Token::Identifier(_) | Token::QuotedIdentifier(_) => self.expression(rbp),
+            Token::LBracket => {
+                self.advance();
+                self.multi_list()
+            }
+            Token::LBrace => {
+                self.advance();
+                self.multi_hash()
+            }
+            other => Err(format!("unexpected {} after '.'", other.describe())),
+        }
+    }
+
+    fn multi_list(&mut self) -> Result<Node, String> {
+        let mut items = Vec::new();
+        loop {
+            items.push(self.expression(0)?);
+            match self.advance() {
+                Token::Comma => continue,
+                Token::RBracket => break,
+                other => {
+                    return Err(format!(
+                        "expected ',' or ']' but found {}",
+                        other.describe()
+                    ))
+                }
+            }
+        }
+        Ok(Node::MultiList(items))
+    }
+
+    fn multi_hash(&mut self) -> Result<Node, String> {
+        let mut entries = Vec::new();
+        loop {
+            let key = match self.advance() {
+                Token::Identifier(key) | Token::QuotedIdentifier(key) => key,
+                other => return Err(format!("expected a key but found {}", other.describe())),
+            };
+            self.expect(Token::Colon)?;
+            entries.push((key, self.expression(0)?));
+            match self.advance() {
+                Token::Comma => continue,
+                Token::RBrace => break,
+                other => {
+                    return Err(format!(
+                        "expected ',' or '}}' but found {}",
+                        other.describe()
+                    ))
+                }
+            }
+        }
+        Ok(Node::MultiHash(entries))
+    }
+
+    fn function_call(&mut self, name: String) -> Result<Node, String> {
+        let mut args = Vec::new();
+        if self.peek() == &Token::RParen {
+            self.advance();
+            return Ok(Node::Function(name, args));
+        }
+        loop {
+            args.push(self.expression(0)?);
+            match self.advance() {
+                Token::Comma => continue,
+                Token::RParen => break,
+                other => {
+                    return Err(format!(
+                        "expected ',' or ')' but found {}",
+                        other.describe()
+                    ))
+                }
+            }
+        }
+        Ok(Node::Function(name, args))
+    }
+}
+
+fn evaluate(node: &Node, value: &Value) -> Result<Value> {
+    Ok(match node {
+        Node::Current => value.clone(),
+        Node::Field(name) => value.get(name).cloned().unwrap_or(Value::Null),
+        Node::Index(index) => match value.as_array() {
+            Some(items) => {
+                let resolved = if *index < 0 {
+                    items.len() as i64 + index
+                } else {
+                    *index
+                };
+                usize::try_from(resolved)
+                    .ok()
+                    .and_then(|resolved| items.get(resolved))
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            }
+            None => Value::Null,
+        },
+        Node::Literal(literal) => literal.clone(),
+        Node::Subexpr(left, right) | Node::Pipe(left, right) => {
+            evaluate(right, &evaluate(left, value)?)?
+        }
+        Node::Projection(left, right) => match evaluate(left, value)? {
+            Value::Array(items) => project(items.iter(), right)?,
+            _ => Value::Null,
+        },
+        Node::ObjectProjection(left, right) => match evaluate(left, value)? {
+            Value::Object(map) => project(map.values(), right)?,
+            _ => Value::Null,
+        },
+        Node::FilterProjection(left, condition, right) => match evaluate(left, value)? {
+            Value::Array(items) => {
+                let mut kept = Vec::new();
+                for item in items {
+                    if is_truthy(&evaluate(condition, &item)?) {
+                        kept.push(item);
+                    }
+                }
+                project(kept.iter(), right)?
+            }
+            _ => Value::Null,
+        },
+        Node::Flatten(inner) => match evaluate(inner, value)? {
+            Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .flat_map(|item| match item {
+                        Value::Array(nested) => nested,
+                        other => vec![other],
+                    })
+                    .collect(),
+            ),
+            _ => Value::Null,
+        },
+        Node::Compare(comparator, left, right) => compare(
+            *comparator,
+            &evaluate(left, value)?,
+            &evaluate(right, value)?,
+        ),
+        Node::And(left, right) => {
+            let left = evaluate(left, value)?;
+            if is_truthy(&left) {
+                evaluate(right, value)?
+            } else {
+                left
+            }
+        }
+        Node::Or(left, right) => {
+            let left = evaluate(left, value)?;
+            if is_truthy(&left) {
+                left
+            } else {
+                evaluate(right, value)?
+            }
+        }
+        Node::Not(inner) => Value::Bool(!is_truthy(&evaluate(inner, value)?)),
+        Node::MultiList(items) => {
+            if value.is_null() {
+                return Ok(Value::Null);
+            }
+            Value::Array(
+                items
+                    .iter()
+                    .map(|item| evaluate(item, value))
+                    .collect::<Result<_>>()?,
+            )
+        }
+        Node::MultiHash(entries) => {
+            if value.is_null() {
+                return Ok(Value::Null);
+            }
+            let mut map = Map::new();
+            for (key, entry) in entries {
+                map.insert(key.clone(), evaluate(entry, value)?);
+            }
+            Value::Object(map)
+        }
+        Node::Function(name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| evaluate(arg, value))
+                .collect::<Result<Vec<_>>>()?;
+            call_function(name, &args)?
+        }
+    })
+}
+
+fn project<'a>(items: impl Iterator<Item = &'a Value>, right: &Node) -> Result<Value> {
+    let mut projected = Vec::new();
+    for item in items {
+        let result = evaluate(right, item)?;
+        if !result.is_null() {
+            projected.push(result);
+        }
+    }
+    Ok(Value::Array(projected))
+}
+
+fn compare(comparator: Comparator, left: &Value, right: &Value) -> Value {
+    match comparator {
+        Comparator::Eq => Value::Bool(left == right),
+        Comparator::Ne => Value::Bool(left != right),
+        _ => {
+            let ordering = match (left, right) {
+                (Value::Number(left), Value::Number(right)) => left
+                    .as_f64()
+                    .zip(right.as_f64())
+                    .and_then(|(left, right)| left.partial_cmp(&right)),
+                (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+                _ => None,
+            };
+            match ordering {
+                Some(ordering) => Value::Bool(match comparator {
+                    Comparator::Lt => ordering == Ordering::Less,
+                    Comparator::Le => ordering != Ordering::Greater,
+                    Comparator::Gt => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                }),
+                None => Value::Null,
+            }
+        }
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(flag) => *flag,
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+        Value::Number(_) => true,
+    }
+}
+
+fn call_function(name: &str, args: &[Value]) -> Result<Value> {
+    let arity = |expected: usize| -> Result<()> {
+        if args.len() != expected {
+            bail!(
+                "{}() takes {} argument(s) but got {}",
+                name,
+                expected,
+                args.len()
+            );
+        }
+        Ok(())
+    };
+    Ok(match name {
+        "length" => {
+            arity(1)?;
+            match &args[0] {
+                Value::String(text) => Value::from(text.chars().count()),
+                Value::Array(items) => Value::from(items.len()),
+                Value::Object(map) => Value::from(map.len()),
+                other => bail!("length() expects a string, array, or object, got {}", other),
+            }
+        }
+        "keys" | "values" => {
+            arity(1)?;
+            let Value::Object(map) = &args[0] else {
+                bail!("{}() expects an object", name);
+            };
+            if name == "keys" {
+                Value::Array(map.keys().cloned().map(Value::String).collect())
+            } else {
+                Value::Array(map.values().cloned().collect())
+            }
+        }
+        "sort" => {
+            arity(1)?;
+            let Value::Array(items) = &args[0] else {
+                bail!("sort() expects an array");
+            };
+            let mut sorted = items.clone();
+            sorted.sort_by(|left, right| match (left, right) {
+                (Value::Number(left), Value::Number(right)) => left
+                    .as_f64()
+                    .partial_cmp(&right.as_f64())
+                    .unwrap_or(Ordering::Equal),
+                _ => left.to_string().cmp(&right.to_string()),
+            });
+            Value::Array(sorted)
+        }
+        "contains" => {
+            arity(2)?;
+            Value::Bool(match (&args[0], &args[1]) {
+                (Value::Array(items), needle) => items.contains(needle),
+                (Value::String(text), Value::String(needle)) => text.contains(needle.as_str()),
+                _ => false,
+            })
+        }
+        "starts_with" => {
+            arity(2)?;
+            match (&args[0], &args[1]) {
+                (Value::String(text), Value::String(prefix)) => {
+                    Value::Bool(text.starts_with(prefix.as_str()))
+                }
+                _ => bail!("starts_with() expects two strings"),
+            }
+        }
+        "join" => {
+            arity(2)?;
+            match (&args[0], &args[1]) {
+                (Value::String(separator), Value::Array(items)) => Value::String(
+                    items
+                        .iter()
+                        .map(|item| match item {
+                            Value::String(text) => Ok(text.clone()),
+                            _ => Err(anyhow!("join() expects an array of strings")),
+                        })
+                        .collect::<Result<Vec<_>>>()?
+                        .join(separator),
+                ),
+                _ => bail!("join() expects a separator string and an array"),
+            }
+        }
+        other => bail!("unknown function {}()", other),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn query(expression: &str, value: &Value) -> Value {
+        JsonQuery::parse(expression)
+            .expect("query should parse")
+            .apply(value)
+            .expect("query should evaluate")
+    }
+
+    #[test]
+    fn filters_and_projects_lists() {
+        let data = json!([
+            {"id": "CDD_001", "status": "pending", "tests": 3},
+            {"id": "CDD_002", "status": "done", "tests": 5},
+            {"id": "CDD_003", "status": "pending", "tests": 8}
+        ]);
+        assert_eq!(
+            query("[?status=='pending'].id", &data),
+            json!(["CDD_001", "CDD_003"])
+        );
+        assert_eq!(
+            query("[?tests > `4` && status != 'done'].id", &data),
+            json!(["CDD_003"])
+        );
+        assert_eq!(query("[*].tests | [-1]", &data), json!(8));
+        assert_eq!(query("length([?status=='done'])", &data), json!(1));
+        assert_eq!(
+            query("[0].{id: id, ok: status == 'pending'}", &data),
+            json!({"id": "CDD_001", "ok": true})
+        );
+    }
+
+    #[test]
+    fn handles_nested_fields_flatten_and_functions() {
+        let data = json!({
+            "runs": [
+                {"run_id": 1, "steps": [{"name": "red"}, {"name": "green"}]},
+                {"run_id": 2, "steps": [{"name": "red"}]}
+            ],
+            "summary": {"failed": 0, "passed": 2}
+        });
+        assert_eq!(
+            query("runs[].steps[].name", &data),
+            json!(["red", "green", "red"])
+        );
+        assert_eq!(query("runs[*].steps[0].name", &data), json!(["red", "red"]));
+        assert_eq!(query("summary.*", &data), json!([0, 2]));
+        assert_eq!(
+            query("sort(keys(summary))", &data),
+            json!(["failed", "passed"])
+        );
+        assert_eq!(
+            query("join(', ', runs[0].steps[*].name)", &data),
+            json!("red, green")
+        );
+        assert_eq!(query("missing.field", &data), Value::Null);
+    }
+
+    #[test]
+    fn rejects_malformed_expressions_with_remediation() {
+        for expression in ["[?status=='x'", "runs[", "a..b", "'unterminated"] {
+            let err = JsonQuery::parse(expression).expect_err("should fail");
+            assert!(err.to_string().contains("Remediation"), "{}", err);
+        }
+        let err = JsonQuery::parse("nope(@)")
+            .expect("parses")
+            .apply(&json!({}))
+            .expect_err("unknown function");
+        assert!(err.to_string().contains("unknown function nope()"));
+    }
+}
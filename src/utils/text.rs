@@ -29,3 +29,95 @@ pub fn format_duration_short(ms: u128) -> String {
     }
     format!("{}:{:02}", minutes, seconds)
 }
+
+/// Compare dotted numeric versions; unparsable parts count as 0. A
+/// prerelease (`1.3.0-beta.1`) is older than the release it precedes, and
+/// prereleases compare identifier by identifier as in semver.
+pub fn version_older(version: &str, than: &str) -> bool {
+    let (version_core, version_pre) = split_version(version);
+    let (than_core, than_pre) = split_version(than);
+    if version_core != than_core {
+        return version_core < than_core;
+    }
+    match (version_pre, than_pre) {
+        (Some(version_pre), Some(than_pre)) => prerelease_older(version_pre, than_pre),
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// The numeric `major.minor.patch` of `version` and its prerelease, if any.
+fn split_version(version: &str) -> (Vec<u64>, Option<&str>) {
+    let version = version.trim_start_matches('v');
+    let version = version.split_once('+').map_or(version, |(core, _)| core);
+    let (core, prerelease) = version
+        .split_once('-')
+        .map_or((version, None), |(core, pre)| (core, Some(pre)));
+    let core = core
+        .split('.')
+        .take(3)
+        .map(|part| part.parse::<u64>().unwrap_or(0))
+        .collect();
+    (core, prerelease)
+}
+
+/// Numeric identifiers sort numerically and before alphanumeric ones; a
+/// shorter prefix sorts first.
+fn prerelease_older(version: &str, than: &str) -> bool {
+    let key = |part: &str| match part.parse::<u64>() {
+        Ok(number) => (0, number, String::new()),
+        Err(_) => (1, 0, part.to_string()),
+    };
+    version.split('.').map(key).lt(than.split('.').map(key))
+}
+
+/// Return the candidate closest to `input` when it is a plausible typo.
+pub fn closest_match<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, edit_distance(input, candidate)))
+        .filter(|(candidate, distance)| *distance <= 2.max(candidate.len() / 3))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right_chars = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right_chars.len()).collect::<Vec<_>>();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1; right_chars.len() + 1];
+        for (j, right_char) in right_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[right_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_older_orders_prereleases_before_their_release() {
+        assert!(version_older("1.2.0", "1.3.0-beta.1"));
+        assert!(version_older("1.3.0-beta.1", "1.3.0"));
+        assert!(version_older("1.3.0-beta.1", "1.3.0-beta.2"));
+        assert!(version_older("1.3.0-alpha", "1.3.0-beta"));
+        assert!(version_older("1.3.0-beta.2", "1.3.0-beta.10"));
+        assert!(!version_older("1.3.0", "1.3.0-beta.1"));
+        assert!(!version_older("v1.3.0", "1.3.0+build.5"));
+    }
+
+    #[test]
+    fn closest_match_suggests_near_typos_only() {
+        let candidates = ["harness", "marketplace", "version"];
+        assert_eq!(closest_match("harnes", &candidates), Some("harness"));
+        assert_eq!(
+            closest_match("marketplce", &candidates),
+            Some("marketplace")
+        );
+        assert_eq!(closest_match("telemetry", &candidates), None);
+    }
+}
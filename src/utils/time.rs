@@ -0,0 +1,91 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn unix_now_seconds() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Format unix seconds as a UTC calendar date (`YYYY-MM-DD`).
+pub fn format_unix_date(seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Format unix seconds as an RFC 3339 UTC timestamp (`YYYY-MM-DDTHH:MM:SSZ`).
+pub fn format_unix_rfc3339(seconds: i64) -> String {
+    let time_of_day = seconds.rem_euclid(86_400);
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_unix_date(seconds),
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60
+    )
+}
+
+/// Describe unix `seconds` relative to `now`, e.g. `3h ago` or `in 5m`.
+pub fn format_relative(seconds: i64, now: i64) -> String {
+    let delta = now - seconds;
+    let magnitude = delta.unsigned_abs();
+    if magnitude < 10 {
+        return "just now".to_string();
+    }
+    let amount = match magnitude {
+        0..=59 => format!("{}s", magnitude),
+        60..=3_599 => format!("{}m", magnitude / 60),
+        3_600..=86_399 => format!("{}h", magnitude / 3_600),
+        86_400..=2_591_999 => format!("{}d", magnitude / 86_400),
+        _ => return format_unix_date(seconds),
+    };
+    if delta >= 0 {
+        format!("{} ago", amount)
+    } else {
+        format!("in {}", amount)
+    }
+}
+
+/// Convert days since the unix epoch into a proleptic Gregorian date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_unix_dates_in_utc() {
+        assert_eq!(format_unix_date(0), "1970-01-01");
+        assert_eq!(format_unix_date(951_782_400), "2000-02-29");
+        assert_eq!(format_unix_date(1_772_150_400), "2026-02-27");
+        assert_eq!(format_unix_date(-86_400), "1969-12-31");
+    }
+
+    #[test]
+    fn formats_rfc3339_and_relative_times() {
+        assert_eq!(format_unix_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_unix_rfc3339(1_772_195_445), "2026-02-27T12:30:45Z");
+        let now = 1_772_195_445;
+        assert_eq!(format_relative(now - 3, now), "just now");
+        assert_eq!(format_relative(now - 42, now), "42s ago");
+        assert_eq!(format_relative(now - 3 * 3_600 - 5, now), "3h ago");
+        assert_eq!(format_relative(now + 300, now), "in 5m");
+        assert_eq!(format_relative(now - 40 * 86_400, now), "2026-01-18");
+    }
+}
@@ -60,11 +60,11 @@ fn sample_project_context_implement_generates_tests() {
         .expect("failed to run opennexus context implement");
     assert!(status.success(), "context implement should succeed");
 
-    let generated = root.join("tests/context_generated_cdd_900_sample_implement.rs");
+    let generated = root.join("tests/sample/cdd/cdd_900-sample-implement/sample_generated_test.rs");
     assert!(generated.exists(), "generated test scaffold should exist");
 
     let generated_content = fs::read_to_string(generated).expect("read generated file");
-    assert!(generated_content.contains("fn sample_generated_test()"));
+    assert!(generated_content.contains("fn test_sample_generated_test()"));
 }
 
 #[test]